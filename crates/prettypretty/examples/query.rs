@@ -12,7 +12,7 @@ fn run_queries(tty: Connection) -> Result<()> {
     let theme11 = Theme::query1(&tty)?;
     let theme12 = Theme::query1(&tty)?;
     let theme13 = Theme::query1(&tty)?;
-    let duration = start.elapsed().map_err(|e| Error::other(e))?;
+    let duration = start.elapsed().map_err(Error::other)?;
     assert_eq!(theme11, theme12);
     assert_eq!(theme11, theme13);
 
@@ -27,7 +27,7 @@ fn run_queries(tty: Connection) -> Result<()> {
     let theme21 = Theme::query2(&tty)?;
     let theme22 = Theme::query2(&tty)?;
     let theme23 = Theme::query2(&tty)?;
-    let duration = start.elapsed().map_err(|e| Error::other(e))?;
+    let duration = start.elapsed().map_err(Error::other)?;
     assert_eq!(theme11, theme21);
     assert_eq!(theme21, theme22);
     assert_eq!(theme21, theme23);
@@ -43,7 +43,7 @@ fn run_queries(tty: Connection) -> Result<()> {
     let theme31 = Theme::query3(&tty)?;
     let theme32 = Theme::query3(&tty)?;
     let theme33 = Theme::query3(&tty)?;
-    let duration = start.elapsed().map_err(|e| Error::other(e))?;
+    let duration = start.elapsed().map_err(Error::other)?;
     assert_eq!(theme21, theme31);
     assert_eq!(theme31, theme32);
     assert_eq!(theme31, theme33);
@@ -0,0 +1,177 @@
+//! Zero-copy access to color coordinates for Python.
+//!
+//! [`Color`] and [`CoordinateTable`] implement Python's buffer protocol, which
+//! lets NumPy, matplotlib, and other libraries view their coordinates without
+//! copying, e.g., with `numpy.asarray(color)`. The views are read-only, since
+//! both types are immutable. They use the same floating point type as
+//! [`Float`], i.e., `float64` by default.
+
+use std::ffi::{c_int, c_void, CStr};
+
+use pyo3::exceptions::{PyBufferError, PyIndexError};
+use pyo3::ffi;
+use pyo3::prelude::*;
+
+use crate::{Color, ColorSpace, Float};
+
+/// The buffer protocol's format character for [`Float`].
+const FORMAT: &CStr = if std::mem::size_of::<Float>() == 8 {
+    c"d"
+} else {
+    c"f"
+};
+
+/// The shape of a color's coordinates.
+static COLOR_SHAPE: [ffi::Py_ssize_t; 1] = [3];
+
+/// The strides of a color's coordinates.
+static COLOR_STRIDES: [ffi::Py_ssize_t; 1] = [std::mem::size_of::<Float>() as ffi::Py_ssize_t];
+
+/// Fill in the buffer view for the given owner, coordinates, shape, and
+/// strides.
+///
+/// # Safety
+///
+/// The view must be valid. The data, shape, and strides must be owned by the
+/// owner or be static and must not change while the owner is alive. The data
+/// must be laid out as C-contiguous array.
+pub(crate) unsafe fn fill_view(
+    owner: &Bound<'_, PyAny>,
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+    data: &[[Float; 3]],
+    shape: &[ffi::Py_ssize_t],
+    strides: &[ffi::Py_ssize_t],
+) -> PyResult<()> {
+    if view.is_null() {
+        return Err(PyBufferError::new_err("buffer view is null"));
+    }
+    if flags & ffi::PyBUF_WRITABLE == ffi::PyBUF_WRITABLE {
+        return Err(PyBufferError::new_err("color coordinates are read-only"));
+    }
+
+    let view = &mut *view;
+    view.obj = owner.clone().into_ptr();
+    view.buf = data.as_ptr() as *mut c_void;
+    view.len = std::mem::size_of_val(data) as ffi::Py_ssize_t;
+    view.readonly = 1;
+    view.itemsize = std::mem::size_of::<Float>() as ffi::Py_ssize_t;
+    view.format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+        FORMAT.as_ptr() as *mut _
+    } else {
+        std::ptr::null_mut()
+    };
+    view.ndim = shape.len() as c_int;
+    view.shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+        shape.as_ptr() as *mut _
+    } else {
+        std::ptr::null_mut()
+    };
+    view.strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+        strides.as_ptr() as *mut _
+    } else {
+        std::ptr::null_mut()
+    };
+    view.suboffsets = std::ptr::null_mut();
+    view.internal = std::ptr::null_mut();
+    Ok(())
+}
+
+/// Fill in the buffer view for a color's coordinates.
+///
+/// # Safety
+///
+/// The view must be valid.
+pub(crate) unsafe fn fill_color_view(
+    color: &Bound<'_, Color>,
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+) -> PyResult<()> {
+    let data = std::slice::from_ref(color.get().as_ref());
+    fill_view(
+        color.as_any(),
+        view,
+        flags,
+        data,
+        &COLOR_SHAPE,
+        &COLOR_STRIDES,
+    )
+}
+
+/// A table of color coordinates. <i class=python-only>Python only!</i>
+///
+/// A coordinate table holds the coordinates for several colors in the same
+/// color space. It implements Python's buffer protocol as a read-only,
+/// two-dimensional array with one row per color and three columns. Hence
+/// `numpy.asarray(table)` has shape `(len(table), 3)`.
+#[pyclass(frozen, module = "prettypretty.color")]
+pub struct CoordinateTable {
+    space: ColorSpace,
+    data: Vec<[Float; 3]>,
+    shape: [ffi::Py_ssize_t; 2],
+    strides: [ffi::Py_ssize_t; 2],
+}
+
+impl CoordinateTable {
+    /// Create a new coordinate table.
+    pub(crate) fn new(space: ColorSpace, data: Vec<[Float; 3]>) -> Self {
+        let size = std::mem::size_of::<Float>() as ffi::Py_ssize_t;
+        Self {
+            space,
+            shape: [data.len() as ffi::Py_ssize_t, 3],
+            strides: [3 * size, size],
+            data,
+        }
+    }
+}
+
+#[pymethods]
+impl CoordinateTable {
+    /// Get the color space of this table's coordinates.
+    pub fn space(&self) -> ColorSpace {
+        self.space
+    }
+
+    /// Get the number of colors in this table.
+    pub fn __len__(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Get the color with the given index.
+    pub fn __getitem__(&self, index: usize) -> PyResult<Color> {
+        self.data
+            .get(index)
+            .map(|coordinates| Color::new(self.space, *coordinates))
+            .ok_or_else(|| PyIndexError::new_err("coordinate table index out of range"))
+    }
+
+    /// Export this table's coordinates as a read-only buffer.
+    ///
+    /// # Safety
+    ///
+    /// The view must be valid.
+    pub unsafe fn __getbuffer__(
+        slf: Bound<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        let table = slf.get();
+        fill_view(
+            slf.as_any(),
+            view,
+            flags,
+            &table.data,
+            &table.shape,
+            &table.strides,
+        )
+    }
+
+    /// Get this table's debug representation.
+    pub fn __repr__(&self) -> String {
+        format!(
+            "CoordinateTable({:?}, {} colors)",
+            self.space,
+            self.data.len()
+        )
+    }
+}
@@ -0,0 +1,143 @@
+//! Batch conversion of NumPy arrays for Python.
+//!
+//! Looping over [`Color`] objects in Python is slow. The functions in this
+//! module instead process arrays of color coordinates with shape `(N, 3)` in
+//! one call and release the GIL while doing so. They access arrays through
+//! Python's buffer protocol, which NumPy arrays implement, and hence require
+//! C-contiguous arrays with the same floating point type as
+//! [`Float`](crate::Float), i.e., `float64` by default. Results are NumPy
+//! arrays, too.
+
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::core::{convert as convert_coordinates, to_gamut as map_to_gamut};
+use crate::{Color, ColorSpace, Float, Translator};
+
+/// Read the array of color coordinates into a vector.
+fn read_coordinates(array: &Bound<'_, PyAny>) -> PyResult<Vec<[Float; 3]>> {
+    let buffer = PyBuffer::<Float>::get(array)?;
+    if buffer.dimensions() != 2 || buffer.shape()[1] != 3 {
+        return Err(PyValueError::new_err(format!(
+            "array of color coordinates should have shape (N, 3) but has {:?}",
+            buffer.shape()
+        )));
+    }
+
+    let mut data = vec![0.0; buffer.item_count()];
+    buffer.copy_to_slice(array.py(), &mut data)?;
+    Ok(data.chunks_exact(3).map(|c| [c[0], c[1], c[2]]).collect())
+}
+
+/// Create a new NumPy array with the given shape and data type and fill it
+/// with the data.
+fn new_array<T: pyo3::buffer::Element + Copy>(
+    py: Python<'_>,
+    shape: (usize, Option<usize>),
+    dtype: &str,
+    data: &[T],
+) -> PyResult<PyObject> {
+    let numpy = PyModule::import(py, "numpy")?;
+    let array = match shape {
+        (rows, Some(columns)) => numpy.call_method1("empty", ((rows, columns), dtype))?,
+        (rows, None) => numpy.call_method1("empty", (rows, dtype))?,
+    };
+
+    PyBuffer::<T>::get(&array)?.copy_from_slice(py, data)?;
+    Ok(array.unbind())
+}
+
+/// Create a new NumPy array with the given color coordinates.
+fn write_coordinates(py: Python<'_>, coordinates: &[[Float; 3]]) -> PyResult<PyObject> {
+    let dtype = if std::mem::size_of::<Float>() == 8 {
+        "float64"
+    } else {
+        "float32"
+    };
+
+    let data = coordinates.iter().flatten().copied().collect::<Vec<_>>();
+    new_array(py, (coordinates.len(), Some(3)), dtype, &data)
+}
+
+/// Convert an array of color coordinates from one color space to another.
+/// <i class=python-only>Python only!</i>
+#[pyfunction]
+pub fn convert(
+    array: &Bound<'_, PyAny>,
+    from_space: ColorSpace,
+    to_space: ColorSpace,
+) -> PyResult<PyObject> {
+    let py = array.py();
+    let coordinates = read_coordinates(array)?;
+    let result = py.allow_threads(|| {
+        coordinates
+            .iter()
+            .map(|c| convert_coordinates(from_space, to_space, c))
+            .collect::<Vec<_>>()
+    });
+    write_coordinates(py, &result)
+}
+
+/// Map an array of color coordinates into the gamut of their color space.
+/// <i class=python-only>Python only!</i>
+///
+/// This function uses the same gamut-mapping algorithm as
+/// [`Color::to_gamut`].
+#[pyfunction]
+pub fn to_gamut(array: &Bound<'_, PyAny>, space: ColorSpace) -> PyResult<PyObject> {
+    let py = array.py();
+    let coordinates = read_coordinates(array)?;
+    let result = py.allow_threads(|| {
+        coordinates
+            .iter()
+            .map(|c| map_to_gamut(space, c))
+            .collect::<Vec<_>>()
+    });
+    write_coordinates(py, &result)
+}
+
+/// Convert an array of color coordinates to ANSI color indices.
+/// <i class=python-only>Python only!</i>
+///
+/// This function returns a one-dimensional `uint8` array with the result of
+/// [`Translator::to_ansi`] for each color.
+#[pyfunction]
+pub fn to_ansi(
+    translator: &Translator,
+    array: &Bound<'_, PyAny>,
+    space: ColorSpace,
+) -> PyResult<PyObject> {
+    let py = array.py();
+    let coordinates = read_coordinates(array)?;
+    let result = py.allow_threads(|| {
+        coordinates
+            .iter()
+            .map(|c| translator.to_ansi(&Color::new(space, *c)) as u8)
+            .collect::<Vec<_>>()
+    });
+    new_array(py, (result.len(), None), "uint8", &result)
+}
+
+/// Convert an array of color coordinates to 8-bit color indices.
+/// <i class=python-only>Python only!</i>
+///
+/// This function returns a one-dimensional `uint8` array with the closest
+/// 8-bit color for each color, considering ANSI colors only if the
+/// translator's options say so.
+#[pyfunction]
+pub fn to_8bit(
+    translator: &Translator,
+    array: &Bound<'_, PyAny>,
+    space: ColorSpace,
+) -> PyResult<PyObject> {
+    let py = array.py();
+    let coordinates = read_coordinates(array)?;
+    let result = py.allow_threads(|| {
+        coordinates
+            .iter()
+            .map(|c| u8::from(translator.to_8bit(&Color::new(space, *c))))
+            .collect::<Vec<_>>()
+    });
+    new_array(py, (result.len(), None), "uint8", &result)
+}
@@ -0,0 +1,38 @@
+use crate::spectrum::MacAdamEllipse;
+
+/// MacAdam's 25 ellipses of chromaticity discrimination.
+///
+/// The ellipses are taken from D. L. MacAdam, "Visual Sensitivities to Color
+/// Differences in Daylight," *Journal of the Optical Society of America* 32,
+/// no. 5 (1942), pp. 247–274. Each ellipse is described by its
+/// center in xy chromaticity coordinates, its semi-major and semi-minor axes
+/// in units of 10⁻³, and the angle of its major axis in degrees. The axes are
+/// the standard deviation of color matching, i.e., one *just noticeable
+/// difference* or step.
+pub static MACADAM_ELLIPSES: [MacAdamEllipse; 25] = [
+    MacAdamEllipse::new(0.160, 0.057, 0.85, 0.35, 62.5),
+    MacAdamEllipse::new(0.187, 0.118, 2.2, 0.55, 77.0),
+    MacAdamEllipse::new(0.253, 0.125, 2.5, 0.5, 55.5),
+    MacAdamEllipse::new(0.150, 0.680, 9.6, 2.3, 105.0),
+    MacAdamEllipse::new(0.131, 0.521, 4.7, 2.0, 112.5),
+    MacAdamEllipse::new(0.212, 0.550, 5.8, 2.3, 100.0),
+    MacAdamEllipse::new(0.258, 0.450, 5.0, 2.0, 92.0),
+    MacAdamEllipse::new(0.152, 0.365, 3.8, 1.9, 110.0),
+    MacAdamEllipse::new(0.280, 0.385, 4.0, 1.5, 75.5),
+    MacAdamEllipse::new(0.380, 0.498, 4.4, 1.2, 70.0),
+    MacAdamEllipse::new(0.160, 0.200, 2.1, 0.95, 104.0),
+    MacAdamEllipse::new(0.228, 0.250, 3.1, 0.9, 72.0),
+    MacAdamEllipse::new(0.305, 0.323, 2.3, 0.9, 58.0),
+    MacAdamEllipse::new(0.385, 0.393, 3.8, 1.6, 65.5),
+    MacAdamEllipse::new(0.472, 0.399, 3.2, 1.4, 51.0),
+    MacAdamEllipse::new(0.527, 0.350, 2.6, 1.3, 20.0),
+    MacAdamEllipse::new(0.475, 0.300, 2.9, 1.1, 28.5),
+    MacAdamEllipse::new(0.510, 0.236, 2.4, 1.2, 29.5),
+    MacAdamEllipse::new(0.596, 0.283, 2.6, 1.3, 33.0),
+    MacAdamEllipse::new(0.344, 0.284, 2.3, 0.9, 60.0),
+    MacAdamEllipse::new(0.390, 0.237, 2.5, 1.0, 47.0),
+    MacAdamEllipse::new(0.441, 0.198, 2.8, 0.95, 34.5),
+    MacAdamEllipse::new(0.278, 0.223, 2.4, 0.55, 57.5),
+    MacAdamEllipse::new(0.300, 0.163, 2.9, 0.6, 54.0),
+    MacAdamEllipse::new(0.365, 0.153, 3.6, 0.95, 40.0),
+];
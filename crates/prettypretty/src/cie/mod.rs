@@ -1,16 +1,21 @@
+mod a_series;
 mod d_series;
+mod macadam;
 mod ten_deg;
 mod two_deg;
 
+pub use a_series::CIE_ILLUMINANT_A;
 pub use d_series::CIE_ILLUMINANT_D50;
 pub use d_series::CIE_ILLUMINANT_D65;
+pub use macadam::MACADAM_ELLIPSES;
 pub use ten_deg::CIE_OBSERVER_10DEG_1964;
 pub use two_deg::CIE_OBSERVER_2DEG_1931;
 
 #[cfg(test)]
 mod test {
     use super::{
-        CIE_ILLUMINANT_D50, CIE_ILLUMINANT_D65, CIE_OBSERVER_10DEG_1964, CIE_OBSERVER_2DEG_1931,
+        CIE_ILLUMINANT_A, CIE_ILLUMINANT_D50, CIE_ILLUMINANT_D65, CIE_OBSERVER_10DEG_1964,
+        CIE_OBSERVER_2DEG_1931,
     };
     use crate::core::{Sum, ThreeSum};
     use crate::spectrum::{IlluminatedObserver, SpectralDistribution, ONE_NANOMETER};
@@ -18,7 +23,7 @@ mod test {
 
     #[test]
     fn test_checksum() {
-        for illuminant in [&CIE_ILLUMINANT_D50, &CIE_ILLUMINANT_D65] {
+        for illuminant in [&CIE_ILLUMINANT_A, &CIE_ILLUMINANT_D50, &CIE_ILLUMINANT_D65] {
             let mut sum = Sum::new();
             for wavelength in illuminant.range() {
                 sum += illuminant.at(wavelength).unwrap();
@@ -85,4 +90,22 @@ mod test {
             assert_eq!(actual_chromaticity, chromaticity);
         }
     }
+
+    #[test]
+    fn test_illuminant_a() {
+        // See https://en.wikipedia.org/wiki/Standard_illuminant#White_points_of_standard_illuminants
+        for (observer, chromaticity) in [
+            (&CIE_OBSERVER_2DEG_1931, (0.44757, 0.40745)),
+            (&CIE_OBSERVER_10DEG_1964, (0.45117, 0.40594)),
+        ] {
+            let table = IlluminatedObserver::new(&CIE_ILLUMINANT_A, observer);
+            let color = table
+                .visual_gamut(ONE_NANOMETER)
+                .pulse_color(0, table.len());
+            // The table covers 360nm and up only, so allow for some rounding.
+            let (x, y) = color.xy_chromaticity();
+            assert!((x - chromaticity.0).abs() < 2e-5);
+            assert!((y - chromaticity.1).abs() < 2e-5);
+        }
+    }
 }
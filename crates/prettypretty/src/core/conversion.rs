@@ -39,41 +39,142 @@ fn multiply(matrix: &[[Float; 3]; 3], vector: &[Float; 3]) -> [Float; 3] {
 
 // --------------------------------------------------------------------------------------------------------------------
 
+/// Decode a single gamma-corrected coordinate using sRGB's gamma.
+#[inline]
+fn decode_gamma(value: Float) -> Float {
+    let magnitude = value.abs();
+    if magnitude <= 0.04045 {
+        value / 12.92
+    } else {
+        ((magnitude + 0.055) / 1.055).powf(2.4).copysign(value)
+    }
+}
+
+/// Encode a single linear coordinate using sRGB's gamma.
+#[inline]
+fn encode_gamma(value: Float) -> Float {
+    let magnitude = value.abs();
+    if magnitude <= 0.00313098 {
+        value * 12.92
+    } else {
+        magnitude
+            .powf(1.0 / 2.4)
+            .mul_add(1.055, -0.055)
+            .copysign(value)
+    }
+}
+
 /// Convert coordinates from gamma-corrected RGB to linear RGB using sRGB's
 /// gamma. Display P3 uses the very same gamma. This is a one-hop, direct
 /// conversion.
 fn rgb_to_linear_rgb(value: &[Float; 3]) -> [Float; 3] {
-    #[inline]
-    fn convert(value: Float) -> Float {
-        let magnitude = value.abs();
-        if magnitude <= 0.04045 {
-            value / 12.92
-        } else {
-            ((magnitude + 0.055) / 1.055).powf(2.4).copysign(value)
-        }
-    }
-
-    [convert(value[0]), convert(value[1]), convert(value[2])]
+    [
+        decode_gamma(value[0]),
+        decode_gamma(value[1]),
+        decode_gamma(value[2]),
+    ]
 }
 
 /// Convert coordinates from linear RGB to gamma-corrected RGB using sRGB's
 /// gamma. Display P3 uses the very same gamma. This is a one-hop, direct
 /// conversion.
 fn linear_rgb_to_rgb(value: &[Float; 3]) -> [Float; 3] {
+    [
+        encode_gamma(value[0]),
+        encode_gamma(value[1]),
+        encode_gamma(value[2]),
+    ]
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+mod lut {
+    use super::{decode_gamma, encode_gamma};
+    use crate::Float;
+    use std::sync::OnceLock;
+
+    /// The number of intervals for each lookup table.
+    const STEPS: usize = 4096;
+
+    /// The lookup tables for decoding and encoding sRGB's gamma. Both tables
+    /// sample the unit interval with `STEPS + 1` evenly spaced points.
+    struct Tables {
+        decode: Box<[Float]>,
+        encode: Box<[Float]>,
+    }
+
+    /// Get the lookup tables, computing them on first use.
+    fn tables() -> &'static Tables {
+        static TABLES: OnceLock<Tables> = OnceLock::new();
+
+        TABLES.get_or_init(|| {
+            let sample = |f: fn(Float) -> Float| {
+                (0..=STEPS)
+                    .map(|index| f(index as Float / STEPS as Float))
+                    .collect()
+            };
+
+            Tables {
+                decode: sample(decode_gamma),
+                encode: sample(encode_gamma),
+            }
+        })
+    }
+
+    /// Linearly interpolate the table entries around the value, which must be
+    /// in the unit interval.
+    #[inline]
+    fn lookup(table: &[Float], value: Float) -> Float {
+        let position = value * STEPS as Float;
+        let index = (position as usize).min(STEPS - 1);
+        let fraction = position - index as Float;
+        fraction.mul_add(table[index + 1] - table[index], table[index])
+    }
+
+    /// Decode a gamma-corrected coordinate with the lookup table. Values
+    /// outside the unit interval fall back on the exact computation.
+    #[inline]
+    fn decode(table: &[Float], value: Float) -> Float {
+        if (0.0..=1.0).contains(&value) {
+            lookup(table, value)
+        } else {
+            decode_gamma(value)
+        }
+    }
+
+    /// Encode a linear coordinate with the lookup table. Values on the linear
+    /// segment near zero and outside the unit interval fall back on the exact
+    /// computation, since the curve is too steep near zero for interpolation.
     #[inline]
-    fn convert(value: Float) -> Float {
-        let magnitude = value.abs();
-        if magnitude <= 0.00313098 {
-            value * 12.92
+    fn encode(table: &[Float], value: Float) -> Float {
+        if (0.00313098..=1.0).contains(&value) {
+            lookup(table, value)
         } else {
-            magnitude
-                .powf(1.0 / 2.4)
-                .mul_add(1.055, -0.055)
-                .copysign(value)
+            encode_gamma(value)
         }
     }
 
-    [convert(value[0]), convert(value[1]), convert(value[2])]
+    /// Convert coordinates from gamma-corrected RGB to linear RGB using the
+    /// lookup table for sRGB's gamma.
+    pub(super) fn rgb_to_linear_rgb(value: &[Float; 3]) -> [Float; 3] {
+        let table = &tables().decode;
+        [
+            decode(table, value[0]),
+            decode(table, value[1]),
+            decode(table, value[2]),
+        ]
+    }
+
+    /// Convert coordinates from linear RGB to gamma-corrected RGB using the
+    /// lookup table for sRGB's gamma.
+    pub(super) fn linear_rgb_to_rgb(value: &[Float; 3]) -> [Float; 3] {
+        let table = &tables().encode;
+        [
+            encode(table, value[0]),
+            encode(table, value[1]),
+            encode(table, value[2]),
+        ]
+    }
 }
 
 // --------------------------------------------------------------------------------------------------------------------
@@ -568,6 +669,40 @@ pub(crate) fn convert(
     }
 }
 
+/// Convert the coordinates from one color space to another, approximating
+/// sRGB's gamma with lookup tables.
+///
+/// This function differs from [`convert`] only for sRGB and Display P3, which
+/// share the same transfer function. It decodes and encodes their gamma by
+/// linearly interpolating between 4,097 precomputed values, which is faster
+/// than exponentiation and accurate to within 2e-5, i.e., well below the
+/// resolution of 24-bit colors.
+#[must_use = "function returns new color coordinates and does not mutate original value"]
+pub(crate) fn convert_approximately(
+    from_space: ColorSpace,
+    to_space: ColorSpace,
+    coordinates: &[Float; 3],
+) -> [Float; 3] {
+    use ColorSpace::*;
+
+    let coordinates = normalize(from_space, coordinates);
+    if from_space == to_space {
+        return coordinates;
+    }
+
+    let (from_space, coordinates) = match from_space {
+        Srgb => (LinearSrgb, lut::rgb_to_linear_rgb(&coordinates)),
+        DisplayP3 => (LinearDisplayP3, lut::rgb_to_linear_rgb(&coordinates)),
+        _ => (from_space, coordinates),
+    };
+
+    match to_space {
+        Srgb => lut::linear_rgb_to_rgb(&convert(from_space, LinearSrgb, &coordinates)),
+        DisplayP3 => lut::linear_rgb_to_rgb(&convert(from_space, LinearDisplayP3, &coordinates)),
+        _ => convert(from_space, to_space, &coordinates),
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::excessive_precision)]
 mod test {
@@ -746,6 +881,37 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_convert_approximately() {
+        for space in [Srgb, DisplayP3] {
+            for index in 0..=1_000 {
+                let value = index as Float / 1_000.0;
+                let coordinates = [value, 1.0 - value, 0.5 * value];
+
+                for target in [LinearSrgb, Xyz, Oklab, Srgb, DisplayP3] {
+                    let exact = convert(space, target, &coordinates);
+                    let approximate = convert_approximately(space, target, &coordinates);
+                    for (e, a) in exact.iter().zip(approximate.iter()) {
+                        assert!((e - a).abs() < 2e-5, "{:?} vs {:?}", exact, approximate);
+                    }
+                }
+
+                let linear = convert(space, Xyz, &coordinates);
+                let exact = convert(Xyz, space, &linear);
+                let approximate = convert_approximately(Xyz, space, &linear);
+                for (e, a) in exact.iter().zip(approximate.iter()) {
+                    assert!((e - a).abs() < 2e-5, "{:?} vs {:?}", exact, approximate);
+                }
+            }
+        }
+
+        // Out-of-gamut coordinates use the exact computation.
+        assert_eq!(
+            convert_approximately(Srgb, LinearSrgb, &[-0.5, 1.5, 0.0]),
+            convert(Srgb, LinearSrgb, &[-0.5, 1.5, 0.0])
+        );
+    }
+
     #[test]
     fn test_oklrxx() {
         // Based on https://github.com/color-js/color.js/pull/511
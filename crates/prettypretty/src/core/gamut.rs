@@ -486,6 +486,132 @@ impl GamutTraversal {
 
 // ====================================================================================================================
 
+/// The number of segments per edge of the RGB cube when computing volumes.
+#[cfg(feature = "gamut")]
+const VOLUME_SEGMENTS: usize = 64;
+
+/// The number of grid cells per Oklab unit when computing coverage.
+#[cfg(feature = "gamut")]
+const COVERAGE_RESOLUTION: Float = 100.0;
+
+/// Sample the surface of the RGB cube in Oklab.
+///
+/// This function returns the `(n + 1)²` vertices of each of the cube's six
+/// faces, with the faces in the same order as the component axes, first for
+/// value 0 and then value 1.
+#[cfg(feature = "gamut")]
+fn sample_surface(space: ColorSpace, n: usize) -> Vec<Vec<[Float; 3]>> {
+    let mut faces = Vec::with_capacity(6);
+    for axis in 0..3 {
+        for side in [0.0, 1.0] {
+            let mut vertices = Vec::with_capacity((n + 1) * (n + 1));
+            for i in 0..=n {
+                for j in 0..=n {
+                    let mut rgb = [0.0; 3];
+                    rgb[axis] = side;
+                    rgb[(axis + 1) % 3] = i as Float / n as Float;
+                    rgb[(axis + 2) % 3] = j as Float / n as Float;
+                    vertices.push(convert(space, ColorSpace::Oklab, &rgb));
+                }
+            }
+            faces.push(vertices);
+        }
+    }
+    faces
+}
+
+/// Compute the volume of the bounded color space's gamut in Oklab.
+///
+/// This function triangulates the surface of the RGB cube, maps the vertices
+/// to Oklab, and sums up the signed volumes of the tetrahedra formed by each
+/// triangle and the origin, which by the divergence theorem yields the volume
+/// enclosed by the surface.
+#[cfg(feature = "gamut")]
+pub(crate) fn gamut_volume(space: ColorSpace) -> Option<Float> {
+    if !space.is_bounded() {
+        return None;
+    }
+
+    fn signed_volume(a: &[Float; 3], b: &[Float; 3], c: &[Float; 3]) -> Float {
+        let cross = [
+            b[1] * c[2] - b[2] * c[1],
+            b[2] * c[0] - b[0] * c[2],
+            b[0] * c[1] - b[1] * c[0],
+        ];
+        (a[0] * cross[0] + a[1] * cross[1] + a[2] * cross[2]) / 6.0
+    }
+
+    let n = VOLUME_SEGMENTS;
+    let mut sum = crate::core::Sum::new();
+    for (index, face) in sample_surface(space, n).iter().enumerate() {
+        // The face for value 0 points the other way than the face for value 1.
+        let sign = if index % 2 == 0 { -1.0 } else { 1.0 };
+        for i in 0..n {
+            for j in 0..n {
+                let p00 = &face[i * (n + 1) + j];
+                let p10 = &face[(i + 1) * (n + 1) + j];
+                let p01 = &face[i * (n + 1) + j + 1];
+                let p11 = &face[(i + 1) * (n + 1) + j + 1];
+                sum += sign * signed_volume(p00, p10, p11);
+                sum += sign * signed_volume(p00, p11, p01);
+            }
+        }
+    }
+
+    Some(sum.value().abs())
+}
+
+/// Compute the fraction of the first color space's gamut that is also within
+/// the second color space's gamut.
+///
+/// This function samples a regular grid in Oklab that covers the first color
+/// space's gamut and counts how many of the in-gamut points also are in gamut
+/// for the second color space. If the second color space is unbounded, the
+/// coverage is 1.
+#[cfg(feature = "gamut")]
+pub(crate) fn gamut_coverage(space: ColorSpace, other: ColorSpace) -> Option<Float> {
+    if !space.is_bounded() {
+        return None;
+    }
+
+    // Determine the bounding box of the gamut.
+    let mut min = [Float::INFINITY; 3];
+    let mut max = [Float::NEG_INFINITY; 3];
+    for face in sample_surface(space, 16) {
+        for vertex in face {
+            for c in 0..3 {
+                min[c] = min[c].min(vertex[c]);
+                max[c] = max[c].max(vertex[c]);
+            }
+        }
+    }
+
+    let step = 1.0 / COVERAGE_RESOLUTION;
+    let steps = |c: usize| ((max[c] - min[c]) * COVERAGE_RESOLUTION).ceil() as usize + 2;
+    let (mut inside, mut covered) = (0_usize, 0_usize);
+    for i in 0..steps(0) {
+        for j in 0..steps(1) {
+            for k in 0..steps(2) {
+                let oklab = [
+                    min[0] + (i as Float - 0.5) * step,
+                    min[1] + (j as Float - 0.5) * step,
+                    min[2] + (k as Float - 0.5) * step,
+                ];
+                if in_gamut(space, &convert(ColorSpace::Oklab, space, &oklab)) {
+                    inside += 1;
+                    if in_gamut(other, &convert(ColorSpace::Oklab, other, &oklab)) {
+                        covered += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    Some(covered as Float / inside as Float)
+}
+
+// ====================================================================================================================
+
 #[cfg(test)]
 mod test {
     use super::to_gamut;
@@ -533,6 +659,26 @@ mod test {
         );
     }
 
+    #[cfg(feature = "gamut")]
+    #[test]
+    fn test_gamut_volume() {
+        use super::{gamut_coverage, gamut_volume};
+        use ColorSpace::*;
+
+        let srgb = gamut_volume(Srgb).unwrap();
+        let p3 = gamut_volume(DisplayP3).unwrap();
+        let rec2020 = gamut_volume(Rec2020).unwrap();
+        assert!(srgb < p3 && p3 < rec2020);
+        assert!((gamut_volume(LinearSrgb).unwrap() - srgb).abs() / srgb < 1e-3);
+        assert_eq!(gamut_volume(Oklab), None);
+
+        assert_eq!(gamut_coverage(Srgb, DisplayP3), Some(1.0));
+        assert_eq!(gamut_coverage(Srgb, Oklch), Some(1.0));
+        assert_eq!(gamut_coverage(Xyz, Srgb), None);
+        let coverage = gamut_coverage(DisplayP3, Srgb).unwrap();
+        assert!((coverage - srgb / p3).abs() < 0.005);
+    }
+
     #[cfg(feature = "gamut")]
     #[test]
     fn test_gamut_iterator() {
@@ -13,7 +13,7 @@ pub(crate) use contrast::{
 };
 
 // conversion
-pub(crate) use conversion::{convert, from_24bit, to_24bit};
+pub(crate) use conversion::{convert, convert_approximately, from_24bit, to_24bit};
 
 // difference
 pub use difference::HueInterpolation;
@@ -30,10 +30,12 @@ pub(crate) use equality::{is_achromatic, is_achromatic_chroma_hue, normalize, to
 // gamut
 pub(crate) use gamut::{clip, in_gamut, to_gamut};
 #[cfg(feature = "gamut")]
+pub(crate) use gamut::{gamut_coverage, gamut_volume};
+#[cfg(feature = "gamut")]
 pub use gamut::{GamutTraversal, GamutTraversalStep};
 
 // math
-#[cfg(all(feature = "gamut", test))]
+#[cfg(feature = "gamut")]
 pub(crate) use math::sum::Sum;
 #[cfg(feature = "gamut")]
 pub(crate) use math::sum::ThreeSum;
@@ -44,3 +46,5 @@ pub use space::ColorSpace;
 
 // string
 pub(crate) use string::{format, parse};
+#[cfg(feature = "wasm")]
+pub(crate) use string::{parse_space, space_name};
@@ -179,12 +179,70 @@ impl ColorSpace {
         crate::gamut::GamutTraversal::new(*self, edge_length)
     }
 
+    /// Compute the volume of this color space's gamut in Oklab. <i
+    /// class=gamut-only>Gamut only</i>
+    ///
+    /// For bounded or RGB color spaces, this method returns the volume of the
+    /// color space's gamut, measured in Oklab's units. The method computes
+    /// the volume from a triangulation of the RGB cube's surface with 64
+    /// segments per edge. Its result is accurate to about 0.1%.
+    ///
+    /// ```
+    /// # use prettypretty::ColorSpace;
+    /// let srgb = ColorSpace::Srgb.gamut_volume().unwrap();
+    /// let p3 = ColorSpace::DisplayP3.gamut_volume().unwrap();
+    /// assert_eq!((100.0 * (p3 / srgb - 1.0)).round(), 35.0);
+    /// ```
+    ///
+    /// If this color space is not bounded, this method returns `None`.
+    #[cfg(feature = "gamut")]
+    pub fn gamut_volume(&self) -> Option<crate::Float> {
+        crate::core::gamut_volume(*self)
+    }
+
+    /// Compute the fraction of this color space's gamut that also is in the
+    /// other color space's gamut. <i class=gamut-only>Gamut only</i>
+    ///
+    /// For bounded or RGB color spaces, this method samples this color space's
+    /// gamut on a regular grid in Oklab with a resolution of 0.01 and returns
+    /// the fraction of in-gamut samples that also are in gamut for the other
+    /// color space. Its result is accurate to about 0.5%. If the other color
+    /// space is not bounded, the result is 1.
+    ///
+    /// ```
+    /// # use prettypretty::ColorSpace;
+    /// assert_eq!(ColorSpace::Srgb.coverage(ColorSpace::DisplayP3), Some(1.0));
+    /// let coverage = ColorSpace::DisplayP3.coverage(ColorSpace::Srgb).unwrap();
+    /// assert_eq!((100.0 * coverage).round(), 74.0);
+    /// ```
+    ///
+    /// If this color space is not bounded, this method returns `None`.
+    #[cfg(feature = "gamut")]
+    pub fn coverage(&self, other: ColorSpace) -> Option<crate::Float> {
+        crate::core::gamut_coverage(*self, other)
+    }
+
     /// Create a human-readable representation for this color space. <i
     /// class=python-only>Python only!</i>
     #[cfg(feature = "pyffi")]
     pub fn __str__(&self) -> String {
         format!("{}", self)
     }
+
+    /// Reduce this color space for pickling. <i class=python-only>Python
+    /// only!</i>
+    ///
+    /// Since enum variants cannot be instantiated from Python, this method
+    /// restores color spaces by looking up the variant on the type.
+    #[cfg(feature = "pyffi")]
+    #[allow(clippy::type_complexity)]
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, (Bound<'py, pyo3::types::PyType>, String))> {
+        let py = slf.py();
+        let getattr = py.import("builtins")?.getattr("getattr")?;
+        Ok((getattr, (py.get_type::<Self>(), format!("{:?}", slf.get()))))
+    }
 }
 
 impl std::fmt::Display for ColorSpace {
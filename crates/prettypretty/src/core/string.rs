@@ -1,22 +1,40 @@
-use crate::error::ColorFormatError;
+use crate::error::{ColorFormatError, ColorFormatErrorKind};
 use crate::{ColorSpace, Float};
 
+const FORMATS: &str = "`#`, `rgb:`, `color()`, `oklab()`, or `oklch()`";
+
+/// Get the empty fragment at the end of the string.
+#[inline]
+fn end(s: &str) -> &str {
+    &s[s.len()..]
+}
+
 /// Parse a 24-bit color in hashed hexadecimal format. If successful, this
 /// function returns the three coordinates as unsigned bytes. It transparently
 /// handles single-digit coordinates.
 fn parse_hashed(s: &str) -> Result<[u8; 3], ColorFormatError> {
+    use ColorFormatErrorKind::*;
+
     if !s.starts_with('#') {
-        return Err(ColorFormatError::UnknownFormat);
+        return Err(ColorFormatError::at(UnknownFormat, s, s, FORMATS));
     } else if s.len() != 4 && s.len() != 7 {
-        return Err(ColorFormatError::UnexpectedCharacters);
+        return Err(ColorFormatError::at(
+            UnexpectedCharacters,
+            s,
+            &s[1..],
+            "3 or 6 hexadecimal digits",
+        ));
     }
 
     fn parse_coordinate(s: &str, index: usize) -> Result<u8, ColorFormatError> {
         let factor = s.len() / 3;
         let t = s
             .get(1 + factor * index..1 + factor * (index + 1))
-            .ok_or(ColorFormatError::UnexpectedCharacters)?;
-        let n = u8::from_str_radix(t, 16).map_err(|_| ColorFormatError::MalformedHex)?;
+            .ok_or_else(|| {
+                ColorFormatError::at(UnexpectedCharacters, s, &s[1..], "ASCII characters only")
+            })?;
+        let n = u8::from_str_radix(t, 16)
+            .map_err(|_| ColorFormatError::at(MalformedHex, s, t, "hexadecimal digits"))?;
 
         Ok(if factor == 1 { 16 * n + n } else { n })
     }
@@ -33,29 +51,50 @@ fn parse_hashed(s: &str) -> Result<[u8; 3], ColorFormatError> {
 /// three pairs with the number of hexadecimal digits and the numeric value for
 /// each coordinate.
 pub(crate) fn parse_x(s: &str) -> Result<[Float; 3], ColorFormatError> {
-    if !s.starts_with("rgb:") {
-        return Err(ColorFormatError::UnknownFormat);
-    }
+    use ColorFormatErrorKind::*;
+
+    let Some(body) = s.strip_prefix("rgb:") else {
+        return Err(ColorFormatError::at(UnknownFormat, s, s, FORMATS));
+    };
 
-    fn parse_coordinate(s: Option<&str>, _: usize) -> Result<Float, ColorFormatError> {
-        let t = s.ok_or(ColorFormatError::MissingCoordinate)?;
+    let parse_coordinate = |t: Option<&str>| -> Result<Float, ColorFormatError> {
+        let t = t.ok_or_else(|| {
+            ColorFormatError::at(MissingCoordinate, s, end(s), "`/` and another coordinate")
+        })?;
         if t.is_empty() {
-            return Err(ColorFormatError::MissingCoordinate);
+            return Err(ColorFormatError::at(
+                MissingCoordinate,
+                s,
+                t,
+                "1-4 hexadecimal digits",
+            ));
         } else if 4 < t.len() {
-            return Err(ColorFormatError::OversizedCoordinate);
+            return Err(ColorFormatError::at(
+                OversizedCoordinate,
+                s,
+                t,
+                "1-4 hexadecimal digits",
+            ));
         }
 
-        let n = u16::from_str_radix(t, 16).map_err(|_| ColorFormatError::MalformedHex)?;
+        let n = u16::from_str_radix(t, 16)
+            .map_err(|_| ColorFormatError::at(MalformedHex, s, t, "hexadecimal digits"))?;
         Ok(n as Float / (16_u32.pow(t.len() as u32) - 1) as Float)
-    }
+    };
 
-    // SAFETY: we tested for just that prefix above.
-    let mut iter = s.strip_prefix("rgb:").unwrap().split('/');
-    let c1 = parse_coordinate(iter.next(), 0)?;
-    let c2 = parse_coordinate(iter.next(), 1)?;
-    let c3 = parse_coordinate(iter.next(), 2)?;
-    if iter.next().is_some() {
-        return Err(ColorFormatError::TooManyCoordinates);
+    let mut iter = body.split('/');
+    let c1 = parse_coordinate(iter.next())?;
+    let c2 = parse_coordinate(iter.next())?;
+    let c3 = parse_coordinate(iter.next())?;
+    if let Some(t) = iter.next() {
+        // Include the separating slash in the offending fragment.
+        let offset = t.as_ptr() as usize - s.as_ptr() as usize;
+        return Err(ColorFormatError::at(
+            TooManyCoordinates,
+            s,
+            &s[offset - 1..],
+            "end after 3 coordinates",
+        ));
     }
 
     Ok([c1, c2, c3])
@@ -82,6 +121,7 @@ const COLOR_SPACES: [(&str, ColorSpace); 11] = [
 /// `--linear-display-p3`, `--linear-rec2020`, `--oklrab`, and `--oklrch`.
 /// Coordinates must not have units including `%`.
 fn parse_css(s: &str) -> Result<(ColorSpace, [Float; 3]), ColorFormatError> {
+    use ColorFormatErrorKind::*;
     use ColorSpace::*;
 
     // Munge CSS function name
@@ -90,16 +130,22 @@ fn parse_css(s: &str) -> Result<(ColorSpace, [Float; 3]), ColorFormatError> {
         .map(|r| (Some(Oklab), r))
         .or_else(|| s.strip_prefix("oklch").map(|r| (Some(Oklch), r)))
         .or_else(|| s.strip_prefix("color").map(|r| (None, r)))
-        .ok_or(ColorFormatError::UnknownFormat)?;
+        .ok_or_else(|| {
+            let name = s
+                .split(|c: char| c == '(' || c.is_whitespace())
+                .next()
+                .unwrap_or(s);
+            ColorFormatError::at(UnknownFormat, s, name, FORMATS)
+        })?;
 
     // Munge parentheses after trimming leading whitespace
+    let rest = rest.trim_start();
     let rest = rest
-        .trim_start()
         .strip_prefix('(')
-        .ok_or(ColorFormatError::NoOpeningParenthesis)
+        .ok_or_else(|| ColorFormatError::at(NoOpeningParenthesis, s, &rest[..0], "`(`"))
         .and_then(|rest| {
             rest.strip_suffix(')')
-                .ok_or(ColorFormatError::NoClosingParenthesis)
+                .ok_or_else(|| ColorFormatError::at(NoClosingParenthesis, s, end(s), "`)`"))
         })?;
 
     let (space, body) = if let Some(s) = space {
@@ -111,22 +157,50 @@ fn parse_css(s: &str) -> Result<(ColorSpace, [Float; 3]), ColorFormatError> {
             .iter()
             .filter_map(|(p, s)| rest.strip_prefix(p).map(|r| (*s, r)))
             .next() // Take first (and only) result
-            .ok_or(ColorFormatError::UnknownColorSpace)?
+            .ok_or_else(|| {
+                let name = rest.split_whitespace().next().unwrap_or(&rest[..0]);
+                ColorFormatError::at(
+                    UnknownColorSpace,
+                    s,
+                    name,
+                    "`srgb`, `linear-srgb`, `display-p3`, `rec2020`, `xyz`, `xyz-d65`, or `xyz-d50`",
+                )
+            })?
     };
 
+    // The empty fragment right before the closing parenthesis.
+    let close = &body[body.len()..];
+
     #[inline]
-    fn parse_coordinate(s: Option<&str>, _: usize) -> Result<Float, ColorFormatError> {
-        s.ok_or(ColorFormatError::MissingCoordinate)
-            .and_then(|t| t.parse().map_err(|_| ColorFormatError::MalformedFloat))
+    fn parse_coordinate(s: &str, close: &str, t: Option<&str>) -> Result<Float, ColorFormatError> {
+        // CSS uses `none` for missing components, which `format` emits for
+        // not-a-number.
+        match t {
+            Some("none") => Ok(Float::NAN),
+            Some(t) => t.parse().map_err(|_| {
+                ColorFormatError::at(MalformedFloat, s, t, "floating point number or `none`")
+            }),
+            None => Err(ColorFormatError::at(
+                MissingCoordinate,
+                s,
+                close,
+                "3 coordinates",
+            )),
+        }
     }
 
     // Munge coordinates. Iterator eats all leading or trailing white space.
     let mut iter = body.split_whitespace();
-    let c1 = parse_coordinate(iter.next(), 0)?;
-    let c2 = parse_coordinate(iter.next(), 1)?;
-    let c3 = parse_coordinate(iter.next(), 2)?;
-    if iter.next().is_some() {
-        return Err(ColorFormatError::TooManyCoordinates);
+    let c1 = parse_coordinate(s, close, iter.next())?;
+    let c2 = parse_coordinate(s, close, iter.next())?;
+    let c3 = parse_coordinate(s, close, iter.next())?;
+    if let Some(t) = iter.next() {
+        return Err(ColorFormatError::at(
+            TooManyCoordinates,
+            s,
+            t,
+            "`)` after 3 coordinates",
+        ));
     }
 
     Ok((space, [c1, c2, c3]))
@@ -143,26 +217,33 @@ fn parse_css(s: &str) -> Result<(ColorSpace, [Float; 3]), ColorFormatError> {
 /// space-separated arguments. Before trying to parse either of these formats,
 /// this function trims leading and trailing white space and converts ASCII
 /// letters to lowercase. However, a valid color string may still contain
-/// Unicode white space characters and hence needn't be all ASCII.
+/// Unicode white space characters and hence needn't be all ASCII. Errors
+/// locate the offending fragment in the original, untrimmed string.
 pub(crate) fn parse(s: &str) -> Result<(ColorSpace, [Float; 3]), ColorFormatError> {
+    let original = s;
     let lowercase = s.trim().to_ascii_lowercase(); // Keep around for fn scope
     let s = lowercase.as_str();
 
-    if s.starts_with('#') {
-        let [c1, c2, c3] = parse_hashed(s)?;
-        Ok((
-            ColorSpace::Srgb,
-            [
-                c1 as Float / 255.0,
-                c2 as Float / 255.0,
-                c3 as Float / 255.0,
-            ],
-        ))
+    let result = if s.starts_with('#') {
+        parse_hashed(s).map(|[c1, c2, c3]| {
+            (
+                ColorSpace::Srgb,
+                [
+                    c1 as Float / 255.0,
+                    c2 as Float / 255.0,
+                    c3 as Float / 255.0,
+                ],
+            )
+        })
     } else if s.starts_with("rgb:") {
-        Ok((ColorSpace::Srgb, parse_x(s)?))
+        parse_x(s).map(|coordinates| (ColorSpace::Srgb, coordinates))
     } else {
         parse_css(s)
-    }
+    };
+
+    // Lowercasing ASCII letters preserves byte offsets, trimming does not.
+    let lead = original.len() - original.trim_start().len();
+    result.map_err(|e| e.relocate(original, lead))
 }
 
 // --------------------------------------------------------------------------------------------------------------------
@@ -232,11 +313,42 @@ pub(crate) fn format(
     f.write_str(")")
 }
 
+/// Look up the color space with the given CSS name.
+///
+/// Besides the names accepted by the `color()` function, this function also
+/// accepts `oklab` and `oklch`.
+#[cfg(feature = "wasm")]
+pub(crate) fn parse_space(name: &str) -> Option<ColorSpace> {
+    match name {
+        "oklab" => Some(ColorSpace::Oklab),
+        "oklch" => Some(ColorSpace::Oklch),
+        _ => COLOR_SPACES
+            .iter()
+            .find(|(css, _)| *css == name)
+            .map(|(_, space)| *space),
+    }
+}
+
+/// Get the CSS name for the color space.
+#[cfg(feature = "wasm")]
+pub(crate) fn space_name(space: ColorSpace) -> &'static str {
+    match space {
+        ColorSpace::Oklab => "oklab",
+        ColorSpace::Oklch => "oklch",
+        // Unwrap is safe b/c all other color spaces have a CSS name.
+        _ => COLOR_SPACES
+            .iter()
+            .find(|(_, candidate)| *candidate == space)
+            .map(|(css, _)| *css)
+            .unwrap(),
+    }
+}
+
 // ====================================================================================================================
 
 #[cfg(test)]
 mod test {
-    use super::{parse, parse_css, parse_hashed, parse_x, ColorFormatError};
+    use super::{parse, parse_css, parse_hashed, parse_x, ColorFormatError, ColorFormatErrorKind};
     use crate::ColorSpace::*;
     use crate::Float;
 
@@ -244,21 +356,30 @@ mod test {
     fn test_parse_hashed() -> Result<(), ColorFormatError> {
         assert_eq!(parse_hashed("#123")?, [0x11_u8, 0x22, 0x33]);
         assert_eq!(parse_hashed("#112233")?, [0x11_u8, 0x22, 0x33]);
-        assert_eq!(parse_hashed("fff"), Err(ColorFormatError::UnknownFormat));
         assert_eq!(
-            parse_hashed("#ff"),
-            Err(ColorFormatError::UnexpectedCharacters)
+            parse_hashed("fff").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::UnknownFormat)
         );
         assert_eq!(
-            parse_hashed("#💩00"),
-            Err(ColorFormatError::UnexpectedCharacters)
+            parse_hashed("#ff").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::UnexpectedCharacters)
+        );
+        assert_eq!(
+            parse_hashed("#💩00").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::UnexpectedCharacters)
         );
 
         let result = parse_hashed("#0g0");
-        assert!(matches!(result, Err(ColorFormatError::MalformedHex)));
+        assert_eq!(
+            result.map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::MalformedHex)
+        );
 
         let result = parse_hashed("#00g");
-        assert!(matches!(result, Err(ColorFormatError::MalformedHex)));
+        assert_eq!(
+            result.map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::MalformedHex)
+        );
 
         Ok(())
     }
@@ -282,32 +403,35 @@ mod test {
             ]
         );
         assert_eq!(
-            parse_x("rgbi:0.1/0.1/0.1"),
-            Err(ColorFormatError::UnknownFormat)
+            parse_x("rgbi:0.1/0.1/0.1").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::UnknownFormat)
+        );
+        assert_eq!(
+            parse_x("rgb:0").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::MissingCoordinate)
         );
-        assert_eq!(parse_x("rgb:0"), Err(ColorFormatError::MissingCoordinate));
         assert_eq!(
-            parse_x("rgb:0//2"),
-            Err(ColorFormatError::MissingCoordinate)
+            parse_x("rgb:0//2").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::MissingCoordinate)
         );
         assert_eq!(
-            parse_x("rgb:1/12345/1"),
-            Err(ColorFormatError::OversizedCoordinate)
+            parse_x("rgb:1/12345/1").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::OversizedCoordinate)
         );
         assert_eq!(
-            parse_x("rgb:1/2/3/4"),
-            Err(ColorFormatError::TooManyCoordinates)
+            parse_x("rgb:1/2/3/4").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::TooManyCoordinates)
         );
 
         let result = parse_x("rgb:f/g/f");
-        assert!(matches!(result, Err(ColorFormatError::MalformedHex)));
+        assert_eq!(
+            result.map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::MalformedHex)
+        );
 
         assert_eq!(
             parse("   RGB:00/55/aa   ")?,
-            (
-                Srgb,
-                [0.0 as Float, 0.33333333333333333, 0.6666666666666666]
-            )
+            (Srgb, [0.0 as Float, 0.3333333333333333, 0.6666666666666666])
         );
 
         Ok(())
@@ -328,33 +452,37 @@ mod test {
             parse_css("color  (  --linear-display-p3   1  1.123  0.3333   )"),
             Ok((LinearDisplayP3, [1.0, 1.123, 0.3333]))
         );
+        assert!(matches!(
+            parse_css("oklch(0.5 0 none)"),
+            Ok((Oklch, [_, _, h])) if h.is_nan()
+        ));
         assert_eq!(
-            parse_css("whatever(1 1 1)"),
-            Err(ColorFormatError::UnknownFormat)
+            parse_css("whatever(1 1 1)").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::UnknownFormat)
         );
         assert_eq!(
-            parse_css("colorsrgb 1 1 1)"),
-            Err(ColorFormatError::NoOpeningParenthesis)
+            parse_css("colorsrgb 1 1 1)").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::NoOpeningParenthesis)
         );
         assert_eq!(
-            parse_css("color(srgb 1 1 1"),
-            Err(ColorFormatError::NoClosingParenthesis)
+            parse_css("color(srgb 1 1 1").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::NoClosingParenthesis)
         );
         assert_eq!(
-            parse_css("color(nemo 1 1 1)"),
-            Err(ColorFormatError::UnknownColorSpace)
+            parse_css("color(nemo 1 1 1)").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::UnknownColorSpace)
+        );
+        assert_eq!(
+            parse_css("color(srgb abc 1 1)").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::MalformedFloat)
         );
-        assert!(matches!(
-            parse_css("color(srgb abc 1 1)"),
-            Err(ColorFormatError::MalformedFloat)
-        ));
         assert_eq!(
-            parse_css("color(srgb 1)"),
-            Err(ColorFormatError::MissingCoordinate)
+            parse_css("color(srgb 1)").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::MissingCoordinate)
         );
         assert_eq!(
-            parse_css("color(srgb 1 1 1 1)"),
-            Err(ColorFormatError::TooManyCoordinates)
+            parse_css("color(srgb 1 1 1 1)").map_err(|e| e.kind()),
+            Err(ColorFormatErrorKind::TooManyCoordinates)
         );
 
         assert_eq!(
@@ -367,6 +495,77 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_location() {
+        fn locate(s: &str) -> (ColorFormatErrorKind, usize, String, &'static str) {
+            let error = parse(s).unwrap_err();
+            assert_eq!(error.input(), s);
+            (
+                error.kind(),
+                error.offset(),
+                error.fragment().to_string(),
+                error.expected().unwrap(),
+            )
+        }
+
+        use ColorFormatErrorKind::*;
+        let (kind, offset, fragment, _) = locate("  #0g0");
+        assert_eq!((kind, offset, fragment.as_str()), (MalformedHex, 4, "g"));
+        let (kind, offset, fragment, _) = locate("rgb:1/12345/1");
+        assert_eq!(
+            (kind, offset, fragment.as_str()),
+            (OversizedCoordinate, 6, "12345")
+        );
+        let (kind, offset, fragment, _) = locate("rgb:0//2");
+        assert_eq!(
+            (kind, offset, fragment.as_str()),
+            (MissingCoordinate, 6, "")
+        );
+        let (kind, offset, fragment, _) = locate("rgb:1/2/3/4");
+        assert_eq!(
+            (kind, offset, fragment.as_str()),
+            (TooManyCoordinates, 9, "/4")
+        );
+        let (kind, offset, fragment, _) = locate(" whatever(1 1 1)");
+        assert_eq!(
+            (kind, offset, fragment.as_str()),
+            (UnknownFormat, 1, "whatever")
+        );
+        let (kind, offset, fragment, expected) = locate("color(srgb 1 1 1");
+        assert_eq!(
+            (kind, offset, fragment.as_str(), expected),
+            (NoClosingParenthesis, 16, "", "`)`")
+        );
+        let (kind, offset, fragment, _) = locate("color( nemo 1 1 1)");
+        assert_eq!(
+            (kind, offset, fragment.as_str()),
+            (UnknownColorSpace, 7, "nemo")
+        );
+        let (kind, offset, fragment, _) = locate("color(srgb 1)");
+        assert_eq!(
+            (kind, offset, fragment.as_str()),
+            (MissingCoordinate, 12, "")
+        );
+        let (kind, offset, fragment, _) = locate("oklab(1 2 3 4)");
+        assert_eq!(
+            (kind, offset, fragment.as_str()),
+            (TooManyCoordinates, 12, "4")
+        );
+
+        assert_eq!(
+            parse("OKLCH(0.5 X 1)").unwrap_err().to_string(),
+            "color format coordinates should be floating point numbers but are not
+    OKLCH(0.5 X 1)
+              ^ expected floating point number or `none`"
+        );
+        assert_eq!(
+            parse("#💩00").unwrap_err().to_string(),
+            "color format should contain only valid ASCII characters
+    #💩00
+     ^^^ expected ASCII characters only"
+        );
+    }
+
     #[test]
     fn test_format() {
         // Color as Display directly invokes format().
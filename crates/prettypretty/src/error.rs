@@ -1,4 +1,9 @@
 //! Utility module with prettypretty's errors.
+//!
+//! Most functions and methods return the specific error type describing what
+//! went wrong. To facilitate using `?` across calls that fail with different
+//! error types, [`Error`] wraps all of them, with [`From`] conversions for
+//! each, and [`Result`] is the corresponding result type.
 
 #[cfg(feature = "pyffi")]
 use pyo3::{exceptions::PyValueError, prelude::*};
@@ -41,6 +46,8 @@ impl std::fmt::Display for OutOfBoundsError {
     }
 }
 
+impl std::error::Error for OutOfBoundsError {}
+
 #[cfg(feature = "pyffi")]
 impl From<OutOfBoundsError> for PyErr {
     fn from(value: OutOfBoundsError) -> Self {
@@ -50,13 +57,9 @@ impl From<OutOfBoundsError> for PyErr {
 
 // ====================================================================================================================
 
-/// An erroneous color format.
-///
-/// The enumeration started out with additional information but PyO3 only
-/// supports unit variants without associated state. Thankfully, the attendant
-/// loss of information is rather limited.
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub enum ColorFormatError {
+/// The kinds of erroneous color formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorFormatErrorKind {
     /// A color format that does not start with a known prefix such as `#` or
     /// `rgb:`.
     UnknownFormat,
@@ -109,9 +112,9 @@ pub enum ColorFormatError {
     MalformedThemeColor,
 }
 
-impl std::fmt::Display for ColorFormatError {
+impl std::fmt::Display for ColorFormatErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        use ColorFormatError::*;
+        use ColorFormatErrorKind::*;
 
         match self {
             UnknownFormat => f.write_str(
@@ -152,6 +155,128 @@ impl std::fmt::Display for ColorFormatError {
     }
 }
 
+/// An erroneous color format.
+///
+/// Besides the [kind](ColorFormatErrorKind) of error, this struct records the
+/// input string, the byte offset and length of the offending fragment, and a
+/// hint for what was expected instead. Its display renders a diagnostic with
+/// carets underneath the offending fragment:
+///
+/// ```
+/// # use prettypretty::Color;
+/// # use prettypretty::error::ColorFormatErrorKind;
+/// # use std::str::FromStr;
+/// let error = Color::from_str("color(srgb 1 0..1 0)").unwrap_err();
+/// assert_eq!(error.kind(), ColorFormatErrorKind::MalformedFloat);
+/// assert_eq!(error.offset(), 13);
+/// assert_eq!(error.fragment(), "0..1");
+/// assert_eq!(
+///     error.to_string(),
+///     "color format coordinates should be floating point numbers but are not
+///     color(srgb 1 0..1 0)
+///                  ^^^^ expected floating point number or `none`"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColorFormatError {
+    kind: ColorFormatErrorKind,
+    input: String,
+    offset: usize,
+    length: usize,
+    expected: Option<&'static str>,
+}
+
+impl ColorFormatError {
+    /// Create a new color format error without location.
+    pub fn new(kind: ColorFormatErrorKind) -> Self {
+        Self {
+            kind,
+            input: String::new(),
+            offset: 0,
+            length: 0,
+            expected: None,
+        }
+    }
+
+    /// Create a new color format error for the fragment of the input. The
+    /// fragment must be a subslice of the input.
+    pub(crate) fn at(
+        kind: ColorFormatErrorKind,
+        input: &str,
+        fragment: &str,
+        expected: &'static str,
+    ) -> Self {
+        let offset = (fragment.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        debug_assert!(offset + fragment.len() <= input.len());
+
+        Self {
+            kind,
+            input: input.to_string(),
+            offset,
+            length: fragment.len(),
+            expected: Some(expected),
+        }
+    }
+
+    /// Move this error to the given input, which contains this error's input
+    /// at the given offset.
+    pub(crate) fn relocate(mut self, input: &str, offset: usize) -> Self {
+        if self.expected.is_some() {
+            self.input = input.to_string();
+            self.offset += offset;
+        }
+        self
+    }
+
+    /// Get the kind of error.
+    pub fn kind(&self) -> ColorFormatErrorKind {
+        self.kind
+    }
+
+    /// Get the input string, which is empty if the error has no location.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Get the byte offset of the offending fragment.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Get the offending fragment, which may be empty for missing tokens.
+    pub fn fragment(&self) -> &str {
+        &self.input[self.offset..self.offset + self.length]
+    }
+
+    /// Get the hint for what was expected instead.
+    pub fn expected(&self) -> Option<&'static str> {
+        self.expected
+    }
+}
+
+impl From<ColorFormatErrorKind> for ColorFormatError {
+    fn from(value: ColorFormatErrorKind) -> Self {
+        Self::new(value)
+    }
+}
+
+impl std::fmt::Display for ColorFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.kind, f)?;
+        let Some(expected) = self.expected else {
+            return Ok(());
+        };
+
+        let column = self.input[..self.offset].chars().count();
+        let width = self.fragment().chars().count().max(1);
+        write!(
+            f,
+            "\n    {}\n    {:column$}{:^<width$} expected {}",
+            self.input, "", "", expected
+        )
+    }
+}
+
 impl std::error::Error for ColorFormatError {}
 
 #[cfg(feature = "pyffi")]
@@ -188,6 +313,159 @@ impl From<HiResColorantError> for PyErr {
 
 // ====================================================================================================================
 
+/// An error indicating a style or colorant without equivalent in another
+/// crate's styling model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The default color.
+    DefaultColor,
+    /// A high-resolution color.
+    HiResColor,
+    /// Explicitly disabled text attributes.
+    DisabledAttributes,
+}
+
+impl std::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let what = match self {
+            Self::DefaultColor => "default color",
+            Self::HiResColor => "high-resolution color",
+            Self::DisabledAttributes => "disabled text attributes",
+        };
+
+        write!(f, "unable to convert {} to other styling model", what)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+// ====================================================================================================================
+
+/// An error indicating unusable samples for a spectral distribution.
+///
+/// [`SampledDistribution`](crate::spectrum::SampledDistribution) returns this
+/// error when the measured samples cannot be resampled to 1nm resolution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SampleError {
+    /// There are fewer than two samples.
+    TooFewSamples,
+    /// A wavelength or value is negative, infinite, or not a number.
+    InvalidValue,
+    /// The wavelengths are not strictly increasing.
+    UnorderedWavelengths,
+    /// The samples do not span a whole nanometer.
+    EmptyRange,
+}
+
+impl std::fmt::Display for SampleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let what = match self {
+            Self::TooFewSamples => "fewer than two samples",
+            Self::InvalidValue => "invalid wavelength or value",
+            Self::UnorderedWavelengths => "wavelengths are not strictly increasing",
+            Self::EmptyRange => "samples do not span a whole nanometer",
+        };
+
+        write!(f, "unusable spectral samples: {}", what)
+    }
+}
+
+impl std::error::Error for SampleError {}
+
+// ====================================================================================================================
+
+/// An error indicating malformed translator tables.
+///
+/// [`Translator::from_bytes`](crate::Translator::from_bytes) returns this
+/// error when the bytes were not created by
+/// [`Translator::to_bytes`](crate::Translator::to_bytes) of a compatible
+/// version of this crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableFormatError {
+    /// The bytes do not start with the expected signature.
+    BadSignature,
+    /// The tables use a different format version or floating point type.
+    Incompatible,
+    /// The bytes end before the tables do.
+    UnexpectedEnd,
+    /// A value is out of range.
+    InvalidValue,
+    /// The bytes continue after the tables end.
+    TrailingBytes,
+}
+
+impl std::fmt::Display for TableFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let what = match self {
+            Self::BadSignature => "unrecognized signature",
+            Self::Incompatible => "incompatible format version",
+            Self::UnexpectedEnd => "unexpected end of data",
+            Self::InvalidValue => "invalid value",
+            Self::TrailingBytes => "trailing bytes",
+        };
+
+        write!(f, "malformed translator tables: {}", what)
+    }
+}
+
+impl std::error::Error for TableFormatError {}
+
+#[cfg(feature = "pyffi")]
+impl From<TableFormatError> for PyErr {
+    fn from(value: TableFormatError) -> Self {
+        PyValueError::new_err(value.to_string())
+    }
+}
+
+// ====================================================================================================================
+
+/// An error indicating a malformed color theme file.
+///
+/// This error records the file format, e.g., `TOML`, and a description of
+/// what is malformed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThemeFormatError {
+    format: &'static str,
+    message: String,
+}
+
+impl ThemeFormatError {
+    /// Create a new theme format error.
+    pub fn new(format: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            format,
+            message: message.into(),
+        }
+    }
+
+    /// Get the file format.
+    pub fn format(&self) -> &'static str {
+        self.format
+    }
+
+    /// Get the description of what is malformed.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for ThemeFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "malformed {} color theme: {}", self.format, self.message)
+    }
+}
+
+impl std::error::Error for ThemeFormatError {}
+
+#[cfg(feature = "pyffi")]
+impl From<ThemeFormatError> for PyErr {
+    fn from(value: ThemeFormatError) -> Self {
+        PyValueError::new_err(value.to_string())
+    }
+}
+
+// ====================================================================================================================
+
 use crate::theme::ThemeEntry;
 
 /// The kinds of errors while querying a terminal for its color theme.
@@ -248,3 +526,146 @@ impl std::error::Error for ThemeError {
         self.source.as_deref().map(|e| e as _)
     }
 }
+
+// ====================================================================================================================
+
+/// Any of prettypretty's errors.
+///
+/// This enumeration wraps prettypretty's more specific errors as well as I/O
+/// errors. It is transparent, i.e., its display and source are those of the
+/// wrapped error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// An index value out of bounds.
+    OutOfBounds(OutOfBoundsError),
+    /// A malformed color format.
+    ColorFormat(ColorFormatError),
+    /// A high-resolution colorant that cannot be displayed.
+    HiResColorant(HiResColorantError),
+    /// A style or colorant without equivalent in another crate.
+    Conversion(ConversionError),
+    /// Invalid samples for a spectral distribution.
+    Sample(SampleError),
+    /// Malformed bytes for a translator's tables.
+    TableFormat(TableFormatError),
+    /// A malformed color theme file.
+    ThemeFormat(ThemeFormatError),
+    /// A failed query for the terminal's color theme.
+    Theme(ThemeError),
+    /// A failed I/O operation.
+    Io(std::io::Error),
+}
+
+/// A result with prettypretty's [`Error`].
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Get the wrapped error.
+    fn inner(&self) -> &(dyn std::error::Error + 'static) {
+        match self {
+            Self::OutOfBounds(error) => error,
+            Self::ColorFormat(error) => error,
+            Self::HiResColorant(error) => error,
+            Self::Conversion(error) => error,
+            Self::Sample(error) => error,
+            Self::TableFormat(error) => error,
+            Self::ThemeFormat(error) => error,
+            Self::Theme(error) => error,
+            Self::Io(error) => error,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.inner(), f)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.inner().source()
+    }
+}
+
+macro_rules! wrap_error {
+    ($($variant:ident($error:ty)),* $(,)?) => {
+        $(
+            impl From<$error> for Error {
+                fn from(value: $error) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+wrap_error!(
+    OutOfBounds(OutOfBoundsError),
+    ColorFormat(ColorFormatError),
+    HiResColorant(HiResColorantError),
+    Conversion(ConversionError),
+    Sample(SampleError),
+    TableFormat(TableFormatError),
+    ThemeFormat(ThemeFormatError),
+    Theme(ThemeError),
+    Io(std::io::Error),
+);
+
+impl From<Error> for std::io::Error {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::Io(error) => error,
+            _ => std::io::Error::other(value),
+        }
+    }
+}
+
+// ====================================================================================================================
+
+#[cfg(test)]
+mod test {
+    use super::{
+        ColorFormatError, ColorFormatErrorKind, Error, OutOfBoundsError, ThemeError, ThemeErrorKind,
+    };
+    use crate::theme::ThemeEntry;
+    use std::error::Error as _;
+
+    fn parse(s: &str) -> super::Result<crate::Color> {
+        Ok(s.parse::<crate::Color>()?)
+    }
+
+    #[test]
+    fn test_error() {
+        let error = parse("#00").unwrap_err();
+        assert!(matches!(
+            &error,
+            Error::ColorFormat(e) if e.kind() == ColorFormatErrorKind::UnexpectedCharacters
+        ));
+        assert_eq!(
+            error.to_string(),
+            "color format should contain only valid ASCII characters\n    \
+            #00\n     ^^ expected 3 or 6 hexadecimal digits"
+        );
+        assert_eq!(
+            ColorFormatError::new(ColorFormatErrorKind::MalformedThemeColor).to_string(),
+            "OSC escape sequence with color is malformed"
+        );
+        assert!(error.source().is_none());
+
+        let error: Error = OutOfBoundsError::new(16_usize, 0..=15).into();
+        assert_eq!(error.to_string(), "16 does not fit into range 0..=15");
+
+        let io = std::io::Error::other("boom");
+        let error: Error = ThemeError::new(
+            ThemeErrorKind::WriteQuery(ThemeEntry::DefaultForeground()),
+            Box::new(io),
+        )
+        .into();
+        assert_eq!(error.source().unwrap().to_string(), "boom");
+
+        let io: std::io::Error = Error::from(std::io::Error::other("bang")).into();
+        assert_eq!(io.to_string(), "bang");
+    }
+}
@@ -0,0 +1,573 @@
+//! Optional module for rendering images in the terminal.
+//!
+//! [`render_image`] scales an [`image::DynamicImage`] to fit a [`Canvas`] of
+//! character cells and writes the result with one of four [`Graphics`]
+//! mechanisms. The kitty graphics protocol and iTerm2's inline images both
+//! transmit the scaled image as PNG and leave color handling to the terminal.
+//! Sixel graphics are limited to a palette, so `render_image` quantizes the
+//! image to the 256 8-bit colors with [`Translator::dither`]. Finally, half
+//! blocks work in every terminal and rely on [`half_blocks`], which dithers
+//! the image to the given fidelity.
+//!
+//! [`Graphics::from_environment`] picks the best mechanism based on
+//! environment variables. With the `tty` feature, [`Graphics::query`] also
+//! asks the terminal whether it supports sixel graphics and [`Canvas::query`]
+//! determines the window and cell sizes.
+//!
+//! ```
+//! # use image::{DynamicImage, Rgb, RgbImage};
+//! # use prettypretty::{OkVersion, Translator};
+//! # use prettypretty::graphics::{render_image, Canvas, Graphics};
+//! # use prettypretty::style::Fidelity;
+//! # use prettypretty::theme::VGA_COLORS;
+//! let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+//! let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(4, 8, Rgb([255, 0, 0])));
+//!
+//! let mut output = Vec::new();
+//! render_image(
+//!     &image,
+//!     Canvas::new(1, 1),
+//!     Graphics::HalfBlocks,
+//!     Fidelity::Ansi,
+//!     &translator,
+//!     &mut output,
+//! )?;
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "\x1b[91;101m▀\x1b[39;49m"
+//! );
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{Error, Result, Write};
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, RgbaImage};
+
+use crate::style::{half_blocks, Fidelity};
+use crate::termco::Colorant;
+use crate::util::{Env, Environment};
+use crate::{Color, Translator};
+
+#[cfg(feature = "tty")]
+use prettytty::{cmd::RequestCellPixels, Connection, Query, Scan};
+
+/// A mechanism for displaying images in the terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Graphics {
+    /// The [kitty graphics
+    /// protocol](https://sw.kovidgoyal.net/kitty/graphics-protocol/),
+    /// supported by kitty, Ghostty, and Konsole.
+    Kitty,
+    /// [iTerm2's inline images](https://iterm2.com/documentation-images.html),
+    /// supported by iTerm2, WezTerm, and VS Code.
+    Iterm,
+    /// Sixel graphics, supported by xterm, foot, mlterm, Windows Terminal, and
+    /// many more.
+    Sixel,
+    /// Upper half blocks with foreground and background colors, supported by
+    /// all terminals.
+    HalfBlocks,
+}
+
+impl Graphics {
+    /// Determine the best graphics mechanism based on environment variables.
+    ///
+    /// This method recognizes terminals with support for the kitty graphics
+    /// protocol or iTerm2's inline images. Since there is no environment
+    /// variable signalling sixel graphics, it falls back on half blocks
+    /// otherwise.
+    pub fn from_environment() -> Self {
+        graphics_from_environment(&Env::default())
+    }
+
+    /// Determine the best graphics mechanism for the terminal. <i
+    /// class=tty-only>TTY only!</i>
+    ///
+    /// This method refines [`Graphics::from_environment`] by asking the
+    /// terminal whether it supports sixel graphics.
+    #[cfg(feature = "tty")]
+    pub fn query(tty: &Connection) -> Self {
+        match Self::from_environment() {
+            Self::HalfBlocks if tty.caps().sixel() => Self::Sixel,
+            graphics => graphics,
+        }
+    }
+}
+
+fn graphics_from_environment(env: &impl Environment) -> Graphics {
+    let term = env.read("TERM").unwrap_or_default();
+    let program = env.read("TERM_PROGRAM").unwrap_or_default();
+
+    if env.is_defined("KITTY_WINDOW_ID")
+        || term.contains("kitty")
+        || term.contains("ghostty")
+        || program == "ghostty"
+        || env.is_defined("KONSOLE_VERSION")
+    {
+        Graphics::Kitty
+    } else if matches!(program.as_str(), "iTerm.app" | "WezTerm" | "vscode")
+        || env.has_value("LC_TERMINAL", "iTerm2")
+    {
+        Graphics::Iterm
+    } else {
+        Graphics::HalfBlocks
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// A rectangular area of character cells.
+///
+/// A canvas has a size in columns and rows as well as a cell size in pixels.
+/// The latter determines the aspect ratio of cells and, for sixel graphics,
+/// the size of the rendered image. It defaults to
+/// [`Canvas::CELL_WIDTH`]×[`Canvas::CELL_HEIGHT`] pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Canvas {
+    columns: u16,
+    rows: u16,
+    cell_width: u16,
+    cell_height: u16,
+}
+
+/// The result of fitting an image to a canvas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Fit {
+    width: u32,
+    height: u32,
+    columns: u16,
+    rows: u16,
+}
+
+impl Canvas {
+    /// The default cell width in pixels.
+    pub const CELL_WIDTH: u16 = 10;
+
+    /// The default cell height in pixels.
+    pub const CELL_HEIGHT: u16 = 20;
+
+    /// Create a new canvas with the given columns and rows.
+    pub const fn new(columns: u16, rows: u16) -> Self {
+        Self {
+            columns,
+            rows,
+            cell_width: Self::CELL_WIDTH,
+            cell_height: Self::CELL_HEIGHT,
+        }
+    }
+
+    /// Update the cell size in pixels.
+    pub const fn with_cell_size(mut self, width: u16, height: u16) -> Self {
+        self.cell_width = if width == 0 { 1 } else { width };
+        self.cell_height = if height == 0 { 1 } else { height };
+        self
+    }
+
+    /// Create a new canvas covering the terminal's window. <i
+    /// class=tty-only>TTY only!</i>
+    ///
+    /// This method determines the window size and then asks the terminal for
+    /// its cell size in pixels. If the terminal does not respond, the canvas
+    /// uses the default cell size.
+    #[cfg(feature = "tty")]
+    pub fn query(tty: &Connection) -> Result<Self> {
+        let (columns, rows) = tty.window_size()?;
+        let canvas = Self::new(columns, rows);
+
+        Ok(match query_cell_size(tty) {
+            Ok((width, height)) => canvas.with_cell_size(width, height),
+            Err(_) => canvas,
+        })
+    }
+
+    /// Get the number of columns.
+    pub const fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    /// Get the number of rows.
+    pub const fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// Get the cell width and height in pixels.
+    pub const fn cell_size(&self) -> (u16, u16) {
+        (self.cell_width, self.cell_height)
+    }
+
+    /// Fit an image with the given width and height in pixels to this canvas.
+    ///
+    /// This method scales the image up or down to the largest size that fits
+    /// the canvas while preserving the aspect ratio. It returns `None` if
+    /// either the canvas or the image are empty.
+    fn fit(&self, width: u32, height: u32) -> Option<Fit> {
+        if self.columns == 0 || self.rows == 0 || width == 0 || height == 0 {
+            return None;
+        }
+
+        let (cell_width, cell_height) = (self.cell_width as f64, self.cell_height as f64);
+        let scale = (self.columns as f64 * cell_width / width as f64)
+            .min(self.rows as f64 * cell_height / height as f64);
+        let width = (width as f64 * scale).round().max(1.0);
+        let height = (height as f64 * scale).round().max(1.0);
+
+        Some(Fit {
+            width: width as u32,
+            height: height as u32,
+            columns: ((width / cell_width).ceil() as u16).clamp(1, self.columns),
+            rows: ((height / cell_height).ceil() as u16).clamp(1, self.rows),
+        })
+    }
+}
+
+/// Query the terminal for its cell width and height in pixels.
+#[cfg(feature = "tty")]
+fn query_cell_size(tty: &Connection) -> Result<(u16, u16)> {
+    let (mut input, mut output) = tty.io();
+    output.exec(RequestCellPixels)?;
+    let payload = input.read_sequence(RequestCellPixels.control())?;
+    let (height, width) = RequestCellPixels.parse(payload)?;
+    Ok((width, height))
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// Render the image with the graphics mechanism.
+///
+/// This function scales the image to the largest size that fits the canvas
+/// while preserving its aspect ratio and then writes it to the output:
+///
+///   * [`Graphics::Kitty`] and [`Graphics::Iterm`] transmit the scaled image
+///     as PNG and ask the terminal to display it across the cells covered by
+///     the image.
+///   * [`Graphics::Sixel`] quantizes the scaled image to the translator's
+///     8-bit colors with [`Translator::dither`]. Its size in pixels depends
+///     on the canvas' cell size. Transparent pixels are not drawn.
+///   * [`Graphics::HalfBlocks`] further scales the image to one pixel per
+///     column and two pixels per row and renders it with [`half_blocks`],
+///     i.e., dithered to the fidelity. It separates lines with carriage
+///     return and line feed, but does not terminate the last line. It ignores
+///     transparency.
+///
+/// Since [`Fidelity::Plain`] and [`Fidelity::NoColor`] rule out colors, this
+/// function always uses half blocks for them. It does not write anything for
+/// an empty canvas or image.
+pub fn render_image(
+    image: &DynamicImage,
+    canvas: Canvas,
+    graphics: Graphics,
+    fidelity: Fidelity,
+    translator: &Translator,
+    output: &mut impl Write,
+) -> Result<()> {
+    let Some(fit) = canvas.fit(image.width(), image.height()) else {
+        return Ok(());
+    };
+
+    let graphics = if fidelity < Fidelity::Ansi {
+        Graphics::HalfBlocks
+    } else {
+        graphics
+    };
+
+    match graphics {
+        Graphics::Kitty => {
+            let data = base64(&to_png(image, fit)?);
+            let count = data.len().div_ceil(4096);
+            for (index, chunk) in data.as_bytes().chunks(4096).enumerate() {
+                let more = u8::from(index + 1 < count);
+                if index == 0 {
+                    write!(
+                        output,
+                        "\x1b_Ga=T,f=100,q=2,c={},r={},m={};",
+                        fit.columns, fit.rows, more
+                    )?;
+                } else {
+                    write!(output, "\x1b_Gm={};", more)?;
+                }
+                output.write_all(chunk)?;
+                output.write_all(b"\x1b\\")?;
+            }
+        }
+        Graphics::Iterm => {
+            let png = to_png(image, fit)?;
+            write!(
+                output,
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+                png.len(),
+                fit.columns,
+                fit.rows,
+                base64(&png)
+            )?;
+        }
+        Graphics::Sixel => {
+            let pixels = image
+                .resize_exact(fit.width, fit.height, FilterType::Triangle)
+                .into_rgba8();
+            write_sixel(&pixels, translator, output)?;
+        }
+        Graphics::HalfBlocks => {
+            let (cell_width, cell_height) = canvas.cell_size();
+            let width = (fit.width as f64 / cell_width as f64).round().max(1.0) as u32;
+            let height = (2.0 * fit.height as f64 / cell_height as f64)
+                .round()
+                .max(1.0) as u32;
+            let pixels = image
+                .resize_exact(width, height, FilterType::Triangle)
+                .into_rgb8()
+                .pixels()
+                .map(|pixel| Color::from_24bit(pixel[0], pixel[1], pixel[2]))
+                .collect::<Vec<_>>();
+
+            let lines = half_blocks(&pixels, width as usize, fidelity, translator);
+            for (index, line) in lines.iter().enumerate() {
+                if 0 < index {
+                    output.write_all(b"\r\n")?;
+                }
+                write!(output, "{}", line)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Scale the image to fit and encode the result as PNG.
+fn to_png(image: &DynamicImage, fit: Fit) -> Result<Vec<u8>> {
+    let mut data = std::io::Cursor::new(Vec::new());
+    image
+        .resize_exact(fit.width, fit.height, FilterType::Triangle)
+        .write_to(&mut data, ImageFormat::Png)
+        .map_err(Error::other)?;
+    Ok(data.into_inner())
+}
+
+/// Write the pixels as sixel graphics.
+///
+/// This function dithers the pixels to the translator's 8-bit colors and only
+/// defines color registers for the colors actually in use.
+fn write_sixel(pixels: &RgbaImage, translator: &Translator, output: &mut impl Write) -> Result<()> {
+    let (width, height) = (pixels.width() as usize, pixels.height() as usize);
+    let colors = pixels
+        .pixels()
+        .map(|pixel| Color::from_24bit(pixel[0], pixel[1], pixel[2]))
+        .collect::<Vec<_>>();
+
+    // Unwrap is safe b/c dithering to 8-bit colors always produces colorants.
+    let indices = translator
+        .dither(&colors, width, Fidelity::EightBit)
+        .unwrap()
+        .iter()
+        .zip(pixels.pixels())
+        .map(|(colorant, pixel)| {
+            if pixel[3] < 128 {
+                None
+            } else {
+                u8::try_from(colorant).ok()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Use 1:1 pixel aspect ratio and leave transparent pixels unchanged.
+    write!(output, "\x1bP0;1q\"1;1;{};{}", width, height)?;
+
+    let mut used = [false; 256];
+    for index in indices.iter().flatten() {
+        used[*index as usize] = true;
+    }
+    for (index, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let [r, g, b] = translator.resolve(Colorant::from(index as u8)).to_24bit();
+        let percent = |value: u8| (value as u16 * 100 + 127) / 255;
+        write!(
+            output,
+            "#{};2;{};{};{}",
+            index,
+            percent(r),
+            percent(g),
+            percent(b)
+        )?;
+    }
+
+    for top in (0..height).step_by(6) {
+        if 0 < top {
+            output.write_all(b"-")?;
+        }
+
+        let band = &indices[top * width..((top + 6) * width).min(indices.len())];
+        let mut used = [false; 256];
+        for index in band.iter().flatten() {
+            used[*index as usize] = true;
+        }
+
+        let mut is_first = true;
+        for (color, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+            if !is_first {
+                output.write_all(b"$")?;
+            }
+            is_first = false;
+            write!(output, "#{}", color)?;
+
+            let mut run = (0, 0);
+            for column in 0..width {
+                let bits = band
+                    .iter()
+                    .skip(column)
+                    .step_by(width)
+                    .enumerate()
+                    .filter(|(_, index)| **index == Some(color as u8))
+                    .fold(0, |bits, (row, _)| bits | (1 << row));
+                let sixel = 63 + bits;
+
+                if run.1 == 0 || run.0 == sixel {
+                    run = (sixel, run.1 + 1);
+                } else {
+                    write_run(run, output)?;
+                    run = (sixel, 1);
+                }
+            }
+            write_run(run, output)?;
+        }
+    }
+
+    output.write_all(b"\x1b\\")
+}
+
+/// Write a run of the same sixel, using the repeat introducer if shorter.
+fn write_run((sixel, count): (u8, usize), output: &mut impl Write) -> Result<()> {
+    if 3 < count {
+        write!(output, "!{}{}", count, sixel as char)
+    } else {
+        for _ in 0..count {
+            output.write_all(&[sixel])?;
+        }
+        Ok(())
+    }
+}
+
+/// The base64 alphabet.
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode the data as base64 with padding.
+fn base64(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, byte)| {
+            bits | (*byte as u32) << (16 - 8 * index)
+        });
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                result.push(BASE64[(bits >> (18 - 6 * index)) as usize & 0x3f] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use image::{DynamicImage, Rgba, RgbaImage};
+
+    use super::{base64, graphics_from_environment, render_image, Canvas, Fit, Graphics};
+    use crate::style::Fidelity;
+    use crate::theme::VGA_COLORS;
+    use crate::util::FakeEnv;
+    use crate::{OkVersion, Translator};
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"\xff\xfe\xfd"), "//79");
+    }
+
+    #[test]
+    fn test_environment() {
+        let mut env = FakeEnv::new();
+        assert_eq!(graphics_from_environment(&env), Graphics::HalfBlocks);
+        env.set("TERM_PROGRAM", "iTerm.app");
+        assert_eq!(graphics_from_environment(&env), Graphics::Iterm);
+        env.set("TERM", "xterm-kitty");
+        assert_eq!(graphics_from_environment(&env), Graphics::Kitty);
+    }
+
+    #[test]
+    fn test_fit() {
+        let canvas = Canvas::new(10, 5);
+        assert_eq!(canvas.fit(0, 10), None);
+        assert_eq!(
+            canvas.fit(200, 100),
+            Some(Fit {
+                width: 100,
+                height: 50,
+                columns: 10,
+                rows: 3
+            })
+        );
+        assert_eq!(
+            canvas.with_cell_size(8, 16).fit(10, 80),
+            Some(Fit {
+                width: 10,
+                height: 80,
+                columns: 2,
+                rows: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_render() -> std::io::Result<()> {
+        let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+        let mut pixels = RgbaImage::from_pixel(2, 2, Rgba([0, 0, 0, 255]));
+        pixels.put_pixel(1, 1, Rgba([0, 0, 0, 0]));
+        let image = DynamicImage::ImageRgba8(pixels);
+        let canvas = Canvas::new(1, 1).with_cell_size(2, 2);
+
+        let mut output = Vec::new();
+        render_image(
+            &image,
+            canvas,
+            Graphics::Sixel,
+            Fidelity::TwentyFourBit,
+            &translator,
+            &mut output,
+        )?;
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\x1bP0;1q\"1;1;2;2#16;2;0;0;0#16B@\x1b\\"
+        );
+
+        let mut output = Vec::new();
+        render_image(
+            &image,
+            canvas,
+            Graphics::Kitty,
+            Fidelity::NoColor,
+            &translator,
+            &mut output,
+        )?;
+        assert_eq!(String::from_utf8(output).unwrap(), " ");
+
+        let mut output = Vec::new();
+        render_image(
+            &image,
+            canvas,
+            Graphics::Kitty,
+            Fidelity::TwentyFourBit,
+            &translator,
+            &mut output,
+        )?;
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("\x1b_Ga=T,f=100,q=2,c=1,r=1,m=0;iVBORw0KGgo"));
+        assert!(output.ends_with("\x1b\\"));
+
+        Ok(())
+    }
+}
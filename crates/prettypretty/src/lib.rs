@@ -71,6 +71,43 @@ feature disabled, [on Docs.rs](https://docs.rs/prettypretty/latest/prettypretty/
 //!     [viz3d.py](https://github.com/apparebit/prettypretty/blob/main/prettypretty/viz3d.py)
 //!     scripts leverage the additional functionality for generating helpful
 //!     color visualizations.
+#![cfg_attr(
+    feature = "viz",
+    doc = "  * The optional [`viz`] submodule renders **chromaticity diagrams** with
+    the spectral locus, gamut triangles, and colors as SVG."
+)]
+#![cfg_attr(
+    not(feature = "viz"),
+    doc = "  * The optional `viz` submodule renders **chromaticity diagrams** with
+    the spectral locus, gamut triangles, and colors as SVG."
+)]
+#![cfg_attr(
+    feature = "tty",
+    doc = "  * The [`prompt`] submodule implements a minimal **line editor** with a
+    styled prompt and history."
+)]
+#![cfg_attr(
+    feature = "image",
+    doc = "  * The optional [`graphics`] submodule renders **images** with the best
+    graphics mechanism supported by the terminal."
+)]
+#![cfg_attr(
+    not(feature = "image"),
+    doc = "  * The optional `graphics` submodule renders **images** with the best
+    graphics mechanism supported by the terminal."
+)]
+#![cfg_attr(
+    feature = "screen",
+    doc = "  * The optional [`screen`] submodule implements a double-buffered **screen
+    buffer** that renders only changed cells and thus serves as a light TUI
+    backend."
+)]
+#![cfg_attr(
+    not(feature = "screen"),
+    doc = "  * The optional `screen` submodule implements a double-buffered **screen
+    buffer** that renders only changed cells and thus serves as a light TUI
+    backend."
+)]
 //!
 //!
 //! ## 2. One-Two-Three: Styles!
@@ -206,26 +243,71 @@ feature disabled, [on Docs.rs](https://docs.rs/prettypretty/latest/prettypretty/
 //!
 //! ## 3. Optional Features
 //!
-//! Prettypretty supports four feature flags:
+//! Prettypretty supports fourteen feature flags:
 //!
 //!   - **`f64`** selects the eponymous type as floating point type [`Float`]
 //!     and `u64` as [`Bits`] instead of `f32` as [`Float`] and `u32` as
 //!     [`Bits`]. This feature is enabled by default.
 //!   - **`tty`** controls [`Theme::query`](theme::Theme::query) and its
 //!     implementation with the [prettytty](https://crates.io/crates/prettytty)
-//!     terminal crate. This feature is enabled by default.
+//!     terminal crate as well as the `prompt` module. This feature is enabled
+//!     by default.
+//!   - **`terminfo`** controls `Fidelity::from_terminfo` and
+//!     `Fidelity::from_environment_and_terminfo`, which consult the terminfo
+//!     database through prettytty's optional `terminfo` module. This feature
+//!     implies `tty` and is disabled by default.
 //!   - **`gamut`** controls support for tracing the boundaries of color spaces
 //!     (`mod gamut`, `ColorSpace::gamut`) and the human visual gamut (`mod
 //!     spectrum`). This feature is disabled by default.
+//!   - **`viz`** controls the `viz` module, which renders chromaticity
+//!     diagrams as SVG. This feature implies `gamut` and is disabled by
+//!     default.
 //!   - **`pyffi`** controls prettypretty's Python integration through
 //!     [PyO3](https://pyo3.rs/). This feature is disabled by default.
+//!   - **`anstyle`** controls conversions between [`Style`](style::Style) and
+//!     [`Colorant`](termco::Colorant) on one side and
+//!     [anstyle](https://crates.io/crates/anstyle)'s styles and colors on the
+//!     other side. This feature is disabled by default.
+//!   - **`crossterm`** similarly controls conversions between prettypretty's
+//!     and [crossterm](https://crates.io/crates/crossterm)'s styles and
+//!     colors. This feature is disabled by default.
+//!   - **`serde`** controls reading and writing [`Theme`](theme::Theme)s as
+//!     TOML or JSON, e.g., to cache a queried theme on disk. This feature is
+//!     disabled by default.
+//!   - **`tokio`** controls [`Theme::query_async`](theme::Theme::query_async)
+//!     for async applications built on [tokio](https://tokio.rs). This feature
+//!     is disabled by default.
+//!   - **`image`** controls the `graphics` module, which renders images from
+//!     the [image](https://crates.io/crates/image) crate with the kitty
+//!     graphics protocol, iTerm2's inline images, sixel graphics, or half
+//!     blocks. This feature is disabled by default.
+//!   - **`screen`** controls the `screen` module, a double-buffered screen
+//!     buffer that renders only changed cells. This feature implies `unicode`
+//!     and is disabled by default.
+//!   - **`wasm`** controls prettypretty's JavaScript integration through
+//!     [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/), which
+//!     exports [`Color`], [`Translator`], and [`Style`](style::Style) for
+//!     rendering styles in web-based terminals and documentation. This feature
+//!     is disabled by default.
+//!   - **`unicode`** controls whether [`style::width`] and related functions
+//!     measure text per grapheme cluster with Unicode's width tables, through
+//!     the [unicode-width](https://crates.io/crates/unicode-width) and
+//!     [unicode-segmentation](https://crates.io/crates/unicode-segmentation)
+//!     crates. Without it, they treat each character as one column. This
+//!     feature is enabled by default.
 //!
 //! Prettypretty's Python extension module is built with
 //! [Maturin](https://www.maturin.rs), PyO3's dedicated build tool. Since Python
-//! packages typically come with "batteries included," the `gamut` feature is
-//! also enabled when building the Python extension module. However, the `tty`
-//! feature is disabled, and prettypretty's Python package includes its own
-//! terminal abstraction.
+//! packages typically come with "batteries included," the `gamut` and `tty`
+//! features are also enabled when building the Python extension module. The
+//! latter exposes terminal access as context managers, which prettypretty's
+//! Python package builds on for cbreak mode and theme queries.
+//!
+//! Without the `tty` feature, prettypretty also compiles to the
+//! `wasm32-unknown-unknown` target, e.g., with `cargo build --target
+//! wasm32-unknown-unknown --no-default-features --features f64,wasm`. The
+//! generated module can then be packaged for the web with `wasm-bindgen` or
+//! `wasm-pack`.
 //!
 //! Throughout the API documentation, items that are only available in Rust are
 //! decorated with <i class=rust-only>Rust only!</i>.
@@ -235,8 +317,10 @@ feature disabled, [on Docs.rs](https://docs.rs/prettypretty/latest/prettypretty/
     class=python-only>Python only!</i>."
 )]
 //! Similarly, items only available with the `tty` feature are decorated with <i
-//! class=tty-only>TTY only!</i> and items only available with the `gamut`
-//! feature are decorated with <i class=gamut-only>Gamut only!</i>.
+//! class=tty-only>TTY only!</i>, items only available with the `gamut`
+//! feature are decorated with <i class=gamut-only>Gamut only!</i>, and items
+//! only available with the `serde` feature are decorated with <i
+//! class=serde-only>Serde only!</i>.
 //!
 //!
 //! ## 4. Acknowledgements
@@ -266,14 +350,26 @@ pub type Bits = u64;
 #[cfg(not(feature = "f64"))]
 pub type Bits = u32;
 
+#[cfg(feature = "pyffi")]
+mod array;
+#[cfg(feature = "pyffi")]
+mod batch;
 mod core;
 pub mod error;
+#[cfg(feature = "image")]
+pub mod graphics;
 mod object;
+#[cfg(feature = "tty")]
+pub mod prompt;
 pub mod style;
 pub mod termco;
+#[cfg(all(feature = "pyffi", feature = "tty"))]
+mod terminal;
 pub mod theme;
 mod trans;
 mod util;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 #[cfg(feature = "gamut")]
 mod cie;
@@ -283,8 +379,12 @@ pub mod gamut {
     //! [`ColorSpace::gamut`](crate::ColorSpace).
     pub use crate::core::{GamutTraversal, GamutTraversalStep};
 }
+#[cfg(feature = "screen")]
+pub mod screen;
 #[cfg(feature = "gamut")]
 pub mod spectrum;
+#[cfg(feature = "viz")]
+pub mod viz;
 
 #[cfg(feature = "pyffi")]
 pub use core::close_enough;
@@ -294,7 +394,9 @@ pub use core::to_eq_bits;
 
 pub use core::{ColorSpace, HueInterpolation};
 pub use object::{Color, Interpolator, OkVersion};
-pub use trans::Translator;
+pub use trans::{
+    AnsiMatching, TranslationReport, Translator, TranslatorOptionBuilder, TranslatorOptions,
+};
 
 #[cfg(feature = "pyffi")]
 use pyo3::prelude::*;
@@ -307,6 +409,7 @@ use pyo3::types::PyDict;
 pub fn color(m: &Bound<'_, PyModule>) -> PyResult<()> {
     let modcolor_name = m.name()?;
     let modcolor_name = modcolor_name.to_str()?;
+    let modbatch_name = format!("{}.batch", modcolor_name);
     let modstyle_name = format!("{}.style", modcolor_name);
     let modtermco_name = format!("{}.termco", modcolor_name);
     let modtheme_name = format!("{}.theme", modcolor_name);
@@ -316,21 +419,39 @@ pub fn color(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     m.add_class::<Color>()?;
     m.add_class::<ColorSpace>()?;
+    m.add_class::<array::CoordinateTable>()?;
     m.add_class::<HueInterpolation>()?;
     m.add_class::<Interpolator>()?;
     m.add_class::<OkVersion>()?;
+    m.add_class::<TranslationReport>()?;
     m.add_class::<Translator>()?;
 
+    // -------------------------------------------------------------------- color.batch
+    let modbatch = PyModule::new(m.py(), "batch")?;
+    modbatch.add("__package__", modcolor_name)?;
+    modbatch.add_function(wrap_pyfunction!(batch::convert, &modbatch)?)?;
+    modbatch.add_function(wrap_pyfunction!(batch::to_gamut, &modbatch)?)?;
+    modbatch.add_function(wrap_pyfunction!(batch::to_ansi, &modbatch)?)?;
+    modbatch.add_function(wrap_pyfunction!(batch::to_8bit, &modbatch)?)?;
+    m.add_submodule(&modbatch)?;
+
+    // Only change __name__ attribute after submodule has been added.
+    modbatch.setattr("__name__", &modbatch_name)?;
+
     // -------------------------------------------------------------------- color.style
     let modstyle = PyModule::new(m.py(), "style")?;
     modstyle.add("__package__", modcolor_name)?;
     modstyle.add_class::<style::Attribute>()?;
     modstyle.add_class::<style::AttributeIter>()?;
     modstyle.add_class::<style::Fidelity>()?;
+    modstyle.add_class::<style::FidelityReport>()?;
     modstyle.add_class::<style::Format>()?;
     modstyle.add_class::<style::FormatUpdate>()?;
     modstyle.add_class::<style::Layer>()?;
     modstyle.add_class::<style::Style>()?;
+    modstyle.add_class::<style::StyleDelta>()?;
+    modstyle.add_class::<style::Role>()?;
+    modstyle.add_class::<style::StyleSheet>()?;
     m.add_submodule(&modstyle)?;
 
     // Only change __name__ attribute after submodule has been added.
@@ -357,7 +478,18 @@ pub fn color(m: &Bound<'_, PyModule>) -> PyResult<()> {
     modtheme.add_class::<theme::Theme>()?;
     modtheme.add_class::<theme::ThemeEntry>()?;
     modtheme.add_class::<theme::ThemeEntryIterator>()?;
+    modtheme.add_class::<theme::ThemeEstimate>()?;
+    modtheme.add_class::<theme::ThemeIssue>()?;
+    modtheme.add_class::<theme::ThemeQuery>()?;
+    modtheme.add_class::<theme::ThemeSource>()?;
     modtheme.add("VGA_COLORS", theme::VGA_COLORS)?;
+    modtheme.add("SOLARIZED_DARK", theme::SOLARIZED_DARK)?;
+    modtheme.add("SOLARIZED_LIGHT", theme::SOLARIZED_LIGHT)?;
+    modtheme.add("DRACULA", theme::DRACULA)?;
+    modtheme.add("GRUVBOX_DARK", theme::GRUVBOX_DARK)?;
+    modtheme.add("NORD", theme::NORD)?;
+    modtheme.add("MACOS_TERMINAL", theme::MACOS_TERMINAL)?;
+    modtheme.add("WINDOWS_TERMINAL", theme::WINDOWS_TERMINAL)?;
     m.add_submodule(&modtheme)?;
 
     // Only change __name__ attribute after submodule has been added.
@@ -369,6 +501,7 @@ pub fn color(m: &Bound<'_, PyModule>) -> PyResult<()> {
     let py_modules: Bound<'_, PyDict> = PyModule::import(m.py(), "sys")?
         .getattr("modules")?
         .downcast_into()?;
+    py_modules.set_item(&modbatch_name, modbatch)?;
     py_modules.set_item(&modstyle_name, modstyle)?;
     py_modules.set_item(&modtermco_name, modtermco)?;
     py_modules.set_item(&modtheme_name, modtheme)?;
@@ -376,6 +509,36 @@ pub fn color(m: &Bound<'_, PyModule>) -> PyResult<()> {
     #[cfg(feature = "gamut")]
     register_modgamut(m)?;
 
+    #[cfg(feature = "tty")]
+    register_modterminal(m)?;
+
+    Ok(())
+}
+
+#[doc(hidden)]
+#[cfg(all(feature = "pyffi", feature = "tty"))]
+fn register_modterminal(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let modcolor_name = m.name()?;
+    let modcolor_name = modcolor_name.to_str()?;
+    let modterminal_name = format!("{}.terminal", modcolor_name);
+
+    // ----------------------------------------------------------------- color.terminal
+    let modterminal = PyModule::new(m.py(), "terminal")?;
+    modterminal.add("__package__", modcolor_name)?;
+    modterminal.add_class::<terminal::AlternateScreen>()?;
+    modterminal.add_class::<terminal::Terminal>()?;
+    modterminal.add_class::<terminal::WindowTitle>()?;
+    m.add_submodule(&modterminal)?;
+
+    // Only change __name__ attribute after submodule has been added.
+    modterminal.setattr("__name__", &modterminal_name)?;
+
+    // -------------------------------------------------------------------- sys.modules
+    let py_modules: Bound<'_, PyDict> = PyModule::import(m.py(), "sys")?
+        .getattr("modules")?
+        .downcast_into()?;
+    py_modules.set_item(&modterminal_name, modterminal)?;
+
     Ok(())
 }
 
@@ -401,6 +564,11 @@ fn register_modgamut(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // ----------------------------------------------------------------- color.spectrum
     let modspectrum = PyModule::new(m.py(), "spectrum")?;
     modspectrum.add("__package__", modcolor_name)?;
+    modspectrum.add(
+        "CIE_ILLUMINANT_A",
+        spectrum::Illuminant::new(Box::new(spectrum::CIE_ILLUMINANT_A.clone())
+            as Box<dyn spectrum::SpectralDistribution<Value = Float> + Send + Sync>),
+    )?;
     modspectrum.add(
         "CIE_ILLUMINANT_D50",
         spectrum::Illuminant::new(Box::new(spectrum::CIE_ILLUMINANT_D50.clone())
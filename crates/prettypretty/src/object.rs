@@ -1197,6 +1197,50 @@ impl Color {
     pub fn __str__(&self) -> String {
         format!("{}", self)
     }
+
+    /// Export this color's coordinates as a read-only buffer. <i
+    /// class=python-only>Python only!</i>
+    ///
+    /// This method lets NumPy view the coordinates without copying them, e.g.,
+    /// with `numpy.asarray(color)`.
+    ///
+    /// # Safety
+    ///
+    /// The view must be valid.
+    #[cfg(feature = "pyffi")]
+    pub unsafe fn __getbuffer__(
+        slf: Bound<'_, Self>,
+        view: *mut pyo3::ffi::Py_buffer,
+        flags: std::ffi::c_int,
+    ) -> PyResult<()> {
+        crate::array::fill_color_view(&slf, view, flags)
+    }
+
+    /// Reduce this color to its constructor arguments for pickling. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> (Bound<'py, pyo3::types::PyType>, (ColorSpace, [Float; 3])) {
+        let color = slf.get();
+        (slf.get_type(), (color.space, color.coordinates))
+    }
+
+    /// Copy this color. <i class=python-only>Python only!</i>
+    ///
+    /// Since colors are immutable, this method returns the same object.
+    #[cfg(feature = "pyffi")]
+    pub fn __copy__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// Deeply copy this color. <i class=python-only>Python only!</i>
+    ///
+    /// Since colors are immutable, this method returns the same object.
+    #[cfg(feature = "pyffi")]
+    pub fn __deepcopy__(slf: Py<Self>, _memo: &Bound<'_, PyAny>) -> Py<Self> {
+        slf
+    }
 }
 
 // --------------------------------------------------------------------------------------------------------------------
@@ -1689,6 +1733,33 @@ impl std::fmt::Display for Color {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Color {
+    /// Serialize this color as a string. <i class=serde-only>Serde only!</i>
+    ///
+    /// This method uses the hashed hexadecimal notation for sRGB colors with
+    /// 24-bit coordinates and the CSS notation with 14 digits past the decimal
+    /// otherwise. Hence, parsing the string produces an equal color.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [r, g, b] = self.to_24bit();
+        if self.space == ColorSpace::Srgb && *self == Self::from_24bit(r, g, b) {
+            serializer.serialize_str(&self.to_hex_format())
+        } else {
+            serializer.serialize_str(&format!("{:.14}", self))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    /// Deserialize a color from a string in any notation recognized by
+    /// [`Color::from_str`]. <i class=serde-only>Serde only!</i>
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Color::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 // ====================================================================================================================
 
 /// A choice of Oklab versions.
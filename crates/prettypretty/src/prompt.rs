@@ -0,0 +1,383 @@
+//! Optional module implementing a minimal line editor with a styled prompt.
+//!
+//! Many command line tools need to read exactly one line of input at a time,
+//! maybe with a colorful prompt, but don't need a full readline
+//! implementation. [`LineEditor`] fills that niche on top of prettytty's
+//! [`key`](prettytty::key) decoder. It supports the following keys:
+//!
+//!   * Left/right arrows and control-b/f move by one character.
+//!   * Home/end and control-a/e move to the start/end of the line.
+//!   * Up/down arrows and control-p/n recall previous/next history entries.
+//!   * Backspace and delete remove the character before/under the cursor.
+//!   * Control-u/k remove everything before/after the cursor.
+//!   * Control-w removes the word before the cursor.
+//!   * Enter completes the line.
+//!   * Control-d on an empty line signals the end of input.
+//!   * Control-c interrupts line editing.
+//!
+//! The line editor redraws the entire line after every edit and assumes that
+//! the prompt and line fit into one terminal row.
+//!
+//! ```
+//! # use prettypretty::prompt::LineEditor;
+//! # use prettypretty::style::Style;
+//! # use prettytty::MockConnection;
+//! let tty = MockConnection::new();
+//! tty.push_input(b"wrold\x1b[D\x1b[D\x1b[D\x7fr\x7f\x1b[Cr\r");
+//!
+//! let mut editor = LineEditor::new("> ").with_style(Style::default().bold());
+//! let (mut input, mut output) = tty.io();
+//! let line = editor.read_line(&mut input, &mut output)?;
+//!
+//! assert_eq!(line.as_deref(), Some("world"));
+//! assert_eq!(editor.history(), &["world".to_string()]);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{ErrorKind, Result, Write};
+
+use crate::style::{width, Style};
+use prettytty::key::{KeyCode, KeyEvent, Modifiers};
+use prettytty::{Scan, Token};
+
+/// A line editor.
+///
+/// A line editor renders its prompt with a [`Style`] and keeps a history of
+/// the lines entered, which can be recalled with the up and down arrow keys.
+/// See the [module-level documentation](self) for the supported keys.
+#[derive(Clone, Debug)]
+pub struct LineEditor {
+    prompt: String,
+    style: Style,
+    history: Vec<String>,
+    history_limit: usize,
+}
+
+/// The state of one line being edited.
+struct Line {
+    text: Vec<char>,
+    cursor: usize,
+    recalled: usize,
+    draft: Vec<char>,
+}
+
+impl Line {
+    fn after(&self) -> String {
+        self.text[self.cursor..].iter().collect()
+    }
+
+    fn replace(&mut self, text: Vec<char>) {
+        self.cursor = text.len();
+        self.text = text;
+    }
+}
+
+impl LineEditor {
+    /// Create a new line editor with the given prompt.
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            style: Style::default(),
+            history: Vec::new(),
+            history_limit: 100,
+        }
+    }
+
+    /// Set the style for rendering the prompt.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the maximum number of history entries, which is 100 by default.
+    pub fn with_history_limit(mut self, limit: usize) -> Self {
+        self.history_limit = limit;
+        self.truncate_history();
+        self
+    }
+
+    /// Get the prompt.
+    pub fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    /// Get the history, with the oldest entry first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Add the line to the history.
+    ///
+    /// This method ignores empty lines and lines that repeat the most recent
+    /// entry.
+    pub fn add_history(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        if line.is_empty() || self.history.last() == Some(&line) {
+            return;
+        }
+
+        self.history.push(line);
+        self.truncate_history();
+    }
+
+    fn truncate_history(&mut self) {
+        if self.history_limit < self.history.len() {
+            let excess = self.history.len() - self.history_limit;
+            self.history.drain(..excess);
+        }
+    }
+
+    /// Read one line of input.
+    ///
+    /// This method writes the prompt and then processes key presses until
+    /// the user presses enter, returning the entered text, which is also
+    /// added to the history. If the user presses control-d on an empty line,
+    /// this method returns `None`. If the user presses control-c, it fails
+    /// with [`ErrorKind::Interrupted`]. It ignores read timeouts.
+    ///
+    /// The terminal should be in raw mode, e.g., by using a
+    /// [`Connection`](prettytty::Connection)'s input and output.
+    pub fn read_line(
+        &mut self,
+        input: &mut impl Scan,
+        output: &mut impl Write,
+    ) -> Result<Option<String>> {
+        let mut line = Line {
+            text: Vec::new(),
+            cursor: 0,
+            recalled: self.history.len(),
+            draft: Vec::new(),
+        };
+        let mut keys = Vec::new();
+
+        self.render(&line, output)?;
+        loop {
+            keys.clear();
+            match input.read_token() {
+                Ok(Token::Paste(data)) => {
+                    let text = String::from_utf8_lossy(data);
+                    keys.extend(
+                        text.chars()
+                            .filter(|c| !c.is_control())
+                            .map(|c| KeyEvent::plain(KeyCode::Char(c))),
+                    );
+                }
+                Ok(token) => keys.extend(token.keys()),
+                Err(err) if err.kind() == ErrorKind::TimedOut => continue,
+                Err(err) => return Err(err),
+            }
+
+            for key in keys.iter() {
+                match self.edit(&mut line, *key) {
+                    Edit::Continue => (),
+                    Edit::Done => {
+                        let text: String = line.text.iter().collect();
+                        write!(output, "\r\n")?;
+                        output.flush()?;
+                        self.add_history(text.clone());
+                        return Ok(Some(text));
+                    }
+                    Edit::EndOfInput => {
+                        write!(output, "\r\n")?;
+                        output.flush()?;
+                        return Ok(None);
+                    }
+                    Edit::Interrupted => {
+                        write!(output, "\r\n")?;
+                        output.flush()?;
+                        return Err(ErrorKind::Interrupted.into());
+                    }
+                }
+            }
+
+            self.render(&line, output)?;
+        }
+    }
+
+    /// Process the key press.
+    fn edit(&self, line: &mut Line, key: KeyEvent) -> Edit {
+        use KeyCode::*;
+
+        let KeyEvent { code, modifiers } = key;
+        if modifiers.contains(Modifiers::CONTROL) {
+            match code {
+                Char('a') => line.cursor = 0,
+                Char('b') => line.cursor = line.cursor.saturating_sub(1),
+                Char('c') => return Edit::Interrupted,
+                Char('d') if line.text.is_empty() => return Edit::EndOfInput,
+                Char('d') if line.cursor < line.text.len() => {
+                    line.text.remove(line.cursor);
+                }
+                Char('e') => line.cursor = line.text.len(),
+                Char('f') => line.cursor = (line.cursor + 1).min(line.text.len()),
+                Char('k') => line.text.truncate(line.cursor),
+                Char('n') => self.recall(line, 1),
+                Char('p') => self.recall(line, -1),
+                Char('u') => {
+                    line.text.drain(..line.cursor);
+                    line.cursor = 0;
+                }
+                Char('w') => {
+                    let mut start = line.cursor;
+                    while 0 < start && line.text[start - 1].is_whitespace() {
+                        start -= 1;
+                    }
+                    while 0 < start && !line.text[start - 1].is_whitespace() {
+                        start -= 1;
+                    }
+                    line.text.drain(start..line.cursor);
+                    line.cursor = start;
+                }
+                _ => (),
+            }
+            return Edit::Continue;
+        }
+
+        match code {
+            Char(c)
+                if !modifiers.contains(Modifiers::ALT) && !modifiers.contains(Modifiers::META) =>
+            {
+                line.text.insert(line.cursor, c);
+                line.cursor += 1;
+            }
+            Enter => return Edit::Done,
+            Backspace if 0 < line.cursor => {
+                line.cursor -= 1;
+                line.text.remove(line.cursor);
+            }
+            Delete if line.cursor < line.text.len() => {
+                line.text.remove(line.cursor);
+            }
+            Left => line.cursor = line.cursor.saturating_sub(1),
+            Right => line.cursor = (line.cursor + 1).min(line.text.len()),
+            Home => line.cursor = 0,
+            End => line.cursor = line.text.len(),
+            Up => self.recall(line, -1),
+            Down => self.recall(line, 1),
+            _ => (),
+        }
+
+        Edit::Continue
+    }
+
+    /// Recall the previous or next history entry.
+    fn recall(&self, line: &mut Line, step: isize) {
+        let Some(target) = line.recalled.checked_add_signed(step) else {
+            return;
+        };
+        if self.history.len() < target {
+            return;
+        }
+
+        if line.recalled == self.history.len() {
+            line.draft = line.text.clone();
+        }
+        line.recalled = target;
+        if target == self.history.len() {
+            line.replace(line.draft.clone());
+        } else {
+            line.replace(self.history[target].chars().collect());
+        }
+    }
+
+    /// Redraw the prompt and line, placing the cursor at the right column.
+    fn render(&self, line: &Line, output: &mut impl Write) -> Result<()> {
+        let text: String = line.text.iter().collect();
+        write!(output, "\r{}{}\x1b[K", self.style.paint(&self.prompt), text)?;
+
+        let back = width(&line.after());
+        if 0 < back {
+            write!(output, "\x1b[{}D", back)?;
+        }
+        output.flush()
+    }
+}
+
+/// The outcome of processing a key press.
+enum Edit {
+    Continue,
+    Done,
+    EndOfInput,
+    Interrupted,
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::LineEditor;
+    use prettytty::MockConnection;
+    use std::io::{ErrorKind, Result};
+
+    fn read(editor: &mut LineEditor, keys: &[u8]) -> Result<Option<String>> {
+        let tty = MockConnection::new();
+        tty.push_input(keys);
+        let (mut input, mut output) = tty.io();
+        editor.read_line(&mut input, &mut output)
+    }
+
+    #[test]
+    fn test_editing() -> Result<()> {
+        let mut editor = LineEditor::new("$ ");
+        assert_eq!(
+            read(&mut editor, b"ab\x01c\x05d\r")?.as_deref(),
+            Some("cabd")
+        );
+        assert_eq!(
+            read(&mut editor, b"one two\x17three\r")?.as_deref(),
+            Some("one three")
+        );
+        assert_eq!(
+            read(&mut editor, b"abcd\x1b[D\x1b[D\x0b\x1b[Hx\x1b[3~\r")?.as_deref(),
+            Some("xb")
+        );
+        assert_eq!(read(&mut editor, b"abc\x15\r")?.as_deref(), Some(""));
+        assert_eq!(read(&mut editor, b"\x04")?, None);
+        assert_eq!(
+            read(&mut editor, b"x\x03").unwrap_err().kind(),
+            ErrorKind::Interrupted
+        );
+        assert_eq!(editor.history(), &["cabd", "one three", "xb"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_history() -> Result<()> {
+        let mut editor = LineEditor::new("").with_history_limit(2);
+        editor.add_history("one");
+        editor.add_history("two");
+        editor.add_history("two");
+        editor.add_history("three");
+        assert_eq!(editor.history(), &["two", "three"]);
+
+        assert_eq!(
+            read(&mut editor, b"\x1b[A\x1b[A\x1b[A\r")?.as_deref(),
+            Some("two")
+        );
+        assert_eq!(
+            read(&mut editor, b"draft\x10\x0e!\r")?.as_deref(),
+            Some("draft!")
+        );
+        assert_eq!(editor.history(), &["two", "draft!"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() -> Result<()> {
+        let tty = MockConnection::new();
+        tty.push_input(b"\x1b[200~h\x1bi\x1b[201~\x1b[D\r");
+        let mut editor = LineEditor::new("> ");
+        {
+            let (mut input, mut output) = tty.io();
+            assert_eq!(
+                editor.read_line(&mut input, &mut output)?.as_deref(),
+                Some("hi")
+            );
+        }
+        assert_eq!(
+            tty.written(),
+            b"\r> \x1b[K\r> hi\x1b[K\r> hi\x1b[K\x1b[1D\r\n"
+        );
+        Ok(())
+    }
+}
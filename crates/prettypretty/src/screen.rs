@@ -0,0 +1,493 @@
+//! Optional module implementing a screen buffer with damage-tracked rendering.
+//!
+//! A [`Screen`] holds two [`Buffer`]s of [`Cell`]s, each cell combining a
+//! character with a [`Style`]. The application draws into the back buffer,
+//! accessible through [`Screen::buffer_mut`]. [`Screen::render`] then compares
+//! the back buffer with the front buffer, which reflects what the terminal is
+//! currently displaying, and writes only the cursor movements, style
+//! transitions, and characters necessary for updating the terminal. Since it
+//! relies on [`StyleWriter`] for style transitions, it only writes the SGR
+//! parameters that actually change between adjacent cells.
+//!
+//! Buffers are grids of character cells, with wide characters, e.g., CJK
+//! ideographs and most emoji, occupying two adjacent cells. Control
+//! characters and zero-width characters, including combining marks, are not
+//! supported and replaced by spaces.
+//!
+//! ```
+//! # use prettypretty::screen::Screen;
+//! # use prettypretty::style::Style;
+//! # use prettypretty::termco::AnsiColor;
+//! let mut screen = Screen::new(2, 8);
+//! let red = Style::default().with_foreground(AnsiColor::Red);
+//! screen.buffer_mut().put_str(0, 0, "Hello", &red);
+//!
+//! // The first render updates the entire screen.
+//! let mut output = Vec::new();
+//! screen.render(&mut output)?;
+//!
+//! // Subsequent renders only update what has changed.
+//! screen.buffer_mut().put_str(0, 0, "J", &red);
+//! output.clear();
+//! screen.render(&mut output)?;
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "\x1b[1;1H\x1b[31mJ\x1b[39m"
+//! );
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{Result, Write};
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::style::{Fidelity, Style, StyleWriter};
+use crate::Translator;
+
+/// The content of the second cell occupied by a wide character.
+const CONTINUATION: char = '\0';
+
+/// A character cell.
+///
+/// A cell combines a character with a style. By default, it is a space with
+/// the default style. A wide character occupies two cells, with the second
+/// cell being a continuation of the first one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cell {
+    content: char,
+    style: Style,
+}
+
+impl Cell {
+    /// Create a new cell.
+    ///
+    /// This method replaces control and zero-width characters with spaces.
+    pub fn new(content: char, style: Style) -> Self {
+        let content = match content.width() {
+            Some(1) | Some(2) => content,
+            _ => ' ',
+        };
+
+        Self { content, style }
+    }
+
+    /// Create a new continuation cell for a wide character.
+    fn continuation(style: Style) -> Self {
+        Self {
+            content: CONTINUATION,
+            style,
+        }
+    }
+
+    /// Get the cell's character.
+    ///
+    /// For the second cell of a wide character, this method returns the null
+    /// character.
+    pub fn content(&self) -> char {
+        self.content
+    }
+
+    /// Get the cell's style.
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Determine whether this cell continues the wide character in the
+    /// preceding cell.
+    pub fn is_continuation(&self) -> bool {
+        self.content == CONTINUATION
+    }
+
+    /// Determine the number of columns occupied by this cell's character.
+    ///
+    /// This method returns 2 for wide characters, 0 for continuations, and 1
+    /// otherwise.
+    pub fn width(&self) -> u16 {
+        if self.is_continuation() {
+            0
+        } else {
+            self.content.width().unwrap_or(1) as u16
+        }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            content: ' ',
+            style: Style::default(),
+        }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(value: char) -> Self {
+        Self::new(value, Style::default())
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// A grid of character cells.
+///
+/// Rows and columns are 0-based. Methods that access individual cells panic
+/// on coordinates outside the buffer, whereas methods that write strings or
+/// fill regions clip them to the buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Buffer {
+    rows: u16,
+    columns: u16,
+    cells: Vec<Cell>,
+}
+
+impl Buffer {
+    /// Create a new buffer with the given number of rows and columns.
+    pub fn new(rows: u16, columns: u16) -> Self {
+        Self {
+            rows,
+            columns,
+            cells: vec![Cell::default(); rows as usize * columns as usize],
+        }
+    }
+
+    /// Get the number of rows.
+    pub fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// Get the number of columns.
+    pub fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    fn index(&self, row: u16, column: u16) -> usize {
+        assert!(
+            row < self.rows && column < self.columns,
+            "cell ({}, {}) is outside {}x{} buffer",
+            row,
+            column,
+            self.rows,
+            self.columns
+        );
+        row as usize * self.columns as usize + column as usize
+    }
+
+    /// Get the cells of the given row.
+    pub fn row(&self, row: u16) -> &[Cell] {
+        let start = self.index(row, 0);
+        &self.cells[start..start + self.columns as usize]
+    }
+
+    /// Get the cell at the given row and column.
+    pub fn get(&self, row: u16, column: u16) -> &Cell {
+        &self.cells[self.index(row, column)]
+    }
+
+    /// Set the cell at the given row and column.
+    ///
+    /// If the new cell overwrites half of a wide character, this method
+    /// replaces the other half with a space. If the new cell holds a wide
+    /// character, it also updates the following cell. If there is no
+    /// following cell, it replaces the wide character with a space.
+    pub fn set(&mut self, row: u16, column: u16, cell: Cell) {
+        let index = self.index(row, column);
+        self.clear_wide(row, column);
+
+        if cell.width() == 2 {
+            if column + 1 < self.columns {
+                self.clear_wide(row, column + 1);
+                self.cells[index + 1] = Cell::continuation(cell.style.clone());
+                self.cells[index] = cell;
+            } else {
+                self.cells[index] = Cell::new(' ', cell.style);
+            }
+        } else if cell.is_continuation() {
+            self.cells[index] = Cell::new(' ', cell.style);
+        } else {
+            self.cells[index] = cell;
+        }
+    }
+
+    /// Replace the other half of any wide character at the given coordinates.
+    fn clear_wide(&mut self, row: u16, column: u16) {
+        let index = self.index(row, column);
+        let cell = &self.cells[index];
+        if cell.is_continuation() && 0 < column {
+            let style = self.cells[index - 1].style.clone();
+            self.cells[index - 1] = Cell::new(' ', style);
+        } else if cell.width() == 2 && column + 1 < self.columns {
+            let style = self.cells[index + 1].style.clone();
+            self.cells[index + 1] = Cell::new(' ', style);
+        }
+    }
+
+    /// Write the string with the given style starting at the given row and
+    /// column.
+    ///
+    /// This method skips control and zero-width characters. It stops at the
+    /// end of the row and returns the number of columns written.
+    pub fn put_str(&mut self, row: u16, column: u16, text: &str, style: &Style) -> u16 {
+        if self.rows <= row {
+            return 0;
+        }
+
+        let mut current = column;
+        for c in text.chars() {
+            let width = match c.width() {
+                Some(width @ (1 | 2)) => width as u16,
+                _ => continue,
+            };
+            if self.columns < current.saturating_add(width) {
+                break;
+            }
+
+            self.set(row, current, Cell::new(c, style.clone()));
+            current += width;
+        }
+
+        current.saturating_sub(column)
+    }
+
+    /// Fill the rectangle with the given cell.
+    ///
+    /// Wide characters are not supported and replaced with spaces.
+    pub fn fill(&mut self, row: u16, column: u16, rows: u16, columns: u16, cell: &Cell) {
+        let cell = if cell.width() == 1 {
+            cell.clone()
+        } else {
+            Cell::new(' ', cell.style.clone())
+        };
+
+        let row_end = row.saturating_add(rows).min(self.rows);
+        let column_end = column.saturating_add(columns).min(self.columns);
+        for r in row..row_end {
+            for c in column..column_end {
+                self.set(r, c, cell.clone());
+            }
+        }
+    }
+
+    /// Reset all cells to spaces with the default style.
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::default());
+    }
+
+    /// Resize this buffer.
+    ///
+    /// This method preserves the content of cells that remain within the
+    /// buffer, while new cells are spaces with the default style.
+    pub fn resize(&mut self, rows: u16, columns: u16) {
+        let mut resized = Self::new(rows, columns);
+        for row in 0..rows.min(self.rows) {
+            for column in 0..columns.min(self.columns) {
+                let cell = self.get(row, column);
+                if !cell.is_continuation() {
+                    resized.set(row, column, cell.clone());
+                }
+            }
+        }
+        *self = resized;
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// A double-buffered screen.
+///
+/// See the [module-level documentation](self) for an overview.
+#[derive(Clone, Debug)]
+pub struct Screen {
+    front: Buffer,
+    back: Buffer,
+    is_valid: bool,
+}
+
+impl Screen {
+    /// Create a new screen with the given number of rows and columns.
+    ///
+    /// The first render updates the entire screen.
+    pub fn new(rows: u16, columns: u16) -> Self {
+        Self {
+            front: Buffer::new(rows, columns),
+            back: Buffer::new(rows, columns),
+            is_valid: false,
+        }
+    }
+
+    /// Get the number of rows.
+    pub fn rows(&self) -> u16 {
+        self.back.rows()
+    }
+
+    /// Get the number of columns.
+    pub fn columns(&self) -> u16 {
+        self.back.columns()
+    }
+
+    /// Get the back buffer.
+    pub fn buffer(&self) -> &Buffer {
+        &self.back
+    }
+
+    /// Get the back buffer for drawing.
+    pub fn buffer_mut(&mut self) -> &mut Buffer {
+        &mut self.back
+    }
+
+    /// Resize the screen.
+    ///
+    /// Since terminals differ in how they reflow content on resize, the next
+    /// render updates the entire screen.
+    pub fn resize(&mut self, rows: u16, columns: u16) {
+        self.back.resize(rows, columns);
+        self.front = Buffer::new(rows, columns);
+        self.is_valid = false;
+    }
+
+    /// Force the next render to update the entire screen.
+    ///
+    /// Use this method after other code has written to the terminal.
+    pub fn invalidate(&mut self) {
+        self.is_valid = false;
+    }
+
+    /// Render the changes to the back buffer.
+    ///
+    /// This method writes the updates to the given writer, restores the
+    /// terminal's default appearance, and flushes the writer. It leaves the
+    /// cursor after the last updated cell. Upon success, the back buffer
+    /// becomes the new front buffer, while also remaining available for
+    /// further drawing.
+    pub fn render(&mut self, output: &mut impl Write) -> Result<()> {
+        self.render_with(StyleWriter::new(output))
+    }
+
+    /// Render the changes to the back buffer while capping styles to the
+    /// given fidelity.
+    pub fn render_with_fidelity(
+        &mut self,
+        output: &mut impl Write,
+        fidelity: Fidelity,
+        translator: &Translator,
+    ) -> Result<()> {
+        self.render_with(StyleWriter::with_fidelity(output, fidelity, translator))
+    }
+
+    fn render_with<W: Write>(&mut self, mut writer: StyleWriter<'_, W>) -> Result<()> {
+        let mut cursor = None;
+
+        for row in 0..self.back.rows() {
+            let back = self.back.row(row);
+            let front = self.front.row(row);
+            if self.is_valid && back == front {
+                continue;
+            }
+
+            let mut column = 0;
+            while column < self.back.columns() {
+                let index = column as usize;
+                let cell = &back[index];
+                if cell.is_continuation() {
+                    column += 1;
+                    continue;
+                }
+
+                let width = cell.width();
+                let end = index + width as usize;
+                if self.is_valid && back[index..end] == front[index..end] {
+                    column += width;
+                    continue;
+                }
+
+                if cursor != Some((row, column)) {
+                    write!(writer.get_mut(), "\x1b[{};{}H", row + 1, column + 1)?;
+                }
+                writer.write_styled(cell.style(), cell.content())?;
+
+                // Writing the last column leaves the cursor in limbo.
+                column += width;
+                cursor = if column < self.back.columns() {
+                    Some((row, column))
+                } else {
+                    None
+                };
+            }
+        }
+
+        writer.into_inner()?;
+        self.front.clone_from(&self.back);
+        self.is_valid = true;
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{Buffer, Cell, Screen};
+    use crate::style::Style;
+    use crate::termco::AnsiColor;
+    use std::io::Result;
+
+    fn render(screen: &mut Screen) -> Result<String> {
+        let mut output = Vec::new();
+        screen.render(&mut output)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_buffer() {
+        let mut buffer = Buffer::new(2, 4);
+        assert_eq!(buffer.put_str(0, 0, "a\u{7}界b", &Style::default()), 4);
+        assert_eq!(buffer.get(0, 1).content(), '界');
+        assert!(buffer.get(0, 2).is_continuation());
+        assert_eq!(buffer.get(0, 3).content(), 'b');
+
+        // Overwriting half a wide character blanks the other half.
+        buffer.set(0, 2, Cell::from('x'));
+        assert_eq!(buffer.get(0, 1).content(), ' ');
+        assert_eq!(buffer.get(0, 2).content(), 'x');
+
+        // Wide characters do not fit into the last column.
+        assert_eq!(buffer.put_str(1, 3, "界", &Style::default()), 0);
+        buffer.set(1, 3, Cell::from('界'));
+        assert_eq!(buffer.get(1, 3).content(), ' ');
+
+        buffer.resize(1, 3);
+        assert_eq!(
+            buffer.row(0),
+            &[Cell::from('a'), Cell::from(' '), Cell::from('x')]
+        );
+    }
+
+    #[test]
+    fn test_render() -> Result<()> {
+        let red = Style::default().with_foreground(AnsiColor::Red);
+        let mut screen = Screen::new(2, 3);
+        screen.buffer_mut().put_str(0, 0, "ab", &red);
+
+        assert_eq!(
+            render(&mut screen)?,
+            "\x1b[1;1H\x1b[31mab\x1b[39m \x1b[2;1H   "
+        );
+        assert_eq!(render(&mut screen)?, "");
+
+        screen.buffer_mut().put_str(1, 1, "界", &Style::default());
+        assert_eq!(render(&mut screen)?, "\x1b[2;2H界");
+
+        screen.buffer_mut().put_str(0, 1, "bc", &red);
+        screen.buffer_mut().put_str(1, 1, "de", &red);
+        assert_eq!(
+            render(&mut screen)?,
+            "\x1b[1;3H\x1b[31mc\x1b[2;2Hde\x1b[39m"
+        );
+
+        screen.invalidate();
+        assert_eq!(
+            render(&mut screen)?,
+            "\x1b[1;1H\x1b[31mabc\x1b[2;1H\x1b[39m \x1b[31mde\x1b[39m"
+        );
+        Ok(())
+    }
+}
@@ -28,6 +28,11 @@
 //!     `SpectralDistribution<Value=Float>`.
 //!   * [`FixedDistribution`] is an implementation of
 //!     `SpectralDistribution<Value=Float>` with a fixed value.
+//!   * [`SampledDistribution`] is an implementation of
+//!     `SpectralDistribution<Value=Float>` that resamples measured data or
+//!     synthesizes Gaussian, band-pass, and monochromatic spectra.
+//!   * [`BlackbodyDistribution`] is an implementation of
+//!     `SpectralDistribution<Value=Float>` based on Planck's law.
 #![cfg_attr(
     feature = "pyffi",
     doc = "  * [`Illuminant`] is an implementation of
@@ -51,19 +56,45 @@
 )]
 //!
 //! Using the above trait implementations, this module exports the following concrete
-//! illuminants and observers, with data directly sourced from the CIE.
+//! illuminants and observers. The data for the D-series illuminants and the
+//! observers is directly sourced from the CIE. The data for illuminant A is
+//! computed from its defining formula in CIE 15:2018, and illuminant E is
+//! constant.
 //!
+//!   * [`CIE_ILLUMINANT_A`] approximates incandescent, tungsten-filament light.
 //!   * [`CIE_ILLUMINANT_D50`] approximates daylight near sunrise and sunset.
 //!   * [`CIE_ILLUMINANT_D65`] approximates daylight around noon.
 //!   * [`CIE_ILLUMINANT_E`] has equal energy.
 //!   * [`CIE_OBSERVER_2DEG_1931`] is the 1931 2º color matching function.
 //!   * [`CIE_OBSERVER_10DEG_1964`] is the 1964 10º color matching function.
 //!
+//! This module does not yet include the fluorescent F-series (F1–F12) or the
+//! LED-series illuminants of CIE 15:2018. Their tables are tabulated at 5nm
+//! resolution and will be added as constants resampled to 1nm resolution with
+//! [`SampledDistribution::from_uniform`]. Until then, applications that need
+//! these illuminants can load the CIE's tables the same way.
+//!
+//! [`MACADAM_ELLIPSES`] are MacAdam's ellipses of chromaticity discrimination.
+//! [`MacAdamEllipse`] scales, traces, and tests chromaticities against them,
+//! and [`macadam_steps`] measures the difference between chromaticities in
+//! just noticeable differences.
+//!
 //! A submodule provides [`std_observer::x()`], [`std_observer::y()`], and
 //! [`std_observer::z()`] as analytical approximations for the 1931 2º observer.
 //! As such, all three functions accept floating point arguments for
 //! wavelengths.
 //!
+//! Blackbody radiators are created with [`blackbody()`]. Their chromaticity is
+//! determined by [`blackbody_chromaticity()`], and [`planckian_locus()`] traces
+//! the Planckian locus across a range of temperatures. Conversely,
+//! [`cct_duv_from_xy`] and [`cct_duv_from_uv_prime`] determine the correlated
+//! color temperature and Duv for a chromaticity.
+//!
+//! [`IlluminatedObserver::reflectance_color`] determines the color of an
+//! object with a given reflectance under an illuminant. [`metamerism_index`]
+//! determines how much two objects matching under one illuminant differ under
+//! others.
+//!
 //! Finally, [`SpectrumTraversal`] is an iterator for tracing the spectral locus
 //! or the human visual gamut. It is instantiated with
 //! [`IlluminatedObserver::visual_gamut`].
@@ -79,13 +110,15 @@
 )]
 //!
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
 #[cfg(feature = "pyffi")]
 use pyo3::prelude::*;
 
 use crate::{
-    core::{GamutTraversalStep, ThreeSum},
+    core::{GamutTraversalStep, Sum, ThreeSum},
+    error::SampleError,
     Color, ColorSpace, Float,
 };
 
@@ -379,21 +412,519 @@ impl SpectralDistribution for FixedDistribution {
 
 // --------------------------------------------------------------------------------------------------------------------
 
+/// The interpolation method for resampling measured data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Linear interpolation between adjacent samples.
+    #[default]
+    Linear,
+    /// A natural cubic spline through all samples.
+    ///
+    /// The spline produces smoother curves than linear interpolation, but it
+    /// may overshoot around abrupt changes.
+    Spline,
+}
+
+/// A spectral distribution resampled from measured data.
+///
+/// This spectral distribution makes measured spectral power distributions and
+/// reflectance curves available at 1nm resolution. Its main constructors accept
+/// samples at arbitrary wavelengths, interpolate between them, and cover all
+/// whole nanometers between the first and last sample, without extrapolation.
+/// The resulting distribution can be used just like the CIE's standard
+/// illuminants, e.g., with [`IlluminatedObserver`].
+///
+/// In addition, [`SampledDistribution::gaussian`],
+/// [`SampledDistribution::band_pass`], and
+/// [`SampledDistribution::monochromatic`] create synthetic distributions,
+/// which are useful for exploring the visual gamut.
+///
+/// ```
+/// # use prettypretty::spectrum::{SampledDistribution, SpectralDistribution};
+/// let spd = SampledDistribution::from_uniform("Measured", 400.0, 10.0, &[20.0, 30.0, 60.0])?;
+/// assert_eq!(spd.range(), 400..421);
+/// assert_eq!(spd.at(405), Some(25.0));
+/// assert_eq!(spd.at(415), Some(45.0));
+/// # Ok::<(), prettypretty::error::SampleError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct SampledDistribution {
+    label: String,
+    start: usize,
+    checksum: Float,
+    data: Vec<Float>,
+}
+
+impl SampledDistribution {
+    /// Create a new sampled distribution with linear interpolation.
+    ///
+    /// The samples are wavelength, value pairs with strictly increasing
+    /// wavelengths.
+    pub fn new(label: impl Into<String>, samples: &[(Float, Float)]) -> Result<Self, SampleError> {
+        Self::with_interpolation(label, samples, Interpolation::Linear)
+    }
+
+    /// Create a new sampled distribution with uniformly spaced samples and
+    /// linear interpolation.
+    ///
+    /// The first value is for the start wavelength and each subsequent value is
+    /// for a wavelength that is the given step longer, e.g., 5nm or 10nm.
+    pub fn from_uniform(
+        label: impl Into<String>,
+        start: Float,
+        step: Float,
+        values: &[Float],
+    ) -> Result<Self, SampleError> {
+        let samples: Vec<_> = values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (start + step * index as Float, *value))
+            .collect();
+        Self::new(label, &samples)
+    }
+
+    /// Create a new sampled distribution with the given interpolation.
+    pub fn with_interpolation(
+        label: impl Into<String>,
+        samples: &[(Float, Float)],
+        interpolation: Interpolation,
+    ) -> Result<Self, SampleError> {
+        if samples.len() < 2 {
+            return Err(SampleError::TooFewSamples);
+        }
+        for (wavelength, value) in samples {
+            if !wavelength.is_finite() || *wavelength < 0.0 || !value.is_finite() || *value < 0.0 {
+                return Err(SampleError::InvalidValue);
+            }
+        }
+        if samples.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+            return Err(SampleError::UnorderedWavelengths);
+        }
+
+        let start = samples[0].0.ceil();
+        let end = samples[samples.len() - 1].0.floor();
+        if end < start {
+            return Err(SampleError::EmptyRange);
+        }
+
+        let curvature = match interpolation {
+            Interpolation::Linear => None,
+            Interpolation::Spline => Some(spline_curvature(samples)),
+        };
+
+        let mut checksum = Sum::new();
+        let mut data = Vec::with_capacity((end - start) as usize + 1);
+        let mut index = 0;
+        for wavelength in start as usize..=end as usize {
+            let wavelength = wavelength as Float;
+            while index + 2 < samples.len() && samples[index + 1].0 < wavelength {
+                index += 1;
+            }
+
+            let (x0, y0) = samples[index];
+            let (x1, y1) = samples[index + 1];
+            let h = x1 - x0;
+            let (a, b) = ((x1 - wavelength) / h, (wavelength - x0) / h);
+            let mut value = a * y0 + b * y1;
+            if let Some(ref m) = curvature {
+                value +=
+                    ((a * a * a - a) * m[index] + (b * b * b - b) * m[index + 1]) * h * h / 6.0;
+            }
+
+            checksum += value;
+            data.push(value);
+        }
+
+        Ok(Self {
+            label: label.into(),
+            start: start as usize,
+            checksum: checksum.value(),
+            data,
+        })
+    }
+
+    /// Create a new synthetic distribution from the function.
+    ///
+    /// The distribution covers the spectrum from 300 to 830 nanometers (both
+    /// inclusive), just like the CIE's standard illuminants.
+    fn from_fn(label: String, f: impl Fn(Float) -> Float) -> Self {
+        let mut checksum = Sum::new();
+        let data: Vec<Float> = (300..=830)
+            .map(|wavelength| {
+                let value = f(wavelength as Float);
+                checksum += value;
+                value
+            })
+            .collect();
+
+        Self {
+            label,
+            start: 300,
+            checksum: checksum.value(),
+            data,
+        }
+    }
+
+    /// Create a new Gaussian distribution with unit height.
+    ///
+    /// The distribution peaks at the center wavelength and has the given
+    /// standard deviation in nanometers. It covers the spectrum from 300 to
+    /// 830 nanometers (both inclusive).
+    pub fn gaussian(center: Float, deviation: Float) -> Self {
+        Self::from_fn(
+            format!("Gaussian {}nm ±{}nm", center, deviation),
+            |wavelength| {
+                let z = (wavelength - center) / deviation;
+                (-0.5 * z * z).exp()
+            },
+        )
+    }
+
+    /// Create a new band-pass distribution with unit height.
+    ///
+    /// The distribution is 1 between the low and high wavelengths (both
+    /// inclusive) and 0 elsewhere. It covers the spectrum from 300 to 830
+    /// nanometers (both inclusive).
+    pub fn band_pass(low: usize, high: usize) -> Self {
+        Self::from_fn(format!("Band-pass {}-{}nm", low, high), |wavelength| {
+            if low as Float <= wavelength && wavelength <= high as Float {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// Create a new monochromatic distribution with unit height.
+    ///
+    /// The distribution is 1 at the wavelength and 0 elsewhere. It covers the
+    /// spectrum from 300 to 830 nanometers (both inclusive).
+    pub fn monochromatic(wavelength: usize) -> Self {
+        let mut spd = Self::band_pass(wavelength, wavelength);
+        spd.label = format!("Monochromatic {}nm", wavelength);
+        spd
+    }
+}
+
+/// Compute the second derivatives of a natural cubic spline through the
+/// samples by solving the tridiagonal system with the Thomas algorithm.
+fn spline_curvature(samples: &[(Float, Float)]) -> Vec<Float> {
+    let n = samples.len();
+    let mut curvature = vec![0.0; n];
+    let mut diagonal = vec![1.0; n];
+    let mut rhs = vec![0.0; n];
+
+    for i in 1..n - 1 {
+        let (x0, y0) = samples[i - 1];
+        let (x1, y1) = samples[i];
+        let (x2, y2) = samples[i + 1];
+        let (h0, h1) = (x1 - x0, x2 - x1);
+
+        // Eliminate the sub-diagonal h0 using the previous row.
+        let factor = if i == 1 { 0.0 } else { h0 / diagonal[i - 1] };
+        diagonal[i] = 2.0 * (h0 + h1) - factor * h0;
+        rhs[i] = 6.0 * ((y2 - y1) / h1 - (y1 - y0) / h0) - factor * rhs[i - 1];
+    }
+
+    for i in (1..n - 1).rev() {
+        let h1 = samples[i + 1].0 - samples[i].0;
+        curvature[i] = (rhs[i] - h1 * curvature[i + 1]) / diagonal[i];
+    }
+
+    curvature
+}
+
+impl SpectralDistribution for SampledDistribution {
+    type Value = Float;
+
+    fn label(&self) -> String {
+        self.label.clone()
+    }
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn at(&self, wavelength: usize) -> Option<Self::Value> {
+        if self.start <= wavelength && wavelength < self.start + self.data.len() {
+            Some(self.data[wavelength - self.start])
+        } else {
+            None
+        }
+    }
+
+    fn checksum(&self) -> Self::Value {
+        self.checksum
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// The second radiation constant `c2 = h·c/k` in meter-kelvin.
+///
+/// This is the value used by CIE 15:2018 for computing Planckian radiators.
+pub const SECOND_RADIATION_CONSTANT: Float = 1.438_776_877e-2;
+
+/// A blackbody radiator at nanometer resolution.
+///
+/// This spectral distribution applies Planck's law to compute the relative
+/// spectral power of an ideal blackbody radiator at the given temperature. It
+/// covers the spectrum from 300 to 830 nanometers (both inclusive), just like
+/// the CIE's standard illuminants. Also like them, its values are not
+/// unit-scaled but normalized to 100 at 560nm.
+#[derive(Clone, Debug)]
+pub struct BlackbodyDistribution {
+    temperature: Float,
+    checksum: Float,
+    data: Vec<Float>,
+}
+
+impl BlackbodyDistribution {
+    /// Create a new blackbody distribution for the temperature in kelvin.
+    ///
+    /// # Panics
+    ///
+    /// If the temperature is not positive.
+    pub fn new(temperature: Float) -> Self {
+        assert!(
+            0.0 < temperature,
+            "temperature {} is not positive",
+            temperature
+        );
+
+        let planck = |wavelength: Float| {
+            let meters = wavelength * 1e-9;
+            1.0 / (meters.powi(5)
+                * ((SECOND_RADIATION_CONSTANT / (meters * temperature)).exp() - 1.0))
+        };
+
+        let reference = planck(560.0);
+        let mut checksum = Sum::new();
+        let data: Vec<Float> = (300..=830)
+            .map(|wavelength| {
+                let value = 100.0 * planck(wavelength as Float) / reference;
+                checksum += value;
+                value
+            })
+            .collect();
+
+        Self {
+            temperature,
+            checksum: checksum.value(),
+            data,
+        }
+    }
+
+    /// Get the blackbody's temperature in kelvin.
+    pub fn temperature(&self) -> Float {
+        self.temperature
+    }
+}
+
+impl SpectralDistribution for BlackbodyDistribution {
+    type Value = Float;
+
+    fn label(&self) -> String {
+        format!("Blackbody {}K", self.temperature)
+    }
+
+    fn start(&self) -> usize {
+        300
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn at(&self, wavelength: usize) -> Option<Self::Value> {
+        if 300 <= wavelength && wavelength < 300 + self.data.len() {
+            Some(self.data[wavelength - 300])
+        } else {
+            None
+        }
+    }
+
+    fn checksum(&self) -> Self::Value {
+        self.checksum
+    }
+}
+
+/// Create a blackbody radiator for the temperature in kelvin.
+///
+/// This function is a shorthand for [`BlackbodyDistribution::new`].
+pub fn blackbody(temperature: Float) -> BlackbodyDistribution {
+    BlackbodyDistribution::new(temperature)
+}
+
+/// Determine the xy chromaticity of a blackbody radiator.
+///
+/// This function computes the white point of a blackbody radiator at the
+/// temperature in kelvin for the given observer and returns its chromaticity
+/// coordinates.
+pub fn blackbody_chromaticity<O>(temperature: Float, observer: &O) -> (Float, Float)
+where
+    O: SpectralDistribution<Value = [Float; 3]>,
+{
+    IlluminatedObserver::new(&blackbody(temperature), observer)
+        .white_point()
+        .xy_chromaticity()
+}
+
+/// Trace the Planckian locus.
+///
+/// This function returns an iterator over the white points of blackbody
+/// radiators with the given temperatures in kelvin for the given observer.
+/// Just like [`SpectrumTraversal`], the iterator yields [`GamutTraversalStep`]s
+/// in the XYZ color space, starting with a move and continuing with lines.
+pub fn planckian_locus<'a, O>(
+    observer: &'a O,
+    temperatures: impl IntoIterator<Item = Float> + 'a,
+) -> impl Iterator<Item = GamutTraversalStep> + 'a
+where
+    O: SpectralDistribution<Value = [Float; 3]>,
+{
+    temperatures
+        .into_iter()
+        .enumerate()
+        .map(move |(index, temperature)| {
+            let color = IlluminatedObserver::new(&blackbody(temperature), observer).white_point();
+            if index == 0 {
+                GamutTraversalStep::MoveTo(color)
+            } else {
+                GamutTraversalStep::LineTo(color)
+            }
+        })
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// The lowest correlated color temperature in kelvin.
+const CCT_MINIMUM: Float = 1_000.0;
+
+/// The highest correlated color temperature in kelvin.
+const CCT_MAXIMUM: Float = 100_000.0;
+
+/// Convert xy to CIE 1960 uv chromaticity coordinates.
+fn xy_to_uv(x: Float, y: Float) -> (Float, Float) {
+    let denominator = -2.0 * x + 12.0 * y + 3.0;
+    (4.0 * x / denominator, 6.0 * y / denominator)
+}
+
+/// Determine the CIE 1960 uv chromaticity of a blackbody radiator.
+fn planckian_uv(temperature: Float) -> (Float, Float) {
+    let (x, y) = blackbody_chromaticity(temperature, &CIE_OBSERVER_2DEG_1931);
+    xy_to_uv(x, y)
+}
+
+/// Determine the correlated color temperature and Duv for xy chromaticity
+/// coordinates.
+///
+/// See [`cct_duv_from_uv_prime`] for details.
+///
+/// # Examples
+///
+/// ```
+/// # use prettypretty::{Color, ColorSpace};
+/// # use prettypretty::spectrum::cct_duv_from_xy;
+/// let background = Color::new(ColorSpace::Srgb, [1.0, 1.0, 1.0]);
+/// let (cct, duv) = cct_duv_from_xy(background.xy_chromaticity()).unwrap();
+/// assert_eq!(cct.round(), 6504.0);
+/// assert_eq!((duv * 10_000.0).round(), 32.0);
+/// ```
+pub fn cct_duv_from_xy(chromaticity: (Float, Float)) -> Option<(Float, Float)> {
+    let (u, v) = xy_to_uv(chromaticity.0, chromaticity.1);
+    cct_duv_from_uv(u, v)
+}
+
+/// Determine the correlated color temperature and Duv for u'v' chromaticity
+/// coordinates.
+///
+/// The correlated color temperature (CCT) is the temperature in kelvin of the
+/// blackbody radiator whose chromaticity is closest to the given chromaticity
+/// in the CIE 1960 uv chromaticity diagram. Duv is the signed distance to that
+/// radiator, with positive values above and negative values below the
+/// Planckian locus. Both are only meaningful for chromaticities close to the
+/// Planckian locus, with Duv between -0.05 and 0.05.
+///
+/// This function implements Ohno's triangulation method from "Practical Use
+/// and Calculation of CCT and Duv" (LEUKOS, 2014). It locates
+/// the closest blackbody radiator by computing the Planckian locus for the
+/// 1931 2º standard observer with successively finer temperature steps and
+/// then triangulates between the two neighboring radiators. It returns `None`
+/// if the correlated color temperature is not between 1,000 and 100,000
+/// kelvin.
+pub fn cct_duv_from_uv_prime(chromaticity: (Float, Float)) -> Option<(Float, Float)> {
+    cct_duv_from_uv(chromaticity.0, 2.0 * chromaticity.1 / 3.0)
+}
+
+fn cct_duv_from_uv(u: Float, v: Float) -> Option<(Float, Float)> {
+    if !u.is_finite() || !v.is_finite() {
+        return None;
+    }
+
+    let distance = |(pu, pv): (Float, Float)| (u - pu).hypot(v - pv);
+
+    // Cascade through logarithmically spaced temperatures.
+    let mut low = CCT_MINIMUM.ln();
+    let mut high = CCT_MAXIMUM.ln();
+    for (round, steps) in [100, 10, 10, 10, 10, 10, 10].into_iter().enumerate() {
+        let step = (high - low) / steps as Float;
+        let mut best = (0, Float::INFINITY);
+        for index in 0..=steps {
+            let d = distance(planckian_uv((low + step * index as Float).exp()));
+            if d < best.1 {
+                best = (index, d);
+            }
+        }
+
+        if round == 0 && (best.0 == 0 || best.0 == steps) {
+            return None;
+        }
+
+        let index = best.0.clamp(1, steps - 1) as Float;
+        (low, high) = (low + step * (index - 1.0), low + step * (index + 1.0));
+    }
+
+    // Triangulate between the two neighbors.
+    let (t0, t2) = (low.exp(), high.exp());
+    let (p0, p2) = (planckian_uv(t0), planckian_uv(t2));
+    let (d0, d2) = (distance(p0), distance(p2));
+    let l = (p2.0 - p0.0).hypot(p2.1 - p0.1);
+    let x = (d0 * d0 - d2 * d2 + l * l) / (2.0 * l);
+
+    let cct = t0 + (t2 - t0) * x / l;
+    let vx = p0.1 + (p2.1 - p0.1) * x / l;
+    let duv = (d0 * d0 - x * x).max(0.0).sqrt().copysign(v - vx);
+    Some((cct, duv))
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
 /// A standard observer at nanometer resolution.
 ///
 /// The CIE's standard observers, or color matching functions, model human color
 /// perception. Since humans are trichromatic, the per-wavelength values of
 /// standard observers are triples of floating point numbers.
+///
+/// Besides the two built-in observers, [`CIE_OBSERVER_2DEG_1931`] and
+/// [`CIE_OBSERVER_10DEG_1964`], observers can be created from tables of color
+/// matching functions, e.g., the CIE 2006 observers, with
+/// [`Observer::from_table`] and [`Observer::from_samples`] or from cone
+/// fundamentals with [`Observer::from_cone_fundamentals`].
 #[cfg_attr(
     feature = "pyffi",
     pyclass(frozen, module = "prettypretty.color.spectrum")
 )]
 #[derive(Clone, Debug)]
 pub struct Observer {
-    label: &'static str,
+    label: Cow<'static, str>,
     start: usize,
     checksum: [Float; 3],
-    data: &'static [[Float; 3]],
+    data: Cow<'static, [[Float; 3]]>,
 }
 
 impl Observer {
@@ -405,12 +936,91 @@ impl Observer {
         data: &'static [[Float; 3]],
     ) -> Self {
         Self {
-            label,
+            label: Cow::Borrowed(label),
             start,
             checksum,
-            data,
+            data: Cow::Borrowed(data),
         }
     }
+
+    /// Create a new observer from a table of color matching functions.
+    ///
+    /// The table has one entry per nanometer, starting with the given
+    /// wavelength.
+    pub fn from_table(label: impl Into<String>, start: usize, data: Vec<[Float; 3]>) -> Self {
+        let mut checksum = ThreeSum::new();
+        for value in data.iter() {
+            checksum += *value;
+        }
+
+        Self {
+            label: Cow::Owned(label.into()),
+            start,
+            checksum: checksum.value(),
+            data: Cow::Owned(data),
+        }
+    }
+
+    /// Create a new observer from samples of color matching functions.
+    ///
+    /// The samples are wavelength, value pairs with strictly increasing
+    /// wavelengths. They are resampled to 1nm resolution as described for
+    /// [`SampledDistribution`].
+    pub fn from_samples(
+        label: impl Into<String>,
+        samples: &[(Float, [Float; 3])],
+        interpolation: Interpolation,
+    ) -> Result<Self, SampleError> {
+        let (start, data) = resample_triples(samples, interpolation)?;
+        Ok(Self::from_table(label, start, data))
+    }
+
+    /// Create a new observer from samples of cone fundamentals.
+    ///
+    /// The samples are wavelength, LMS value pairs with strictly increasing
+    /// wavelengths. They are resampled to 1nm resolution as described for
+    /// [`SampledDistribution`] and then converted to color matching functions
+    /// with the given matrix. For example, CIE 170-2:2015 defines the matrices
+    /// for deriving the CIE 2006 2º and 10º observers from the corresponding
+    /// cone fundamentals.
+    pub fn from_cone_fundamentals(
+        label: impl Into<String>,
+        samples: &[(Float, [Float; 3])],
+        lms_to_xyz: &[[Float; 3]; 3],
+        interpolation: Interpolation,
+    ) -> Result<Self, SampleError> {
+        let (start, data) = resample_triples(samples, interpolation)?;
+        let data = data
+            .into_iter()
+            .map(|[l, m, s]| lms_to_xyz.map(|[c0, c1, c2]| c0 * l + c1 * m + c2 * s))
+            .collect();
+        Ok(Self::from_table(label, start, data))
+    }
+}
+
+/// Resample the triples to 1nm resolution, returning the start wavelength and
+/// the resampled triples.
+fn resample_triples(
+    samples: &[(Float, [Float; 3])],
+    interpolation: Interpolation,
+) -> Result<(usize, Vec<[Float; 3]>), SampleError> {
+    let component = |index: usize| {
+        let component: Vec<_> = samples
+            .iter()
+            .map(|(wavelength, value)| (*wavelength, value[index]))
+            .collect();
+        SampledDistribution::with_interpolation("", &component, interpolation)
+    };
+
+    let [c0, c1, c2] = [component(0)?, component(1)?, component(2)?];
+    let data = c0
+        .data
+        .iter()
+        .zip(c1.data.iter())
+        .zip(c2.data.iter())
+        .map(|((v0, v1), v2)| [*v0, *v1, *v2])
+        .collect();
+    Ok((c0.start, data))
 }
 
 #[cfg_attr(feature = "pyffi", pymethods)]
@@ -551,8 +1161,8 @@ impl IlluminatedObserver {
     /// Create a new illuminated observer.
     pub fn new<Illuminant, Observer>(illuminant: &Illuminant, observer: &Observer) -> Self
     where
-        Illuminant: SpectralDistribution<Value = Float>,
-        Observer: SpectralDistribution<Value = [Float; 3]>,
+        Illuminant: SpectralDistribution<Value = Float> + ?Sized,
+        Observer: SpectralDistribution<Value = [Float; 3]> + ?Sized,
     {
         let start = illuminant.start().max(observer.start());
         let end = illuminant.end().min(observer.end());
@@ -589,6 +1199,123 @@ impl IlluminatedObserver {
             data: Arc::new(data),
         }
     }
+
+    /// Determine the color of an object under this illuminated observer.
+    ///
+    /// This method integrates the product of the object's reflectance, with
+    /// values between 0 and 1, and this illuminated observer's values. For
+    /// wavelengths outside the reflectance's range, it uses the value at the
+    /// nearest end, as recommended by ASTM E308. The result is scaled so that
+    /// a perfect reflector has a luminance Y of 1 and further adjusted
+    /// according to the normalization.
+    ///
+    /// Comparing the same object under different illuminants is
+    /// straight-forward:
+    ///
+    /// ```
+    /// # use prettypretty::spectrum::{
+    /// #     FixedDistribution, IlluminatedObserver, Normalization, CIE_ILLUMINANT_A,
+    /// #     CIE_ILLUMINANT_D50, CIE_OBSERVER_2DEG_1931,
+    /// # };
+    /// let chip = FixedDistribution::new("Gray", 380, 401, 200.5, 0.5);
+    /// let d50 = IlluminatedObserver::new(&CIE_ILLUMINANT_D50, &CIE_OBSERVER_2DEG_1931);
+    /// let a = IlluminatedObserver::new(&CIE_ILLUMINANT_A, &CIE_OBSERVER_2DEG_1931);
+    ///
+    /// let under_d50 = d50.reflectance_color(&chip, Normalization::Relative);
+    /// let under_a = a.reflectance_color(&chip, Normalization::Relative);
+    /// assert!(under_d50.xy_chromaticity().0 < under_a.xy_chromaticity().0);
+    ///
+    /// let adapted = a.reflectance_color(&chip, Normalization::AdaptToD65);
+    /// let (x, y) = adapted.xy_chromaticity();
+    /// assert!((x - 0.3127).abs() < 1e-4 && (y - 0.3290).abs() < 1e-4);
+    /// ```
+    pub fn reflectance_color<R>(&self, reflectance: &R, normalization: Normalization) -> Color
+    where
+        R: SpectralDistribution<Value = Float> + ?Sized,
+    {
+        let first = reflectance.at(reflectance.start()).unwrap_or(0.0);
+        let last = reflectance
+            .at(reflectance.end().saturating_sub(1))
+            .unwrap_or(0.0);
+
+        let mut sum = ThreeSum::new();
+        for (index, [x, y, z]) in self.data.iter().enumerate() {
+            let wavelength = self.start + index;
+            let r = reflectance
+                .at(wavelength)
+                .unwrap_or(if wavelength < reflectance.start() {
+                    first
+                } else {
+                    last
+                });
+            sum += [r * x, r * y, r * z];
+        }
+
+        let luminosity = self.luminosity();
+        let [x, y, z] = sum.value();
+        let xyz = [x / luminosity, y / luminosity, z / luminosity];
+
+        let coordinates = match normalization {
+            Normalization::Relative => xyz,
+            Normalization::AdaptToD65 => {
+                let white = *self.white_point().as_ref();
+                bradford(&white, &D65_WHITE, &xyz)
+            }
+        };
+
+        Color::new(ColorSpace::Xyz, coordinates)
+    }
+}
+
+/// The normalization for the colors of illuminated objects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Normalization {
+    /// Scale by the illuminant only, so that a perfect reflector's color is the
+    /// illuminant's white point with luminance Y of 1.
+    #[default]
+    Relative,
+    /// Also chromatically adapt from the illuminant's white point to D65 with
+    /// the Bradford method, so that a perfect reflector's color is D65 white.
+    /// This makes the result suitable for conversion to other color spaces.
+    AdaptToD65,
+}
+
+/// The D65 white point in XYZ.
+const D65_WHITE: [Float; 3] = [0.3127 / 0.3290, 1.0, (1.0 - 0.3127 - 0.3290) / 0.3290];
+
+/// Chromatically adapt XYZ coordinates from one white point to another with
+/// the Bradford method.
+fn bradford(source: &[Float; 3], target: &[Float; 3], xyz: &[Float; 3]) -> [Float; 3] {
+    #[rustfmt::skip]
+    const XYZ_TO_LMS: [[Float; 3]; 3] = [
+        [  0.8951,  0.2664, -0.1614 ],
+        [ -0.7502,  1.7135,  0.0367 ],
+        [  0.0389, -0.0685,  1.0296 ],
+    ];
+
+    #[rustfmt::skip]
+    const LMS_TO_XYZ: [[Float; 3]; 3] = [
+        [  0.9869929, -0.1470543, 0.1599627 ],
+        [  0.4323053,  0.5183603, 0.0492912 ],
+        [ -0.0085287,  0.0400428, 0.9684867 ],
+    ];
+
+    fn multiply(matrix: &[[Float; 3]; 3], vector: &[Float; 3]) -> [Float; 3] {
+        let [a, b, c] = *vector;
+        matrix.map(|[m0, m1, m2]| m0 * a + m1 * b + m2 * c)
+    }
+
+    let source = multiply(&XYZ_TO_LMS, source);
+    let target = multiply(&XYZ_TO_LMS, target);
+    let [l, m, s] = multiply(&XYZ_TO_LMS, xyz);
+    multiply(
+        &LMS_TO_XYZ,
+        &[
+            l * target[0] / source[0],
+            m * target[1] / source[1],
+            s * target[2] / source[2],
+        ],
+    )
 }
 
 #[cfg_attr(feature = "pyffi", pymethods)]
@@ -741,6 +1468,106 @@ impl SpectralDistribution for IlluminatedObserver {
 
 // --------------------------------------------------------------------------------------------------------------------
 
+/// Convert XYZ coordinates to CIELAB relative to the white point.
+fn xyz_to_lab(xyz: &[Float; 3], white: &[Float; 3]) -> [Float; 3] {
+    const DELTA: Float = 6.0 / 29.0;
+
+    let f = |t: Float| {
+        if DELTA * DELTA * DELTA < t {
+            t.cbrt()
+        } else {
+            t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+        }
+    };
+
+    let [fx, fy, fz] = [
+        f(xyz[0] / white[0]),
+        f(xyz[1] / white[1]),
+        f(xyz[2] / white[2]),
+    ];
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Compute the special metamerism index for two samples.
+///
+/// Two object colors are *metameric* if they match under one illuminant but
+/// have different reflectances, which typically causes them to differ under
+/// other illuminants. Following CIE 15:2018, this function quantifies that
+/// difference with the special metamerism index for a change in illuminant.
+/// It computes the CIELAB color difference ΔE*ab between the two samples under
+/// each test illuminant, returning one index per test illuminant.
+///
+/// If the samples do not match exactly under the reference illuminant, this
+/// function applies the multiplicative correction, scaling the second sample's
+/// tristimulus values under a test illuminant by the ratios of the two
+/// samples' tristimulus values under the reference illuminant.
+///
+/// ```
+/// # use prettypretty::spectrum::{
+/// #     metamerism_index, SampledDistribution, SpectralDistribution, CIE_ILLUMINANT_A,
+/// #     CIE_ILLUMINANT_D65, CIE_OBSERVER_2DEG_1931,
+/// # };
+/// let gray = SampledDistribution::new("Gray", &[(380.0, 0.4), (780.0, 0.4)])?;
+/// let index = metamerism_index(
+///     &gray,
+///     &gray,
+///     &CIE_ILLUMINANT_D65,
+///     &[&CIE_ILLUMINANT_A as &dyn SpectralDistribution<Value = _>],
+///     &CIE_OBSERVER_2DEG_1931,
+/// );
+/// assert_eq!(index, vec![0.0]);
+/// # Ok::<(), prettypretty::error::SampleError>(())
+/// ```
+pub fn metamerism_index<S1, S2, R, O>(
+    sample1: &S1,
+    sample2: &S2,
+    reference: &R,
+    tests: &[&dyn SpectralDistribution<Value = Float>],
+    observer: &O,
+) -> Vec<Float>
+where
+    S1: SpectralDistribution<Value = Float> + ?Sized,
+    S2: SpectralDistribution<Value = Float> + ?Sized,
+    R: SpectralDistribution<Value = Float> + ?Sized,
+    O: SpectralDistribution<Value = [Float; 3]> + ?Sized,
+{
+    let table = IlluminatedObserver::new(reference, observer);
+    let reference1 = *table
+        .reflectance_color(sample1, Normalization::Relative)
+        .as_ref();
+    let reference2 = *table
+        .reflectance_color(sample2, Normalization::Relative)
+        .as_ref();
+    let correction = [0, 1, 2].map(|c| {
+        if reference2[c] == 0.0 {
+            1.0
+        } else {
+            reference1[c] / reference2[c]
+        }
+    });
+
+    tests
+        .iter()
+        .map(|test| {
+            let table = IlluminatedObserver::new(*test, observer);
+            let white = *table.white_point().as_ref();
+            let xyz1 = *table
+                .reflectance_color(sample1, Normalization::Relative)
+                .as_ref();
+            let xyz2 = *table
+                .reflectance_color(sample2, Normalization::Relative)
+                .as_ref();
+            let xyz2 = [0, 1, 2].map(|c| xyz2[c] * correction[c]);
+
+            let [l1, a1, b1] = xyz_to_lab(&xyz1, &white);
+            let [l2, a2, b2] = xyz_to_lab(&xyz2, &white);
+            ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+        })
+        .collect()
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
 pub mod std_observer {
     //! Free-standing functions related to the CIE standard observer.
 
@@ -981,10 +1808,130 @@ impl std::iter::FusedIterator for SpectrumTraversal {}
 
 // --------------------------------------------------------------------------------------------------------------------
 
+/// A MacAdam ellipse.
+///
+/// A MacAdam ellipse describes the region of chromaticities around its center
+/// that the average observer cannot distinguish from the center. It is
+/// specified in CIE 1931 xy chromaticity coordinates, with the semi-major and
+/// semi-minor axes in units of 10⁻³ and the angle between the x axis and the
+/// major axis in degrees. The axes correspond to one step, i.e., one *just
+/// noticeable difference*. Differences of several steps are modelled by scaling
+/// the ellipse accordingly. [`MACADAM_ELLIPSES`] has MacAdam's original 25
+/// ellipses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MacAdamEllipse {
+    x: Float,
+    y: Float,
+    a: Float,
+    b: Float,
+    angle: Float,
+}
+
+impl MacAdamEllipse {
+    /// Create a new MacAdam ellipse.
+    pub const fn new(x: Float, y: Float, a: Float, b: Float, angle: Float) -> Self {
+        Self { x, y, a, b, angle }
+    }
+
+    /// Find the MacAdam ellipse whose center is closest to the xy
+    /// chromaticity.
+    pub fn nearest(chromaticity: (Float, Float)) -> &'static Self {
+        let distance =
+            |ellipse: &Self| (ellipse.x - chromaticity.0).hypot(ellipse.y - chromaticity.1);
+
+        MACADAM_ELLIPSES
+            .iter()
+            .min_by(|e1, e2| distance(e1).total_cmp(&distance(e2)))
+            .expect("there are MacAdam ellipses")
+    }
+
+    /// Get the center's xy chromaticity.
+    pub fn center(&self) -> (Float, Float) {
+        (self.x, self.y)
+    }
+
+    /// Get the semi-major axis in units of 10⁻³.
+    pub fn semi_major(&self) -> Float {
+        self.a
+    }
+
+    /// Get the semi-minor axis in units of 10⁻³.
+    pub fn semi_minor(&self) -> Float {
+        self.b
+    }
+
+    /// Get the angle of the major axis in degrees.
+    pub fn angle(&self) -> Float {
+        self.angle
+    }
+
+    /// Move this ellipse to the given center, keeping its shape.
+    pub fn centered_at(&self, chromaticity: (Float, Float)) -> Self {
+        Self::new(chromaticity.0, chromaticity.1, self.a, self.b, self.angle)
+    }
+
+    /// Scale this ellipse by the given number of steps.
+    pub fn scaled(&self, steps: Float) -> Self {
+        Self::new(self.x, self.y, self.a * steps, self.b * steps, self.angle)
+    }
+
+    /// Determine the number of steps between this ellipse's center and the xy
+    /// chromaticity.
+    ///
+    /// The result is the factor by which this ellipse needs to be scaled so
+    /// that the chromaticity falls onto its outline.
+    pub fn steps_to(&self, chromaticity: (Float, Float)) -> Float {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let dx = chromaticity.0 - self.x;
+        let dy = chromaticity.1 - self.y;
+        let major = (dx * cos + dy * sin) / (self.a * 1e-3);
+        let minor = (-dx * sin + dy * cos) / (self.b * 1e-3);
+        major.hypot(minor)
+    }
+
+    /// Determine whether the xy chromaticity is within the n-step ellipse.
+    pub fn contains(&self, chromaticity: (Float, Float), steps: Float) -> bool {
+        self.steps_to(chromaticity) <= steps
+    }
+
+    /// Trace the outline of the n-step ellipse.
+    ///
+    /// This method returns the given number of xy chromaticities, evenly
+    /// spaced by angle, which are suitable for drawing this ellipse as a
+    /// closed polygon.
+    pub fn outline(&self, steps: Float, segments: usize) -> Vec<(Float, Float)> {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let (a, b) = (self.a * 1e-3 * steps, self.b * 1e-3 * steps);
+
+        (0..segments)
+            .map(|index| {
+                let t = std::f64::consts::TAU as Float * index as Float / segments as Float;
+                let (u, v) = (a * t.cos(), b * t.sin());
+                (self.x + u * cos - v * sin, self.y + u * sin + v * cos)
+            })
+            .collect()
+    }
+}
+
+/// Determine the number of MacAdam steps between two xy chromaticities.
+///
+/// This function uses the shape of the MacAdam ellipse closest to the first
+/// chromaticity, centers it on that chromaticity, and determines the number of
+/// steps to the second chromaticity. Differences up to one step are not
+/// noticeable, differences of up to a few steps are small, and larger
+/// differences are readily apparent.
+pub fn macadam_steps(chromaticity1: (Float, Float), chromaticity2: (Float, Float)) -> Float {
+    MacAdamEllipse::nearest(chromaticity1)
+        .centered_at(chromaticity1)
+        .steps_to(chromaticity2)
+}
+
+pub use crate::cie::CIE_ILLUMINANT_A;
 pub use crate::cie::CIE_ILLUMINANT_D50;
 pub use crate::cie::CIE_ILLUMINANT_D65;
 pub use crate::cie::CIE_OBSERVER_10DEG_1964;
 pub use crate::cie::CIE_OBSERVER_2DEG_1931;
+pub use crate::cie::MACADAM_ELLIPSES;
 
 /// The CIE standard illuminant E at 1nm resolution.
 pub static CIE_ILLUMINANT_E: FixedDistribution =
@@ -995,10 +1942,15 @@ pub static CIE_ILLUMINANT_E: FixedDistribution =
 #[cfg(test)]
 mod test {
     use super::{
-        GamutTraversalStep, IlluminatedObserver, SpectralDistribution, CIE_ILLUMINANT_D50,
-        CIE_ILLUMINANT_D65, CIE_OBSERVER_10DEG_1964, CIE_OBSERVER_2DEG_1931,
+        blackbody, blackbody_chromaticity, cct_duv_from_uv_prime, cct_duv_from_xy, macadam_steps,
+        metamerism_index, planckian_locus, GamutTraversalStep, IlluminatedObserver, Interpolation,
+        MacAdamEllipse, Normalization, Observer, SampledDistribution, SpectralDistribution,
+        CIE_ILLUMINANT_A, CIE_ILLUMINANT_D50, CIE_ILLUMINANT_D65, CIE_ILLUMINANT_E,
+        CIE_OBSERVER_10DEG_1964, CIE_OBSERVER_2DEG_1931, ONE_NANOMETER, SECOND_RADIATION_CONSTANT,
     };
     use crate::core::Sum;
+    use crate::error::SampleError;
+    use crate::Float;
 
     #[test]
     fn test_checksum() {
@@ -1031,6 +1983,259 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_blackbody() {
+        // Illuminant A uses an older value for the second radiation constant.
+        let temperature = 2848.0 * SECOND_RADIATION_CONSTANT / 1.435e-2;
+        let radiator = blackbody(temperature);
+        assert_eq!(radiator.range(), CIE_ILLUMINANT_A.range());
+        assert!((radiator.at(560).unwrap() - 100.0).abs() < 1e-10);
+
+        for wavelength in radiator.range() {
+            let expected = CIE_ILLUMINANT_A.at(wavelength).unwrap();
+            let actual = radiator.at(wavelength).unwrap();
+            assert!((actual - expected).abs() / expected < 1e-5);
+        }
+
+        let (x, y) = blackbody_chromaticity(temperature, &CIE_OBSERVER_2DEG_1931);
+        assert!((x - 0.44757).abs() < 2e-5);
+        assert!((y - 0.40745).abs() < 2e-5);
+
+        let locus: Vec<_> =
+            planckian_locus(&CIE_OBSERVER_2DEG_1931, [2000.0, temperature, 10_000.0]).collect();
+        assert_eq!(locus.len(), 3);
+        assert!(matches!(locus[0], GamutTraversalStep::MoveTo(_)));
+        let GamutTraversalStep::LineTo(ref color) = locus[1] else {
+            panic!("second step should draw line");
+        };
+        assert_eq!(color.xy_chromaticity(), (x, y));
+    }
+
+    #[test]
+    fn test_cct_duv() {
+        for temperature in [1_500.0, 2_700.0, 5_000.0, 9_300.0, 25_000.0] {
+            let xy = blackbody_chromaticity(temperature, &CIE_OBSERVER_2DEG_1931);
+            let (cct, duv) = cct_duv_from_xy(xy).unwrap();
+            assert!((cct - temperature).abs() / temperature < 1e-5);
+            assert!(duv.abs() < 1e-6);
+        }
+
+        // D65's CCT is 6504K and its Duv is 0.0032.
+        let (cct, duv) = cct_duv_from_xy((0.31271, 0.32902)).unwrap();
+        assert_eq!(cct.round(), 6504.0);
+        assert_eq!((duv * 10_000.0).round(), 32.0);
+
+        let (u, v) = (0.19783, 0.46833);
+        let (cct_prime, duv_prime) = cct_duv_from_uv_prime((u, v)).unwrap();
+        assert!((cct_prime - cct).abs() < 1.0);
+        assert!((duv_prime - duv).abs() < 1e-5);
+
+        assert_eq!(cct_duv_from_xy((0.7, 0.29)), None);
+        assert_eq!(cct_duv_from_xy((0.24, 0.23)), None);
+        assert_eq!(cct_duv_from_xy((Float::NAN, 0.3)), None);
+    }
+
+    #[test]
+    fn test_sampled_distribution() -> Result<(), SampleError> {
+        let linear =
+            SampledDistribution::new("Linear", &[(399.5, 0.0), (402.0, 5.0), (404.5, 0.0)])?;
+        assert_eq!(linear.range(), 400..405);
+        assert_eq!(linear.at(400), Some(1.0));
+        assert_eq!(linear.at(402), Some(5.0));
+        assert_eq!(linear.at(404), Some(1.0));
+        assert_eq!(linear.checksum(), 13.0);
+
+        // A spline through samples of a smooth curve closely tracks that curve.
+        let curve = |wavelength: Float| 50.0 + 40.0 * (wavelength / 40.0).sin();
+        let samples: Vec<_> = (0..=30)
+            .map(|index| {
+                let wavelength = 380.0 + 10.0 * index as Float;
+                (wavelength, curve(wavelength))
+            })
+            .collect();
+        let spline =
+            SampledDistribution::with_interpolation("Spline", &samples, Interpolation::Spline)?;
+        assert_eq!(spline.range(), 380..681);
+        for wavelength in 400..660 {
+            let expected = curve(wavelength as Float);
+            assert!((spline.at(wavelength).unwrap() - expected).abs() < 0.01);
+        }
+
+        // The sampled distribution works as illuminant.
+        let flat = SampledDistribution::from_uniform("Flat", 360.0, 5.0, &[100.0; 95])?;
+        let table = IlluminatedObserver::new(&flat, &CIE_OBSERVER_2DEG_1931);
+        assert_eq!(table.range(), 360..831);
+
+        assert_eq!(
+            SampledDistribution::new("", &[(400.0, 1.0)]).unwrap_err(),
+            SampleError::TooFewSamples
+        );
+        assert_eq!(
+            SampledDistribution::new("", &[(400.0, 1.0), (410.0, Float::NAN)]).unwrap_err(),
+            SampleError::InvalidValue
+        );
+        assert_eq!(
+            SampledDistribution::new("", &[(400.0, 1.0), (400.0, 2.0)]).unwrap_err(),
+            SampleError::UnorderedWavelengths
+        );
+        assert_eq!(
+            SampledDistribution::new("", &[(400.2, 1.0), (400.8, 2.0)]).unwrap_err(),
+            SampleError::EmptyRange
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_synthetic_distributions() {
+        let gaussian = SampledDistribution::gaussian(550.0, 20.0);
+        assert_eq!(gaussian.range(), 300..831);
+        assert_eq!(gaussian.at(550), Some(1.0));
+        assert_eq!(gaussian.at(530), gaussian.at(570));
+        let area = 20.0 * (2.0 * std::f64::consts::PI as Float).sqrt();
+        assert!((gaussian.checksum() - area).abs() < 1e-6);
+
+        let band = SampledDistribution::band_pass(500, 509);
+        assert_eq!(band.checksum(), 10.0);
+        assert_eq!(band.at(499), Some(0.0));
+        assert_eq!(band.at(500), Some(1.0));
+        assert_eq!(band.at(509), Some(1.0));
+        assert_eq!(band.at(510), Some(0.0));
+
+        // A monochromatic light's color lies on the spectral locus.
+        let table = IlluminatedObserver::new(&CIE_ILLUMINANT_E, &CIE_OBSERVER_2DEG_1931);
+        let line = SampledDistribution::monochromatic(520);
+        let color = table.reflectance_color(&line, Normalization::Relative);
+        let pulse = table
+            .visual_gamut(ONE_NANOMETER)
+            .pulse_color(520 - table.start(), 1);
+        let (x1, y1) = color.xy_chromaticity();
+        let (x2, y2) = pulse.xy_chromaticity();
+        assert!((x1 - x2).abs() < 1e-12 && (y1 - y2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reflectance_color() -> Result<(), SampleError> {
+        let table = IlluminatedObserver::new(&CIE_ILLUMINANT_D50, &CIE_OBSERVER_2DEG_1931);
+
+        // A perfect reflector has the illuminant's color, even when the
+        // reflectance covers part of the spectrum only.
+        let perfect = SampledDistribution::new("Perfect", &[(400.0, 1.0), (700.0, 1.0)])?;
+        let color = table.reflectance_color(&perfect, Normalization::Relative);
+        let [x, y, z] = *color.as_ref();
+        let [wx, wy, wz] = *table.white_point().as_ref();
+        assert!((x - wx).abs() < 1e-12 && (y - wy).abs() < 1e-12 && (z - wz).abs() < 1e-12);
+
+        let adapted = table.reflectance_color(&perfect, Normalization::AdaptToD65);
+        let [x, y, z] = *adapted.as_ref();
+        assert!((x - 0.95046).abs() < 1e-4 && (y - 1.0).abs() < 1e-4 && (z - 1.08906).abs() < 1e-4);
+
+        // Half the reflectance means half the luminance.
+        let gray = SampledDistribution::new("Gray", &[(400.0, 0.5), (700.0, 0.5)])?;
+        let color = table.reflectance_color(&gray, Normalization::Relative);
+        assert!((color.as_ref()[1] - 0.5).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_macadam_ellipses() {
+        let ellipse = MacAdamEllipse::nearest((0.31, 0.32));
+        assert_eq!(ellipse.center(), (0.305, 0.323));
+        assert_eq!(ellipse.semi_major(), 2.3);
+
+        // Points on the outline are exactly the number of steps away.
+        for (x, y) in ellipse.outline(3.0, 12) {
+            assert!((ellipse.steps_to((x, y)) - 3.0).abs() < 1e-9);
+        }
+
+        let (sin, cos) = ellipse.angle().to_radians().sin_cos();
+        let along_major = (0.305 + 0.0023 * cos, 0.323 + 0.0023 * sin);
+        assert!(ellipse.contains(along_major, 1.0 + 1e-9));
+        assert!(!ellipse.contains(along_major, 0.99));
+
+        let along_minor = (0.305 - 0.0009 * sin, 0.323 + 0.0009 * cos);
+        assert!((ellipse.steps_to(along_minor) - 1.0).abs() < 1e-9);
+        assert!((ellipse.scaled(2.0).steps_to(along_minor) - 0.5).abs() < 1e-9);
+
+        let steps = macadam_steps((0.31, 0.32), (0.31, 0.325));
+        let expected = ellipse.centered_at((0.31, 0.32)).steps_to((0.31, 0.325));
+        assert_eq!(steps, expected);
+        assert!(1.0 < steps);
+    }
+
+    #[test]
+    fn test_metamerism_index() -> Result<(), SampleError> {
+        let flat = SampledDistribution::new("Flat", &[(360.0, 0.5), (830.0, 0.5)])?;
+        let narrow = SampledDistribution::gaussian(450.0, 30.0);
+        let wide = SampledDistribution::gaussian(600.0, 40.0);
+        let tests = [
+            &CIE_ILLUMINANT_D65 as &dyn SpectralDistribution<Value = Float>,
+            &CIE_ILLUMINANT_A,
+        ];
+
+        let same = metamerism_index(
+            &flat,
+            &flat,
+            &CIE_ILLUMINANT_D65,
+            &tests,
+            &CIE_OBSERVER_2DEG_1931,
+        );
+        assert_eq!(same, vec![0.0, 0.0]);
+
+        // A two-band reflectance differs from the flat one. But thanks to the
+        // multiplicative correction, the two match under the reference
+        // illuminant. They differ noticeably under illuminant A.
+        let mix = SampledDistribution::from_uniform(
+            "Mix",
+            300.0,
+            1.0,
+            &(300..=830)
+                .map(|w| 0.5 * narrow.at(w).unwrap() + 0.5 * wide.at(w).unwrap())
+                .collect::<Vec<_>>(),
+        )?;
+        let index = metamerism_index(
+            &flat,
+            &mix,
+            &CIE_ILLUMINANT_D65,
+            &tests,
+            &CIE_OBSERVER_2DEG_1931,
+        );
+        assert!(index[0] < 1e-9);
+        assert!(1.0 < index[1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_observer() -> Result<(), SampleError> {
+        let builtin = &CIE_OBSERVER_2DEG_1931;
+        let table: Vec<_> = builtin.range().map(|w| builtin.at(w).unwrap()).collect();
+        let custom = Observer::from_table("Custom", builtin.start(), table);
+        assert_eq!(custom.range(), builtin.range());
+        assert_eq!(custom.checksum(), builtin.checksum());
+
+        let white1 = IlluminatedObserver::new(&CIE_ILLUMINANT_D65, builtin).white_point();
+        let white2 = IlluminatedObserver::new(&CIE_ILLUMINANT_D65, &custom).white_point();
+        assert_eq!(white1, white2);
+
+        // Resampling 5nm samples at 1nm recovers the samples.
+        let samples: Vec<_> = (0..=80)
+            .map(|index| {
+                let wavelength = 380 + 5 * index;
+                (wavelength as Float, builtin.at(wavelength).unwrap())
+            })
+            .collect();
+        let resampled = Observer::from_samples("Resampled", &samples, Interpolation::Spline)?;
+        assert_eq!(resampled.range(), 380..781);
+        assert_eq!(resampled.at(555), builtin.at(555));
+
+        // Cone fundamentals are converted with the matrix.
+        let swap = [[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 2.0]];
+        let cones =
+            Observer::from_cone_fundamentals("Cones", &samples, &swap, Interpolation::Linear)?;
+        let [x, y, z] = builtin.at(600).unwrap();
+        assert_eq!(cones.at(600), Some([y, x, 2.0 * z]));
+        Ok(())
+    }
+
     #[test]
     fn test_spectrum_traversal() {
         for (stride, line_count, line_length) in [(9, 53, 53), (10, 47, 48)] {
@@ -1055,7 +2260,7 @@ mod test {
                 }
             }
 
-            assert!(matches!(traversal.next(), None));
+            assert!(traversal.next().is_none());
         }
     }
 }
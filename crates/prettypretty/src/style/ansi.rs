@@ -0,0 +1,877 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io::Write;
+
+use super::{Attribute, Format, FormatUpdate, Style};
+use crate::termco::{AnsiColor, Colorant, Rgb};
+
+/// A fragment of text with embedded ANSI escape sequences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Fragment<'a> {
+    /// Text without escape sequences.
+    Text(&'a str),
+    /// A well-formed SGR escape sequence, including `ESC [` and `m`.
+    Sgr(&'a str),
+    /// Any other escape sequence, including malformed and truncated ones.
+    Control(&'a str),
+}
+
+/// Determine whether the escape sequence is a well-formed SGR sequence.
+fn is_sgr(sequence: &str) -> bool {
+    sequence.len() >= 3
+        && sequence.starts_with("\x1b[")
+        && sequence.ends_with('m')
+        && sequence.as_bytes()[2..sequence.len() - 1]
+            .iter()
+            .all(|byte| (b'0'..=b';').contains(byte))
+}
+
+/// Determine the byte to scan for the character starting with the given byte.
+///
+/// Since escape sequences are restricted to ASCII, this function maps the first
+/// byte of non-ASCII characters to DEL. Inside escape sequences, DEL is ignored.
+/// Outside escape sequences, it is text.
+fn unit(byte: u8) -> u8 {
+    if byte < 0x80 {
+        byte
+    } else {
+        0x7f
+    }
+}
+
+/// The role of a character in text with escape sequences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    /// The character is text, even if it is a C0 control in the middle of an
+    /// escape sequence.
+    Text,
+    /// The character is an `ESC` starting an escape sequence. It also ends the
+    /// current escape sequence, if any.
+    Start,
+    /// The character continues an escape sequence.
+    Continue,
+    /// The character completes an escape sequence.
+    End,
+}
+
+/// The result of scanning a character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Step {
+    /// The flag for the preceding `ESC` ending a control string and starting
+    /// another escape sequence instead.
+    restart: bool,
+    /// The character's role.
+    role: Role,
+}
+
+#[cfg(feature = "tty")]
+type Scan = TokenScanner;
+
+#[cfg(not(feature = "tty"))]
+type Scan = ScanState;
+
+/// A reader that produces at most one byte.
+#[cfg(feature = "tty")]
+#[derive(Debug)]
+struct NextByte(std::sync::Arc<std::sync::atomic::AtomicU8>);
+
+#[cfg(feature = "tty")]
+impl NextByte {
+    /// The value for no byte, which is not an ASCII byte.
+    const NONE: u8 = 0xff;
+}
+
+#[cfg(feature = "tty")]
+impl std::io::Read for NextByte {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use std::sync::atomic::Ordering;
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let byte = self.0.swap(Self::NONE, Ordering::Relaxed);
+        if byte == Self::NONE {
+            Ok(0)
+        } else {
+            buf[0] = byte;
+            Ok(1)
+        }
+    }
+}
+
+/// A scanner for escape sequences driven by prettytty's [`Scanner`].
+///
+/// This struct feeds the scanner one character at a time and determines each
+/// character's role from the resulting tokens and errors. Both [`Fragments`]
+/// and [`StripWriter`] use this scanner when the `tty` feature is enabled.
+///
+/// [`Scanner`]: prettytty::Scanner
+#[cfg(feature = "tty")]
+#[derive(Debug)]
+struct TokenScanner {
+    next: std::sync::Arc<std::sync::atomic::AtomicU8>,
+    scanner: prettytty::Scanner<NextByte>,
+}
+
+#[cfg(feature = "tty")]
+impl TokenScanner {
+    fn new() -> Self {
+        use prettytty::opt::Options;
+
+        // Keep escape sequences from ever becoming pathological, since
+        // ScanState has no such limit.
+        let options = Options::builder()
+            .read_buffer_size(64)
+            .pathological_size(usize::MAX)
+            .build();
+        let next = std::sync::Arc::new(std::sync::atomic::AtomicU8::new(NextByte::NONE));
+        let scanner = prettytty::Scanner::with_options(&options, NextByte(next.clone()));
+        Self { next, scanner }
+    }
+
+    fn in_flight(&self) -> bool {
+        self.scanner.in_flight()
+    }
+
+    /// Scan the character starting with the given byte.
+    fn step(&mut self, byte: u8) -> Step {
+        use prettytty::err::ErrorKind;
+        use prettytty::Token;
+        use std::sync::atomic::Ordering;
+
+        self.next.store(unit(byte), Ordering::Relaxed);
+        let mut restart = false;
+        loop {
+            let was_in_flight = self.scanner.in_flight();
+            let result = self.scanner.tokens().next().map(|result| {
+                result.map(|token| matches!(token, Token::Text(_) | Token::Control(_)))
+            });
+
+            let role = match result {
+                Some(Ok(true)) => Role::Text,
+                Some(Ok(false)) => Role::End,
+                Some(Err(error)) if error.kind() == ErrorKind::MalformedSequence => {
+                    if self.scanner.in_flight() {
+                        // The ESC of a control string's terminator starts
+                        // another escape sequence, which continues with the
+                        // character.
+                        restart = true;
+                        continue;
+                    } else if matches!(self.scanner.fill_buf(), Ok(bytes) if !bytes.is_empty()) {
+                        // The character aborted the escape sequence but is
+                        // not part of it.
+                        continue;
+                    }
+                    Role::End
+                }
+                Some(Err(_)) => Role::End,
+                None if self.scanner.in_paste() => {
+                    // Treat the start of a bracketed paste like any other
+                    // escape sequence.
+                    *self = Self::new();
+                    Role::End
+                }
+                None => match (was_in_flight, self.scanner.in_flight()) {
+                    (false, true) => Role::Start,
+                    (true, true) => Role::Continue,
+                    (true, false) => Role::End,
+                    (false, false) => Role::Text,
+                },
+            };
+
+            return Step { restart, role };
+        }
+    }
+}
+
+/// The state of scanning text for escape sequences.
+///
+/// This state machine is a port of the state machine for DEC's
+/// ANSI-compatible terminals used by prettytty's scanner. Since it only
+/// processes ASCII characters and DEL as a stand-in for non-ASCII characters,
+/// it omits all transitions for C1 controls. [`Fragments`] and
+/// [`StripWriter`] use this state machine as a fallback when the `tty`
+/// feature is disabled.
+#[cfg(any(test, not(feature = "tty")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ScanState {
+    Ground,
+    Escape,
+    EscapeIntermediate,
+    SingleShift,
+    String,
+    StringEnd,
+    CsiEntry,
+    CsiParam,
+    CsiIntermediate,
+    CsiIgnore,
+    DcsEntry,
+    DcsParam,
+    DcsIntermediate,
+    DcsPassthrough,
+    DcsPassthroughEnd,
+    DcsIgnore,
+    DcsIgnoreEnd,
+}
+
+#[cfg(any(test, not(feature = "tty")))]
+impl ScanState {
+    fn new() -> Self {
+        Self::Ground
+    }
+
+    fn in_flight(&self) -> bool {
+        *self != Self::Ground
+    }
+
+    /// Scan the character starting with the given byte.
+    fn step(&mut self, byte: u8) -> Step {
+        let byte = unit(byte);
+        let restart = matches!(self, Self::StringEnd | Self::DcsPassthroughEnd) && byte != b'\\';
+        let state = if restart { Self::Escape } else { *self };
+
+        let role;
+        (*self, role) = state.transition(byte);
+        Step { restart, role }
+    }
+
+    /// Handle the byte in the same way for all states.
+    fn otherwise(self, byte: u8) -> (Self, Role) {
+        use self::ScanState::*;
+
+        match byte {
+            0x18 | 0x1a => (Ground, Role::Text),
+            0x1b => (Escape, Role::Start),
+            0x00..=0x1f => (self, Role::Text),
+            _ => (self, Role::Continue),
+        }
+    }
+
+    /// Transition to the next state. This method returns the next state and
+    /// the role of the byte.
+    fn transition(self, byte: u8) -> (Self, Role) {
+        use self::ScanState::*;
+
+        let is_ignored_control = matches!(byte, 0x00..=0x06 | 0x08..=0x17 | 0x19 | 0x1c..=0x1f);
+        match (self, byte) {
+            (Ground, 0x1b) => (Escape, Role::Start),
+            (Ground, _) => (Ground, Role::Text),
+
+            (Escape, b'N' | b'O') => (SingleShift, Role::Continue),
+            (Escape, b'P') => (DcsEntry, Role::Continue),
+            (Escape, b'X' | b']' | b'^' | b'_') => (String, Role::Continue),
+            (Escape, b'[') => (CsiEntry, Role::Continue),
+            (Escape | EscapeIntermediate, 0x20..=0x2f) => (EscapeIntermediate, Role::Continue),
+            (Escape | EscapeIntermediate, 0x30..=0x7e) => (Ground, Role::End),
+            (SingleShift, 0x20..=0x7e) => (Ground, Role::End),
+
+            (String, 0x07) => (Ground, Role::End),
+            (String, 0x1b) => (StringEnd, Role::Continue),
+            (String, 0x20..=0x7f) => (String, Role::Continue),
+            (String, _) if is_ignored_control => (String, Role::Continue),
+            (StringEnd | DcsPassthroughEnd, _) => (Ground, Role::End),
+
+            (CsiEntry, 0x30..=0x3f) | (CsiParam, 0x30..=0x3b) => (CsiParam, Role::Continue),
+            (CsiEntry | CsiParam | CsiIntermediate, 0x20..=0x2f) => {
+                (CsiIntermediate, Role::Continue)
+            }
+            (CsiParam | CsiIntermediate | CsiIgnore, 0x20..=0x3f) => (CsiIgnore, Role::Continue),
+            (CsiEntry | CsiParam | CsiIntermediate | CsiIgnore, 0x40..=0x7e) => (Ground, Role::End),
+
+            (DcsEntry | DcsParam | DcsIntermediate, _) if is_ignored_control => {
+                (self, Role::Continue)
+            }
+            (DcsEntry | DcsParam | DcsIntermediate, 0x20..=0x2f) => {
+                (DcsIntermediate, Role::Continue)
+            }
+            (DcsEntry, 0x30..=0x39 | 0x3b..=0x3f) | (DcsParam, 0x30..=0x39 | 0x3b) => {
+                (DcsParam, Role::Continue)
+            }
+            (DcsEntry | DcsParam | DcsIntermediate, 0x30..=0x3f) => (DcsIgnore, Role::Continue),
+            (DcsEntry | DcsParam | DcsIntermediate, 0x40..=0x7e) => {
+                (DcsPassthrough, Role::Continue)
+            }
+            (DcsPassthrough | DcsIgnore, 0x07) => (Ground, Role::End),
+            (DcsPassthrough, 0x1b) => (DcsPassthroughEnd, Role::Continue),
+            (DcsIgnore, 0x1b) => (DcsIgnoreEnd, Role::Continue),
+            (DcsPassthrough | DcsIgnore, _) if is_ignored_control => (self, Role::Continue),
+            (DcsPassthrough | DcsIgnore, 0x20..=0x7e) => (self, Role::Continue),
+            (DcsIgnoreEnd, b'\\' | 0x20..=0x7f) => (Ground, Role::End),
+            (DcsIgnoreEnd, _) => Ground.otherwise(byte),
+
+            _ => self.otherwise(byte),
+        }
+    }
+}
+
+/// An iterator over text and escape sequences.
+///
+/// This iterator operates on string slices in memory instead of terminal
+/// input. It recognizes escape sequences in the same way as [`StripWriter`],
+/// i.e., by scanning them with prettytty's scanner or, without the `tty`
+/// feature, an equivalent state machine.
+///
+/// Non-ASCII characters inside escape sequences are ignored and hence part of
+/// the sequence. C0 controls other than CAN, SUB, and ESC are text, even
+/// inside escape sequences. They split such a sequence into two control
+/// fragments. Malformed escape sequences end with the character that made
+/// them malformed, unless that character is CAN or SUB, which are text, or
+/// ESC, which starts another escape sequence. Truncated escape sequences
+/// extend to the end of the string.
+#[derive(Clone, Debug)]
+pub(crate) struct Fragments<'a> {
+    text: &'a str,
+    queue: VecDeque<Fragment<'a>>,
+}
+
+impl<'a> Fragments<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Queue a fragment for the non-empty range of text.
+    fn queue(&mut self, text: &'a str, is_text: bool) {
+        if text.is_empty() {
+            return;
+        }
+
+        self.queue.push_back(if is_text {
+            Fragment::Text(text)
+        } else if is_sgr(text) {
+            Fragment::Sgr(text)
+        } else {
+            Fragment::Control(text)
+        });
+    }
+
+    /// Scan the escape sequence at the start of the text, including any
+    /// escape sequences and text until the scanner is back in ground state.
+    fn scan(&mut self) {
+        let text = self.text;
+        let mut scan = Scan::new();
+        let mut start = 0;
+        let mut is_text = false;
+
+        for (index, c) in text.char_indices() {
+            let Step { restart, role } = scan.step(text.as_bytes()[index]);
+            if restart {
+                // The control string ends before its terminator's ESC.
+                self.queue(&text[start..index - 1], false);
+                start = index - 1;
+            }
+
+            let end = index + c.len_utf8();
+            match role {
+                Role::Text => {
+                    if !is_text {
+                        self.queue(&text[start..index], false);
+                        start = index;
+                        is_text = true;
+                    }
+                }
+                Role::Start | Role::Continue | Role::End => {
+                    if is_text || role == Role::Start {
+                        self.queue(&text[start..index], is_text);
+                        start = index;
+                        is_text = false;
+                    }
+                    if role == Role::End {
+                        self.queue(&text[start..end], false);
+                        start = end;
+                    }
+                }
+            }
+
+            if !scan.in_flight() {
+                // Leave any text to the fast path.
+                self.text = &text[start..];
+                return;
+            }
+        }
+
+        self.queue(&text[start..], is_text);
+        self.text = "";
+    }
+}
+
+impl<'a> Iterator for Fragments<'a> {
+    type Item = Fragment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(fragment) = self.queue.pop_front() {
+            return Some(fragment);
+        }
+
+        let text = self.text;
+        if *text.as_bytes().first()? != 0x1b {
+            let end = text.find('\x1b').unwrap_or(text.len());
+            self.text = &text[end..];
+            return Some(Fragment::Text(&text[..end]));
+        }
+
+        self.scan();
+        self.queue.pop_front()
+    }
+}
+
+impl std::iter::FusedIterator for Fragments<'_> {}
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// The state of a terminal's text attributes and colors.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SgrState {
+    format: Format,
+    foreground: Option<Colorant>,
+    background: Option<Colorant>,
+}
+
+impl SgrState {
+    pub fn style(&self) -> Style {
+        Style::from_parts(
+            FormatUpdate::from(self.format),
+            self.foreground.clone(),
+            self.background.clone(),
+        )
+    }
+
+    fn enable(&mut self, attribute: Attribute) {
+        if matches!(attribute, Attribute::Bold | Attribute::Thin) {
+            self.disable(Attribute::Bold);
+            self.disable(Attribute::Thin);
+        }
+        self.format = self.format + attribute;
+    }
+
+    fn disable(&mut self, attribute: Attribute) {
+        self.format = (self.format - attribute).enable();
+    }
+
+    /// Parse an extended color from the parameters. The first parameter is the
+    /// one following 38 or 48, if any.
+    fn parse_color<'p>(params: &mut impl Iterator<Item = &'p str>) -> Option<Colorant> {
+        match params.next()?.parse::<u8>().ok()? {
+            5 => params.next()?.parse::<u8>().ok().map(Colorant::from),
+            2 => {
+                let r = params.next()?.parse::<u8>().ok()?;
+                let g = params.next()?.parse::<u8>().ok()?;
+                let b = params.next()?.parse::<u8>().ok()?;
+                Some(Colorant::Rgb(Rgb::new(r, g, b)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse an extended color from colon-separated subparameters. The first
+    /// subparameter is the one following 38 or 48, if any.
+    fn parse_subcolor<'p>(subparams: &mut impl Iterator<Item = &'p str>) -> Option<Colorant> {
+        let subparams = subparams.collect::<Vec<_>>();
+        match subparams.as_slice() {
+            ["5", index] => index.parse::<u8>().ok().map(Colorant::from),
+            ["2", r, g, b] | ["2", _, r, g, b] => Some(Colorant::Rgb(Rgb::new(
+                r.parse().ok()?,
+                g.parse().ok()?,
+                b.parse().ok()?,
+            ))),
+            _ => None,
+        }
+    }
+
+    /// Update this state with the SGR escape sequence, including `ESC [` and
+    /// `m`.
+    pub fn update(&mut self, sequence: &str) {
+        use self::Attribute::*;
+
+        let mut params = sequence[2..sequence.len() - 1].split(';');
+        while let Some(param) = params.next() {
+            let mut subparams = param.split(':');
+            let code = subparams.next().unwrap_or("");
+            let code = if code.is_empty() {
+                0
+            } else if let Ok(code) = code.parse::<u16>() {
+                code
+            } else {
+                continue;
+            };
+
+            let has_subparams = param.contains(':');
+            match code {
+                0 => *self = Self::default(),
+                1 => self.enable(Bold),
+                2 => self.enable(Thin),
+                3 => self.enable(Italic),
+                4 if subparams.next() == Some("0") => self.disable(Underlined),
+                4 | 21 => self.enable(Underlined),
+                5 | 6 => self.enable(Blinking),
+                7 => self.enable(Reversed),
+                8 => self.enable(Hidden),
+                9 => self.enable(Stricken),
+                22 => {
+                    self.disable(Bold);
+                    self.disable(Thin);
+                }
+                23 => self.disable(Italic),
+                24 => self.disable(Underlined),
+                25 => self.disable(Blinking),
+                27 => self.disable(Reversed),
+                28 => self.disable(Hidden),
+                29 => self.disable(Stricken),
+                30..=37 => self.foreground = ansi(code - 30),
+                38 | 48 | 58 => {
+                    let color = if has_subparams {
+                        Self::parse_subcolor(&mut subparams)
+                    } else {
+                        Self::parse_color(&mut params)
+                    };
+                    match code {
+                        38 => self.foreground = color.or(self.foreground.take()),
+                        48 => self.background = color.or(self.background.take()),
+                        _ => {}
+                    }
+                }
+                39 => self.foreground = None,
+                40..=47 => self.background = ansi(code - 40),
+                49 => self.background = None,
+                90..=97 => self.foreground = ansi(code - 90 + 8),
+                100..=107 => self.background = ansi(code - 100 + 8),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn ansi(index: u16) -> Option<Colorant> {
+    AnsiColor::try_from(index as u8).ok().map(Colorant::Ansi)
+}
+
+/// An iterator over styled text spans.
+///
+/// This iterator is created by [`parse_sgr`].
+#[derive(Clone, Debug)]
+pub struct SgrSpans<'a> {
+    fragments: Fragments<'a>,
+    state: SgrState,
+}
+
+impl<'a> Iterator for SgrSpans<'a> {
+    type Item = (Style, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for fragment in self.fragments.by_ref() {
+            match fragment {
+                Fragment::Text(text) => return Some((self.state.style(), text)),
+                Fragment::Sgr(sequence) => self.state.update(sequence),
+                Fragment::Control(_) => {}
+            }
+        }
+
+        None
+    }
+}
+
+impl std::iter::FusedIterator for SgrSpans<'_> {}
+
+/// Parse text with SGR escape sequences into styled spans.
+///
+/// The returned iterator yields each span of text together with the style
+/// active for that span. The style only includes text attributes and colors
+/// that differ from the terminal's default appearance. The iterator updates
+/// the active style for every SGR escape sequence, including colors in 8-bit
+/// and 24-bit formats with semicolon or colon separators. It ignores all other
+/// escape sequences, including malformed and truncated ones, and does not
+/// yield empty spans.
+///
+/// ```
+/// # use prettypretty::style::{parse_sgr, Style};
+/// # use prettypretty::termco::{AnsiColor, Rgb};
+/// let spans: Vec<_> = parse_sgr(
+///     "plain \x1b[1;31mbold red\x1b[22;48;2;0;0;255m on blue\x1b[m!"
+/// ).collect();
+///
+/// let red = Style::default().with_foreground(AnsiColor::Red);
+/// assert_eq!(spans, vec![
+///     (Style::default(), "plain "),
+///     (red.bold(), "bold red"),
+///     (red.with_background(Rgb::new(0, 0, 255)), " on blue"),
+///     (Style::default(), "!"),
+/// ]);
+/// ```
+pub fn parse_sgr(text: &str) -> SgrSpans<'_> {
+    SgrSpans {
+        fragments: Fragments::new(text),
+        state: SgrState::default(),
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// Strip all ANSI escape sequences from the text.
+///
+/// This function removes CSI, OSC, DCS, and other escape sequences, including
+/// malformed and truncated ones. It keeps C0 controls such as newlines, even
+/// if they appear in the middle of an escape sequence. If the text contains no
+/// escape sequences, it returns the text without copying it.
+///
+/// ```
+/// # use prettypretty::style::strip;
+/// assert_eq!(strip("\x1b[1mbold\x1b[m and \x1b]0;title\x07plain"), "bold and plain");
+/// assert_eq!(strip("truncated\x1b[3"), "truncated");
+/// ```
+pub fn strip(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    for fragment in Fragments::new(text) {
+        if let Fragment::Text(text) = fragment {
+            result.push_str(text);
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// A writer that strips ANSI escape sequences.
+///
+/// This writer removes the same escape sequences as [`strip`] from the bytes
+/// written to it before passing them on to the wrapped writer. Since it
+/// tracks escape sequences across writes, it correctly strips escape sequences
+/// that are split across several writes.
+///
+/// ```
+/// # use prettypretty::style::StripWriter;
+/// # use std::io::Write;
+/// let mut writer = StripWriter::new(Vec::new());
+/// writer.write_all(b"\x1b[1mbold\x1b")?;
+/// writer.write_all(b"[22m text")?;
+/// assert_eq!(writer.into_inner(), b"bold text");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct StripWriter<W: Write> {
+    inner: W,
+    scan: Scan,
+    keep: bool,
+}
+
+impl<W: Write> StripWriter<W> {
+    /// Create a new stripping writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            scan: Scan::new(),
+            keep: true,
+        }
+    }
+
+    /// Determine whether this writer is in the middle of an escape sequence.
+    pub fn in_flight(&self) -> bool {
+        self.scan.in_flight()
+    }
+
+    /// Get a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the wrapped writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Return the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for StripWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut start = None;
+        for (index, &byte) in buf.iter().enumerate() {
+            // Continuation bytes share the role of the character's first byte.
+            let keep = if (0x80..0xc0).contains(&byte) {
+                self.keep
+            } else if byte != 0x1b && !self.scan.in_flight() {
+                true
+            } else {
+                self.scan.step(byte).role == Role::Text
+            };
+            self.keep = keep;
+
+            match (keep, start) {
+                (true, None) => start = Some(index),
+                (false, Some(first)) => {
+                    self.inner.write_all(&buf[first..index])?;
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(first) = start {
+            self.inner.write_all(&buf[first..])?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{parse_sgr, strip, Fragment, Fragments, StripWriter};
+    use crate::style::Style;
+    use crate::termco::{AnsiColor, Colorant, Rgb};
+
+    #[test]
+    fn test_fragments() {
+        let fragments: Vec<_> =
+            Fragments::new("a\x1b[1mb\x1b]0;title\x07c\x1b[?25hd\x1b[3\x1b(Be\x1b[").collect();
+        assert_eq!(
+            fragments,
+            vec![
+                Fragment::Text("a"),
+                Fragment::Sgr("\x1b[1m"),
+                Fragment::Text("b"),
+                Fragment::Control("\x1b]0;title\x07"),
+                Fragment::Text("c"),
+                Fragment::Control("\x1b[?25h"),
+                Fragment::Text("d"),
+                Fragment::Control("\x1b[3"),
+                Fragment::Control("\x1b(B"),
+                Fragment::Text("e"),
+                Fragment::Control("\x1b["),
+            ]
+        );
+
+        let fragments: Vec<_> = Fragments::new("\x1b]0;t\x1b7\x1b[1 m\x1b[1\u{2736}").collect();
+        assert_eq!(
+            fragments,
+            vec![
+                Fragment::Control("\x1b]0;t"),
+                Fragment::Control("\x1b7"),
+                Fragment::Control("\x1b[1 m"),
+                Fragment::Control("\x1b[1\u{2736}"),
+            ]
+        );
+
+        let fragments: Vec<_> =
+            Fragments::new("\x1b[1\n2ma\x1b[3\x18b\x1bP:\x1bxc\x1b[38:5:1m\x1b[200~d").collect();
+        assert_eq!(
+            fragments,
+            vec![
+                Fragment::Control("\x1b[1"),
+                Fragment::Text("\n"),
+                Fragment::Control("2m"),
+                Fragment::Text("a"),
+                Fragment::Control("\x1b[3"),
+                Fragment::Text("\x18b"),
+                Fragment::Control("\x1bP:\x1bx"),
+                Fragment::Text("c"),
+                Fragment::Sgr("\x1b[38:5:1m"),
+                Fragment::Control("\x1b[200~"),
+                Fragment::Text("d"),
+            ]
+        );
+    }
+
+    #[cfg(feature = "tty")]
+    #[test]
+    fn test_scanners() {
+        use super::{ScanState, TokenScanner};
+
+        let inputs = [
+            "a\x1b[1;38:5:196mb\x1b]8;;https://example.com\x1b\\c\x1b[m",
+            "\x1b[31\x1b[32mmalformed\x1b[3\u{2736}\x1b",
+            "\x1b[1\n2m\x1b[3\x18\x1b]0;t\x1b7\x1b]0;t\x1b\x1a\x1bN\x07x",
+            "\x1bP1;2|x\ny\x1b\\\x1bP1:\x1bx\x1bP=\x1b\x1b[?25h\x1b[1<2m",
+            "\x1b[1 m\x1b[1 2m\x1b[\u{e9}m\x1b(B\x1b%\x18\x1b[I\x1b[200~p\x1b[201~",
+            "\x1b_apc\x7f\x1b\\\x1bX\x05\x1b\\\x1b^\x1b[\x1b]",
+        ];
+
+        for input in inputs {
+            let mut token_scanner = TokenScanner::new();
+            let mut scan_state = ScanState::new();
+            for (index, c) in input.char_indices() {
+                let byte = input.as_bytes()[index];
+                assert_eq!(
+                    token_scanner.step(byte),
+                    scan_state.step(byte),
+                    "{:?} at {} of {:?}",
+                    c,
+                    index,
+                    input
+                );
+                assert_eq!(token_scanner.in_flight(), scan_state.in_flight());
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_sgr() {
+        let spans: Vec<_> =
+            parse_sgr("\x1b[1;2;3ma\x1b[38:5:196;48:2::1:2:3;4:0mb\x1b[0;97;1;22mc").collect();
+        assert_eq!(
+            spans,
+            vec![
+                (Style::default().thin().italic(), "a"),
+                (
+                    Style::default()
+                        .thin()
+                        .italic()
+                        .with_foreground(Colorant::from(196))
+                        .with_background(Rgb::new(1, 2, 3)),
+                    "b"
+                ),
+                (
+                    Style::default().with_foreground(AnsiColor::BrightWhite),
+                    "c"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip() -> std::io::Result<()> {
+        use std::io::Write;
+
+        let inputs = [
+            "plain",
+            "\x1b[1;38;5;196mred\x1b[m",
+            "a\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\b",
+            "\x1bPdcs\x1b\\\x1b7\x1b(Bc\x1b\x1b[3m",
+            "\x1b[31\x1b[32mmalformed\x1b[3\u{2736}\x1b",
+            "\x1b[1\n2mline\x1b[3\x18\u{2736}\x1b]0;t\x1b7x\x1bP:\x1by\x1b[200",
+        ];
+        let expected = [
+            "plain",
+            "red",
+            "alinkb",
+            "c",
+            "malformed",
+            "\nline\x18\u{2736}x",
+        ];
+
+        for (input, expected) in inputs.into_iter().zip(expected) {
+            assert_eq!(strip(input), expected);
+
+            // Write input one byte at a time.
+            let mut writer = StripWriter::new(Vec::new());
+            for byte in input.as_bytes() {
+                writer.write_all(&[*byte])?;
+            }
+            assert_eq!(writer.into_inner(), expected.as_bytes());
+        }
+
+        Ok(())
+    }
+}
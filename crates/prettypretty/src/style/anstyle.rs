@@ -0,0 +1,191 @@
+//! Conversions between prettypretty's and anstyle's styles and colors.
+
+use ::anstyle::{Ansi256Color, Effects, RgbColor};
+
+use super::{Attribute, Style};
+use crate::error::ConversionError;
+use crate::termco::{Colorant, Rgb};
+
+/// The pairs of corresponding text attributes and anstyle effects.
+const ATTRIBUTE_EFFECTS: [(Attribute, Effects); 8] = [
+    (Attribute::Bold, Effects::BOLD),
+    (Attribute::Thin, Effects::DIMMED),
+    (Attribute::Italic, Effects::ITALIC),
+    (Attribute::Underlined, Effects::UNDERLINE),
+    (Attribute::Blinking, Effects::BLINK),
+    (Attribute::Reversed, Effects::INVERT),
+    (Attribute::Hidden, Effects::HIDDEN),
+    (Attribute::Stricken, Effects::STRIKETHROUGH),
+];
+
+/// The anstyle effects that prettypretty renders as plain underlines.
+const UNDERLINE_EFFECTS: Effects = Effects::new()
+    .insert(Effects::DOUBLE_UNDERLINE)
+    .insert(Effects::CURLY_UNDERLINE)
+    .insert(Effects::DOTTED_UNDERLINE)
+    .insert(Effects::DASHED_UNDERLINE);
+
+impl From<::anstyle::Color> for Colorant {
+    fn from(value: ::anstyle::Color) -> Self {
+        match value {
+            ::anstyle::Color::Ansi(color) => Colorant::from(Ansi256Color::from_ansi(color).0),
+            ::anstyle::Color::Ansi256(color) => Colorant::from(color.0),
+            ::anstyle::Color::Rgb(RgbColor(r, g, b)) => Colorant::Rgb(Rgb::new(r, g, b)),
+        }
+    }
+}
+
+impl TryFrom<&Colorant> for ::anstyle::Color {
+    type Error = ConversionError;
+
+    /// Convert the colorant to an anstyle color.
+    ///
+    /// This conversion fails for the default color, which anstyle represents
+    /// by the absence of a color, and for high-resolution colors.
+    fn try_from(value: &Colorant) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Colorant::Default() => return Err(ConversionError::DefaultColor),
+            Colorant::Ansi(color) => Self::Ansi(
+                Ansi256Color(u8::from(*color))
+                    .into_ansi()
+                    .expect("ANSI colors have anstyle equivalents"),
+            ),
+            Colorant::Embedded(color) => Self::Ansi256(Ansi256Color(u8::from(*color))),
+            Colorant::Gray(color) => Self::Ansi256(Ansi256Color(u8::from(*color))),
+            Colorant::Rgb(color) => Self::Rgb(RgbColor(color[0], color[1], color[2])),
+            Colorant::HiRes(_) => return Err(ConversionError::HiResColor),
+        })
+    }
+}
+
+impl TryFrom<Colorant> for ::anstyle::Color {
+    type Error = ConversionError;
+
+    fn try_from(value: Colorant) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl From<::anstyle::Style> for Style {
+    /// Convert the anstyle style to a style.
+    ///
+    /// Since prettypretty supports only one kind of underline, this conversion
+    /// maps all of anstyle's underline effects to [`Attribute::Underlined`].
+    /// It also ignores anstyle's underline color.
+    fn from(value: ::anstyle::Style) -> Self {
+        let effects = value.get_effects();
+
+        let mut style = Style::default();
+        for (attribute, effect) in ATTRIBUTE_EFFECTS {
+            let enabled = effects.contains(effect)
+                || (attribute == Attribute::Underlined
+                    && effects.iter().any(|e| UNDERLINE_EFFECTS.contains(e)));
+            if enabled {
+                style = match attribute {
+                    Attribute::Bold => style.bold(),
+                    Attribute::Thin => style.thin(),
+                    Attribute::Italic => style.italic(),
+                    Attribute::Underlined => style.underlined(),
+                    Attribute::Blinking => style.blinking(),
+                    Attribute::Reversed => style.reversed(),
+                    Attribute::Hidden => style.hidden(),
+                    Attribute::Stricken => style.stricken(),
+                };
+            }
+        }
+
+        if let Some(color) = value.get_fg_color() {
+            style = style.with_foreground(color);
+        }
+        if let Some(color) = value.get_bg_color() {
+            style = style.with_background(color);
+        }
+        style
+    }
+}
+
+impl TryFrom<&Style> for ::anstyle::Style {
+    type Error = ConversionError;
+
+    /// Convert the style to an anstyle style.
+    ///
+    /// Since anstyle styles only ever add attributes and colors, this
+    /// conversion fails for styles that disable text attributes or set the
+    /// default color, e.g., the negation of a style. It also fails for styles
+    /// with high-resolution colors.
+    fn try_from(value: &Style) -> Result<Self, Self::Error> {
+        let format = value.format();
+        if !format.disable().is_empty() {
+            return Err(ConversionError::DisabledAttributes);
+        }
+
+        let effects = format
+            .enable()
+            .attributes()
+            .filter_map(|attribute| {
+                ATTRIBUTE_EFFECTS
+                    .iter()
+                    .find(|(other, _)| *other == attribute)
+                    .map(|(_, effect)| *effect)
+            })
+            .fold(Effects::new(), Effects::insert);
+
+        Ok(::anstyle::Style::new()
+            .effects(effects)
+            .fg_color(value.foreground().map(TryInto::try_into).transpose()?)
+            .bg_color(value.background().map(TryInto::try_into).transpose()?))
+    }
+}
+
+impl TryFrom<Style> for ::anstyle::Style {
+    type Error = ConversionError;
+
+    fn try_from(value: Style) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use ::anstyle::{Ansi256Color, AnsiColor as AnAnsiColor, Color, Effects, RgbColor};
+
+    use crate::error::ConversionError;
+    use crate::style::Style;
+    use crate::termco::{AnsiColor, Colorant, EmbeddedRgb, Rgb};
+
+    #[test]
+    fn test_anstyle() -> Result<(), ConversionError> {
+        let style = Style::default()
+            .bold()
+            .underlined()
+            .with_foreground(AnsiColor::BrightRed)
+            .with_background(Rgb::new(1, 2, 3));
+        let other = ::anstyle::Style::new()
+            .effects(Effects::BOLD | Effects::UNDERLINE)
+            .fg_color(Some(Color::Ansi(AnAnsiColor::BrightRed)))
+            .bg_color(Some(Color::Rgb(RgbColor(1, 2, 3))));
+
+        assert_eq!(::anstyle::Style::try_from(&style)?, other);
+        assert_eq!(Style::from(other), style);
+
+        let embedded = Colorant::Embedded(EmbeddedRgb::new(1, 2, 3).unwrap());
+        let color = Color::try_from(&embedded)?;
+        assert_eq!(color, Color::Ansi256(Ansi256Color(67)));
+        assert_eq!(Colorant::from(color), embedded);
+
+        let curly = ::anstyle::Style::new().effects(Effects::CURLY_UNDERLINE);
+        assert_eq!(Style::from(curly), Style::default().underlined());
+
+        assert_eq!(
+            Color::try_from(Colorant::Default()),
+            Err(ConversionError::DefaultColor)
+        );
+        assert_eq!(
+            ::anstyle::Style::try_from(-&style),
+            Err(ConversionError::DisabledAttributes)
+        );
+        Ok(())
+    }
+}
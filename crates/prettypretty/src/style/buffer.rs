@@ -0,0 +1,169 @@
+/// A fixed-size buffer for SGR escape sequences.
+///
+/// An SGR buffer holds the escape sequence for a single [`Style`] or
+/// [`StyleDelta`] without allocating memory. It implements
+/// [`std::fmt::Write`] and hence can be passed to [`Style::write_to`] and
+/// [`StyleDelta::write_to`]. Its capacity suffices for the longest possible
+/// escape sequence, which enables and disables text attributes and sets both
+/// foreground and background to 24-bit colors. A render loop can reuse one
+/// buffer for all styles by clearing the buffer between styles. It then
+/// writes the buffer's contents to the terminal with one system call.
+///
+/// ```
+/// # use prettypretty::style::{SgrBuffer, Style};
+/// # use prettypretty::termco::Rgb;
+/// let style = Style::default()
+///     .bold()
+///     .with_foreground(Rgb::new(255, 140, 0))
+///     .with_background(Rgb::new(0, 0, 128));
+///
+/// let mut buffer = SgrBuffer::new();
+/// style.write_to(&mut buffer)?;
+/// assert_eq!(buffer.as_str(), "\x1b[1;38;2;255;140;0;48;2;0;0;128m");
+///
+/// buffer.clear();
+/// (-&style).write_to(&mut buffer)?;
+/// assert_eq!(buffer.as_bytes(), b"\x1b[22;39;49m");
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+///
+/// [`Style`]: super::Style
+/// [`StyleDelta`]: super::StyleDelta
+/// [`Style::write_to`]: super::Style::write_to
+/// [`StyleDelta::write_to`]: super::StyleDelta::write_to
+#[derive(Clone, Copy)]
+pub struct SgrBuffer {
+    len: usize,
+    data: [u8; SgrBuffer::CAPACITY],
+}
+
+impl SgrBuffer {
+    /// The buffer's capacity in bytes.
+    pub const CAPACITY: usize = 96;
+
+    /// Create a new, empty buffer.
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            data: [0; Self::CAPACITY],
+        }
+    }
+
+    /// Determine whether this buffer is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the number of bytes in this buffer.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Clear this buffer.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Get this buffer's contents as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Get this buffer's contents as a string slice.
+    pub fn as_str(&self) -> &str {
+        // Unwrap is safe b/c the buffer only ever appends entire string slices.
+        std::str::from_utf8(self.as_bytes()).unwrap()
+    }
+}
+
+impl Default for SgrBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Write for SgrBuffer {
+    /// Append the string slice to this buffer.
+    ///
+    /// If the string slice does not fit into the remaining capacity, this
+    /// method leaves the buffer unchanged and returns an error.
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let end = self.len + s.len();
+        if Self::CAPACITY < end {
+            return Err(std::fmt::Error);
+        }
+
+        self.data[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl AsRef<str> for SgrBuffer {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for SgrBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl std::fmt::Debug for SgrBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SgrBuffer").field(&self.as_str()).finish()
+    }
+}
+
+impl std::fmt::Display for SgrBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SgrBuffer;
+    use crate::style::{Attribute, FormatUpdate, Style};
+    use crate::termco::Rgb;
+    use std::fmt::Write;
+
+    #[test]
+    fn test_buffer() {
+        let mut buffer = SgrBuffer::new();
+        assert!(buffer.is_empty());
+        buffer.write_str("\x1b[1m").unwrap();
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(buffer.as_str(), "\x1b[1m");
+
+        let oversized = "x".repeat(SgrBuffer::CAPACITY);
+        assert!(buffer.write_str(&oversized).is_err());
+        assert_eq!(buffer.as_str(), "\x1b[1m");
+
+        buffer.clear();
+        assert!(buffer.write_str(&oversized).is_ok());
+        assert_eq!(buffer.len(), SgrBuffer::CAPACITY);
+    }
+
+    #[test]
+    fn test_longest_sequence() {
+        use Attribute::*;
+
+        let all = Bold + Italic + Underlined + Blinking + Reversed + Hidden + Stricken;
+        let white = Rgb::new(255, 255, 255);
+        let style = Style::from_parts(
+            FormatUpdate::from(all),
+            Some(white.into()),
+            Some(white.into()),
+        );
+        let disabled = Style::from_parts(-all - Thin, Some(white.into()), Some(white.into()));
+
+        for style in [style, disabled] {
+            let mut buffer = SgrBuffer::new();
+            style.write_to(&mut buffer).unwrap();
+            assert_eq!(buffer.as_str(), style.to_string());
+        }
+    }
+}
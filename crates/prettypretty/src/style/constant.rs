@@ -0,0 +1,195 @@
+/// A style with precomputed escape sequences.
+///
+/// Rendering a [`Style`](super::Style) formats its SGR parameters every time.
+/// For the handful of fixed styles used by many command line tools, that is
+/// unnecessary work. A static style instead holds the escape sequences for
+/// applying and reverting the style as `&'static str`s. Since it doesn't
+/// depend on a translator or fidelity, it is best limited to ANSI colors and
+/// text attributes, which are supported by nearly all terminals.
+///
+/// Use the [`static_style!`](crate::static_style) macro to create static
+/// styles, which bakes the escape sequences into the binary at compile time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StaticStyle {
+    apply: &'static str,
+    revert: &'static str,
+}
+
+impl StaticStyle {
+    /// Create a new static style from the escape sequences for applying and
+    /// reverting the style.
+    pub const fn new(apply: &'static str, revert: &'static str) -> Self {
+        Self { apply, revert }
+    }
+
+    /// Get the escape sequence for applying this style.
+    pub const fn apply_sgr(&self) -> &'static str {
+        self.apply
+    }
+
+    /// Get the escape sequence for reverting this style.
+    pub const fn revert_sgr(&self) -> &'static str {
+        self.revert
+    }
+}
+
+impl std::fmt::Display for StaticStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.apply)
+    }
+}
+
+/// Create a new [`StaticStyle`](crate::style::StaticStyle) at compile time.
+///
+/// This macro takes a comma-separated list of style specifications:
+///
+///   * The text attributes `bold`, `thin`, `italic`, `underlined`,
+///     `blinking`, `reversed`, `hidden`, and `stricken`;
+///   * `fg = ` or `bg = ` followed by
+///       * an ANSI color name in snake case, e.g., `red` or `bright_blue`,
+///       * an 8-bit color as integer literal, e.g., `208`, or
+///       * a 24-bit color as parenthesized triple of integer literals, e.g.,
+///         `(255, 140, 0)`.
+///
+/// It bakes the escape sequences for applying and reverting the style into
+/// string literals. Since the macro only concatenates literals, it also is
+/// safe to use in const expressions.
+///
+/// ```
+/// # use prettypretty::static_style;
+/// # use prettypretty::style::{StaticStyle, Style};
+/// # use prettypretty::termco::AnsiColor;
+/// const WARNING: StaticStyle = static_style!(bold, fg = bright_yellow, bg = 236);
+///
+/// assert_eq!(WARNING.apply_sgr(), "\x1b[1;93;48;5;236m");
+/// assert_eq!(WARNING.revert_sgr(), "\x1b[22;39;49m");
+///
+/// let style = Style::default().bold().with_foreground(AnsiColor::BrightYellow);
+/// assert_eq!(static_style!(bold, fg = bright_yellow).apply_sgr(), style.to_string());
+/// ```
+#[macro_export]
+macro_rules! static_style {
+    ($($spec:tt)*) => {
+        $crate::__static_style!(@munch [] [] $($spec)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __static_style {
+    // Assemble the escape sequences.
+    (@munch [] []) => {
+        $crate::style::StaticStyle::new("", "")
+    };
+    (@munch [$a:expr $(, $ax:expr)*] [$r:expr $(, $rx:expr)*]) => {
+        $crate::style::StaticStyle::new(
+            concat!("\x1b[", $a, $(";", $ax,)* "m"),
+            concat!("\x1b[", $r, $(";", $rx,)* "m"),
+        )
+    };
+
+    // Munch colors.
+    (@munch [$($a:expr),*] [$($r:expr),*] fg = ($red:literal, $green:literal, $blue:literal) $(, $($rest:tt)*)?) => {
+        $crate::__static_style!(
+            @munch [$($a,)* concat!("38;2;", $red, ";", $green, ";", $blue)] [$($r,)* "39"] $($($rest)*)?)
+    };
+    (@munch [$($a:expr),*] [$($r:expr),*] fg = $index:literal $(, $($rest:tt)*)?) => {
+        $crate::__static_style!(
+            @munch [$($a,)* concat!("38;5;", $index)] [$($r,)* "39"] $($($rest)*)?)
+    };
+    (@munch [$($a:expr),*] [$($r:expr),*] fg = $name:ident $(, $($rest:tt)*)?) => {
+        $crate::__static_style!(
+            @munch [$($a,)* $crate::__static_style!(@ansi "3" "9" $name)] [$($r,)* "39"] $($($rest)*)?)
+    };
+    (@munch [$($a:expr),*] [$($r:expr),*] bg = ($red:literal, $green:literal, $blue:literal) $(, $($rest:tt)*)?) => {
+        $crate::__static_style!(
+            @munch [$($a,)* concat!("48;2;", $red, ";", $green, ";", $blue)] [$($r,)* "49"] $($($rest)*)?)
+    };
+    (@munch [$($a:expr),*] [$($r:expr),*] bg = $index:literal $(, $($rest:tt)*)?) => {
+        $crate::__static_style!(
+            @munch [$($a,)* concat!("48;5;", $index)] [$($r,)* "49"] $($($rest)*)?)
+    };
+    (@munch [$($a:expr),*] [$($r:expr),*] bg = $name:ident $(, $($rest:tt)*)?) => {
+        $crate::__static_style!(
+            @munch [$($a,)* $crate::__static_style!(@ansi "4" "10" $name)] [$($r,)* "49"] $($($rest)*)?)
+    };
+
+    // Munch text attributes.
+    (@munch [$($a:expr),*] [$($r:expr),*] $attr:ident $(, $($rest:tt)*)?) => {
+        $crate::__static_style!(
+            @munch [$($a,)* $crate::__static_style!(@enable $attr)]
+                [$($r,)* $crate::__static_style!(@disable $attr)] $($($rest)*)?)
+    };
+
+    // Map names to SGR parameters.
+    (@enable bold) => { "1" };
+    (@enable thin) => { "2" };
+    (@enable italic) => { "3" };
+    (@enable underlined) => { "4" };
+    (@enable blinking) => { "5" };
+    (@enable reversed) => { "7" };
+    (@enable hidden) => { "8" };
+    (@enable stricken) => { "9" };
+
+    (@disable bold) => { "22" };
+    (@disable thin) => { "22" };
+    (@disable italic) => { "23" };
+    (@disable underlined) => { "24" };
+    (@disable blinking) => { "25" };
+    (@disable reversed) => { "27" };
+    (@disable hidden) => { "28" };
+    (@disable stricken) => { "29" };
+
+    (@ansi $base:literal $bright:literal black) => { concat!($base, "0") };
+    (@ansi $base:literal $bright:literal red) => { concat!($base, "1") };
+    (@ansi $base:literal $bright:literal green) => { concat!($base, "2") };
+    (@ansi $base:literal $bright:literal yellow) => { concat!($base, "3") };
+    (@ansi $base:literal $bright:literal blue) => { concat!($base, "4") };
+    (@ansi $base:literal $bright:literal magenta) => { concat!($base, "5") };
+    (@ansi $base:literal $bright:literal cyan) => { concat!($base, "6") };
+    (@ansi $base:literal $bright:literal white) => { concat!($base, "7") };
+    (@ansi $base:literal $bright:literal bright_black) => { concat!($bright, "0") };
+    (@ansi $base:literal $bright:literal bright_red) => { concat!($bright, "1") };
+    (@ansi $base:literal $bright:literal bright_green) => { concat!($bright, "2") };
+    (@ansi $base:literal $bright:literal bright_yellow) => { concat!($bright, "3") };
+    (@ansi $base:literal $bright:literal bright_blue) => { concat!($bright, "4") };
+    (@ansi $base:literal $bright:literal bright_magenta) => { concat!($bright, "5") };
+    (@ansi $base:literal $bright:literal bright_cyan) => { concat!($bright, "6") };
+    (@ansi $base:literal $bright:literal bright_white) => { concat!($bright, "7") };
+}
+
+#[cfg(test)]
+mod test {
+    use super::StaticStyle;
+    use crate::style::Style;
+    use crate::termco::{AnsiColor, EmbeddedRgb, Rgb};
+
+    const PLAIN: StaticStyle = static_style!();
+    const FANCY: StaticStyle =
+        static_style!(italic, stricken, fg = (255, 140, 0), bg = bright_black,);
+
+    #[test]
+    fn test_static_style() {
+        assert_eq!(PLAIN.apply_sgr(), "");
+        assert_eq!(PLAIN.revert_sgr(), "");
+        assert_eq!(PLAIN.apply_sgr(), Style::default().to_string());
+
+        let fancy = Style::default()
+            .italic()
+            .stricken()
+            .with_foreground(Rgb::new(255, 140, 0))
+            .with_background(AnsiColor::BrightBlack);
+        assert_eq!(FANCY.apply_sgr(), fancy.to_string());
+        assert_eq!(FANCY.revert_sgr(), (-&fancy).to_string());
+        assert_eq!(FANCY.to_string(), fancy.to_string());
+
+        let style = static_style!(underlined, bg = red, fg = 30);
+        let expected = Style::default()
+            .underlined()
+            .with_foreground(EmbeddedRgb::new(0, 2, 2).unwrap())
+            .with_background(AnsiColor::Red);
+        assert_eq!(style.apply_sgr(), "\x1b[4;41;38;5;30m");
+        assert_eq!(style.revert_sgr(), "\x1b[24;49;39m");
+        assert_eq!(expected.to_string(), "\x1b[4;38;5;30;41m");
+    }
+}
@@ -87,9 +87,15 @@ impl Fidelity {
     /// variables.
     ///
     /// This method determines fidelity based on heuristics about environment
-    /// variables. Its primary sources are [NO_COLOR](https://no-color.org) and
-    /// [FORCE_COLOR](https://force-color.org). Its secondary source is Chalk's
+    /// variables. Its primary sources are [NO_COLOR](https://no-color.org),
+    /// [FORCE_COLOR](https://force-color.org), and
+    /// [CLICOLOR/CLICOLOR_FORCE](http://bixense.com/clicolors/), in that order
+    /// of precedence. Any non-empty `FORCE_COLOR` forces colors, with its value
+    /// only determining the minimum fidelity level. Its secondary source is
+    /// Chalk's
     /// [supports-color](https://github.com/chalk/supports-color/blob/main/index.js).
+    /// Use [`Fidelity::report_environment`] to find out why this method
+    /// picked a fidelity level.
     #[cfg(feature = "pyffi")]
     #[staticmethod]
     pub fn from_environment(has_tty: bool) -> Self {
@@ -100,14 +106,35 @@ impl Fidelity {
     /// variables.
     ///
     /// This method determines fidelity based on heuristics about environment
-    /// variables. Its primary sources are [NO_COLOR](https://no-color.org) and
-    /// [FORCE_COLOR](https://force-color.org). Its secondary source is Chalk's
+    /// variables. Its primary sources are [NO_COLOR](https://no-color.org),
+    /// [FORCE_COLOR](https://force-color.org), and
+    /// [CLICOLOR/CLICOLOR_FORCE](http://bixense.com/clicolors/), in that order
+    /// of precedence. Any non-empty `FORCE_COLOR` forces colors, with its value
+    /// only determining the minimum fidelity level. Its secondary source is
+    /// Chalk's
     /// [supports-color](https://github.com/chalk/supports-color/blob/main/index.js).
+    /// Use [`Fidelity::report_environment`] to find out why this method
+    /// picked a fidelity level.
     #[cfg(not(feature = "pyffi"))]
     pub fn from_environment(has_tty: bool) -> Self {
         fidelity_from_environment(&Env::default(), has_tty)
     }
 
+    /// Determine the fidelity level for terminal output based on environment
+    /// variables and report the decision trail.
+    #[cfg(feature = "pyffi")]
+    #[staticmethod]
+    pub fn report_environment(has_tty: bool) -> FidelityReport {
+        report_from_environment(&Env::default(), has_tty)
+    }
+
+    /// Determine the fidelity level for terminal output based on environment
+    /// variables and report the decision trail.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn report_environment(has_tty: bool) -> FidelityReport {
+        report_from_environment(&Env::default(), has_tty)
+    }
+
     /// Determine whether this fidelity level suffices for rendering the
     /// colorant as is, without conversion.
     #[cfg(feature = "pyffi")]
@@ -126,6 +153,141 @@ impl Fidelity {
     pub fn covers(&self, colorant: impl Into<Colorant>) -> bool {
         Fidelity::from(colorant.into()) <= *self
     }
+
+    /// Determine the fidelity level for terminal output based on the
+    /// terminfo entry for the current `TERM`.
+    ///
+    /// This method returns `None` if there is no `TERM` environment variable
+    /// or the terminfo database has no entry for its value. It is only
+    /// available with the `terminfo` feature.
+    #[cfg(feature = "terminfo")]
+    pub fn from_terminfo() -> Option<Self> {
+        prettytty::terminfo::TermInfo::from_env()
+            .ok()
+            .map(|info| fidelity_from_terminfo(&info))
+    }
+
+    /// Determine the fidelity level for terminal output based on environment
+    /// variables and, if they are inconclusive, the terminfo database.
+    ///
+    /// This method refines the result of [`Fidelity::from_environment`] with
+    /// [`Fidelity::from_terminfo`], which helps with the many terminals that
+    /// do not set `COLORTERM`. The terminfo database can only raise the
+    /// fidelity level. It is not consulted when the output is not a
+    /// terminal, `TERM` is `dumb`, or `NO_COLOR` or `FORCE_COLOR` are set.
+    /// This method is only available with the `terminfo` feature.
+    #[cfg(feature = "terminfo")]
+    pub fn from_environment_and_terminfo(has_tty: bool) -> Self {
+        refine_with_terminfo(&Env::default(), has_tty, Self::from_terminfo)
+    }
+
+    /// Probe the terminal for 24-bit color support.
+    ///
+    /// Environment variables and the terminfo database may claim 24-bit color
+    /// support for terminals that do not actually honor it, notably tmux
+    /// without the `Tc` or `RGB` terminal feature and older VTE-based
+    /// terminals. Hence, this method sets an unusual 24-bit foreground color
+    /// and reads it back with a DECRQSS query for the active style. If the
+    /// terminal reports the exact color, it truly supports 24-bit colors. If
+    /// the terminal does not support DECRQSS, this method falls back on an
+    /// XTGETTCAP query for the `RGB` capability. In either case, it restores
+    /// the previous style.
+    ///
+    /// If the terminal supports neither query, this method fails with an
+    /// error, often after the connection's read timeout. Since the XTGETTCAP
+    /// query goes through [`Connection::caps`](prettytty::Connection::caps),
+    /// its result is cached with the connection. <i class=tty-only>TTY
+    /// only!</i>
+    #[cfg(feature = "tty")]
+    pub fn probe_truecolor(connection: &prettytty::Connection) -> std::io::Result<bool> {
+        use prettytty::cmd::{DynRestoreStyle, RequestActiveStyle, SetForeground24};
+        use prettytty::{Query, Scan};
+        use std::io::{Error, ErrorKind, Write};
+
+        {
+            let (mut input, mut output) = connection.io();
+
+            output.exec(RequestActiveStyle)?;
+            let previous = input
+                .read_sequence(RequestActiveStyle.control())
+                .and_then(|payload| RequestActiveStyle.parse(payload));
+
+            if let Ok(previous) = previous {
+                write!(output, "{}", SetForeground24::<1, 2, 3>)?;
+                output.exec(RequestActiveStyle)?;
+                let current = input
+                    .read_sequence(RequestActiveStyle.control())
+                    .and_then(|payload| RequestActiveStyle.parse(payload));
+
+                output.exec(DynRestoreStyle::new(&previous))?;
+
+                return Ok(has_truecolor_probe(&current?));
+            }
+        }
+
+        connection
+            .caps()
+            .rgb()
+            .ok_or_else(|| Error::from(ErrorKind::Unsupported))
+    }
+}
+
+/// Determine whether the SGR parameters reported by DECRQSS include the probe
+/// color 1, 2, 3 as a 24-bit color.
+#[cfg(feature = "tty")]
+fn has_truecolor_probe(params: &[u8]) -> bool {
+    let params = params.split(|b| *b == b';').collect::<Vec<_>>();
+
+    // Legacy semicolon-separated form
+    if params
+        .windows(5)
+        .any(|window| window == [&b"38"[..], b"2", b"1", b"2", b"3"])
+    {
+        return true;
+    }
+
+    // Colon-separated form with or without color space identifier
+    params
+        .iter()
+        .any(|param| matches!(*param, b"38:2:1:2:3" | b"38:2::1:2:3"))
+}
+
+/// Determine the fidelity level from a terminfo entry.
+#[cfg(feature = "terminfo")]
+fn fidelity_from_terminfo(info: &prettytty::terminfo::TermInfo) -> Fidelity {
+    if info.has_truecolor() {
+        return Fidelity::TwentyFourBit;
+    }
+
+    match info.colors() {
+        Some(256..) => Fidelity::EightBit,
+        Some(8..) => Fidelity::Ansi,
+        _ => Fidelity::NoColor,
+    }
+}
+
+/// Refine the fidelity level determined from the environment with the
+/// terminfo-based fidelity, unless the environment is conclusive.
+#[cfg(feature = "terminfo")]
+pub(crate) fn refine_with_terminfo(
+    env: &impl Environment,
+    has_tty: bool,
+    terminfo: impl FnOnce() -> Option<Fidelity>,
+) -> Fidelity {
+    let report = report_from_environment(env, has_tty);
+    let is_explicit = matches!(
+        report.variable,
+        Some("NO_COLOR" | "FORCE_COLOR" | "CLICOLOR_FORCE" | "CLICOLOR")
+    );
+    if !has_tty
+        || is_explicit
+        || env.has_value("TERM", "dumb")
+        || Fidelity::TwentyFourBit <= report.fidelity
+    {
+        return report.fidelity;
+    }
+
+    terminfo().map_or(report.fidelity, |other| report.fidelity.max(other))
 }
 
 impl From<&Colorant> for Fidelity {
@@ -145,27 +307,241 @@ impl From<Colorant> for Fidelity {
     }
 }
 
+/// The outcome of fidelity detection based on environment variables.
+///
+/// Besides the fidelity level, a report includes the environment variable that
+/// decided the outcome as well as the decision trail, i.e., a human-readable
+/// description of each check in order. Both help with debugging user reports
+/// about unexpected styling or lack thereof.
+///
+/// ```
+/// # use prettypretty::style::{Fidelity, FidelityReport};
+/// let report = Fidelity::report_environment(false);
+/// assert_eq!(report.fidelity(), Fidelity::from_environment(false));
+/// assert!(!report.trail().is_empty());
+/// ```
+#[cfg_attr(
+    feature = "pyffi",
+    pyclass(eq, frozen, hash, module = "prettypretty.color.style")
+)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FidelityReport {
+    fidelity: Fidelity,
+    variable: Option<&'static str>,
+    trail: Vec<String>,
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl FidelityReport {
+    /// Get the fidelity level.
+    pub fn fidelity(&self) -> Fidelity {
+        self.fidelity
+    }
+
+    /// Get the name of the environment variable that decided the fidelity
+    /// level.
+    ///
+    /// This method returns `None` if the decision did not depend on an
+    /// environment variable, e.g., because the output is not a terminal.
+    pub fn variable(&self) -> Option<&'static str> {
+        self.variable
+    }
+
+    /// Get the decision trail. <i class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "trail")]
+    pub fn py_trail(&self) -> Vec<String> {
+        self.trail.clone()
+    }
+
+    /// Get this fidelity report's debug representation. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    /// Get the decision trail, one step per line. <i class=python-only>Python
+    /// only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __str__(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl FidelityReport {
+    /// Get the decision trail.
+    pub fn trail(&self) -> &[String] {
+        &self.trail
+    }
+}
+
+impl std::fmt::Display for FidelityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, step) in self.trail.iter().enumerate() {
+            if 0 < index {
+                f.write_str("\n")?;
+            }
+            f.write_str(step)?;
+        }
+        Ok(())
+    }
+}
+
+/// A helper for building up a fidelity report.
+#[derive(Default)]
+struct Trail {
+    steps: Vec<String>,
+}
+
+impl Trail {
+    fn note(&mut self, step: impl Into<String>) {
+        self.steps.push(step.into());
+    }
+
+    fn decide(
+        mut self,
+        variable: Option<&'static str>,
+        fidelity: Fidelity,
+        reason: impl std::fmt::Display,
+    ) -> FidelityReport {
+        self.steps.push(format!("{} → {:?}", reason, fidelity));
+        FidelityReport {
+            fidelity,
+            variable,
+            trail: self.steps,
+        }
+    }
+}
+
+/// Read the non-empty environment variable, noting its absence in the trail.
+fn read_noting(env: &impl Environment, trail: &mut Trail, key: &str) -> Option<String> {
+    if env.is_non_empty(key) {
+        return env
+            .read_os(key)
+            .map(|value| value.to_string_lossy().into_owned());
+    }
+
+    if env.is_defined(key) {
+        trail.note(format!("{} is empty, ignored", key));
+    } else {
+        trail.note(format!("{} is not set", key));
+    }
+    None
+}
+
+pub(crate) fn fidelity_from_environment(env: &impl Environment, has_tty: bool) -> Fidelity {
+    report_from_environment(env, has_tty).fidelity
+}
+
+/// Determine the fidelity level based on environment variables.
+///
+/// Explicit user preferences come first, in this order:
+///
+///   * A non-empty `NO_COLOR` disables colors.
+///   * A non-empty `FORCE_COLOR` forces colors even if the output is not a
+///     terminal. Its value serves as a floor: `2` and `3` select at least
+///     8-bit and 24-bit colors, respectively, and any other value, including
+///     `0` and `false`, selects at least ANSI colors. The heuristics may still
+///     pick a higher level.
+///   * A non-empty `CLICOLOR_FORCE` other than `0` forces at least ANSI colors
+///     even if the output is not a terminal.
+///   * `CLICOLOR=0` disables colors.
+///
+/// Only then does this function fall back on heuristics.
+pub(crate) fn report_from_environment(env: &impl Environment, has_tty: bool) -> FidelityReport {
+    let mut trail = Trail::default();
+
+    if read_noting(env, &mut trail, "NO_COLOR").is_some() {
+        return trail.decide(Some("NO_COLOR"), Fidelity::NoColor, "NO_COLOR is set");
+    }
+
+    let mut floor = None;
+    if let Some(value) = read_noting(env, &mut trail, "FORCE_COLOR") {
+        let level = match value.as_str() {
+            "2" => Fidelity::EightBit,
+            "3" => Fidelity::TwentyFourBit,
+            _ => Fidelity::Ansi,
+        };
+
+        trail.note(format!("FORCE_COLOR={} forces at least {:?}", value, level));
+        floor = Some(("FORCE_COLOR", level));
+    } else if let Some(value) = read_noting(env, &mut trail, "CLICOLOR_FORCE") {
+        if value == "0" {
+            trail.note("CLICOLOR_FORCE=0, ignored");
+        } else {
+            trail.note(format!(
+                "CLICOLOR_FORCE={} forces at least {:?}",
+                value,
+                Fidelity::Ansi
+            ));
+            floor = Some(("CLICOLOR_FORCE", Fidelity::Ansi));
+        }
+    }
+
+    if let Some((variable, level)) = floor {
+        // Forcing colors implies treating the output as a terminal.
+        let report = heuristics(env, true, trail);
+        if level <= report.fidelity {
+            return report;
+        }
+
+        let mut trail = Trail {
+            steps: report.trail,
+        };
+        trail.note(format!("{} takes precedence", variable));
+        return trail.decide(Some(variable), level, format!("{} is set", variable));
+    }
+
+    match env.read("CLICOLOR") {
+        Ok(value) if value == "0" => {
+            let fidelity = if has_tty {
+                Fidelity::NoColor
+            } else {
+                Fidelity::Plain
+            };
+            return trail.decide(Some("CLICOLOR"), fidelity, "CLICOLOR=0");
+        }
+        Ok(value) => trail.note(format!("CLICOLOR={} does not disable colors", value)),
+        Err(_) => trail.note("CLICOLOR is not set"),
+    }
+
+    heuristics(env, has_tty, trail)
+}
+
 // While implementing this function, I was also writing helper functions to
 // simplify environment access. So, when it came to testing this function, an
 // answer offered itself: Mock the environment! Well, not really: I simply
 // abstracted environment access behind a trait and use a different
 // implementation for testing. That way, I continue to adhere to the first law
 // of mocking: Mock people, not code! 😈
-pub(crate) fn fidelity_from_environment(env: &impl Environment, has_tty: bool) -> Fidelity {
-    if env.is_non_empty("NO_COLOR") {
-        return Fidelity::NoColor;
-    } else if env.is_non_empty("FORCE_COLOR") {
-        return Fidelity::Ansi;
-    } else if env.is_defined("TF_BUILD") || env.is_defined("AGENT_NAME") {
+fn heuristics(env: &impl Environment, has_tty: bool, mut trail: Trail) -> FidelityReport {
+    if env.is_defined("TF_BUILD") || env.is_defined("AGENT_NAME") {
         // Supports-color states that this test must come before TTY test.
-        return Fidelity::Ansi;
+        let variable = if env.is_defined("TF_BUILD") {
+            "TF_BUILD"
+        } else {
+            "AGENT_NAME"
+        };
+        return trail.decide(
+            Some(variable),
+            Fidelity::Ansi,
+            format!("{} indicates Azure Pipelines", variable),
+        );
     } else if !has_tty {
-        return Fidelity::Plain;
+        return trail.decide(None, Fidelity::Plain, "output is not a terminal");
     } else if env.has_value("TERM", "dumb") {
-        return Fidelity::Plain; // FIXME Check Windows version!
+        // FIXME Check Windows version!
+        return trail.decide(Some("TERM"), Fidelity::Plain, "TERM=dumb");
     } else if env.is_defined("CI") {
-        if env.is_defined("GITHUB_ACTIONS") || env.is_defined("GITEA_ACTIONS") {
-            return Fidelity::TwentyFourBit;
+        for ci in ["GITHUB_ACTIONS", "GITEA_ACTIONS"] {
+            if env.is_defined(ci) {
+                return trail.decide(
+                    Some(ci),
+                    Fidelity::TwentyFourBit,
+                    format!("CI with {} is defined", ci),
+                );
+            }
         }
 
         for ci in [
@@ -177,15 +553,19 @@ pub(crate) fn fidelity_from_environment(env: &impl Environment, has_tty: bool) -
             "DRONE",
         ] {
             if env.is_defined(ci) {
-                return Fidelity::Ansi;
+                return trail.decide(
+                    Some(ci),
+                    Fidelity::Ansi,
+                    format!("CI with {} is defined", ci),
+                );
             }
         }
 
         if env.has_value("CI_NAME", "codeship") {
-            return Fidelity::Ansi;
+            return trail.decide(Some("CI_NAME"), Fidelity::Ansi, "CI_NAME=codeship");
         }
 
-        return Fidelity::Plain;
+        return trail.decide(Some("CI"), Fidelity::Plain, "CI is defined");
     }
 
     if let Ok(teamcity) = env.read("TEAMCITY_VERSION") {
@@ -194,7 +574,7 @@ pub(crate) fn fidelity_from_environment(env: &impl Environment, has_tty: bool) -
         let c1 = charity.next();
         let c2 = charity.next();
 
-        if c1
+        let fidelity = if c1
             .filter(|c| *c == '9')
             .and(c2.filter(|c| *c == '.'))
             .or(c1
@@ -203,14 +583,30 @@ pub(crate) fn fidelity_from_environment(env: &impl Environment, has_tty: bool) -
                 .and(charity.next().filter(|c| *c == '.')))
             .is_some()
         {
-            return Fidelity::Ansi;
-        }
+            Fidelity::Ansi
+        } else {
+            Fidelity::Plain
+        };
 
-        return Fidelity::Plain;
-    } else if env.has_value("COLORTERM", "truecolor") || env.has_value("TERM", "xterm-kitty") {
-        return Fidelity::TwentyFourBit;
+        return trail.decide(
+            Some("TEAMCITY_VERSION"),
+            fidelity,
+            format!("TEAMCITY_VERSION={}", teamcity),
+        );
+    } else if env.has_value("COLORTERM", "truecolor") {
+        return trail.decide(
+            Some("COLORTERM"),
+            Fidelity::TwentyFourBit,
+            "COLORTERM=truecolor",
+        );
+    } else if env.has_value("TERM", "xterm-kitty") {
+        return trail.decide(Some("TERM"), Fidelity::TwentyFourBit, "TERM=xterm-kitty");
     } else if env.has_value("TERM_PROGRAM", "Apple_Terminal") {
-        return Fidelity::EightBit;
+        return trail.decide(
+            Some("TERM_PROGRAM"),
+            Fidelity::EightBit,
+            "TERM_PROGRAM=Apple_Terminal",
+        );
     } else if env.has_value("TERM_PROGRAM", "iTerm.app") {
         if let Ok(version) = env.read("TERM_PROGRAM_VERSION") {
             let mut charity = version.chars();
@@ -220,17 +616,25 @@ pub(crate) fn fidelity_from_environment(env: &impl Environment, has_tty: bool) -
                 .and(charity.next().filter(|c| *c == '.'))
                 .is_some()
             {
-                return Fidelity::TwentyFourBit;
+                return trail.decide(
+                    Some("TERM_PROGRAM_VERSION"),
+                    Fidelity::TwentyFourBit,
+                    format!("TERM_PROGRAM=iTerm.app with version {}", version),
+                );
             }
         }
-        return Fidelity::EightBit;
+        return trail.decide(
+            Some("TERM_PROGRAM"),
+            Fidelity::EightBit,
+            "TERM_PROGRAM=iTerm.app",
+        );
     }
 
     if let Ok(mut term) = env.read("TERM") {
         term.make_ascii_lowercase();
 
         if term.ends_with("-256") || term.ends_with("-256color") {
-            return Fidelity::EightBit;
+            return trail.decide(Some("TERM"), Fidelity::EightBit, format!("TERM={}", term));
         } else if term.starts_with("screen")
             || term.starts_with("xterm")
             || term.starts_with("vt100")
@@ -241,18 +645,20 @@ pub(crate) fn fidelity_from_environment(env: &impl Environment, has_tty: bool) -
             || term == "cygwin"
             || term == "linux"
         {
-            return Fidelity::Ansi;
+            return trail.decide(Some("TERM"), Fidelity::Ansi, format!("TERM={}", term));
         }
+
+        trail.note(format!("TERM={} is not recognized", term));
     } else if env.is_defined("COLORTERM") {
-        return Fidelity::Ansi;
+        return trail.decide(Some("COLORTERM"), Fidelity::Ansi, "COLORTERM is defined");
     }
 
-    Fidelity::Plain
+    trail.decide(None, Fidelity::Plain, "no environment variable applies")
 }
 
 #[cfg(test)]
 mod test {
-    use super::{fidelity_from_environment, Fidelity};
+    use super::{fidelity_from_environment, report_from_environment, Fidelity};
     use crate::util::FakeEnv;
 
     #[test]
@@ -283,4 +689,82 @@ mod test {
         env.set("NO_COLOR", "1");
         assert_eq!(fidelity_from_environment(env, true), Fidelity::NoColor);
     }
+
+    #[test]
+    fn test_precedence() {
+        let env = &mut FakeEnv::new();
+        env.set("TERM", "xterm-256color");
+        env.set("CLICOLOR", "0");
+        let report = report_from_environment(env, true);
+        assert_eq!(report.fidelity(), Fidelity::NoColor);
+        assert_eq!(report.variable(), Some("CLICOLOR"));
+
+        env.set("CLICOLOR_FORCE", "1");
+        let report = report_from_environment(env, false);
+        assert_eq!(report.fidelity(), Fidelity::EightBit);
+        assert_eq!(report.variable(), Some("TERM"));
+
+        env.set("FORCE_COLOR", "3");
+        let report = report_from_environment(env, false);
+        assert_eq!(report.fidelity(), Fidelity::TwentyFourBit);
+        assert_eq!(report.variable(), Some("FORCE_COLOR"));
+        assert_eq!(
+            report.trail().last().map(String::as_str),
+            Some("FORCE_COLOR is set → TwentyFourBit")
+        );
+
+        env.set("FORCE_COLOR", "false");
+        let report = report_from_environment(env, false);
+        assert_eq!(report.fidelity(), Fidelity::EightBit);
+        assert_eq!(report.variable(), Some("TERM"));
+
+        let env = &mut FakeEnv::new();
+        env.set("FORCE_COLOR", "0");
+        let report = report_from_environment(env, false);
+        assert_eq!(report.fidelity(), Fidelity::Ansi);
+        assert_eq!(report.variable(), Some("FORCE_COLOR"));
+
+        env.set("NO_COLOR", "1");
+        env.set("FORCE_COLOR", "1");
+        let report = report_from_environment(env, true);
+        assert_eq!(report.fidelity(), Fidelity::NoColor);
+        assert_eq!(report.variable(), Some("NO_COLOR"));
+        assert_eq!(report.to_string(), "NO_COLOR is set → NoColor");
+    }
+
+    #[cfg(feature = "tty")]
+    #[test]
+    fn test_truecolor_probe() {
+        use super::has_truecolor_probe;
+
+        assert!(has_truecolor_probe(b"0;38;2;1;2;3"));
+        assert!(has_truecolor_probe(b"38:2::1:2:3"));
+        assert!(!has_truecolor_probe(b"0;38;5;16"));
+        assert!(!has_truecolor_probe(b"38;2;1;2;30"));
+    }
+
+    #[cfg(feature = "terminfo")]
+    #[test]
+    fn test_terminfo() {
+        use super::refine_with_terminfo;
+
+        let env = &mut FakeEnv::new();
+        let truecolor = || Some(Fidelity::TwentyFourBit);
+        env.set("TERM", "xterm");
+        assert_eq!(
+            refine_with_terminfo(env, true, truecolor),
+            Fidelity::TwentyFourBit
+        );
+        assert_eq!(refine_with_terminfo(env, false, truecolor), Fidelity::Plain);
+        assert_eq!(refine_with_terminfo(env, true, || None), Fidelity::Ansi);
+        assert_eq!(
+            refine_with_terminfo(env, true, || Some(Fidelity::NoColor)),
+            Fidelity::Ansi
+        );
+        env.set("NO_COLOR", "1");
+        assert_eq!(
+            refine_with_terminfo(env, true, truecolor),
+            Fidelity::NoColor
+        );
+    }
 }
@@ -0,0 +1,222 @@
+//! Conversions between prettypretty's and crossterm's styles and colors.
+
+use ::crossterm::style::{Attribute as CtAttribute, Attributes, Color, ContentStyle};
+
+use super::{Attribute, Format, Style};
+use crate::error::ConversionError;
+use crate::termco::{AnsiColor, Colorant, Rgb};
+
+/// The ANSI colors in crossterm's order, which differs from the ANSI order.
+const ANSI_COLORS: [(Color, AnsiColor); 16] = [
+    (Color::Black, AnsiColor::Black),
+    (Color::DarkRed, AnsiColor::Red),
+    (Color::DarkGreen, AnsiColor::Green),
+    (Color::DarkYellow, AnsiColor::Yellow),
+    (Color::DarkBlue, AnsiColor::Blue),
+    (Color::DarkMagenta, AnsiColor::Magenta),
+    (Color::DarkCyan, AnsiColor::Cyan),
+    (Color::Grey, AnsiColor::White),
+    (Color::DarkGrey, AnsiColor::BrightBlack),
+    (Color::Red, AnsiColor::BrightRed),
+    (Color::Green, AnsiColor::BrightGreen),
+    (Color::Yellow, AnsiColor::BrightYellow),
+    (Color::Blue, AnsiColor::BrightBlue),
+    (Color::Magenta, AnsiColor::BrightMagenta),
+    (Color::Cyan, AnsiColor::BrightCyan),
+    (Color::White, AnsiColor::BrightWhite),
+];
+
+/// The text attributes with crossterm's attributes for enabling and disabling
+/// them.
+#[rustfmt::skip]
+const ATTRIBUTES: [(Attribute, CtAttribute, CtAttribute); 8] = [
+    (Attribute::Bold, CtAttribute::Bold, CtAttribute::NormalIntensity),
+    (Attribute::Thin, CtAttribute::Dim, CtAttribute::NormalIntensity),
+    (Attribute::Italic, CtAttribute::Italic, CtAttribute::NoItalic),
+    (Attribute::Underlined, CtAttribute::Underlined, CtAttribute::NoUnderline),
+    (Attribute::Blinking, CtAttribute::SlowBlink, CtAttribute::NoBlink),
+    (Attribute::Reversed, CtAttribute::Reverse, CtAttribute::NoReverse),
+    (Attribute::Hidden, CtAttribute::Hidden, CtAttribute::NoHidden),
+    (Attribute::Stricken, CtAttribute::CrossedOut, CtAttribute::NotCrossedOut),
+];
+
+/// The crossterm attributes that prettypretty treats as equivalent to one of
+/// the attributes in [`ATTRIBUTES`].
+const ALIASES: [(CtAttribute, Attribute); 5] = [
+    (CtAttribute::DoubleUnderlined, Attribute::Underlined),
+    (CtAttribute::Undercurled, Attribute::Underlined),
+    (CtAttribute::Underdotted, Attribute::Underlined),
+    (CtAttribute::Underdashed, Attribute::Underlined),
+    (CtAttribute::RapidBlink, Attribute::Blinking),
+];
+
+impl From<Color> for Colorant {
+    fn from(value: Color) -> Self {
+        match value {
+            Color::Reset => Colorant::Default(),
+            Color::AnsiValue(index) => Colorant::from(index),
+            Color::Rgb { r, g, b } => Colorant::Rgb(Rgb::new(r, g, b)),
+            _ => ANSI_COLORS
+                .iter()
+                .find(|(color, _)| *color == value)
+                .map(|(_, ansi)| Colorant::Ansi(*ansi))
+                .expect("all named crossterm colors are ANSI colors"),
+        }
+    }
+}
+
+impl TryFrom<&Colorant> for Color {
+    type Error = ConversionError;
+
+    /// Convert the colorant to a crossterm color.
+    ///
+    /// This conversion fails for high-resolution colors.
+    fn try_from(value: &Colorant) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Colorant::Default() => Color::Reset,
+            Colorant::Ansi(color) => ANSI_COLORS
+                .iter()
+                .find(|(_, ansi)| ansi == color)
+                .map(|(color, _)| *color)
+                .expect("all ANSI colors are named crossterm colors"),
+            Colorant::Embedded(color) => Color::AnsiValue(u8::from(*color)),
+            Colorant::Gray(color) => Color::AnsiValue(u8::from(*color)),
+            Colorant::Rgb(color) => Color::Rgb {
+                r: color[0],
+                g: color[1],
+                b: color[2],
+            },
+            Colorant::HiRes(_) => return Err(ConversionError::HiResColor),
+        })
+    }
+}
+
+impl TryFrom<Colorant> for Color {
+    type Error = ConversionError;
+
+    fn try_from(value: Colorant) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl From<ContentStyle> for Style {
+    /// Convert the crossterm style to a style.
+    ///
+    /// Since prettypretty supports only one kind of underline and blinking,
+    /// this conversion maps crossterm's variations to [`Attribute::Underlined`]
+    /// and [`Attribute::Blinking`], respectively. It ignores crossterm's
+    /// underline color and attributes without prettypretty equivalent, such as
+    /// framed text. Finally, it maps [`CtAttribute::NormalIntensity`] to
+    /// disabling [`Attribute::Bold`], which has the same effect as disabling
+    /// [`Attribute::Thin`].
+    fn from(value: ContentStyle) -> Self {
+        let attributes = value.attributes;
+        let mut enable = Format::default();
+        let mut disable = Format::default();
+
+        for (attribute, on, off) in ATTRIBUTES {
+            if attributes.has(on) {
+                enable = enable + attribute;
+            }
+            // Disabling both bold and thin would cancel out.
+            if attributes.has(off) && attribute != Attribute::Thin {
+                disable = disable + attribute;
+            }
+        }
+        for (alias, attribute) in ALIASES {
+            if attributes.has(alias) {
+                enable = enable + attribute;
+            }
+        }
+
+        Style::from_parts(
+            -disable + enable,
+            value.foreground_color.map(Colorant::from),
+            value.background_color.map(Colorant::from),
+        )
+    }
+}
+
+impl TryFrom<&Style> for ContentStyle {
+    type Error = ConversionError;
+
+    /// Convert the style to a crossterm style.
+    ///
+    /// This conversion fails for styles with high-resolution colors.
+    fn try_from(value: &Style) -> Result<Self, Self::Error> {
+        let format = value.format();
+        let mut attributes = Attributes::default();
+
+        for (attribute, on, off) in ATTRIBUTES {
+            if format.enable().attributes().any(|a| a == attribute) {
+                attributes.set(on);
+            }
+            if format.disable().attributes().any(|a| a == attribute) {
+                attributes.set(off);
+            }
+        }
+
+        Ok(ContentStyle {
+            foreground_color: value.foreground().map(TryInto::try_into).transpose()?,
+            background_color: value.background().map(TryInto::try_into).transpose()?,
+            underline_color: None,
+            attributes,
+        })
+    }
+}
+
+impl TryFrom<Style> for ContentStyle {
+    type Error = ConversionError;
+
+    fn try_from(value: Style) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use ::crossterm::style::{Attribute as CtAttribute, Attributes, Color, ContentStyle};
+
+    use crate::error::ConversionError;
+    use crate::style::Style;
+    use crate::termco::{AnsiColor, Colorant, Rgb};
+    use crate::Color as HiResColor;
+
+    #[test]
+    fn test_crossterm() -> Result<(), ConversionError> {
+        let style = Style::default()
+            .bold()
+            .italic()
+            .with_foreground(AnsiColor::Red)
+            .with_background(Rgb::new(1, 2, 3));
+        let other = ContentStyle {
+            foreground_color: Some(Color::DarkRed),
+            background_color: Some(Color::Rgb { r: 1, g: 2, b: 3 }),
+            underline_color: None,
+            attributes: Attributes::from([CtAttribute::Bold, CtAttribute::Italic].as_slice()),
+        };
+
+        assert_eq!(ContentStyle::try_from(&style)?, other);
+        assert_eq!(Style::from(other), style);
+
+        let undo = ContentStyle {
+            foreground_color: Some(Color::Reset),
+            background_color: Some(Color::Reset),
+            underline_color: None,
+            attributes: Attributes::from(
+                [CtAttribute::NormalIntensity, CtAttribute::NoItalic].as_slice(),
+            ),
+        };
+        assert_eq!(ContentStyle::try_from(-&style)?, undo);
+        assert_eq!(Style::from(undo), -&style);
+
+        assert_eq!(Colorant::from(Color::AnsiValue(244)), Colorant::from(244));
+        assert_eq!(
+            Color::try_from(Colorant::HiRes(HiResColor::default())),
+            Err(ConversionError::HiResColor)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,314 @@
+use std::fmt::Write;
+
+use super::{Attribute, Layer, Style};
+use crate::termco::Colorant;
+use crate::Translator;
+
+/// The presentation of a style independent of output format.
+struct Presentation {
+    foreground: Option<String>,
+    background: Option<String>,
+    bold: bool,
+    thin: bool,
+    italic: bool,
+    underlined: bool,
+    stricken: bool,
+    hidden: bool,
+}
+
+impl Presentation {
+    fn new(style: &Style, translator: &Translator) -> Self {
+        let has = |attribute| style.format().enable().attributes().any(|a| a == attribute);
+        let color = |colorant: Option<&Colorant>, layer| {
+            colorant
+                .filter(|c| !c.is_default())
+                .map(|c| translator.resolve_all(c.clone(), layer).to_hex_format())
+        };
+
+        let (foreground, background) = if has(Attribute::Reversed) {
+            // Reversed video requires concrete colors, even for the defaults.
+            let resolve = |colorant: Option<&Colorant>, layer| {
+                translator
+                    .resolve_all(colorant.cloned().unwrap_or(Colorant::Default()), layer)
+                    .to_hex_format()
+            };
+            (
+                Some(resolve(style.background(), Layer::Background)),
+                Some(resolve(style.foreground(), Layer::Foreground)),
+            )
+        } else {
+            (
+                color(style.foreground(), Layer::Foreground),
+                color(style.background(), Layer::Background),
+            )
+        };
+
+        Self {
+            foreground,
+            background,
+            bold: has(Attribute::Bold),
+            thin: has(Attribute::Thin),
+            italic: has(Attribute::Italic),
+            underlined: has(Attribute::Underlined),
+            stricken: has(Attribute::Stricken),
+            hidden: has(Attribute::Hidden),
+        }
+    }
+
+    fn decoration(&self) -> Option<&'static str> {
+        match (self.underlined, self.stricken) {
+            (true, true) => Some("underline line-through"),
+            (true, false) => Some("underline"),
+            (false, true) => Some("line-through"),
+            (false, false) => None,
+        }
+    }
+
+    fn to_css(&self) -> String {
+        let mut css = String::new();
+        if let Some(color) = &self.foreground {
+            let _ = write!(css, "color:{};", color);
+        }
+        if let Some(color) = &self.background {
+            let _ = write!(css, "background-color:{};", color);
+        }
+        if self.bold {
+            css.push_str("font-weight:bold;");
+        }
+        if self.thin {
+            css.push_str("opacity:0.5;");
+        }
+        if self.italic {
+            css.push_str("font-style:italic;");
+        }
+        if let Some(decoration) = self.decoration() {
+            let _ = write!(css, "text-decoration:{};", decoration);
+        }
+        if self.hidden {
+            css.push_str("visibility:hidden;");
+        }
+        css.pop();
+        css
+    }
+
+    fn to_svg_attributes(&self) -> String {
+        let mut attributes = String::new();
+        if let Some(color) = &self.foreground {
+            let _ = write!(attributes, r#" fill="{}""#, color);
+        }
+        if self.bold {
+            attributes.push_str(r#" font-weight="bold""#);
+        }
+        if self.thin {
+            attributes.push_str(r#" opacity="0.5""#);
+        }
+        if self.italic {
+            attributes.push_str(r#" font-style="italic""#);
+        }
+        if let Some(decoration) = self.decoration() {
+            let _ = write!(attributes, r#" text-decoration="{}""#, decoration);
+        }
+        if self.hidden {
+            attributes.push_str(r#" visibility="hidden""#);
+        }
+        attributes
+    }
+}
+
+/// Escape the text for inclusion in HTML or SVG.
+fn escape(text: &str, output: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Render styled text as HTML.
+///
+/// This function renders each span of styled text as an HTML `<span>` element
+/// with an inline `style` attribute. It resolves ANSI colors with the
+/// translator's theme and renders all colors, including 24-bit colors, as
+/// hashed hexadecimal colors. Unstyled text and default colors inherit their
+/// appearance from the surrounding HTML, with the exception of reversed
+/// text, which uses the theme's default colors. Since browsers do not support
+/// blinking text, this function ignores [`Attribute::Blinking`].
+///
+/// The spans may come from [`parse_sgr`](crate::style::parse_sgr), which makes
+/// it easy to convert captured terminal output. Since the result does not
+/// include a wrapping element, it can be embedded in a `<pre>` element or
+/// another element with `white-space: pre`.
+///
+/// ```
+/// # use prettypretty::{OkVersion, Translator};
+/// # use prettypretty::style::{parse_sgr, to_html};
+/// # use prettypretty::theme::VGA_COLORS;
+/// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+/// let html = to_html(parse_sgr("\x1b[1;31m<error>\x1b[m ok"), &translator);
+/// assert_eq!(
+///     html,
+///     "<span style=\"color:#aa0000;font-weight:bold\">&lt;error&gt;</span> ok"
+/// );
+/// ```
+pub fn to_html<'a>(
+    spans: impl IntoIterator<Item = (Style, &'a str)>,
+    translator: &Translator,
+) -> String {
+    let mut html = String::new();
+
+    for (style, text) in spans {
+        let css = Presentation::new(&style, translator).to_css();
+        if css.is_empty() {
+            escape(text, &mut html);
+        } else {
+            let _ = write!(html, r#"<span style="{}">"#, css);
+            escape(text, &mut html);
+            html.push_str("</span>");
+        }
+    }
+
+    html
+}
+
+// SVG layout in user units: A 10-unit font in cells 6 units wide and 12 units
+// high, with the baseline 9 units below a cell's top edge.
+const FONT_SIZE: usize = 10;
+const CELL_WIDTH: usize = 6;
+const CELL_HEIGHT: usize = 12;
+const BASELINE: usize = 9;
+
+/// Render styled text as SVG.
+///
+/// This function renders styled text as a standalone SVG document with one
+/// `<tspan>` element per span and line. Since SVG does not support
+/// backgrounds for text, it renders background colors as `<rect>` elements
+/// behind the text. To keep the two aligned, it lays out text on a grid of
+/// terminal cells, using [`width`](crate::style::width) for determining the
+/// number of columns of each span. As for [`to_html`], unstyled text and
+/// default colors inherit their appearance, with the exception of reversed
+/// text, and blinking text is ignored.
+///
+/// ```
+/// # use prettypretty::{OkVersion, Translator};
+/// # use prettypretty::style::{to_svg, Style};
+/// # use prettypretty::termco::Rgb;
+/// # use prettypretty::theme::VGA_COLORS;
+/// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+/// let style = Style::default().with_background(Rgb::new(255, 128, 0));
+/// let svg = to_svg([(style, "hi")], &translator);
+/// assert!(svg.contains(r##"<rect x="0" y="0" width="12" height="12" fill="#ff8000"/>"##));
+/// assert!(svg.contains(r#"<tspan x="0" y="9">hi</tspan>"#));
+/// ```
+pub fn to_svg<'a>(
+    spans: impl IntoIterator<Item = (Style, &'a str)>,
+    translator: &Translator,
+) -> String {
+    let mut rects = String::new();
+    let mut text = String::new();
+    let (mut line, mut column, mut columns) = (0, 0, 0);
+
+    for (style, content) in spans {
+        let presentation = Presentation::new(&style, translator);
+        let attributes = presentation.to_svg_attributes();
+
+        for (index, fragment) in content.split('\n').enumerate() {
+            if 0 < index {
+                line += 1;
+                column = 0;
+            }
+            if fragment.is_empty() {
+                continue;
+            }
+
+            let width = super::width(fragment);
+            let (x, y) = (column * CELL_WIDTH, line * CELL_HEIGHT);
+            if let Some(color) = &presentation.background {
+                let _ = writeln!(
+                    rects,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    x,
+                    y,
+                    width * CELL_WIDTH,
+                    CELL_HEIGHT,
+                    color
+                );
+            }
+
+            let _ = write!(
+                text,
+                r#"<tspan x="{}" y="{}"{}>"#,
+                x,
+                y + BASELINE,
+                attributes
+            );
+            escape(fragment, &mut text);
+            text.push_str("</tspan>");
+
+            column += width;
+            columns = columns.max(column);
+        }
+    }
+
+    let (width, height) = (columns * CELL_WIDTH, (line + 1) * CELL_HEIGHT);
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" "#,
+            r#"viewBox="0 0 {0} {1}" font-family="monospace" font-size="{2}">"#,
+            "\n{3}<text xml:space=\"preserve\">{4}</text>\n</svg>\n"
+        ),
+        width, height, FONT_SIZE, rects, text
+    )
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{to_html, to_svg};
+    use crate::style::{parse_sgr, Style};
+    use crate::termco::{AnsiColor, Rgb};
+    use crate::theme::VGA_COLORS;
+    use crate::{OkVersion, Translator};
+
+    #[test]
+    fn test_export() {
+        let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+
+        let html = to_html(
+            parse_sgr("a\x1b[3;4;38;2;1;2;3mb\x1b[7;24mc\x1b[m"),
+            &translator,
+        );
+        assert_eq!(
+            html,
+            concat!(
+                "a<span style=\"color:#010203;font-style:italic;text-decoration:underline\">",
+                "b</span><span style=\"color:#ffffff;background-color:#010203;",
+                "font-style:italic\">c</span>"
+            )
+        );
+
+        let style = Style::default()
+            .bold()
+            .with_foreground(AnsiColor::Blue)
+            .with_background(Rgb::new(0, 0, 0));
+        let svg = to_svg([(style, "x\ny<z"), (Style::default(), "!")], &translator);
+        assert_eq!(
+            svg,
+            concat!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\" height=\"24\" ",
+                "viewBox=\"0 0 24 24\" font-family=\"monospace\" font-size=\"10\">\n",
+                "<rect x=\"0\" y=\"0\" width=\"6\" height=\"12\" fill=\"#000000\"/>\n",
+                "<rect x=\"0\" y=\"12\" width=\"18\" height=\"12\" fill=\"#000000\"/>\n",
+                "<text xml:space=\"preserve\">",
+                "<tspan x=\"0\" y=\"9\" fill=\"#0000aa\" font-weight=\"bold\">x</tspan>",
+                "<tspan x=\"0\" y=\"21\" fill=\"#0000aa\" font-weight=\"bold\">y&lt;z</tspan>",
+                "<tspan x=\"18\" y=\"21\">!</tspan></text>\n</svg>\n"
+            )
+        );
+    }
+}
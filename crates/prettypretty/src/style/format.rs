@@ -362,6 +362,19 @@ impl FormatUpdate {
 
         Self { disable, enable }
     }
+
+    /// Create a new format update from the bits of disabled and enabled
+    /// attributes.
+    #[cfg(feature = "pyffi")]
+    pub(crate) const fn from_bits(disable: u8, enable: u8) -> Self {
+        Self::new(Format::new(disable), Format::new(enable))
+    }
+
+    /// Get the bits of disabled and enabled attributes.
+    #[cfg(feature = "pyffi")]
+    pub(crate) const fn bits(&self) -> (u8, u8) {
+        (self.disable.bits(), self.enable.bits())
+    }
 }
 
 #[cfg_attr(feature = "pyffi", pymethods)]
@@ -0,0 +1,69 @@
+use super::width::clusters;
+use super::{Fidelity, Layer, Style, Styled, StyledText};
+use crate::{Float, Interpolator, Translator};
+
+/// Apply a color gradient across the text.
+///
+/// This function splits the text into grapheme clusters, or characters
+/// without the `unicode` feature, and styles each
+/// cluster with the color that the interpolator computes for the cluster's
+/// position within the text, with the first cluster at fraction 0.0 and the
+/// last cluster at fraction 1.0. The layer determines whether the gradient
+/// colors the foreground or the background. The base style is merged with
+/// each cluster's color and thus contributes text attributes and the color
+/// for the other layer. Finally, each style is capped to the given fidelity.
+///
+/// Since [`StyledText`] only writes necessary style changes, clusters that end
+/// up with the same color after capping do not incur extra escape sequences.
+///
+/// ```
+/// # use prettypretty::{Color, ColorSpace, HueInterpolation, OkVersion, Translator};
+/// # use prettypretty::style::{gradient, Fidelity, Layer, Style};
+/// # use prettypretty::theme::VGA_COLORS;
+/// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+/// let interpolator = Color::from_24bit(255, 0, 0).interpolate(
+///     &Color::from_24bit(0, 0, 255),
+///     ColorSpace::Oklrch,
+///     HueInterpolation::Shorter,
+/// );
+///
+/// let text = gradient(
+///     "abc",
+///     &Style::default(),
+///     &interpolator,
+///     Layer::Foreground,
+///     Fidelity::TwentyFourBit,
+///     &translator,
+/// );
+/// assert_eq!(text.len(), 3);
+/// assert_eq!(
+///     text.to_string(),
+///     "\x1b[38;2;255;0;0ma\x1b[38;2;183;0;190mb\x1b[38;2;0;0;255mc\x1b[39m"
+/// );
+/// ```
+pub fn gradient(
+    text: &str,
+    base: &Style,
+    interpolator: &Interpolator,
+    layer: Layer,
+    fidelity: Fidelity,
+    translator: &Translator,
+) -> StyledText {
+    let clusters = clusters(text)
+        .map(|(_, cluster)| cluster)
+        .collect::<Vec<_>>();
+    let last = clusters.len().saturating_sub(1).max(1) as Float;
+
+    clusters
+        .into_iter()
+        .enumerate()
+        .map(|(index, cluster)| {
+            let color = interpolator.at(index as Float / last);
+            let style = match layer {
+                Layer::Foreground => base.with_foreground(color),
+                Layer::Background => base.with_background(color),
+            };
+            Styled::new(style.cap(fidelity, translator), cluster)
+        })
+        .collect()
+}
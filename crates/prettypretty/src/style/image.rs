@@ -0,0 +1,368 @@
+use super::{Fidelity, Style, Styled, StyledText};
+use crate::{Color, ColorSpace, Float, Translator};
+
+/// The quadrant characters indexed by bit mask, with bit 0 for the upper-left,
+/// bit 1 for the upper-right, bit 2 for the lower-left, and bit 3 for the
+/// lower-right quadrant.
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// Determine whether the color is light, i.e., has more than 50% lightness.
+fn is_light(color: &Color) -> bool {
+    0.5 < color.to(ColorSpace::Oklab).as_ref()[0]
+}
+
+/// Render the pixels with upper and lower half blocks.
+///
+/// This function treats the pixels as a row-major buffer `width` pixels wide,
+/// with the last row possibly being shorter, and renders two rows of pixels
+/// per line of text. Each cell displays the upper pixel with the foreground
+/// color of an upper half block `▀` and the lower pixel with the background
+/// color. It downsamples colors with [`Translator::dither`] and hence avoids
+/// banding even for [`Fidelity::Ansi`]. For [`Fidelity::Plain`] and
+/// [`Fidelity::NoColor`], it falls back on the default colors, using `▀`, `▄`,
+/// `█`, and space to display light pixels.
+///
+/// The result has one [`StyledText`] per line of text, which does not include
+/// any line terminator. Since styled text implements `Display`, it can be
+/// written directly to a terminal's output.
+///
+/// ```
+/// # use prettypretty::{Color, OkVersion, Translator};
+/// # use prettypretty::style::{half_blocks, Fidelity};
+/// # use prettypretty::theme::VGA_COLORS;
+/// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+/// let red = Color::from_24bit(255, 0, 0);
+/// let blue = Color::from_24bit(0, 0, 255);
+///
+/// let lines = half_blocks(&[red, blue], 1, Fidelity::TwentyFourBit, &translator);
+/// assert_eq!(lines.len(), 1);
+/// assert_eq!(
+///     lines[0].to_string(),
+///     "\x1b[38;2;255;0;0;48;2;0;0;255m▀\x1b[39;49m"
+/// );
+/// ```
+pub fn half_blocks(
+    pixels: &[Color],
+    width: usize,
+    fidelity: Fidelity,
+    translator: &Translator,
+) -> Vec<StyledText> {
+    let width = width.max(1);
+    let colorants = translator.dither(pixels, width, fidelity);
+
+    let mut lines = Vec::new();
+    for top in (0..pixels.len()).step_by(2 * width) {
+        let mut line = StyledText::new();
+
+        for upper in top..(top + width).min(pixels.len()) {
+            let lower = upper + width;
+            let has_lower = lower < pixels.len();
+
+            let styled = if let Some(ref colorants) = colorants {
+                let mut style = Style::default().with_foreground(colorants[upper].clone());
+                if has_lower {
+                    style = style.with_background(colorants[lower].clone());
+                }
+                Styled::new(style, '▀')
+            } else {
+                let lower = has_lower && is_light(&pixels[lower]);
+                let c = match (is_light(&pixels[upper]), lower) {
+                    (false, false) => ' ',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (true, true) => '█',
+                };
+                Styled::new(Style::default(), c)
+            };
+
+            line.push(styled);
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Render the pixels with quadrant characters.
+///
+/// Like [`half_blocks`], this function treats the pixels as a row-major buffer
+/// `width` pixels wide. But it renders 2×2 pixels per cell, thus doubling the
+/// horizontal resolution. Since each cell still has only two colors, this
+/// function partitions the four pixels into the two groups that best fit
+/// their average colors in Oklab and picks the corresponding quadrant
+/// character. The lighter group becomes the foreground, so that the result
+/// remains recognizable with [`Fidelity::Plain`] and [`Fidelity::NoColor`].
+/// This function caps the average colors to the given fidelity without
+/// dithering.
+///
+/// ```
+/// # use prettypretty::{Color, OkVersion, Translator};
+/// # use prettypretty::style::{quadrants, Fidelity};
+/// # use prettypretty::theme::VGA_COLORS;
+/// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+/// let black = Color::from_24bit(0, 0, 0);
+/// let white = Color::from_24bit(255, 255, 255);
+///
+/// let lines = quadrants(
+///     &[white, black.clone(), black.clone(), black],
+///     2,
+///     Fidelity::Plain,
+///     &translator,
+/// );
+/// assert_eq!(lines.len(), 1);
+/// assert_eq!(lines[0].to_string(), "▘");
+/// ```
+pub fn quadrants(
+    pixels: &[Color],
+    width: usize,
+    fidelity: Fidelity,
+    translator: &Translator,
+) -> Vec<StyledText> {
+    let width = width.max(1);
+    let oklab = pixels
+        .iter()
+        .map(|color| *color.to(ColorSpace::Oklab).as_ref())
+        .collect::<Vec<_>>();
+
+    let mut lines = Vec::new();
+    for top in (0..oklab.len()).step_by(2 * width) {
+        let mut line = StyledText::new();
+
+        for left in (top..(top + width).min(oklab.len())).step_by(2) {
+            let right = (left + 1 < top + width).then_some(left + 1);
+            let cell = [
+                Some(left),
+                right,
+                Some(left + width),
+                right.map(|index| index + width),
+            ]
+            .map(|index| index.and_then(|index| oklab.get(index)));
+
+            let (mask, foreground, background) = partition(&cell);
+            let mut style = Style::default();
+            if let Some(color) = foreground {
+                style = style.with_foreground(Color::new(ColorSpace::Oklab, color).to_gamut());
+            }
+            if let Some(color) = background {
+                style = style.with_background(Color::new(ColorSpace::Oklab, color).to_gamut());
+            }
+            line.push(Styled::new(
+                style.cap(fidelity, translator),
+                QUADRANTS[mask as usize],
+            ));
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// The braille dot bits indexed by row and column within a 2×4 cell.
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Render the pixels with braille characters.
+///
+/// This function treats the pixels as a row-major buffer `width` pixels wide,
+/// with the last row possibly being shorter, and renders 2×4 pixels per cell
+/// as a braille character. Pixels that are `None` are blank, whereas pixels
+/// with a color are dots. Since braille characters have only one foreground
+/// color, each cell uses the average color of its dots in Oklab, capped to
+/// the given fidelity. Cells without dots use no style at all. For a
+/// monochrome bitmap, use [`Fidelity::Plain`] and any color for the dots.
+///
+/// With eight times the resolution of regular text, braille characters are
+/// well-suited to sparklines and plots.
+///
+/// ```
+/// # use prettypretty::{Color, OkVersion, Translator};
+/// # use prettypretty::style::{braille, Fidelity};
+/// # use prettypretty::theme::VGA_COLORS;
+/// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+/// let dot = Some(Color::from_24bit(255, 0, 0));
+///
+/// let lines = braille(
+///     &[dot.clone(), None, None, dot.clone(), dot, None],
+///     2,
+///     Fidelity::TwentyFourBit,
+///     &translator,
+/// );
+/// assert_eq!(lines.len(), 1);
+/// assert_eq!(lines[0].to_string(), "\x1b[38;2;255;0;0m⠕\x1b[39m");
+/// ```
+pub fn braille(
+    pixels: &[Option<Color>],
+    width: usize,
+    fidelity: Fidelity,
+    translator: &Translator,
+) -> Vec<StyledText> {
+    let width = width.max(1);
+
+    let mut lines = Vec::new();
+    for top in (0..pixels.len()).step_by(4 * width) {
+        let mut line = StyledText::new();
+
+        for left in (top..(top + width).min(pixels.len())).step_by(2) {
+            let mut bits = 0;
+            let mut sum = [0.0; 3];
+            let mut count = 0;
+
+            for (row, dots) in BRAILLE_DOTS.iter().enumerate() {
+                for (column, dot) in dots.iter().enumerate() {
+                    if top + width <= left + column {
+                        continue;
+                    }
+
+                    let Some(Some(color)) = pixels.get(left + column + row * width) else {
+                        continue;
+                    };
+
+                    bits |= dot;
+                    let color = color.to(ColorSpace::Oklab);
+                    for (s, c) in sum.iter_mut().zip(color.as_ref().iter()) {
+                        *s += c;
+                    }
+                    count += 1;
+                }
+            }
+
+            let style = if 0 < count {
+                let color = Color::new(ColorSpace::Oklab, sum.map(|s| s / count as Float));
+                Style::default()
+                    .with_foreground(color.to_gamut())
+                    .cap(fidelity, translator)
+            } else {
+                Style::default()
+            };
+
+            // Unwrap is safe b/c all braille patterns are valid characters.
+            line.push(Styled::new(style, char::from_u32(0x2800 + bits).unwrap()));
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Partition the cell's pixels into two groups.
+///
+/// This function returns the bit mask for the foreground group as well as the
+/// average colors of the foreground and background groups. Missing pixels
+/// belong to neither group.
+fn partition(cell: &[Option<&[Float; 3]>; 4]) -> (u8, Option<[Float; 3]>, Option<[Float; 3]>) {
+    let present = cell
+        .iter()
+        .enumerate()
+        .filter(|(_, pixel)| pixel.is_some())
+        .fold(0u8, |mask, (index, _)| mask | (1 << index));
+
+    let average = |mask: u8| {
+        let mut sum = [0.0; 3];
+        let mut count = 0;
+        for (index, pixel) in cell.iter().enumerate() {
+            if let Some(pixel) = pixel {
+                if mask & (1 << index) != 0 {
+                    for (s, p) in sum.iter_mut().zip(pixel.iter()) {
+                        *s += p;
+                    }
+                    count += 1;
+                }
+            }
+        }
+
+        (0 < count).then(|| sum.map(|s| s / count as Float))
+    };
+
+    let error = |mask: u8, color: Option<[Float; 3]>| {
+        let Some(color) = color else {
+            return 0.0;
+        };
+        cell.iter()
+            .enumerate()
+            .filter(|(index, _)| mask & (1 << index) != 0)
+            .filter_map(|(_, pixel)| *pixel)
+            .map(|pixel| {
+                pixel
+                    .iter()
+                    .zip(color.iter())
+                    .map(|(p, c)| (p - c) * (p - c))
+                    .sum::<Float>()
+            })
+            .sum::<Float>()
+    };
+
+    let mut best = (present, average(present), None);
+    let mut best_error = error(present, best.1);
+    for mask in 1..present {
+        if mask & !present != 0 {
+            continue;
+        }
+
+        let rest = present & !mask;
+        let (fg, bg) = (average(mask), average(rest));
+        let total = error(mask, fg) + error(rest, bg);
+        if total < best_error {
+            best = (mask, fg, bg);
+            best_error = total;
+        }
+    }
+
+    // Make the lighter group the foreground. A uniformly dark cell becomes
+    // the background.
+    let (mask, fg, bg) = best;
+    match (fg, bg) {
+        (Some(f), Some(b)) if f[0] < b[0] => (present & !mask, bg, fg),
+        (Some(f), None) if present == 0b1111 && f[0] <= 0.5 => (0, None, fg),
+        _ => (mask, fg, bg),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{braille, half_blocks, partition};
+    use crate::style::Fidelity;
+    use crate::theme::VGA_COLORS;
+    use crate::{Color, OkVersion, Translator};
+
+    #[test]
+    fn test_images() {
+        let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+        let black = Color::from_24bit(0, 0, 0);
+        let white = Color::from_24bit(255, 255, 255);
+
+        let pixels = [white.clone(), black.clone(), black.clone(), white, black];
+        let lines = half_blocks(&pixels, 2, Fidelity::NoColor, &translator);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].to_string(), "▀▄");
+        assert_eq!(lines[1].to_string(), " ");
+
+        let lines = half_blocks(&pixels, 2, Fidelity::Ansi, &translator);
+        assert_eq!(lines[1].to_string(), "\x1b[30m▀\x1b[39m");
+
+        let dot = Some(Color::from_24bit(0, 0, 0));
+        let dots = [dot.clone(), dot.clone(), None, None, None, None, None, dot];
+        let lines = braille(&dots, 3, Fidelity::Plain, &translator);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].to_string(), "⠩⠀");
+
+        let (light, dark) = ([1.0, 0.0, 0.0], [0.0, 0.0, 0.0]);
+        let (mask, fg, bg) = partition(&[Some(&dark), Some(&light), Some(&light), None]);
+        assert_eq!(mask, 0b0110);
+        assert_eq!(fg, Some(light));
+        assert_eq!(bg, Some(dark));
+
+        let (mask, fg, bg) = partition(&[Some(&dark); 4]);
+        assert_eq!(mask, 0);
+        assert_eq!(fg, None);
+        assert_eq!(bg, Some(dark));
+
+        let (mask, fg, bg) = partition(&[Some(&dark), None, None, None]);
+        assert_eq!(mask, 0b0001);
+        assert_eq!(fg, Some(dark));
+        assert_eq!(bg, None);
+    }
+}
@@ -9,9 +9,57 @@
 //! [`Colorant`](crate::termco::Colorant) and an optional background
 //! [`Colorant`](crate::termco::Colorant).
 //!
+//! **`Styled`**, **`StyledStr`**, and **`StyledText`**: [`Style::paint`] and
+//! [`Style::apply`] wrap content with a style as [`Styled`] content, whose
+//! display also restores the default appearance. Adding styled content
+//! produces [`StyledText`]. [`gradient`] styles each grapheme cluster of a
+//! string with its own color along a color gradient. [`half_blocks`] and
+//! [`quadrants`] render images with block characters, whereas [`braille`]
+//! renders bitmaps with braille characters.
+//!
+//! **`StyleDelta`**: [`Style::transition`] computes the minimal [`StyleDelta`]
+//! for changing from one style to the next, including colors.
+//!
+//! **`StyleWriter`**: A [`StyleWriter`] wraps another writer, tracks the active
+//! style, only writes necessary style changes, optionally caps styles to a
+//! fidelity, and restores the default appearance when dropped.
+//!
+//! **`SgrBuffer`**: [`Style::write_to`] and [`StyleDelta::write_to`] write
+//! escape sequences to any [`std::fmt::Write`]. Paired with the fixed-size
+//! [`SgrBuffer`], they render styles in hot loops without allocating memory.
+//! For styles known at compile time, the
+//! [`static_style!`](crate::static_style) macro instead bakes the escape
+//! sequences into a [`StaticStyle`].
+//!
+//! **`StyleSheet`**: A [`StyleSheet`] maps semantic names to styles with
+//! variants for light and dark themes and caps all of them in one pass.
+//! [`Role`]s derive contrasting styles for errors, warnings, and so on from a
+//! terminal's color theme.
+//!
+//! **`parse_sgr`**: Going in the opposite direction, [`parse_sgr`] turns text
+//! with SGR escape sequences back into spans of text and their [`Style`]s.
+//! [`strip`] and [`StripWriter`] remove all escape sequences from text.
+//!
+//! **`to_html`** and **`to_svg`**: These functions render spans of styled
+//! text, e.g., as produced by [`parse_sgr`], as HTML or SVG, which helps with
+//! documenting and sharing terminal output on the web.
+//!
+//! **`width`**, **`truncate`**, **`pad`**, and **`fit`**: These functions
+//! measure and lay out text with escape sequences in terminal columns, while
+//! keeping SGR escape sequences balanced.
+//!
 //! **`Fidelity`** and **`Layer`**: It also defines [`Layer`] to distinguish between
 //! foreground and background colors as well as [`Fidelity`] to capture a
-//! terminal's level of color support.
+//! terminal's level of color support. A [`FidelityReport`] explains how
+//! [`Fidelity::report_environment`] arrived at a fidelity level.
+//!
+//! **anstyle**: With the `anstyle` feature enabled, [`Style`] and
+//! [`Colorant`](crate::termco::Colorant) convert to and from the
+//! corresponding types of the [anstyle](https://crates.io/crates/anstyle)
+//! crate, which is widely used by command line tools, including clap.
+//! Similarly, with the `crossterm` feature enabled, they convert to and from
+//! the [crossterm](https://crates.io/crates/crossterm) crate's content styles
+//! and colors.
 //!
 //! **`Attribute`**, **`Format`**, and **`FormatUpdate`**: A [`FormatUpdate`]
 //! comprises a disabling [`Format`] and an enabling [`Format`]. Each
@@ -129,12 +177,57 @@
 //!     src="https://raw.githubusercontent.com/apparebit/prettypretty/main/docs/figures/wow.png"
 //!     alt="wow!" width="44">
 //!
+//! Alternatively, [`Style::paint`] combines style, text, and negation into one
+//! [`StyledStr`], which can't be left unbalanced:
+//! ```
+//! # use prettypretty::{OkVersion, Translator};
+//! # use prettypretty::style::{Fidelity, Style};
+//! # use prettypretty::termco::{AnsiColor, Colorant, Rgb};
+//! # use prettypretty::theme::VGA_COLORS;
+//! # let style = Style::default()
+//! #     .bold()
+//! #     .with_foreground(Rgb::new(215, 40, 39))
+//! #     .underlined();
+//! # let translator = Translator::new(
+//! #     OkVersion::Revised, VGA_COLORS.clone());
+//! # let style = style.cap(Fidelity::Ansi, &translator);
+//! let s = format!("{}", style.paint("Wow!"));
+//!
+//! assert_eq!(s, "\x1b[1;4;31mWow!\x1b[22;24;39m");
+//! ```
+//!
 //! <hr>
 
+mod ansi;
+#[cfg(feature = "anstyle")]
+mod anstyle;
+mod buffer;
+mod constant;
 mod context;
+#[cfg(feature = "crossterm")]
+mod crossterm;
+mod export;
 mod format;
+mod gradient;
+mod image;
+mod role;
+mod sheet;
+mod styled;
 mod styling;
+mod width;
+mod writer;
 
-pub use context::{Fidelity, Layer};
+pub use ansi::{parse_sgr, strip, SgrSpans, StripWriter};
+pub use buffer::SgrBuffer;
+pub use constant::StaticStyle;
+pub use context::{Fidelity, FidelityReport, Layer};
+pub use export::{to_html, to_svg};
 pub use format::{Attribute, AttributeIter, Format, FormatUpdate};
-pub use styling::Style;
+pub use gradient::gradient;
+pub use image::{braille, half_blocks, quadrants};
+pub use role::Role;
+pub use sheet::StyleSheet;
+pub use styled::{Styled, StyledStr, StyledText};
+pub use styling::{Style, StyleDelta};
+pub use width::{fit, pad, truncate, width};
+pub use writer::StyleWriter;
@@ -0,0 +1,247 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use super::{Layer, Style, StyleSheet};
+use crate::termco::AnsiColor;
+use crate::theme::Theme;
+use crate::{Color, ColorSpace, Float};
+
+/// A semantic role for styled output.
+///
+/// Roles capture the purpose of styled output, such as signalling an error or
+/// a success, independent of concrete colors. [`Role::color`] derives a
+/// concrete color from a terminal's color [`Theme`] by picking the theme's
+/// color for the role's [`Role::ansi_color`] and then adjusting its lightness
+/// until it has at least [`Role::MIN_CONTRAST`] against the theme's default
+/// background color. That is a best effort: Against a background of medium
+/// lightness, no color may reach the minimum contrast. In that case, the role's
+/// color is black or white, whichever has the higher contrast.
+///
+/// ```
+/// # use prettypretty::style::Role;
+/// # use prettypretty::theme::VGA_COLORS;
+/// let color = Role::Warning.color(&VGA_COLORS);
+/// let background = &VGA_COLORS[prettypretty::style::Layer::Background];
+/// assert!(Role::MIN_CONTRAST <= color.contrast_against(background).abs());
+/// ```
+#[cfg_attr(
+    feature = "pyffi",
+    pyclass(eq, eq_int, frozen, hash, module = "prettypretty.color.style")
+)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Something went wrong.
+    Error,
+    /// Something may go wrong.
+    Warning,
+    /// Something went right.
+    Success,
+    /// Something worth knowing.
+    Info,
+    /// Something worth highlighting.
+    Accent,
+}
+
+impl Role {
+    /// The minimum perceptual contrast of a role's color against the
+    /// background.
+    ///
+    /// The value is the absolute contrast computed by
+    /// [`Color::contrast_against`] and corresponds to an APCA contrast of 60,
+    /// the minimum for larger, non-body text.
+    pub const MIN_CONTRAST: Float = 0.6;
+
+    /// Get all roles.
+    pub fn all() -> impl Iterator<Item = Role> {
+        [
+            Self::Error,
+            Self::Warning,
+            Self::Success,
+            Self::Info,
+            Self::Accent,
+        ]
+        .into_iter()
+    }
+
+    /// Derive the style for this role from the theme.
+    ///
+    /// The style uses [`Role::color`] as foreground color. Since that color
+    /// is a high-resolution color, the style typically needs to be capped with
+    /// [`Style::cap`] before display.
+    pub fn style(&self, theme: &Theme) -> Style {
+        Style::default().with_foreground(self.color(theme))
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl Role {
+    /// Get the name of this role, which also serves as its name in a
+    /// [`StyleSheet`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Success => "success",
+            Self::Info => "info",
+            Self::Accent => "accent",
+        }
+    }
+
+    /// Get the ANSI color for this role.
+    pub fn ansi_color(&self) -> AnsiColor {
+        match self {
+            Self::Error => AnsiColor::Red,
+            Self::Warning => AnsiColor::Yellow,
+            Self::Success => AnsiColor::Green,
+            Self::Info => AnsiColor::Cyan,
+            Self::Accent => AnsiColor::Magenta,
+        }
+    }
+
+    /// Derive the color for this role from the theme.
+    pub fn color(&self, theme: &Theme) -> Color {
+        adjust_contrast(&theme[self.ansi_color()], &theme[Layer::Background])
+    }
+
+    /// Derive the style for this role from the theme. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "style")]
+    pub fn py_style(&self, theme: &Theme) -> Style {
+        self.style(theme)
+    }
+
+    /// Get this role's debug representation. <i class=python-only>Python
+    /// only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+/// Adjust the lightness of the color until it has at least the minimum
+/// contrast against the background.
+///
+/// If the color does not reach the minimum contrast, this function falls back
+/// on black or white, whichever has the higher contrast.
+fn adjust_contrast(color: &Color, background: &Color) -> Color {
+    let is_dark = !background.use_black_text();
+    let mut color = color.to(ColorSpace::Srgb).to_gamut();
+
+    // Lightness is bounded, so a few steps suffice to reach black or white.
+    for _ in 0..30 {
+        if Role::MIN_CONTRAST <= color.contrast_against(background).abs() {
+            return color;
+        }
+
+        color = if is_dark {
+            color.lighten(1.1)
+        } else {
+            color.darken(1.1)
+        }
+        .to(ColorSpace::Srgb)
+        .to_gamut();
+    }
+
+    let black = Color::srgb(0.0, 0.0, 0.0);
+    let white = Color::srgb(1.0, 1.0, 1.0);
+    if black.contrast_against(background).abs() < white.contrast_against(background).abs() {
+        white
+    } else {
+        black
+    }
+}
+
+impl StyleSheet {
+    /// Insert the styles for all [`Role`]s derived from the theme.
+    ///
+    /// This method inserts each role under its [`Role::name`]. The variant
+    /// matching the theme's appearance adjusts the role's color against the
+    /// theme's default background color. The other variant adjusts it against
+    /// the theme's default foreground color, which stands in for the
+    /// background color of a theme with the opposite appearance.
+    ///
+    /// ```
+    /// # use prettypretty::style::{Role, StyleSheet};
+    /// # use prettypretty::theme::VGA_COLORS;
+    /// let mut sheet = StyleSheet::new();
+    /// sheet.insert_roles(&VGA_COLORS);
+    /// assert_eq!(sheet.len(), 5);
+    /// assert_eq!(
+    ///     sheet.get("error", false),
+    ///     Some(&Role::Error.style(&VGA_COLORS))
+    /// );
+    /// ```
+    pub fn insert_roles(&mut self, theme: &Theme) {
+        let foreground = &theme[Layer::Foreground];
+        let background = &theme[Layer::Background];
+        let is_dark = !background.use_black_text();
+
+        for role in Role::all() {
+            let color = &theme[role.ansi_color()];
+            let same = Style::default().with_foreground(adjust_contrast(color, background));
+            let other = Style::default().with_foreground(adjust_contrast(color, foreground));
+
+            if is_dark {
+                self.insert_variants(role.name(), other, same);
+            } else {
+                self.insert_variants(role.name(), same, other);
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{adjust_contrast, Role};
+    use crate::style::{Layer, StyleSheet};
+    use crate::theme::{ThemeEntry, VGA_COLORS};
+    use crate::Color;
+
+    #[test]
+    fn test_roles() {
+        let mut dark = VGA_COLORS.clone();
+        dark[ThemeEntry::DefaultForeground()] = VGA_COLORS[Layer::Background].clone();
+        dark[ThemeEntry::DefaultBackground()] = VGA_COLORS[Layer::Foreground].clone();
+
+        let mut sheet = StyleSheet::new();
+        sheet.insert_roles(&dark);
+
+        for role in Role::all() {
+            for theme in [&VGA_COLORS, &dark] {
+                let color = role.color(theme);
+                let contrast = color.contrast_against(&theme[Layer::Background]);
+                assert!(Role::MIN_CONTRAST <= contrast.abs(), "{:?}", role);
+            }
+
+            assert_eq!(sheet.get(role.name(), true), Some(&role.style(&dark)));
+            assert_eq!(
+                sheet.get(role.name(), false),
+                Some(&role.style(&VGA_COLORS))
+            );
+        }
+    }
+
+    #[test]
+    fn test_mid_gray() {
+        // Neither black nor white reach the minimum contrast.
+        let background = Color::srgb(0.62, 0.62, 0.62);
+        let black = Color::srgb(0.0, 0.0, 0.0);
+        let white = Color::srgb(1.0, 1.0, 1.0);
+        let best = if black.contrast_against(&background).abs()
+            < white.contrast_against(&background).abs()
+        {
+            white
+        } else {
+            black
+        };
+        assert!(best.contrast_against(&background).abs() < Role::MIN_CONTRAST);
+
+        for role in Role::all() {
+            let color = adjust_contrast(&VGA_COLORS[role.ansi_color()], &background);
+            assert_eq!(color, best, "{:?}", role);
+        }
+    }
+}
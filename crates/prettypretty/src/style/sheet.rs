@@ -0,0 +1,174 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use std::collections::BTreeMap;
+
+use super::{Fidelity, Style};
+use crate::Translator;
+
+/// A style sheet.
+///
+/// A style sheet maps semantic names, such as `"error"`, `"heading"`, or
+/// `"hint"`, to [`Style`]s. Each entry has a variant for light themes and a
+/// variant for dark themes, which may just be the same style. Applications
+/// assemble their style sheet once and then adjust all styles to the
+/// terminal's fidelity level in one pass with [`StyleSheet::cap_all`].
+///
+/// ```
+/// # use prettypretty::{OkVersion, Translator};
+/// # use prettypretty::style::{Fidelity, Style, StyleSheet};
+/// # use prettypretty::termco::{AnsiColor, Colorant, Rgb};
+/// # use prettypretty::theme::VGA_COLORS;
+/// let mut sheet = StyleSheet::new();
+/// sheet.insert("heading", Style::default().bold());
+/// sheet.insert_variants(
+///     "error",
+///     Style::default().with_foreground(Rgb::new(170, 0, 0)),
+///     Style::default().with_foreground(Rgb::new(255, 85, 85)),
+/// );
+///
+/// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+/// let sheet = sheet.cap_all(Fidelity::Ansi, &translator);
+///
+/// assert_eq!(sheet.get("heading", true), Some(&Style::default().bold()));
+/// assert_eq!(
+///     sheet.get("error", false).and_then(|s| s.foreground()),
+///     Some(&Colorant::Ansi(AnsiColor::Red))
+/// );
+/// assert_eq!(
+///     sheet.get("error", true).and_then(|s| s.foreground()),
+///     Some(&Colorant::Ansi(AnsiColor::BrightRed))
+/// );
+/// assert_eq!(sheet.get("hint", false), None);
+/// ```
+#[cfg_attr(feature = "pyffi", pyclass(module = "prettypretty.color.style"))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StyleSheet {
+    entries: BTreeMap<String, [Style; 2]>,
+}
+
+impl StyleSheet {
+    /// Create a new empty style sheet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert the style for both light and dark themes.
+    pub fn insert(&mut self, name: impl Into<String>, style: Style) {
+        self.entries.insert(name.into(), [style.clone(), style]);
+    }
+
+    /// Insert separate styles for light and dark themes.
+    pub fn insert_variants(&mut self, name: impl Into<String>, light: Style, dark: Style) {
+        self.entries.insert(name.into(), [light, dark]);
+    }
+
+    /// Get the style with the given name for a light or dark theme.
+    pub fn get(&self, name: &str, is_dark: bool) -> Option<&Style> {
+        self.entries
+            .get(name)
+            .map(|variants| &variants[is_dark as usize])
+    }
+
+    /// Get an iterator over the names in this style sheet.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl StyleSheet {
+    /// Create a new empty style sheet. <i class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub fn py_new() -> Self {
+        Self::new()
+    }
+
+    /// Insert the style for both light and dark themes. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "insert")]
+    pub fn py_insert(&mut self, name: String, style: Style) {
+        self.insert(name, style)
+    }
+
+    /// Insert separate styles for light and dark themes. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "insert_variants")]
+    pub fn py_insert_variants(&mut self, name: String, light: Style, dark: Style) {
+        self.insert_variants(name, light, dark)
+    }
+
+    /// Get the style with the given name for a light or dark theme. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "get")]
+    pub fn py_get(&self, name: &str, is_dark: bool) -> Option<Style> {
+        self.get(name, is_dark).cloned()
+    }
+
+    /// Get the names in this style sheet. <i class=python-only>Python
+    /// only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "names")]
+    pub fn py_names(&self) -> Vec<String> {
+        self.entries.keys().cloned().collect()
+    }
+
+    /// Determine whether this style sheet has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the number of entries in this style sheet.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Determine whether this style sheet has an entry with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Cap all styles in this style sheet to the given fidelity.
+    pub fn cap_all(&self, fidelity: Fidelity, translator: &Translator) -> Self {
+        let entries = self
+            .entries
+            .iter()
+            .map(|(name, [light, dark])| {
+                (
+                    name.clone(),
+                    [
+                        light.cap(fidelity, translator),
+                        dark.cap(fidelity, translator),
+                    ],
+                )
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Get the number of entries in this style sheet. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __len__(&self) -> usize {
+        self.len()
+    }
+
+    /// Determine whether this style sheet has an entry with the given name. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __contains__(&self, name: &str) -> bool {
+        self.contains(name)
+    }
+
+    /// Get this style sheet's debug representation. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
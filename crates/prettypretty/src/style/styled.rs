@@ -0,0 +1,214 @@
+use super::Style;
+
+/// Content with a terminal style.
+///
+/// Displaying styled content writes the style, the content, and the style's
+/// negation. As a result, the terminal's default appearance is restored after
+/// the content and the escape sequences are always balanced. If the style is
+/// the default style, displaying styled content only writes the content.
+///
+/// [`Style::paint`] and [`Style::apply`] create styled content. Adding styled
+/// content to more styled content creates [`StyledText`].
+///
+/// ```
+/// # use prettypretty::style::Style;
+/// # use prettypretty::termco::AnsiColor;
+/// let bold = Style::default().bold();
+/// let red = Style::default().with_foreground(AnsiColor::Red);
+///
+/// assert_eq!(bold.paint("Wow!").to_string(), "\x1b[1mWow!\x1b[22m");
+///
+/// let text = bold.paint("Wow") + red.apply(665);
+/// assert_eq!(text.to_string(), "\x1b[1mWow\x1b[22;31m665\x1b[39m");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Styled<T> {
+    style: Style,
+    content: T,
+}
+
+/// A styled string slice.
+pub type StyledStr<'a> = Styled<&'a str>;
+
+impl<T> Styled<T> {
+    /// Create new styled content.
+    pub fn new(style: Style, content: T) -> Self {
+        Self { style, content }
+    }
+
+    /// Get the style.
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Get the content.
+    pub fn content(&self) -> &T {
+        &self.content
+    }
+
+    /// Unwrap the style and content.
+    pub fn into_parts(self) -> (Style, T) {
+        (self.style, self.content)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Styled<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.style.is_default() {
+            write!(f, "{}", self.content)
+        } else {
+            write!(f, "{}{}{}", self.style, self.content, -&self.style)
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// A sequence of styled strings.
+///
+/// Styled text is the result of concatenating [`Styled`] content with `+`.
+/// Since the concatenated content may have different types, styled text
+/// renders each span's content into a string. Its display uses
+/// [`Style::transition`] between adjacent spans and hence only emits the SGR
+/// parameters necessary for changing from one span's style to the next. It
+/// still restores the default appearance after the last span.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StyledText {
+    spans: Vec<Styled<String>>,
+}
+
+impl StyledText {
+    /// Create new empty styled text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append the styled content.
+    pub fn push<T: std::fmt::Display>(&mut self, styled: Styled<T>) {
+        let (style, content) = styled.into_parts();
+        self.spans.push(Styled::new(style, content.to_string()));
+    }
+
+    /// Determine whether this styled text has no spans.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Get the number of spans.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Get the spans.
+    pub fn spans(&self) -> &[Styled<String>] {
+        &self.spans
+    }
+}
+
+impl<T: std::fmt::Display> From<Styled<T>> for StyledText {
+    fn from(value: Styled<T>) -> Self {
+        let mut text = Self::new();
+        text.push(value);
+        text
+    }
+}
+
+impl<T: std::fmt::Display> Extend<Styled<T>> for StyledText {
+    fn extend<I: IntoIterator<Item = Styled<T>>>(&mut self, iter: I) {
+        for styled in iter {
+            self.push(styled);
+        }
+    }
+}
+
+impl<T: std::fmt::Display> FromIterator<Styled<T>> for StyledText {
+    fn from_iter<I: IntoIterator<Item = Styled<T>>>(iter: I) -> Self {
+        let mut text = Self::new();
+        text.extend(iter);
+        text
+    }
+}
+
+impl<T: std::fmt::Display, U: std::fmt::Display> std::ops::Add<Styled<U>> for Styled<T> {
+    type Output = StyledText;
+
+    fn add(self, other: Styled<U>) -> Self::Output {
+        let mut text = StyledText::from(self);
+        text.push(other);
+        text
+    }
+}
+
+impl<T: std::fmt::Display> std::ops::Add<Styled<T>> for StyledText {
+    type Output = StyledText;
+
+    fn add(mut self, other: Styled<T>) -> Self::Output {
+        self.push(other);
+        self
+    }
+}
+
+impl std::ops::Add for StyledText {
+    type Output = StyledText;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        self.spans.extend(other.spans);
+        self
+    }
+}
+
+impl<T: std::fmt::Display> std::ops::AddAssign<Styled<T>> for StyledText {
+    fn add_assign(&mut self, other: Styled<T>) {
+        self.push(other);
+    }
+}
+
+impl std::fmt::Display for StyledText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let default = Style::default();
+        let mut current = &default;
+
+        for span in self.spans.iter() {
+            write!(f, "{}{}", current.transition(&span.style), span.content)?;
+            current = &span.style;
+        }
+
+        write!(f, "{}", current.transition(&default))
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{Styled, StyledText};
+    use crate::style::Style;
+    use crate::termco::{AnsiColor, Rgb};
+
+    #[test]
+    fn test_styled() {
+        let plain = Style::default().paint("plain");
+        assert_eq!(plain.to_string(), "plain");
+
+        let chic = Style::default()
+            .italic()
+            .with_background(Rgb::new(255, 0, 0));
+        let styled = chic.paint("chic");
+        assert_eq!(styled.content(), &"chic");
+        assert_eq!(styled.to_string(), "\x1b[3;48;2;255;0;0mchic\x1b[23;49m");
+
+        let mut text = plain + Style::default().with_foreground(AnsiColor::Blue).apply(42);
+        assert_eq!(text.len(), 2);
+        text += styled;
+        assert_eq!(
+            text.to_string(),
+            "plain\x1b[34m42\x1b[3;39;48;2;255;0;0mchic\x1b[23;49m"
+        );
+
+        let text: StyledText = ["a", "b"]
+            .into_iter()
+            .map(|s| Styled::new(Style::default().bold(), s))
+            .collect();
+        assert_eq!(text.to_string(), "\x1b[1mab\x1b[22m");
+    }
+}
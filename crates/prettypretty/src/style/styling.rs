@@ -1,9 +1,10 @@
 #[cfg(feature = "pyffi")]
 use pyo3::prelude::*;
 
-use super::{Attribute, Fidelity, FormatUpdate, Layer};
-use crate::termco::Colorant;
-use crate::Translator;
+use super::{Attribute, Fidelity, FormatUpdate, Layer, Styled, StyledStr};
+use crate::termco::{AnsiColor, Colorant, Rgb};
+use crate::theme::Theme;
+use crate::{Color, Float, Translator};
 
 /// A terminal style.
 ///
@@ -115,6 +116,24 @@ impl Style {
         self.with_background(colorant)
     }
 
+    /// Create a new style with black or white text, whichever has the higher
+    /// perceptual contrast against the given background color. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "with_auto_foreground")]
+    pub fn py_with_auto_foreground(&self, background: Color) -> Self {
+        self.with_auto_foreground(background)
+    }
+
+    /// Create a new style with the theme's ANSI color that has the highest
+    /// perceptual contrast against the given background color. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "with_theme_foreground")]
+    pub fn py_with_theme_foreground(&self, background: Color, theme: &Theme) -> Self {
+        self.with_theme_foreground(background, theme)
+    }
+
     /// Determine this style's fidelity.
     ///
     /// This method computes the maximum fidelity of this style's format,
@@ -176,6 +195,104 @@ impl Style {
         self.background().cloned()
     }
 
+    /// Determine the minimal transition from this style to the next style.
+    ///
+    /// This method treats both styles as terminal states relative to the
+    /// default appearance. It returns a style delta, whose display emits only
+    /// the SGR parameters needed for changing the terminal's appearance from
+    /// this style to the next style. Notably, formats that are active in both
+    /// styles are not touched and colors that are the same in both styles are
+    /// not repeated. If the next style has no foreground or background color,
+    /// the delta restores the default color instead.
+    ///
+    /// ```
+    /// # use prettypretty::style::Style;
+    /// # use prettypretty::termco::AnsiColor;
+    /// let style1 = Style::default().bold().with_foreground(AnsiColor::Red);
+    /// let style2 = Style::default().bold().italic();
+    ///
+    /// let delta = style1.transition(&style2);
+    /// assert_eq!(format!("{}", delta), "\x1b[3;39m");
+    /// assert!(style2.transition(&style2).is_empty());
+    /// ```
+    pub fn transition(&self, next: &Style) -> StyleDelta {
+        fn delta(current: Option<&Colorant>, next: Option<&Colorant>) -> Option<Colorant> {
+            let current = current.filter(|c| !c.is_default());
+            let next = next.filter(|c| !c.is_default());
+
+            if current == next {
+                None
+            } else {
+                Some(next.cloned().unwrap_or(Colorant::Default()))
+            }
+        }
+
+        StyleDelta {
+            format: next.format - self.format,
+            foreground: delta(self.foreground(), next.foreground()),
+            background: delta(self.background(), next.background()),
+        }
+    }
+
+    /// Merge the overlay style into this style.
+    ///
+    /// The result combines the text attributes of both styles, with the
+    /// overlay taking precedence. That is, if the overlay enables bold or thin
+    /// formatting, it replaces this style's font weight, and if the overlay
+    /// disables a text attribute, the result does not enable it. Similarly,
+    /// the overlay's foreground and background colors replace this style's
+    /// colors, but only if they are set. Merging thus composes styles for
+    /// nested content, e.g., a bold region inside a colored region. [`Style`]
+    /// also implements `+` for merging.
+    ///
+    /// ```
+    /// # use prettypretty::style::Style;
+    /// # use prettypretty::termco::AnsiColor;
+    /// let region = Style::default().italic().with_foreground(AnsiColor::Blue);
+    /// let nested = Style::default().bold().with_background(AnsiColor::White);
+    ///
+    /// assert_eq!(
+    ///     region.merge(&nested),
+    ///     Style::default()
+    ///         .italic()
+    ///         .bold()
+    ///         .with_foreground(AnsiColor::Blue)
+    ///         .with_background(AnsiColor::White)
+    /// );
+    /// ```
+    pub fn merge(&self, overlay: &Style) -> Self {
+        let (disable, enable) = (overlay.format.disable(), overlay.format.enable());
+
+        let mut base = self.format.enable();
+        if enable
+            .attributes()
+            .any(|a| matches!(a, Attribute::Bold | Attribute::Thin))
+        {
+            base = (base - Attribute::Bold - Attribute::Thin).enable();
+        }
+        let base_disable = (self.format.disable() - enable).enable();
+        let base = (base - disable).enable();
+
+        Self {
+            format: -(base_disable + disable) + (base + enable),
+            foreground: overlay
+                .foreground
+                .clone()
+                .or_else(|| self.foreground.clone()),
+            background: overlay
+                .background
+                .clone()
+                .or_else(|| self.background.clone()),
+        }
+    }
+
+    /// Merge the overlay style into this style. <i class=python-only>Python
+    /// only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __add__(&self, overlay: &Style) -> Self {
+        self.merge(overlay)
+    }
+
     /// Negate this style. <i class=python-only>Python only!</i>
     #[cfg(feature = "pyffi")]
     pub fn __neg__(&self) -> Self {
@@ -195,9 +312,85 @@ impl Style {
     pub fn __str__(&self) -> String {
         format!("{}", self)
     }
+
+    /// Restore a style from its pickled state. <i class=python-only>Python
+    /// only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(
+        name = "_from_state",
+        signature = (disable, enable, foreground = None, background = None)
+    )]
+    #[staticmethod]
+    pub fn py_from_state(
+        disable: u8,
+        enable: u8,
+        foreground: Option<Colorant>,
+        background: Option<Colorant>,
+    ) -> Self {
+        Self::from_parts(
+            FormatUpdate::from_bits(disable, enable),
+            foreground,
+            background,
+        )
+    }
+
+    /// Reduce this style for pickling. <i class=python-only>Python only!</i>
+    ///
+    /// The style's state comprises the bits of disabled and enabled
+    /// attributes as well as the optional foreground and background colorants.
+    #[cfg(feature = "pyffi")]
+    #[allow(clippy::type_complexity)]
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(
+        Bound<'py, PyAny>,
+        (u8, u8, Option<Colorant>, Option<Colorant>),
+    )> {
+        let restore = slf.py().get_type::<Self>().getattr("_from_state")?;
+        let style = slf.get();
+        let (disable, enable) = style.format.bits();
+        Ok((
+            restore,
+            (
+                disable,
+                enable,
+                style.foreground.clone(),
+                style.background.clone(),
+            ),
+        ))
+    }
+
+    /// Copy this style. <i class=python-only>Python only!</i>
+    ///
+    /// Since styles are immutable, this method returns the same object.
+    #[cfg(feature = "pyffi")]
+    pub fn __copy__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// Deeply copy this style. <i class=python-only>Python only!</i>
+    ///
+    /// Since styles are immutable, this method returns the same object.
+    #[cfg(feature = "pyffi")]
+    pub fn __deepcopy__(slf: Py<Self>, _memo: &Bound<'_, PyAny>) -> Py<Self> {
+        slf
+    }
 }
 
 impl Style {
+    /// Create a new style from its parts.
+    pub(crate) fn from_parts(
+        format: FormatUpdate,
+        foreground: Option<Colorant>,
+        background: Option<Colorant>,
+    ) -> Self {
+        Self {
+            format,
+            foreground,
+            background,
+        }
+    }
+
     /// Create a new style with the given foreground color.
     pub fn with_foreground(&self, color: impl Into<Colorant>) -> Self {
         Self {
@@ -206,6 +399,50 @@ impl Style {
         }
     }
 
+    /// Create a new style with black or white text, whichever has the higher
+    /// perceptual contrast against the given background color.
+    ///
+    /// This method uses [`Color::use_black_text`] to pick the foreground
+    /// color. It leaves the style's background unchanged, so the background
+    /// color typically is also set with [`Style::with_background`].
+    ///
+    /// ```
+    /// # use prettypretty::style::Style;
+    /// # use prettypretty::termco::{Colorant, Rgb};
+    /// let blue = Rgb::new(0x68, 0x72, 0xff);
+    /// let badge = Style::default()
+    ///     .with_background(blue)
+    ///     .with_auto_foreground(blue);
+    /// assert_eq!(badge.foreground(), Some(&Colorant::Rgb(Rgb::new(255, 255, 255))));
+    /// ```
+    pub fn with_auto_foreground(&self, background: impl Into<Color>) -> Self {
+        let value = if background.into().use_black_text() {
+            0
+        } else {
+            255
+        };
+        self.with_foreground(Rgb::new(value, value, value))
+    }
+
+    /// Create a new style with the theme's ANSI color that has the highest
+    /// perceptual contrast against the given background color.
+    ///
+    /// This method uses [`Color::contrast_against`] to compare the theme's
+    /// ANSI colors. Since the foreground color is an ANSI color, the style
+    /// works even if the terminal only supports ANSI colors.
+    pub fn with_theme_foreground(&self, background: impl Into<Color>, theme: &Theme) -> Self {
+        let background = background.into();
+        let mut best = (AnsiColor::Black, Float::NEG_INFINITY);
+        for color in AnsiColor::all() {
+            let contrast = theme[color].contrast_against(&background).abs();
+            if best.1 < contrast {
+                best = (color, contrast);
+            }
+        }
+
+        self.with_foreground(best.0)
+    }
+
     /// Create a new style with the given background color.
     pub fn with_background(&self, color: impl Into<Colorant>) -> Self {
         Self {
@@ -224,6 +461,48 @@ impl Style {
     pub fn background(&self) -> Option<&Colorant> {
         self.background.as_ref()
     }
+
+    /// Apply this style to the string slice.
+    ///
+    /// The display of the result writes this style, the text, and this style's
+    /// negation.
+    pub fn paint<'a>(&self, text: &'a str) -> StyledStr<'a> {
+        Styled::new(self.clone(), text)
+    }
+
+    /// Apply this style to the displayable content.
+    ///
+    /// The display of the result writes this style, the content, and this
+    /// style's negation.
+    pub fn apply<T: std::fmt::Display>(&self, content: T) -> Styled<T> {
+        Styled::new(self.clone(), content)
+    }
+
+    /// Write this style's SGR escape sequence to the writer.
+    ///
+    /// This method writes the same escape sequence as this style's display,
+    /// but without going through a formatter. Combined with an
+    /// [`SgrBuffer`](super::SgrBuffer), it renders styles without allocating
+    /// memory.
+    pub fn write_to(&self, out: &mut (impl std::fmt::Write + ?Sized)) -> std::fmt::Result {
+        write_sgr(self.format, self.foreground(), self.background(), out)
+    }
+}
+
+impl std::ops::Add<&Style> for &Style {
+    type Output = Style;
+
+    fn add(self, other: &Style) -> Self::Output {
+        self.merge(other)
+    }
+}
+
+impl std::ops::Add for Style {
+    type Output = Style;
+
+    fn add(self, other: Style) -> Self::Output {
+        self.merge(&other)
+    }
 }
 
 impl std::ops::Neg for &Style {
@@ -246,44 +525,142 @@ impl std::ops::Neg for Style {
     }
 }
 
+/// Write the SGR escape sequence for the format update and colors.
+///
+/// This function writes nothing if the format update is empty and both
+/// colors are `None`. It writes directly to the given writer without
+/// allocating memory.
+fn write_sgr(
+    format: FormatUpdate,
+    foreground: Option<&Colorant>,
+    background: Option<&Colorant>,
+    f: &mut (impl std::fmt::Write + ?Sized),
+) -> std::fmt::Result {
+    if format.is_empty() && foreground.is_none() && background.is_none() {
+        return Ok(());
+    }
+
+    let mut first = true;
+    macro_rules! maybe_emit_semicolon {
+        () => {
+            if first {
+                #[allow(unused_assignments)]
+                {
+                    first = false;
+                }
+            } else {
+                f.write_str(";")?;
+            }
+        };
+    }
+
+    f.write_str("\x1b[")?;
+    for attr in format.disable().attributes() {
+        maybe_emit_semicolon!();
+        write!(f, "{}", attr.disable_sgr())?;
+    }
+    for attr in format.enable().attributes() {
+        maybe_emit_semicolon!();
+        write!(f, "{}", attr.enable_sgr())?;
+    }
+    if let Some(colorant) = foreground {
+        maybe_emit_semicolon!();
+        colorant.write_sgr_params(Layer::Foreground, f)?;
+    }
+    if let Some(colorant) = background {
+        maybe_emit_semicolon!();
+        colorant.write_sgr_params(Layer::Background, f)?;
+    }
+    f.write_str("m")
+}
+
 impl std::fmt::Display for Style {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.is_default() {
-            return Ok(());
-        }
+        write_sgr(self.format, self.foreground(), self.background(), f)
+    }
+}
 
-        let mut first = true;
-        macro_rules! maybe_emit_semicolon {
-            () => {
-                if first {
-                    #[allow(unused_assignments)]
-                    {
-                        first = false;
-                    }
-                } else {
-                    f.write_str(";")?;
-                }
-            };
-        }
+// ----------------------------------------------------------------------------------------------------------
 
-        f.write_str("\x1b[")?;
-        for attr in self.format.disable().attributes() {
-            maybe_emit_semicolon!();
-            write!(f, "{}", attr.disable_sgr())?;
-        }
-        for attr in self.format.enable().attributes() {
-            maybe_emit_semicolon!();
-            write!(f, "{}", attr.enable_sgr())?;
-        }
-        if let Some(ref colorant) = self.foreground {
-            maybe_emit_semicolon!();
-            colorant.write_sgr_params(Layer::Foreground, f)?;
-        }
-        if let Some(ref colorant) = self.background {
-            maybe_emit_semicolon!();
-            colorant.write_sgr_params(Layer::Background, f)?;
-        }
-        f.write_str("m")
+/// The minimal change between two styles.
+///
+/// [`Style::transition`] computes style deltas. Displaying a style delta emits
+/// only the SGR parameters necessary for changing from one style to the next
+/// and nothing at all if the two styles are the same.
+#[cfg_attr(
+    feature = "pyffi",
+    pyclass(eq, frozen, hash, module = "prettypretty.color.style")
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StyleDelta {
+    format: FormatUpdate,
+    foreground: Option<Colorant>,
+    background: Option<Colorant>,
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl StyleDelta {
+    /// Determine whether this style delta changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.format.is_empty() && self.foreground.is_none() && self.background.is_none()
+    }
+
+    /// Get the format update.
+    pub fn format(&self) -> FormatUpdate {
+        self.format
+    }
+
+    /// Get the foreground color change. <i class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "foreground")]
+    pub fn py_foreground(&self) -> Option<Colorant> {
+        self.foreground().cloned()
+    }
+
+    /// Get the background color change. <i class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "background")]
+    pub fn py_background(&self) -> Option<Colorant> {
+        self.background().cloned()
+    }
+
+    /// Get this style delta's debug representation. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    /// Get this style delta's string representation. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __str__(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl StyleDelta {
+    /// Get the foreground color change.
+    pub fn foreground(&self) -> Option<&Colorant> {
+        self.foreground.as_ref()
+    }
+
+    /// Get the background color change.
+    pub fn background(&self) -> Option<&Colorant> {
+        self.background.as_ref()
+    }
+
+    /// Write this style delta's SGR escape sequence to the writer.
+    ///
+    /// Like [`Style::write_to`], this method does not allocate memory.
+    pub fn write_to(&self, out: &mut (impl std::fmt::Write + ?Sized)) -> std::fmt::Result {
+        write_sgr(self.format, self.foreground(), self.background(), out)
+    }
+}
+
+impl std::fmt::Display for StyleDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_sgr(self.format, self.foreground(), self.background(), f)
     }
 }
 
@@ -293,6 +670,7 @@ impl std::fmt::Display for Style {
 mod test {
     use super::*;
     use crate::termco::EmbeddedRgb;
+    use crate::theme::VGA_COLORS;
 
     #[test]
     fn test_style() {
@@ -334,4 +712,58 @@ mod test {
         assert_eq!(negated.background(), None);
         assert_eq!(format!("{}", negated), "\x1b[22;24;39m");
     }
+
+    #[test]
+    fn test_transition() {
+        let plain = Style::default();
+        let bold_red = Style::default().bold().with_foreground(AnsiColor::Red);
+        let thin_red = Style::default().thin().with_foreground(AnsiColor::Red);
+        let on_blue = Style::default().with_background(AnsiColor::Blue);
+
+        assert!(plain.transition(&plain).is_empty());
+        assert!(bold_red.transition(&bold_red).is_empty());
+        assert_eq!(format!("{}", plain.transition(&bold_red)), "\x1b[1;31m");
+        assert_eq!(format!("{}", bold_red.transition(&thin_red)), "\x1b[2m");
+        assert_eq!(format!("{}", thin_red.transition(&plain)), "\x1b[22;39m");
+        assert_eq!(
+            format!("{}", bold_red.transition(&on_blue)),
+            "\x1b[22;39;44m"
+        );
+
+        let delta = on_blue.transition(&plain);
+        assert_eq!(delta.foreground(), None);
+        assert_eq!(delta.background(), Some(&Colorant::Default()));
+    }
+
+    #[test]
+    fn test_auto_foreground() {
+        let yellow = Rgb::new(255, 255, 85);
+        let style = Style::default().with_auto_foreground(yellow);
+        assert_eq!(style.foreground(), Some(&Colorant::Rgb(Rgb::new(0, 0, 0))));
+
+        let style = Style::default().with_theme_foreground(yellow, &VGA_COLORS);
+        assert_eq!(style.foreground(), Some(&Colorant::Ansi(AnsiColor::Black)));
+        let style = Style::default().with_theme_foreground(Rgb::new(0, 0, 170), &VGA_COLORS);
+        assert_eq!(
+            style.foreground(),
+            Some(&Colorant::Ansi(AnsiColor::BrightWhite))
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        use super::Attribute::*;
+
+        let thin_red = Style::default().thin().with_foreground(AnsiColor::Red);
+        let bold = Style::default().bold().underlined();
+        let merged = &thin_red + &bold;
+        assert_eq!(merged.format(), (Bold + Underlined).into());
+        assert_eq!(merged.foreground(), Some(&Colorant::Ansi(AnsiColor::Red)));
+
+        let merged = merged + -Style::default().underlined();
+        assert_eq!(merged.format(), FormatUpdate::from(Bold) - Underlined);
+        assert_eq!(merged.foreground(), Some(&Colorant::Ansi(AnsiColor::Red)));
+        assert_eq!(Style::default().merge(&thin_red), thin_red);
+        assert_eq!(thin_red.merge(&Style::default()), thin_red);
+    }
 }
@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+
+#[cfg(feature = "unicode")]
+use unicode_segmentation::UnicodeSegmentation;
+#[cfg(feature = "unicode")]
+use unicode_width::UnicodeWidthStr;
+
+use super::ansi::{Fragment, Fragments, SgrState};
+
+/// Determine the display width of the text.
+///
+/// This function ignores all ANSI escape sequences and measures the remaining
+/// text in terminal columns, one grapheme cluster at a time. Wide clusters,
+/// such as most East Asian ideographs and emoji, take up two columns, whereas
+/// control characters take up none. A combining mark takes up no column
+/// beyond its base character's. Without the `unicode` feature, this function
+/// measures characters instead, with every character other than a control
+/// character taking up one column.
+///
+/// ```
+/// # use prettypretty::style::width;
+/// assert_eq!(width("\x1b[1mbold\x1b[22m"), 4);
+/// assert_eq!(width("\x1b[31m日本\x1b[39m"), 4);
+/// ```
+pub fn width(text: &str) -> usize {
+    Fragments::new(text)
+        .map(|fragment| match fragment {
+            Fragment::Text(text) => clusters(text)
+                .map(|(_, cluster)| cluster_width(cluster))
+                .sum(),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Split the text into grapheme clusters, returning each with its byte index.
+#[cfg(feature = "unicode")]
+pub(crate) fn clusters(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.grapheme_indices(true)
+}
+
+/// Split the text into characters, returning each with its byte index.
+#[cfg(not(feature = "unicode"))]
+pub(crate) fn clusters(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.char_indices()
+        .map(move |(index, c)| (index, &text[index..index + c.len_utf8()]))
+}
+
+/// Determine the display width of the grapheme cluster.
+#[cfg(feature = "unicode")]
+fn cluster_width(cluster: &str) -> usize {
+    cluster.width()
+}
+
+/// Determine the display width of the character.
+#[cfg(not(feature = "unicode"))]
+fn cluster_width(cluster: &str) -> usize {
+    match cluster.chars().next() {
+        Some(c) if !c.is_control() => 1,
+        _ => 0,
+    }
+}
+
+/// Truncate the text to the given number of columns.
+///
+/// If the text is wider than the number of columns, this function cuts off
+/// the text at that width and, if necessary, restores the terminal's default
+/// appearance afterwards. Hence SGR escape sequences in the result are
+/// balanced, even if the cut falls in the middle of styled text. A wide
+/// grapheme cluster that does not fit into the remaining columns is dropped. All
+/// escape sequences preceding the cut are retained.
+///
+/// ```
+/// # use prettypretty::style::truncate;
+/// assert_eq!(truncate("\x1b[1mbold\x1b[22m text", 2), "\x1b[1mbo\x1b[22m");
+/// assert_eq!(truncate("short", 10), "short");
+/// ```
+pub fn truncate(text: &str, columns: usize) -> Cow<'_, str> {
+    if width(text) <= columns {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut state = SgrState::default();
+    let mut remaining = columns;
+
+    'outer: for fragment in Fragments::new(text) {
+        match fragment {
+            Fragment::Text(text) => {
+                for (index, cluster) in clusters(text) {
+                    let w = cluster_width(cluster);
+                    if remaining < w {
+                        result.push_str(&text[..index]);
+                        break 'outer;
+                    }
+                    remaining -= w;
+                }
+                result.push_str(text);
+            }
+            Fragment::Sgr(sequence) => {
+                state.update(sequence);
+                result.push_str(sequence);
+            }
+            Fragment::Control(sequence) => result.push_str(sequence),
+        }
+    }
+
+    let style = state.style();
+    if !style.is_default() {
+        result.push_str(&(-&style).to_string());
+    }
+    Cow::Owned(result)
+}
+
+/// Pad the text with spaces to the given number of columns.
+///
+/// If the text is narrower than the number of columns, this function appends
+/// spaces to the end. It does not truncate wider text.
+///
+/// ```
+/// # use prettypretty::style::pad;
+/// assert_eq!(pad("\x1b[1mbold\x1b[22m", 6), "\x1b[1mbold\x1b[22m  ");
+/// ```
+pub fn pad(text: &str, columns: usize) -> Cow<'_, str> {
+    let width = width(text);
+    if columns <= width {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(format!("{}{}", text, " ".repeat(columns - width)))
+    }
+}
+
+/// Fit the text to exactly the given number of columns.
+///
+/// This function first [`truncate`]s the text and then [`pad`]s it, so that
+/// the result always has the given display width. That makes it suitable for
+/// table cells and progress bars.
+pub fn fit(text: &str, columns: usize) -> Cow<'_, str> {
+    match truncate(text, columns) {
+        Cow::Borrowed(text) => pad(text, columns),
+        Cow::Owned(text) => Cow::Owned(pad(&text, columns).into_owned()),
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{fit, truncate, width};
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_width() {
+        let text = "\x1b[1;31m日本\x1b[22m語\x1b]0;title\x07e\u{301}\x1b[m";
+        assert_eq!(width(text), 7);
+        assert_eq!(truncate(text, 7), text);
+        assert_eq!(truncate(text, 5), "\x1b[1;31m日本\x1b[22m\x1b[39m");
+        assert_eq!(truncate(text, 3), "\x1b[1;31m日\x1b[22;39m");
+        assert_eq!(truncate(text, 0), "\x1b[1;31m\x1b[22;39m");
+        assert_eq!(fit(text, 5), "\x1b[1;31m日本\x1b[22m\x1b[39m ");
+        assert_eq!(fit("abc", 5), "abc  ");
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_clusters() {
+        let text = "a\u{1f44d}\u{1f3fd}b";
+        assert_eq!(width(text), 4);
+        assert_eq!(truncate(text, 3), "a\u{1f44d}\u{1f3fd}");
+        assert_eq!(truncate(text, 2), "a");
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    #[test]
+    fn test_chars() {
+        let text = "\x1b[1m日本\x1b[22me\u{301}\x07";
+        assert_eq!(width(text), 4);
+        assert_eq!(truncate(text, 3), "\x1b[1m日本\x1b[22me");
+        assert_eq!(fit("abc", 5), "abc  ");
+    }
+}
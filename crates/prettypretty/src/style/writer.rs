@@ -0,0 +1,198 @@
+use std::io::{Result, Write};
+
+use super::{Fidelity, SgrBuffer, Style};
+use crate::Translator;
+
+/// A writer that tracks the active style.
+///
+/// A style writer wraps another writer and keeps track of the terminal's
+/// currently active style. [`StyleWriter::set_style`] only writes the SGR
+/// parameters necessary for transitioning from the active style to the new
+/// one, omitting the escape sequence altogether if nothing changes. If the
+/// writer has been created with [`StyleWriter::with_fidelity`], it also caps
+/// every style to that fidelity before comparing styles. All other writes are
+/// passed through unchanged.
+///
+/// When dropped, a style writer restores the terminal's default appearance
+/// and flushes the wrapped writer, ignoring any errors. Use
+/// [`StyleWriter::into_inner`] to observe errors instead.
+///
+/// ```
+/// # use prettypretty::style::{Style, StyleWriter};
+/// # use prettypretty::termco::AnsiColor;
+/// # use std::io::Write;
+/// let bold = Style::default().bold();
+/// let bold_red = bold.clone().with_foreground(AnsiColor::Red);
+///
+/// let mut writer = StyleWriter::new(Vec::new());
+/// writer.set_style(&bold)?;
+/// write!(writer, "bold ")?;
+/// writer.set_style(&bold)?;
+/// write!(writer, "still bold ")?;
+/// writer.set_style(&bold_red)?;
+/// write!(writer, "red")?;
+/// let output = writer.into_inner()?;
+///
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "\x1b[1mbold still bold \x1b[31mred\x1b[22;39m"
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct StyleWriter<'t, W: Write> {
+    inner: Option<W>,
+    active: Style,
+    cap: Option<(Fidelity, &'t Translator)>,
+}
+
+impl<W: Write> StyleWriter<'_, W> {
+    /// Create a new style writer that does not cap styles.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: Some(inner),
+            active: Style::default(),
+            cap: None,
+        }
+    }
+}
+
+impl<'t, W: Write> StyleWriter<'t, W> {
+    /// Create a new style writer that caps styles to the given fidelity.
+    pub fn with_fidelity(inner: W, fidelity: Fidelity, translator: &'t Translator) -> Self {
+        Self {
+            inner: Some(inner),
+            active: Style::default(),
+            cap: Some((fidelity, translator)),
+        }
+    }
+
+    /// Get the fidelity styles are capped to, if any.
+    pub fn fidelity(&self) -> Option<Fidelity> {
+        self.cap.map(|(fidelity, _)| fidelity)
+    }
+
+    /// Get the currently active style.
+    ///
+    /// If this writer caps styles, the active style has been capped as well.
+    pub fn style(&self) -> &Style {
+        &self.active
+    }
+
+    /// Get a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("style writer has inner writer")
+    }
+
+    /// Get a mutable reference to the wrapped writer.
+    ///
+    /// Writing escape sequences directly to the wrapped writer invalidates
+    /// the active style tracked by this writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect("style writer has inner writer")
+    }
+
+    /// Make the given style the active style.
+    ///
+    /// This method writes the minimal SGR escape sequence for changing from
+    /// the active style to the given style, after capping the given style if
+    /// this writer has a fidelity.
+    pub fn set_style(&mut self, style: &Style) -> Result<()> {
+        let style = match self.cap {
+            Some((fidelity, translator)) => style.cap(fidelity, translator),
+            None => style.clone(),
+        };
+
+        let delta = self.active.transition(&style);
+        if !delta.is_empty() {
+            // Buffer the escape sequence to write it all at once.
+            let mut buffer = SgrBuffer::new();
+            delta.write_to(&mut buffer).map_err(std::io::Error::other)?;
+            self.get_mut().write_all(buffer.as_bytes())?;
+        }
+        self.active = style;
+        Ok(())
+    }
+
+    /// Restore the terminal's default appearance.
+    pub fn reset(&mut self) -> Result<()> {
+        self.set_style(&Style::default())
+    }
+
+    /// Write the content with the given style.
+    ///
+    /// Unlike [`Styled`](super::Styled) content, this method leaves the
+    /// style active after writing the content.
+    pub fn write_styled<T: std::fmt::Display>(&mut self, style: &Style, content: T) -> Result<()> {
+        self.set_style(style)?;
+        write!(self.get_mut(), "{}", content)
+    }
+
+    /// Restore the default appearance, flush, and return the wrapped writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.reset()?;
+        self.flush()?;
+        Ok(self.inner.take().expect("style writer has inner writer"))
+    }
+}
+
+impl<W: Write> Write for StyleWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.get_mut().write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.get_mut().write_all(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.get_mut().flush()
+    }
+}
+
+impl<W: Write> Drop for StyleWriter<'_, W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.reset();
+            let _ = self.flush();
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{StyleWriter, Write};
+    use crate::style::{Fidelity, Style};
+    use crate::termco::{AnsiColor, Rgb};
+    use crate::theme::VGA_COLORS;
+    use crate::{OkVersion, Translator};
+
+    #[test]
+    fn test_style_writer() -> std::io::Result<()> {
+        let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+        let red = Style::default().with_foreground(Rgb::new(215, 40, 39));
+
+        let mut output = Vec::new();
+        {
+            let mut writer = StyleWriter::with_fidelity(&mut output, Fidelity::Ansi, &translator);
+            writer.write_styled(&red, "red")?;
+            writer.write_styled(&red.clone().italic(), "italic")?;
+            assert_eq!(writer.style().foreground(), Some(&AnsiColor::Red.into()));
+        }
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\x1b[31mred\x1b[3mitalic\x1b[23;39m"
+        );
+
+        let mut output = Vec::new();
+        {
+            let mut writer = StyleWriter::with_fidelity(&mut output, Fidelity::Plain, &translator);
+            writer.write_styled(&red, "plain")?;
+            writer.write_all(b" text")?;
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), "plain text");
+        Ok(())
+    }
+}
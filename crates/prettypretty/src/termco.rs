@@ -9,7 +9,10 @@
 //! high-resolution [`Color`]s can be wrapped as [`Colorant`]s. That type also
 //! includes a variant for the [`Colorant::Default`].
 #[cfg(feature = "pyffi")]
-use pyo3::{prelude::*, types::PyInt};
+use pyo3::{
+    prelude::*,
+    types::{PyInt, PyTuple, PyType},
+};
 
 use crate::error::{HiResColorantError, OutOfBoundsError};
 use crate::style::Layer;
@@ -1017,6 +1020,13 @@ impl Rgb {
         format!("Rgb({}, {}, {})", self.0[0], self.0[1], self.0[2])
     }
 
+    /// Reduce this true color to its constructor arguments for pickling. <i
+    /// class=python-only>Python only!</i>
+    pub fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, (u8, u8, u8)) {
+        let [r, g, b] = slf.get().0;
+        (slf.get_type(), (r, g, b))
+    }
+
     /// Convert this true color to hashed hexadecimal notation. <i
     /// class=python-only>Python only!</i>
     pub fn __str__(&self) -> String {
@@ -1159,10 +1169,13 @@ pub enum Colorant {
 #[cfg_attr(feature = "pyffi", pymethods)]
 impl Colorant {
     /// Wrap any color as colorant. <i class=python-only>Python only!</i>
+    ///
+    /// Without argument, this method returns the default colorant.
     #[cfg(feature = "pyffi")]
     #[staticmethod]
-    pub fn of(#[pyo3(from_py_with = "crate::termco::into_colorant")] colorant: Colorant) -> Self {
-        colorant
+    #[pyo3(signature = (colorant = None))]
+    pub fn of(colorant: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        colorant.map_or(Ok(Self::Default()), into_colorant)
     }
 
     /// Determine whether this colorant is the default.
@@ -1247,15 +1260,56 @@ impl Colorant {
     pub fn py_display(&self, layer: Layer) -> PyResult<String> {
         Ok(format!("{}", self.display(layer)?))
     }
+
+    /// Reduce this colorant for pickling. <i class=python-only>Python
+    /// only!</i>
+    ///
+    /// Since variants of complex enums cannot be looked up by name, this
+    /// method restores colorants with [`Colorant::of`], passing 8-bit colors
+    /// as their indices.
+    #[cfg(feature = "pyffi")]
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyTuple>)> {
+        let py = slf.py();
+        let of = py.get_type::<Self>().getattr("of")?;
+        let colorant = slf.get();
+        let args = match colorant {
+            Self::Default() => PyTuple::empty(py),
+            Self::Rgb(c) => PyTuple::new(py, [Bound::new(py, *c)?])?,
+            Self::HiRes(c) => PyTuple::new(py, [Bound::new(py, c.clone())?])?,
+            _ => {
+                // Unwrap is safe b/c the remaining variants are 8-bit colors.
+                PyTuple::new(py, [u8::try_from(colorant).unwrap()])?
+            }
+        };
+        Ok((of, args))
+    }
+
+    /// Copy this colorant. <i class=python-only>Python only!</i>
+    ///
+    /// Since colorants are immutable, this method returns the same object.
+    #[cfg(feature = "pyffi")]
+    pub fn __copy__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// Deeply copy this colorant. <i class=python-only>Python only!</i>
+    ///
+    /// Since colorants are immutable, this method returns the same object.
+    #[cfg(feature = "pyffi")]
+    pub fn __deepcopy__(slf: Py<Self>, _memo: &Bound<'_, PyAny>) -> Py<Self> {
+        slf
+    }
 }
 
 impl Colorant {
-    /// Write the colorant's SGR parameters for the given layer with the given
-    /// formatter. <i class=rust-only>Rust only!</i>
+    /// Write the colorant's SGR parameters for the given layer to the given
+    /// writer, which may be a formatter. <i class=rust-only>Rust only!</i>
     pub fn write_sgr_params(
         &self,
         layer: Layer,
-        f: &mut std::fmt::Formatter<'_>,
+        f: &mut (impl std::fmt::Write + ?Sized),
     ) -> std::fmt::Result {
         match self {
             Self::Default() => write!(f, "{}", 39 + layer.offset()),
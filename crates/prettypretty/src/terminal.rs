@@ -0,0 +1,256 @@
+//! Terminal access for Python.
+//!
+//! This module exposes [`prettytty`]'s terminal connection to Python. Since
+//! Python has no equivalent to Rust's drop handlers, the classes in this
+//! module are context managers instead. [`Terminal::access`] creates a context
+//! manager that connects to the terminal upon entry and always restores the
+//! terminal upon exit, including when the body raises an exception:
+//!
+//! ```python
+//! with Terminal.access(mode="raw") as tty:
+//!     with tty.alternate_screen(), tty.window_title("Pretty"):
+//!         tty.print("Hello, terminal!")
+//! ```
+//!
+//! Similarly, [`Terminal::alternate_screen`] and [`Terminal::window_title`]
+//! return context managers that restore the main screen and previous window
+//! title, respectively, mirroring [`prettytty::AlternateScreen`] and
+//! [`prettytty::WindowTitle`].
+
+use std::io::{BufRead, Write};
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyTuple};
+
+use prettytty::cmd::{
+    DynSetWindowTitle, EnterAlternateScreen, ExitAlternateScreen, RestoreWindowTitle,
+    SaveWindowTitle, ShowCursor,
+};
+use prettytty::opt::{Mode, Options};
+use prettytty::Connection;
+
+use crate::theme::Theme;
+
+/// Parse the name of a terminal mode.
+fn parse_mode(mode: &str) -> PyResult<Mode> {
+    match mode {
+        "charred" => Ok(Mode::Charred),
+        "cooked" => Ok(Mode::Cooked),
+        "rare" | "cbreak" => Ok(Mode::Rare),
+        "raw" => Ok(Mode::Raw),
+        _ => Err(PyValueError::new_err(format!(
+            "terminal mode should be charred, cooked, rare, or raw but is {}",
+            mode
+        ))),
+    }
+}
+
+/// Access to the terminal. <i class=python-only>Python only!</i>
+///
+/// An instance is a reusable context manager that opens a connection to the
+/// terminal when entering the context and closes the connection again, thereby
+/// restoring the terminal's original configuration, when exiting the context.
+/// The methods for interacting with the terminal fail with a `RuntimeError`
+/// outside the context.
+#[pyclass(module = "prettypretty.color.terminal")]
+pub struct Terminal {
+    mode: Mode,
+    timeout: u8,
+    connection: Option<Connection>,
+}
+
+impl Terminal {
+    /// Get the terminal connection.
+    fn connection(&self) -> PyResult<&Connection> {
+        self.connection
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("terminal is not being accessed"))
+    }
+}
+
+#[pymethods]
+impl Terminal {
+    /// Create a new context manager for accessing the terminal.
+    ///
+    /// The mode is one of `charred`, `cooked`, `rare` (the default), or
+    /// `raw`, with `cbreak` an alias for `rare`. The timeout is the read
+    /// timeout in multiples of 0.1s.
+    #[staticmethod]
+    #[pyo3(signature = (*, mode = "rare", timeout = 50))]
+    pub fn access(mode: &str, timeout: u8) -> PyResult<Self> {
+        Ok(Self {
+            mode: parse_mode(mode)?,
+            timeout,
+            connection: None,
+        })
+    }
+
+    /// Determine whether the terminal is being accessed.
+    pub fn is_active(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// Connect to the terminal.
+    pub fn __enter__(mut slf: PyRefMut<'_, Self>) -> PyResult<PyRefMut<'_, Self>> {
+        if slf.connection.is_some() {
+            return Err(PyRuntimeError::new_err(
+                "terminal is already being accessed",
+            ));
+        }
+
+        let options = Options::builder()
+            .mode(slf.mode)
+            .timeout(slf.timeout)
+            .build();
+        slf.connection = Some(Connection::with_options(options)?);
+        Ok(slf)
+    }
+
+    /// Disconnect from the terminal and restore its configuration.
+    ///
+    /// This method does not suppress exceptions.
+    #[pyo3(signature = (*_args))]
+    pub fn __exit__(&mut self, _args: &Bound<'_, PyTuple>) -> bool {
+        // Dropping the connection restores the terminal.
+        self.connection = None;
+        false
+    }
+
+    /// Get the terminal's window size as columns and rows.
+    pub fn window_size(&self) -> PyResult<(u16, u16)> {
+        Ok(self.connection()?.window_size()?)
+    }
+
+    /// Write and flush the text.
+    pub fn print(&self, text: &str) -> PyResult<()> {
+        Ok(self.connection()?.output().print(text)?)
+    }
+
+    /// Write and flush the text followed by carriage return and line feed.
+    pub fn println(&self, text: &str) -> PyResult<()> {
+        Ok(self.connection()?.output().println(text)?)
+    }
+
+    /// Write and flush the bytes.
+    pub fn write(&self, data: &[u8]) -> PyResult<()> {
+        let connection = self.connection()?;
+        let mut output = connection.output();
+        output.write_all(data)?;
+        Ok(output.flush()?)
+    }
+
+    /// Read the currently available bytes from the terminal.
+    ///
+    /// This method releases the GIL while waiting for input. It returns an
+    /// empty byte string if the read times out.
+    pub fn read<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let connection = self.connection()?;
+        let data = py.allow_threads(|| {
+            let mut input = connection.input();
+            let data = input.fill_buf()?.to_vec();
+            input.consume(data.len());
+            Ok::<_, std::io::Error>(data)
+        })?;
+        Ok(PyBytes::new(py, &data))
+    }
+
+    /// Query the terminal for its current color theme.
+    pub fn query_theme(&self, py: Python<'_>) -> PyResult<Theme> {
+        let connection = self.connection()?;
+        Ok(py.allow_threads(|| Theme::query(connection))?)
+    }
+
+    /// Create a new context manager for the alternate screen.
+    pub fn alternate_screen(slf: Py<Self>) -> AlternateScreen {
+        AlternateScreen { terminal: slf }
+    }
+
+    /// Create a new context manager for temporarily changing the window
+    /// title.
+    pub fn window_title(slf: Py<Self>, title: String) -> WindowTitle {
+        WindowTitle {
+            terminal: slf,
+            title,
+        }
+    }
+
+    /// Get this terminal's debug representation.
+    pub fn __repr__(&self) -> String {
+        let mode = format!("{:?}", self.mode).to_lowercase();
+        format!("Terminal(mode={}, active={})", mode, self.is_active())
+    }
+}
+
+/// A context manager for the alternate screen. <i class=python-only>Python
+/// only!</i>
+///
+/// [`Terminal::alternate_screen`] returns this context manager. Entering it
+/// switches to the alternate screen. Exiting it switches back to the main
+/// screen and shows the cursor, just like dropping a
+/// [`prettytty::AlternateScreen`]. If the terminal is not being accessed
+/// anymore, exiting has no effect.
+#[pyclass(module = "prettypretty.color.terminal")]
+pub struct AlternateScreen {
+    terminal: Py<Terminal>,
+}
+
+#[pymethods]
+impl AlternateScreen {
+    /// Switch to the alternate screen.
+    pub fn __enter__(slf: PyRef<'_, Self>) -> PyResult<PyRef<'_, Self>> {
+        let terminal = slf.terminal.borrow(slf.py());
+        terminal.connection()?.output().exec(EnterAlternateScreen)?;
+        drop(terminal);
+        Ok(slf)
+    }
+
+    /// Switch back to the main screen and show the cursor.
+    #[pyo3(signature = (*_args))]
+    pub fn __exit__(&self, py: Python<'_>, _args: &Bound<'_, PyTuple>) -> PyResult<bool> {
+        let terminal = self.terminal.borrow(py);
+        if let Some(connection) = terminal.connection.as_ref() {
+            let mut output = connection.output();
+            write!(output, "{}{}", ShowCursor, ExitAlternateScreen)?;
+            output.flush()?;
+        }
+        Ok(false)
+    }
+}
+
+/// A context manager for a temporary window title. <i
+/// class=python-only>Python only!</i>
+///
+/// [`Terminal::window_title`] returns this context manager. Entering it saves
+/// the current window title and sets the new one. Exiting it restores the
+/// previous window title, just like dropping a [`prettytty::WindowTitle`]. If
+/// the terminal is not being accessed anymore, exiting has no effect.
+#[pyclass(module = "prettypretty.color.terminal")]
+pub struct WindowTitle {
+    terminal: Py<Terminal>,
+    title: String,
+}
+
+#[pymethods]
+impl WindowTitle {
+    /// Save the current window title and set the new one.
+    pub fn __enter__(slf: PyRef<'_, Self>) -> PyResult<PyRef<'_, Self>> {
+        let terminal = slf.terminal.borrow(slf.py());
+        let mut output = terminal.connection()?.output();
+        write!(output, "{}", SaveWindowTitle)?;
+        output.exec(DynSetWindowTitle::new(slf.title.clone()))?;
+        drop(output);
+        drop(terminal);
+        Ok(slf)
+    }
+
+    /// Restore the previous window title.
+    #[pyo3(signature = (*_args))]
+    pub fn __exit__(&self, py: Python<'_>, _args: &Bound<'_, PyTuple>) -> PyResult<bool> {
+        let terminal = self.terminal.borrow(py);
+        if let Some(connection) = terminal.connection.as_ref() {
+            connection.output().exec(RestoreWindowTitle)?;
+        }
+        Ok(false)
+    }
+}
@@ -0,0 +1,162 @@
+use std::str::FromStr;
+
+use super::{Theme, ThemeEntry};
+use crate::error::ThemeFormatError;
+use crate::Color;
+
+fn error(message: impl Into<String>) -> ThemeFormatError {
+    ThemeFormatError::new("Alacritty", message)
+}
+
+/// The names of ANSI colors in Alacritty's `normal` and `bright` tables.
+const NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Get the table and key for the theme entry.
+fn path(entry: ThemeEntry) -> (&'static str, &'static str) {
+    match entry {
+        ThemeEntry::Ansi(color) => {
+            let index = color as usize;
+            let table = if color.is_bright() {
+                "bright"
+            } else {
+                "normal"
+            };
+            (table, NAMES[index % 8])
+        }
+        ThemeEntry::DefaultForeground() => ("primary", "foreground"),
+        ThemeEntry::DefaultBackground() => ("primary", "background"),
+        ThemeEntry::Cursor() => ("cursor", "cursor"),
+        ThemeEntry::HighlightForeground() => ("selection", "text"),
+        ThemeEntry::HighlightBackground() => ("selection", "background"),
+    }
+}
+
+/// Parse an Alacritty color, which may use `0x` instead of `#` as prefix.
+fn parse(s: &str) -> Option<Color> {
+    match s.strip_prefix("0x") {
+        Some(hex) => Color::from_str(&format!("#{}", hex)).ok(),
+        None => Color::from_str(s).ok(),
+    }
+}
+
+impl Theme {
+    /// Read a color theme from an Alacritty TOML configuration. <i
+    /// class=serde-only>Serde only!</i>
+    ///
+    /// This method reads the `colors` table of Alacritty's configuration
+    /// format, which is also the format of shared Alacritty themes. It requires
+    /// the `colors.normal` and `colors.bright` tables with all eight colors
+    /// each as well as the `foreground` and `background` in `colors.primary`.
+    /// It also reads the optional `colors.cursor.cursor`,
+    /// `colors.selection.text`, and `colors.selection.background` into the
+    /// extra theme entries. Alacritty's special `CellForeground` and
+    /// `CellBackground` values are not colors and hence ignored, as are all
+    /// other keys.
+    ///
+    /// ```
+    /// # use prettypretty::Color;
+    /// # use prettypretty::error::ThemeFormatError;
+    /// # use prettypretty::termco::AnsiColor;
+    /// # use prettypretty::theme::Theme;
+    /// let config = r##"
+    /// [colors.primary]
+    /// background = '#1d1f21'
+    /// foreground = '#c5c8c6'
+    ///
+    /// [colors.normal]
+    /// black = '#1d1f21'
+    /// red = '#cc6666'
+    /// green = '#b5bd68'
+    /// yellow = '#f0c674'
+    /// blue = '#81a2be'
+    /// magenta = '#b294bb'
+    /// cyan = '#8abeb7'
+    /// white = '#c5c8c6'
+    ///
+    /// [colors.bright]
+    /// black = '#666666'
+    /// red = '#d54e53'
+    /// green = '#b9ca4a'
+    /// yellow = '#e7c547'
+    /// blue = '#7aa6da'
+    /// magenta = '#c397d8'
+    /// cyan = '#70c0b1'
+    /// white = '#eaeaea'
+    /// "##;
+    ///
+    /// let theme = Theme::from_alacritty(config)?;
+    /// assert_eq!(theme[AnsiColor::BrightRed], Color::from_24bit(0xd5, 0x4e, 0x53));
+    /// # Ok::<(), ThemeFormatError>(())
+    /// ```
+    pub fn from_alacritty(text: &str) -> Result<Self, ThemeFormatError> {
+        let config: toml::Table = toml::from_str(text).map_err(|e| error(e.message()))?;
+        let colors = config
+            .get("colors")
+            .and_then(toml::Value::as_table)
+            .ok_or_else(|| error("missing colors table"))?;
+
+        let mut theme = Theme::new();
+        for entry in ThemeEntry::all().chain(ThemeEntry::extras()) {
+            let (table, key) = path(entry);
+            let value = colors
+                .get(table)
+                .and_then(toml::Value::as_table)
+                .and_then(|t| t.get(key));
+
+            match value {
+                Some(toml::Value::String(s)) if entry.is_extra() && s.starts_with("Cell") => (),
+                Some(value) => {
+                    theme[entry] = value.as_str().and_then(parse).ok_or_else(|| {
+                        error(format!("colors.{}.{} is not a valid color", table, key))
+                    })?;
+                }
+                None if entry.is_extra() => (),
+                None => return Err(error(format!("missing colors.{}.{}", table, key))),
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::error::ThemeFormatError;
+    use crate::termco::AnsiColor;
+    use crate::theme::{Theme, ThemeEntry, VGA_COLORS};
+
+    #[test]
+    fn test_alacritty() -> Result<(), ThemeFormatError> {
+        let mut config = String::new();
+        for (table, colors) in [("normal", 0..8), ("bright", 8..16)] {
+            config.push_str(&format!("[colors.{}]\n", table));
+            for (name, index) in super::NAMES.iter().zip(colors) {
+                let color = &VGA_COLORS[AnsiColor::try_from(index as u8).unwrap()];
+                let hex = color.to_hex_format().replace('#', "0x");
+                config.push_str(&format!("{} = \"{}\"\n", name, hex));
+            }
+        }
+
+        let error = Theme::from_alacritty(&config).unwrap_err();
+        assert_eq!(error.message(), "missing colors.primary.foreground");
+
+        config.push_str(concat!(
+            "[colors.primary]\nforeground = \"#000000\"\nbackground = \"#ffffff\"\n",
+            "[colors.cursor]\ntext = \"CellBackground\"\ncursor = \"CellForeground\"\n",
+            "[colors.selection]\ntext = \"CellBackground\"\nbackground = \"#123456\"\n",
+        ));
+        let theme = Theme::from_alacritty(&config)?;
+        assert_eq!(theme.as_ref(), VGA_COLORS.as_ref());
+        assert!(!theme.has_extra(ThemeEntry::Cursor()));
+        assert!(!theme.has_extra(ThemeEntry::HighlightForeground()));
+        assert_eq!(
+            theme[ThemeEntry::HighlightBackground()].to_hex_format(),
+            "#123456"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use std::str::FromStr;
+
+use super::{Theme, ThemeEntry};
+use crate::error::ThemeFormatError;
+use crate::Color;
+
+fn error(message: impl Into<String>) -> ThemeFormatError {
+    ThemeFormatError::new("Base16", message)
+}
+
+/// The base16 slots for the 16 ANSI colors followed by the default foreground
+/// and background colors.
+const BASE16: [usize; ThemeEntry::COUNT] = [
+    0x00, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x05, // normal
+    0x03, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x07, // bright
+    0x05, 0x00, // foreground, background
+];
+
+/// The base24 slots for the 16 ANSI colors followed by the default foreground
+/// and background colors.
+const BASE24: [usize; ThemeEntry::COUNT] = [
+    0x00, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x06, // normal
+    0x02, 0x12, 0x14, 0x13, 0x16, 0x17, 0x15, 0x07, // bright
+    0x05, 0x00, // foreground, background
+];
+
+/// Parse a line of a scheme into slot and color, if it defines a slot.
+fn parse_line(line: &str) -> Option<Result<(usize, Color), String>> {
+    let (key, value) = line.trim().split_once(':')?;
+    let slot = key.trim().strip_prefix("base")?;
+    if slot.len() != 2 {
+        return None;
+    }
+    let slot = usize::from_str_radix(slot, 16).ok().filter(|s| *s < 24)?;
+
+    // Drop a trailing comment, which must be separated by white space, and
+    // then any quotes.
+    let value = value.trim();
+    let value = match value.find(" #") {
+        Some(index) => value[..index].trim_end(),
+        None => value,
+    };
+    let value = value.trim_matches(|c| c == '"' || c == '\'');
+
+    let color = if value.starts_with('#') {
+        Color::from_str(value)
+    } else {
+        Color::from_str(&format!("#{}", value))
+    };
+    Some(
+        color
+            .map(|c| (slot, c))
+            .map_err(|_| format!("{} is not a valid color", key.trim())),
+    )
+}
+
+impl Theme {
+    /// Read a color theme from a base16 or base24 scheme.
+    ///
+    /// [Base16](https://github.com/tinted-theming/home) schemes define 16
+    /// colors `base00` through `base0F`, which are assigned to ANSI colors
+    /// with the conventional mapping used by base16-shell and most terminal
+    /// templates. Notably, bright ANSI colors repeat the regular accent
+    /// colors. Base24 schemes add `base10` through `base17`, which provide
+    /// distinct bright colors. If a scheme defines all 24 colors, this method
+    /// uses the base24 mapping. Either way, it also assigns `base05` and
+    /// `base02` to the highlight foreground and background.
+    ///
+    /// This method accepts both the original format, with slots as top-level
+    /// keys and colors without the leading `#`, and the current Tinted Theming
+    /// format, with slots nested inside a `palette`. Instead of relying on a
+    /// full YAML parser, it only looks at lines defining slots.
+    ///
+    /// ```
+    /// # use prettypretty::Color;
+    /// # use prettypretty::error::ThemeFormatError;
+    /// # use prettypretty::termco::AnsiColor;
+    /// # use prettypretty::theme::Theme;
+    /// let mut scheme = String::from("system: \"base16\"\nname: \"Gray\"\npalette:\n");
+    /// for index in 0..16 {
+    ///     scheme.push_str(&format!("  base{:02X}: \"#{:02x}0000\"\n", index, index * 16));
+    /// }
+    ///
+    /// let theme = Theme::from_base16(&scheme)?;
+    /// assert_eq!(theme[AnsiColor::Red], Color::from_24bit(0x80, 0, 0));
+    /// assert_eq!(theme[AnsiColor::BrightRed], Color::from_24bit(0x80, 0, 0));
+    /// # Ok::<(), ThemeFormatError>(())
+    /// ```
+    pub fn from_base16(text: &str) -> Result<Self, ThemeFormatError> {
+        let mut slots: [Option<Color>; 24] = Default::default();
+        for line in text.lines() {
+            if let Some(result) = parse_line(line) {
+                let (slot, color) = result.map_err(error)?;
+                slots[slot] = Some(color);
+            }
+        }
+
+        if let Some(slot) = slots[..16].iter().position(Option::is_none) {
+            return Err(error(format!("missing base{:02X}", slot)));
+        }
+        let mapping = if slots[16..].iter().all(Option::is_some) {
+            &BASE24
+        } else {
+            &BASE16
+        };
+
+        // Unwraps are safe b/c the first 16 slots are all defined and BASE16
+        // uses only those.
+        let color = |slot: usize| slots[slot].clone().unwrap();
+        let mut theme = Theme::new();
+        for (entry, slot) in ThemeEntry::all().zip(mapping) {
+            theme[entry] = color(*slot);
+        }
+        theme[ThemeEntry::HighlightForeground()] = color(0x05);
+        theme[ThemeEntry::HighlightBackground()] = color(0x02);
+        Ok(theme)
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::error::ThemeFormatError;
+    use crate::termco::AnsiColor;
+    use crate::theme::{Theme, ThemeEntry};
+    use crate::Color;
+
+    #[test]
+    fn test_base16() -> Result<(), ThemeFormatError> {
+        let mut scheme = String::from("scheme: \"Test\" # legacy format\nauthor: \"#me\"\n");
+        for index in 0..16 {
+            scheme.push_str(&format!(
+                "base{:02X}: \"{:02x}{:02x}00\"  # slot\n",
+                index, index, index
+            ));
+        }
+
+        let theme = Theme::from_base16(&scheme)?;
+        assert_eq!(theme[AnsiColor::Black], Color::from_24bit(0, 0, 0));
+        assert_eq!(theme[AnsiColor::White], Color::from_24bit(5, 5, 0));
+        assert_eq!(theme[AnsiColor::BrightBlack], Color::from_24bit(3, 3, 0));
+        assert_eq!(theme[AnsiColor::BrightBlue], Color::from_24bit(13, 13, 0));
+        assert_eq!(
+            theme[ThemeEntry::DefaultForeground()],
+            Color::from_24bit(5, 5, 0)
+        );
+        assert_eq!(
+            theme[ThemeEntry::HighlightBackground()],
+            Color::from_24bit(2, 2, 0)
+        );
+        assert!(!theme.has_extra(ThemeEntry::Cursor()));
+
+        for index in 16..24 {
+            scheme.push_str(&format!(
+                "base{:02X}: '{:02x}{:02x}00'\n",
+                index, index, index
+            ));
+        }
+        let theme = Theme::from_base16(&scheme)?;
+        assert_eq!(theme[AnsiColor::White], Color::from_24bit(6, 6, 0));
+        assert_eq!(theme[AnsiColor::BrightBlack], Color::from_24bit(2, 2, 0));
+        assert_eq!(theme[AnsiColor::BrightBlue], Color::from_24bit(22, 22, 0));
+
+        let error = Theme::from_base16(&scheme.replace("base0C", "base0c: oops\n#")).unwrap_err();
+        assert_eq!(error.message(), "base0c is not a valid color");
+        let error = Theme::from_base16(&scheme.replace("base0C", "#base0C")).unwrap_err();
+        assert_eq!(error.message(), "missing base0C");
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use super::{Theme, ThemeEntry, MACOS_TERMINAL, VGA_COLORS, WINDOWS_TERMINAL};
+use crate::termco::AnsiColor;
+use crate::util::{Env, Environment};
+use crate::Float;
+
+#[cfg(feature = "tty")]
+use prettytty::Connection;
+
+/// The source of a color theme.
+#[cfg_attr(
+    feature = "pyffi",
+    pyclass(eq, eq_int, frozen, hash, module = "prettypretty.color.theme")
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ThemeSource {
+    /// The terminal's responses to OSC queries.
+    Query,
+    /// The Windows console's color table.
+    ConsolePalette,
+    /// The `COLORFGBG` environment variable.
+    ColorFgBg,
+    /// The Windows console's text attributes.
+    ConsoleAttributes,
+    /// The `TERM_PROGRAM` environment variable.
+    TermProgram,
+    /// No source at all.
+    Default,
+}
+
+impl ThemeSource {
+    /// Get the confidence in colors from this source.
+    fn confidence(&self) -> Float {
+        match self {
+            Self::Query => 1.0,
+            Self::ConsolePalette => 0.9,
+            Self::ConsoleAttributes => 0.8,
+            Self::ColorFgBg => 0.7,
+            Self::TermProgram => 0.4,
+            Self::Default => 0.1,
+        }
+    }
+}
+
+/// A color theme tagged with its source and the confidence in its colors.
+///
+/// [`Theme::estimate`] and [`Theme::query_or_estimate`] produce this struct.
+/// Only themes with source [`ThemeSource::Query`] reflect the terminal's
+/// actual colors. All other sources determine at most the default foreground
+/// and background colors and hence the theme's polarity. They fill in the
+/// remaining colors from a built-in theme.
+#[cfg_attr(
+    feature = "pyffi",
+    pyclass(frozen, module = "prettypretty.color.theme")
+)]
+#[derive(Clone, Debug)]
+pub struct ThemeEstimate {
+    theme: Theme,
+    source: ThemeSource,
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl ThemeEstimate {
+    /// Get the color theme.
+    pub fn theme(&self) -> Theme {
+        self.theme.clone()
+    }
+
+    /// Get the color theme's source.
+    pub fn source(&self) -> ThemeSource {
+        self.source
+    }
+
+    /// Get the confidence in the color theme, which ranges from 0 to 1.
+    ///
+    /// A theme queried from the terminal has confidence 1. A theme based on
+    /// the environment has lower confidence, since the environment may be
+    /// stale or only identify the terminal's default theme.
+    pub fn confidence(&self) -> Float {
+        self.source.confidence()
+    }
+
+    /// Determine whether the color theme is a dark theme.
+    pub fn is_dark(&self) -> bool {
+        self.theme.is_dark()
+    }
+
+    /// Get this estimate's debug representation. <i class=python-only>Python
+    /// only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl ThemeEstimate {
+    fn new(theme: Theme, source: ThemeSource) -> Self {
+        Self { theme, source }
+    }
+
+    /// Convert this estimate into its color theme.
+    pub fn into_theme(self) -> Theme {
+        self.theme
+    }
+}
+
+/// Create a theme from the palette with the given default colors.
+fn with_defaults(palette: &Theme, foreground: AnsiColor, background: AnsiColor) -> Theme {
+    let mut theme = palette.clone();
+    theme[ThemeEntry::DefaultForeground()] = palette[foreground].clone();
+    theme[ThemeEntry::DefaultBackground()] = palette[background].clone();
+    theme
+}
+
+/// Parse the value of `COLORFGBG` into foreground and background colors.
+///
+/// The value comprises two or three semicolon-separated fields, with the first
+/// field being the foreground and the last field the background color. rxvt
+/// uses the optional middle field for an XPM background.
+fn parse_colorfgbg(value: &str) -> Option<(AnsiColor, AnsiColor)> {
+    let fields = value.split(';').collect::<Vec<_>>();
+    if !(2..=3).contains(&fields.len()) {
+        return None;
+    }
+
+    let color = |s: &str| {
+        s.trim()
+            .parse::<u8>()
+            .ok()
+            .and_then(|n| AnsiColor::try_from(n).ok())
+    };
+    Some((color(fields[0])?, color(fields[fields.len() - 1])?))
+}
+
+/// Convert a Windows console color, i.e., a nibble with intensity, red, green,
+/// and blue bits in that order, into an ANSI color.
+fn console_color(nibble: u16) -> AnsiColor {
+    let index =
+        ((nibble & 0b1000) | (nibble & 0b100) >> 2 | (nibble & 0b10) | (nibble & 1) << 2) as u8;
+    // Unwrap is safe b/c index is smaller than 16.
+    AnsiColor::try_from(index).unwrap()
+}
+
+/// Create a theme from the Windows console's color table and text attributes.
+#[cfg(any(feature = "tty", test))]
+pub(crate) fn from_console(palette: &[[u8; 3]; 16], attributes: u16) -> Theme {
+    let mut theme = Theme::new();
+    for (index, [r, g, b]) in palette.iter().enumerate() {
+        theme[console_color(index as u16)] = crate::Color::from_24bit(*r, *g, *b);
+    }
+
+    let fg = console_color(attributes & 0xf);
+    let bg = console_color((attributes >> 4) & 0xf);
+    theme[ThemeEntry::DefaultForeground()] = theme[fg].clone();
+    theme[ThemeEntry::DefaultBackground()] = theme[bg].clone();
+    theme
+}
+
+/// Estimate the color theme from the environment and console attributes.
+pub(crate) fn estimate(env: &impl Environment, attributes: Option<u16>) -> ThemeEstimate {
+    if let Some((fg, bg)) = env
+        .read("COLORFGBG")
+        .ok()
+        .as_deref()
+        .and_then(parse_colorfgbg)
+    {
+        return ThemeEstimate::new(with_defaults(&VGA_COLORS, fg, bg), ThemeSource::ColorFgBg);
+    }
+
+    if let Some(attributes) = attributes {
+        let fg = console_color(attributes & 0xf);
+        let bg = console_color((attributes >> 4) & 0xf);
+        return ThemeEstimate::new(
+            with_defaults(&WINDOWS_TERMINAL, fg, bg),
+            ThemeSource::ConsoleAttributes,
+        );
+    }
+
+    if env.has_value("TERM_PROGRAM", "Apple_Terminal") {
+        return ThemeEstimate::new(MACOS_TERMINAL, ThemeSource::TermProgram);
+    }
+
+    ThemeEstimate::new(VGA_COLORS, ThemeSource::Default)
+}
+
+impl Theme {
+    /// Estimate the current color theme from the environment.
+    ///
+    /// When a terminal does not respond to OSC queries for its colors, this
+    /// associated function provides a fallback. It first checks the
+    /// `COLORFGBG` environment variable, which rxvt, Konsole, and several
+    /// other terminals set to the ANSI colors serving as default foreground
+    /// and background, and combines them with the [`VGA_COLORS`]. Failing
+    /// that, it checks whether `TERM_PROGRAM` identifies macOS Terminal.app
+    /// and uses its default theme, [`MACOS_TERMINAL`]. If all else fails, it
+    /// returns the [`VGA_COLORS`] with very low confidence.
+    ///
+    /// ```
+    /// # use prettypretty::theme::{Theme, ThemeSource};
+    /// let estimate = Theme::estimate();
+    /// assert_ne!(estimate.source(), ThemeSource::Query);
+    /// assert!(estimate.confidence() < 1.0);
+    /// ```
+    pub fn estimate() -> ThemeEstimate {
+        estimate(&Env::default(), None)
+    }
+
+    /// Query the terminal for the current color theme, falling back on an
+    /// estimate. <i class=tty-only>TTY only!</i>
+    ///
+    /// If [`Theme::query`] fails, e.g., because the terminal does not support
+    /// OSC queries and the query times out, this associated function falls
+    /// back on the Windows console's color table, which reflects the user's
+    /// actual colors on Windows versions whose console does not respond to OSC
+    /// 4, 10, and 11. Failing that, it falls back on [`Theme::estimate`]. On
+    /// Windows, it also considers the console's current text attributes, which
+    /// it interprets with the default colors of the Windows console, i.e.,
+    /// [`WINDOWS_TERMINAL`]. They take precedence over `TERM_PROGRAM` but not
+    /// `COLORFGBG`.
+    #[cfg(feature = "tty")]
+    pub fn query_or_estimate(connection: &Connection) -> ThemeEstimate {
+        if let Ok(theme) = Self::query(connection) {
+            return ThemeEstimate::new(theme, ThemeSource::Query);
+        }
+
+        let attributes = connection.console_attributes().ok();
+        if let (Ok(palette), Some(attributes)) = (connection.console_palette(), attributes) {
+            return ThemeEstimate::new(
+                from_console(&palette, attributes),
+                ThemeSource::ConsolePalette,
+            );
+        }
+
+        estimate(&Env::default(), attributes)
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{console_color, estimate, from_console, ThemeSource};
+    use crate::style::Layer;
+    use crate::termco::AnsiColor;
+    use crate::theme::{MACOS_TERMINAL, VGA_COLORS, WINDOWS_TERMINAL};
+    use crate::util::FakeEnv;
+
+    #[test]
+    fn test_estimate() {
+        let mut env = FakeEnv::new();
+        let result = estimate(&env, None);
+        assert_eq!(result.source(), ThemeSource::Default);
+        assert_eq!(result.theme(), VGA_COLORS);
+
+        env.set("TERM_PROGRAM", "Apple_Terminal");
+        let result = estimate(&env, None);
+        assert_eq!(result.source(), ThemeSource::TermProgram);
+        assert_eq!(result.theme(), MACOS_TERMINAL);
+        assert!(!result.is_dark());
+
+        // Gray on blue with intensity
+        let result = estimate(&env, Some(0x97));
+        assert_eq!(result.source(), ThemeSource::ConsoleAttributes);
+        assert!(result.is_dark());
+        assert_eq!(
+            result.theme()[Layer::Background],
+            WINDOWS_TERMINAL[AnsiColor::BrightBlue]
+        );
+
+        env.set("COLORFGBG", "15;default;0");
+        let result = estimate(&env, Some(0x97));
+        assert_eq!(result.source(), ThemeSource::ColorFgBg);
+        assert_eq!(result.confidence(), 0.7);
+        assert!(result.is_dark());
+        assert_eq!(
+            result.theme()[Layer::Foreground],
+            VGA_COLORS[AnsiColor::BrightWhite]
+        );
+
+        // The console palette in console order
+        let mut palette = [[0; 3]; 16];
+        for (index, rgb) in palette.iter_mut().enumerate() {
+            *rgb = WINDOWS_TERMINAL[console_color(index as u16)].to_24bit();
+        }
+        let theme = from_console(&palette, 0x97);
+        assert_eq!(theme.as_ref()[..16], WINDOWS_TERMINAL.as_ref()[..16]);
+        assert_eq!(theme[Layer::Foreground], WINDOWS_TERMINAL[AnsiColor::White]);
+
+        env.set("COLORFGBG", "15;0;default");
+        assert_eq!(estimate(&env, None).source(), ThemeSource::TermProgram);
+    }
+}
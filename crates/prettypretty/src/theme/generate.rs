@@ -0,0 +1,201 @@
+use super::{Theme, ThemeEntry};
+use crate::termco::AnsiColor;
+use crate::{Color, ColorSpace, Float};
+
+/// The six chromatic ANSI colors with their hues in Oklrch, in hue order.
+const ACCENTS: [(AnsiColor, Float); 6] = [
+    (AnsiColor::Red, 29.0),
+    (AnsiColor::Yellow, 110.0),
+    (AnsiColor::Green, 142.0),
+    (AnsiColor::Cyan, 195.0),
+    (AnsiColor::Blue, 264.0),
+    (AnsiColor::Magenta, 328.0),
+];
+
+/// The chroma for seeds that are too gray to provide one.
+const DEFAULT_CHROMA: Float = 0.12;
+
+/// The revised lightness of regular and bright accents, blacks, whites, as well
+/// as default foreground and background for dark themes and light themes.
+struct Ramp {
+    accent: [Float; 2],
+    black: [Float; 2],
+    white: [Float; 2],
+    foreground: Float,
+    background: Float,
+}
+
+const DARK: Ramp = Ramp {
+    accent: [0.62, 0.76],
+    black: [0.24, 0.46],
+    white: [0.80, 0.96],
+    foreground: 0.88,
+    background: 0.18,
+};
+
+const LIGHT: Ramp = Ramp {
+    accent: [0.48, 0.60],
+    black: [0.20, 0.44],
+    white: [0.84, 0.98],
+    foreground: 0.26,
+    background: 0.97,
+};
+
+/// Determine the seed's hue, chroma, and closest chromatic ANSI color.
+fn analyze(seed: &Color) -> Option<(Float, Float, usize)> {
+    let [_, chroma, hue] = *seed.to(ColorSpace::Oklrch).as_ref();
+    if hue.is_nan() || chroma < 0.02 {
+        return None;
+    }
+
+    let distance = |h: Float| {
+        let d = (hue - h).rem_euclid(360.0);
+        d.min(360.0 - d)
+    };
+    let mut closest = 0;
+    for (index, (_, h)) in ACCENTS.iter().enumerate() {
+        if distance(*h) < distance(ACCENTS[closest].1) {
+            closest = index;
+        }
+    }
+
+    Some((hue, chroma, closest))
+}
+
+/// Determine the signed difference between hues, in the range -180..180.
+fn hue_offset(hue: Float, base: Float) -> Float {
+    (hue - base + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Create an sRGB color from Oklrch coordinates.
+///
+/// If the color is out of gamut, this function reduces chroma until it fits.
+/// Unlike [`Color::to_gamut`], which may end up clipping coordinates, that
+/// preserves hue and lightness.
+fn color(lr: Float, chroma: Float, hue: Float) -> Color {
+    let hue = hue.rem_euclid(360.0);
+    let srgb = |c: Float| Color::oklrch(lr, c, hue).to(ColorSpace::Srgb);
+
+    let candidate = srgb(chroma);
+    if candidate.in_gamut() {
+        return candidate;
+    }
+
+    let (mut low, mut high) = (0.0, chroma);
+    for _ in 0..20 {
+        let middle = (low + high) / 2.0;
+        if srgb(middle).in_gamut() {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    srgb(low).to_gamut()
+}
+
+impl Theme {
+    /// Generate a color theme from one or two seed colors.
+    ///
+    /// This associated function derives a complete theme from the seeds, which
+    /// usually are a brand's accent colors. It matches each seed to the ANSI
+    /// color with the closest hue and uses the seed's hue for that ANSI color.
+    /// It then rotates the hues of the remaining ANSI colors by half the
+    /// primary seed's offset from its ANSI color, which keeps the hues in the
+    /// same order and hence compatible with the hue-lightness algorithm of
+    /// [`Translator::to_ansi`](crate::Translator::to_ansi). All accents share
+    /// the primary seed's chroma, limited to 0.08–0.2, while grays, default
+    /// foreground, and default background are slightly tinted with the primary
+    /// seed's hue. Lightness follows fixed ramps that keep regular and bright
+    /// accents readable against the dark or light background. The result
+    /// consists of sRGB colors only.
+    ///
+    /// ```
+    /// # use prettypretty::Color;
+    /// # use prettypretty::termco::AnsiColor;
+    /// # use prettypretty::theme::Theme;
+    /// let purple = Color::from_24bit(0x80, 0x40, 0xc0);
+    /// let theme = Theme::from_seed(&purple, None, true);
+    /// assert!(theme.is_dark());
+    /// assert_eq!(theme[AnsiColor::Magenta].to_hex_format(), "#af72f5");
+    /// assert!(!Theme::from_seed(&purple, None, false).is_dark());
+    /// ```
+    pub fn from_seed(primary: &Color, secondary: Option<&Color>, dark: bool) -> Self {
+        let ramp = if dark { &DARK } else { &LIGHT };
+
+        let mut hues = ACCENTS.map(|(_, h)| h);
+        let (tint, chroma) = match analyze(primary) {
+            Some((hue, chroma, closest)) => {
+                let offset = hue_offset(hue, hues[closest]);
+                for h in hues.iter_mut() {
+                    *h += offset / 2.0;
+                }
+                hues[closest] = hue;
+                (Some((hue, closest)), chroma.clamp(0.08, 0.2))
+            }
+            None => (None, DEFAULT_CHROMA),
+        };
+
+        if let Some((hue, _, closest)) = secondary.and_then(analyze) {
+            // The primary seed takes precedence and the secondary seed must
+            // not change the order of hues.
+            let previous = hues[(closest + 5) % 6];
+            let next = hues[(closest + 1) % 6];
+            let is_taken = tint.is_some_and(|(_, index)| index == closest);
+            if !is_taken && 0.0 < hue_offset(hue, previous) && 0.0 < hue_offset(next, hue) {
+                hues[closest] = hue;
+            }
+        }
+
+        let (tint_chroma, tint_hue) = match tint {
+            Some((hue, _)) => (0.012, hue),
+            None => (0.0, 0.0),
+        };
+        let gray = |lr: Float| color(lr, tint_chroma, tint_hue);
+
+        let mut theme = Theme::new();
+        for ((regular, _), hue) in ACCENTS.into_iter().zip(hues) {
+            theme[regular] = color(ramp.accent[0], chroma, hue);
+            theme[regular.to_bright()] = color(ramp.accent[1], chroma, hue);
+        }
+        theme[AnsiColor::Black] = gray(ramp.black[0]);
+        theme[AnsiColor::BrightBlack] = gray(ramp.black[1]);
+        theme[AnsiColor::White] = gray(ramp.white[0]);
+        theme[AnsiColor::BrightWhite] = gray(ramp.white[1]);
+        theme[ThemeEntry::DefaultForeground()] = gray(ramp.foreground);
+        theme[ThemeEntry::DefaultBackground()] = gray(ramp.background);
+        theme
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::termco::AnsiColor;
+    use crate::theme::Theme;
+    use crate::trans::HueLightnessTable;
+    use crate::{Color, ColorSpace};
+
+    #[test]
+    fn test_from_seed() {
+        let orange = Color::from_24bit(0xff, 0x80, 0x00);
+        let teal = Color::from_24bit(0x00, 0x80, 0x80);
+        let hue = |color: &Color| color.to(ColorSpace::Oklrch)[2];
+
+        for dark in [true, false] {
+            let theme = Theme::from_seed(&orange, Some(&teal), dark);
+            assert_eq!(theme.is_dark(), dark);
+            assert!(0.3 < theme.polarity_confidence());
+            assert!(HueLightnessTable::new(&theme, 0.05).is_some());
+
+            // Orange is closest to red and teal closest to cyan.
+            assert!((hue(&theme[AnsiColor::Red]) - hue(&orange)).abs() < 0.1);
+            assert!((hue(&theme[AnsiColor::Cyan]) - hue(&teal)).abs() < 0.1);
+        }
+
+        // Grays have no hue to rotate by.
+        let theme = Theme::from_seed(&Color::from_24bit(0x80, 0x80, 0x80), None, true);
+        assert!(HueLightnessTable::new(&theme, 0.05).is_some());
+        assert!(theme[AnsiColor::White].to(ColorSpace::Oklrch)[1] < 0.001);
+    }
+}
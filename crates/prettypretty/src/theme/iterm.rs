@@ -0,0 +1,308 @@
+use super::{Theme, ThemeEntry};
+use crate::error::ThemeFormatError;
+use crate::{Color, ColorSpace, Float};
+
+/// A value in an XML property list.
+#[derive(Debug)]
+enum Value {
+    Dict(Vec<(String, Value)>),
+    /// An array, whose elements are not needed for color themes.
+    Array,
+    Scalar(String),
+}
+
+impl Value {
+    fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Dict(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+fn error(message: impl Into<String>) -> ThemeFormatError {
+    ThemeFormatError::new("iTerm2", message)
+}
+
+/// A parser for the subset of XML used by property lists.
+struct Parser<'a> {
+    text: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    /// Skip white space, the XML declaration, the document type, and comments.
+    fn skip(&mut self) -> Result<(), ThemeFormatError> {
+        loop {
+            self.text = self.text.trim_start();
+            let end = if self.text.starts_with("<?") {
+                "?>"
+            } else if self.text.starts_with("<!--") {
+                "-->"
+            } else if self.text.starts_with("<!") {
+                ">"
+            } else {
+                return Ok(());
+            };
+
+            let index = self
+                .text
+                .find(end)
+                .ok_or_else(|| error("unterminated XML markup"))?;
+            self.text = &self.text[index + end.len()..];
+        }
+    }
+
+    /// Read the next tag, returning its name and whether it is self-closing.
+    fn tag(&mut self) -> Result<(&'a str, bool), ThemeFormatError> {
+        self.skip()?;
+        let rest = self
+            .text
+            .strip_prefix('<')
+            .ok_or_else(|| error("expected XML tag"))?;
+        let index = rest
+            .find('>')
+            .ok_or_else(|| error("unterminated XML tag"))?;
+        self.text = &rest[index + 1..];
+
+        let tag = &rest[..index];
+        let (tag, empty) = match tag.strip_suffix('/') {
+            Some(tag) => (tag, true),
+            None => (tag, false),
+        };
+        // Drop attributes such as plist's version.
+        let name = tag.split_whitespace().next().unwrap_or("");
+        Ok((name, empty))
+    }
+
+    /// Read text up to the closing tag with the given name.
+    fn text(&mut self, name: &str) -> Result<String, ThemeFormatError> {
+        let closing = format!("</{}>", name);
+        let index = self
+            .text
+            .find(&closing)
+            .ok_or_else(|| error(format!("missing closing tag for <{}>", name)))?;
+        let text = unescape(self.text[..index].trim());
+        self.text = &self.text[index + closing.len()..];
+        Ok(text)
+    }
+
+    /// Read the value starting with a tag with the given name.
+    fn value(&mut self, name: &str, empty: bool) -> Result<Value, ThemeFormatError> {
+        match (name, empty) {
+            ("true", true) | ("false", true) => Ok(Value::Scalar(name.to_string())),
+            (_, true) => Ok(Value::Scalar(String::new())),
+            ("dict", _) => {
+                let mut entries = Vec::new();
+                loop {
+                    match self.tag()? {
+                        ("/dict", _) => return Ok(Value::Dict(entries)),
+                        ("key", false) => {
+                            let key = self.text("key")?;
+                            let (name, empty) = self.tag()?;
+                            entries.push((key, self.value(name, empty)?));
+                        }
+                        (name, _) => return Err(error(format!("unexpected <{}> in <dict>", name))),
+                    }
+                }
+            }
+            ("array", _) => loop {
+                match self.tag()? {
+                    ("/array", _) => return Ok(Value::Array),
+                    (name, empty) => {
+                        self.value(name, empty)?;
+                    }
+                }
+            },
+            ("real" | "integer" | "string" | "date" | "data", _) => {
+                Ok(Value::Scalar(self.text(name)?))
+            }
+            _ => Err(error(format!("unexpected <{}>", name))),
+        }
+    }
+
+    /// Parse the property list.
+    fn parse(mut self) -> Result<Value, ThemeFormatError> {
+        let (mut name, mut empty) = self.tag()?;
+        if name == "plist" {
+            (name, empty) = self.tag()?;
+        }
+        self.value(name, empty)
+    }
+}
+
+/// Replace the predefined XML entities.
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Convert a color dictionary to a color.
+fn to_color(key: &str, value: &Value) -> Result<Color, ThemeFormatError> {
+    let component = |name: &str| -> Result<Float, ThemeFormatError> {
+        value
+            .get(name)
+            .and_then(Value::as_str)
+            .and_then(|s| s.parse::<Float>().ok())
+            .ok_or_else(|| error(format!("{} lacks valid {}", key, name)))
+    };
+
+    let coordinates = [
+        component("Red Component")?,
+        component("Green Component")?,
+        component("Blue Component")?,
+    ];
+
+    // Calibrated and device colors are treated as sRGB, which is what they
+    // usually are.
+    let space = match value.get("Color Space").and_then(Value::as_str) {
+        Some("P3") => ColorSpace::DisplayP3,
+        _ => ColorSpace::Srgb,
+    };
+    Ok(Color::new(space, coordinates))
+}
+
+impl Theme {
+    /// Read a color theme from an iTerm2 `.itermcolors` file.
+    ///
+    /// iTerm2's color schemes are XML property lists with a dictionary per
+    /// color. This method requires the `Ansi 0 Color` through `Ansi 15 Color`
+    /// as well as the `Foreground Color` and `Background Color` entries. It
+    /// also reads the optional `Cursor Color`, `Selected Text Color`, and
+    /// `Selection Color` entries into the extra theme entries. It ignores all
+    /// other entries, including the `(Light)` and `(Dark)` variants. Colors
+    /// in the `P3` color space become Display P3 colors, all other colors
+    /// become sRGB colors.
+    ///
+    /// ```
+    /// # use prettypretty::Color;
+    /// # use prettypretty::error::ThemeFormatError;
+    /// # use prettypretty::theme::{Theme, ThemeEntry};
+    /// let mut plist = String::from("<plist version=\"1.0\"><dict>");
+    /// for index in 0..16 {
+    ///     plist.push_str(&format!("<key>Ansi {} Color</key>", index));
+    ///     plist.push_str("<dict><key>Red Component</key><real>1</real>");
+    ///     plist.push_str("<key>Green Component</key><real>0.5</real>");
+    ///     plist.push_str("<key>Blue Component</key><real>0</real></dict>");
+    /// }
+    /// for key in ["Foreground Color", "Background Color", "Cursor Color"] {
+    ///     plist.push_str(&format!("<key>{}</key><dict><key>Color Space</key>", key));
+    ///     plist.push_str("<string>P3</string><key>Red Component</key><real>0</real>");
+    ///     plist.push_str("<key>Green Component</key><real>0</real>");
+    ///     plist.push_str("<key>Blue Component</key><real>1</real></dict>");
+    /// }
+    /// plist.push_str("</dict></plist>");
+    ///
+    /// let theme = Theme::from_itermcolors(&plist)?;
+    /// assert_eq!(theme[ThemeEntry::Cursor()], Color::p3(0, 0, 1));
+    /// assert!(!theme.has_extra(ThemeEntry::HighlightBackground()));
+    /// # Ok::<(), ThemeFormatError>(())
+    /// ```
+    pub fn from_itermcolors(text: &str) -> Result<Self, ThemeFormatError> {
+        let plist = Parser { text }.parse()?;
+        if !matches!(plist, Value::Dict(_)) {
+            return Err(error("property list is not a dictionary"));
+        }
+
+        let mut theme = Theme::new();
+        for entry in ThemeEntry::all().chain(ThemeEntry::extras()) {
+            let key = match entry {
+                ThemeEntry::Ansi(color) => format!("Ansi {} Color", color as u8),
+                ThemeEntry::DefaultForeground() => "Foreground Color".to_string(),
+                ThemeEntry::DefaultBackground() => "Background Color".to_string(),
+                ThemeEntry::Cursor() => "Cursor Color".to_string(),
+                ThemeEntry::HighlightForeground() => "Selected Text Color".to_string(),
+                ThemeEntry::HighlightBackground() => "Selection Color".to_string(),
+            };
+
+            match plist.get(&key) {
+                Some(value) => theme[entry] = to_color(&key, value)?,
+                None if entry.is_extra() => (),
+                None => return Err(error(format!("missing {}", key))),
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::error::ThemeFormatError;
+    use crate::termco::AnsiColor;
+    use crate::theme::{Theme, ThemeEntry};
+    use crate::Color;
+
+    #[test]
+    fn test_itermcolors() -> Result<(), ThemeFormatError> {
+        let mut plist = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" ",
+            "\"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+            "<plist version=\"1.0\">\n<dict>\n",
+            "  <!-- Exported by iTerm2 -->\n",
+            "  <key>Badge Color</key>\n  <dict><key>Alpha Component</key>",
+            "<real>0.5</real></dict>\n",
+        ));
+
+        for (index, key) in (0..16)
+            .map(|n| format!("Ansi {} Color", n))
+            .chain(["Foreground Color".into(), "Selection Color".into()])
+            .enumerate()
+        {
+            plist.push_str(&format!(
+                concat!(
+                    "  <key>{}</key>\n  <dict>\n",
+                    "    <key>Blue Component</key><real>{}</real>\n",
+                    "    <key>Color Space</key><string>sRGB</string>\n",
+                    "    <key>Green Component</key><integer>0</integer>\n",
+                    "    <key>Red Component</key><real>1</real>\n",
+                    "  </dict>\n"
+                ),
+                key,
+                index as f64 / 20.0
+            ));
+        }
+        plist.push_str("</dict>\n</plist>\n");
+
+        // The background color is missing.
+        let error = Theme::from_itermcolors(&plist).unwrap_err();
+        assert_eq!(error.message(), "missing Background Color");
+
+        let plist = plist.replace(
+            "</dict>\n</plist>",
+            concat!(
+                "  <key>Background Color</key>\n  <dict><key>Red Component</key><real>0</real>",
+                "<key>Green Component</key><real>0</real><key>Blue Component</key>",
+                "<real>0.25</real></dict>\n</dict>\n</plist>"
+            ),
+        );
+        let theme = Theme::from_itermcolors(&plist)?;
+        assert_eq!(theme[AnsiColor::Red], Color::srgb(1, 0, 0.05));
+        assert_eq!(
+            theme[ThemeEntry::DefaultForeground()],
+            Color::srgb(1, 0, 0.8)
+        );
+        assert_eq!(
+            theme[ThemeEntry::DefaultBackground()],
+            Color::srgb(0, 0, 0.25)
+        );
+        assert_eq!(
+            theme[ThemeEntry::HighlightBackground()],
+            Color::srgb(1, 0, 0.85)
+        );
+        assert!(!theme.has_extra(ThemeEntry::Cursor()));
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use std::str::FromStr;
+
+use super::{Theme, ThemeEntry};
+use crate::error::ThemeFormatError;
+use crate::termco::AnsiColor;
+use crate::Color;
+
+fn error(message: impl Into<String>) -> ThemeFormatError {
+    ThemeFormatError::new("Kitty", message)
+}
+
+/// Get the theme entry for the Kitty option.
+fn entry(option: &str) -> Option<ThemeEntry> {
+    let entry = match option {
+        "foreground" => ThemeEntry::DefaultForeground(),
+        "background" => ThemeEntry::DefaultBackground(),
+        "cursor" => ThemeEntry::Cursor(),
+        "selection_foreground" => ThemeEntry::HighlightForeground(),
+        "selection_background" => ThemeEntry::HighlightBackground(),
+        _ => {
+            let index = option.strip_prefix("color")?.parse::<u8>().ok()?;
+            ThemeEntry::Ansi(AnsiColor::try_from(index).ok()?)
+        }
+    };
+    Some(entry)
+}
+
+impl Theme {
+    /// Read a color theme from a Kitty configuration file.
+    ///
+    /// This method reads the `color0` through `color15`, `foreground`, and
+    /// `background` directives, which are required, as well as the optional
+    /// `cursor`, `selection_foreground`, and `selection_background`
+    /// directives, which set the extra theme entries. It ignores comments,
+    /// blank lines, and all other directives, including `color16` and up. The
+    /// special value `none` leaves an extra theme entry at its default. If a
+    /// directive appears more than once, the last one wins, just as for Kitty.
+    ///
+    /// ```
+    /// # use prettypretty::Color;
+    /// # use prettypretty::error::ThemeFormatError;
+    /// # use prettypretty::termco::AnsiColor;
+    /// # use prettypretty::theme::{Theme, ThemeEntry};
+    /// let mut config = String::from("# Tomorrow Night\nforeground #c5c8c6\n");
+    /// config.push_str("background #1d1f21\nselection_foreground none\n");
+    /// for index in 0..16 {
+    ///     config.push_str(&format!("color{}  #{:02x}0000\n", index, index * 16));
+    /// }
+    ///
+    /// let theme = Theme::from_kitty(&config)?;
+    /// assert_eq!(theme[AnsiColor::Blue], Color::from_24bit(64, 0, 0));
+    /// assert!(!theme.has_extra(ThemeEntry::HighlightForeground()));
+    /// # Ok::<(), ThemeFormatError>(())
+    /// ```
+    pub fn from_kitty(text: &str) -> Result<Self, ThemeFormatError> {
+        let mut theme = Theme::new();
+        let mut seen = [false; ThemeEntry::COUNT];
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (option, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let Some(entry) = entry(option) else {
+                continue;
+            };
+
+            let value = value.trim();
+            if entry.is_extra() && value == "none" {
+                theme.clear_extra(entry);
+                continue;
+            }
+
+            theme[entry] = Color::from_str(value).map_err(|e| {
+                error(format!(
+                    "{} on line {} is not a valid color: {}",
+                    option,
+                    number + 1,
+                    e
+                ))
+            })?;
+            if let Some(index) = ThemeEntry::all().position(|e| e == entry) {
+                seen[index] = true;
+            }
+        }
+
+        if let Some(index) = seen.iter().position(|s| !s) {
+            // Unwrap is safe b/c index is smaller than ThemeEntry::COUNT.
+            let entry = ThemeEntry::try_from(index).unwrap();
+            return Err(error(format!("missing {}", entry.name())));
+        }
+        Ok(theme)
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::error::ThemeFormatError;
+    use crate::termco::AnsiColor;
+    use crate::theme::{Theme, ThemeEntry, VGA_COLORS};
+
+    #[test]
+    fn test_kitty() -> Result<(), ThemeFormatError> {
+        let mut config = String::from("# VGA\n\nfont_size 12\ncursor #ffffff\n");
+        for index in 0..16 {
+            let color = &VGA_COLORS[AnsiColor::try_from(index).unwrap()];
+            config.push_str(&format!("color{}\t{}\n", index, color.to_hex_format()));
+        }
+        config.push_str("color16 #123456\nforeground #000\n");
+
+        let error = Theme::from_kitty(&config).unwrap_err();
+        assert_eq!(error.message(), "missing default background");
+
+        config.push_str("  background #fff  \nselection_background #808080\n");
+        let theme = Theme::from_kitty(&config)?;
+        assert_eq!(theme.as_ref(), VGA_COLORS.as_ref());
+        assert_eq!(theme[ThemeEntry::Cursor()].to_hex_format(), "#ffffff");
+        assert!(theme.has_extra(ThemeEntry::HighlightBackground()));
+
+        config.push_str("cursor none\n");
+        let theme = Theme::from_kitty(&config)?;
+        assert!(!theme.has_extra(ThemeEntry::Cursor()));
+
+        config.push_str("color3 yellow\n");
+        let error = Theme::from_kitty(&config).unwrap_err();
+        assert!(error
+            .message()
+            .starts_with("color3 on line 26 is not a valid color: color format should start"));
+        assert!(error.message().ends_with(
+            "    yellow\n    ^^^^^^ expected `#`, `rgb:`, `color()`, `oklab()`, or `oklch()`"
+        ));
+        Ok(())
+    }
+}
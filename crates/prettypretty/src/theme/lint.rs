@@ -0,0 +1,169 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use super::Theme;
+use crate::core::is_achromatic_chroma_hue;
+use crate::style::Layer;
+use crate::termco::AnsiColor;
+use crate::trans::{HueLightnessTable, TranslatorOptions};
+use crate::{ColorSpace, Float, OkVersion};
+
+/// The minimum contrast of chromatic ANSI colors against the default
+/// background, which corresponds to APCA's Lc 15 for non-text elements.
+const MIN_CONTRAST: Float = 0.15;
+
+/// The maximum distance in Oklrab between near-duplicate ANSI colors.
+const MAX_DISTANCE: Float = 0.02;
+
+/// A problem with a color theme.
+///
+/// [`Theme::lint`] produces these diagnostics. The first two variants flag
+/// colors that are hard to tell apart, from the background or from each other.
+/// The remaining three variants flag themes that violate the invariants of
+/// [`Translator::to_ansi`](crate::Translator::to_ansi)'s hue-lightness
+/// algorithm, which causes the translator to fall back on closest matches.
+#[cfg_attr(
+    feature = "pyffi",
+    pyclass(eq, frozen, module = "prettypretty.color.theme")
+)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ThemeIssue {
+    /// A chromatic ANSI color's contrast against the default background is
+    /// too low.
+    LowContrast(AnsiColor, Float),
+    /// Two ANSI colors are at the given, small distance in Oklrab.
+    NearDuplicate(AnsiColor, AnsiColor, Float),
+    /// A gray ANSI color has a hue.
+    NotGray(AnsiColor),
+    /// A chromatic ANSI color has no hue.
+    NotChromatic(AnsiColor),
+    /// The hues of chromatic ANSI colors are not in the order red, yellow,
+    /// green, cyan, blue, and magenta.
+    HueOrder(),
+}
+
+#[cfg(feature = "pyffi")]
+#[pymethods]
+impl ThemeIssue {
+    /// Describe this issue.
+    pub fn __str__(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl std::fmt::Display for ThemeIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LowContrast(color, contrast) => write!(
+                f,
+                "{} has low contrast {:.3} against default background",
+                color.name(),
+                contrast
+            ),
+            Self::NearDuplicate(color1, color2, distance) => write!(
+                f,
+                "{} and {} are near-duplicates at distance {:.3}",
+                color1.name(),
+                color2.name(),
+                distance
+            ),
+            Self::NotGray(color) => write!(f, "{} is not gray", color.name()),
+            Self::NotChromatic(color) => write!(f, "{} is gray", color.name()),
+            Self::HueOrder() => f.write_str("chromatic colors are out of hue order"),
+        }
+    }
+}
+
+/// Lint the color theme.
+pub(super) fn lint(theme: &Theme) -> Vec<ThemeIssue> {
+    let threshold = TranslatorOptions::default().achromatic_threshold();
+    let background = &theme[Layer::Background];
+    let mut issues = Vec::new();
+
+    for color in AnsiColor::all() {
+        let [_, chroma, hue] = *theme[color].to(ColorSpace::Oklrch).as_ref();
+        let is_gray = is_achromatic_chroma_hue(chroma, hue, threshold);
+
+        if color.is_achromatic() {
+            if !is_gray {
+                issues.push(ThemeIssue::NotGray(color));
+            }
+            continue;
+        } else if is_gray {
+            issues.push(ThemeIssue::NotChromatic(color));
+        }
+
+        let contrast = theme[color].contrast_against(background);
+        if contrast.abs() < MIN_CONTRAST {
+            issues.push(ThemeIssue::LowContrast(color, contrast));
+        }
+    }
+
+    // The hue-lightness table requires gray and chromatic colors first.
+    let has_gray_issue = issues
+        .iter()
+        .any(|issue| matches!(issue, ThemeIssue::NotGray(_) | ThemeIssue::NotChromatic(_)));
+    if !has_gray_issue && HueLightnessTable::new(theme, threshold).is_none() {
+        issues.push(ThemeIssue::HueOrder());
+    }
+
+    for color1 in AnsiColor::all() {
+        for color2 in AnsiColor::all().skip(color1 as usize + 1) {
+            let distance = theme[color1].distance(&theme[color2], OkVersion::Revised);
+            if distance < MAX_DISTANCE {
+                issues.push(ThemeIssue::NearDuplicate(color1, color2, distance));
+            }
+        }
+    }
+
+    issues
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::ThemeIssue;
+    use crate::style::Layer;
+    use crate::termco::AnsiColor;
+    use crate::theme::{Theme, MACOS_TERMINAL};
+    use crate::Color;
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(MACOS_TERMINAL.lint(), vec![]);
+
+        let mut theme = MACOS_TERMINAL.clone();
+        theme[AnsiColor::Blue] = theme[Layer::Background].clone();
+        theme[AnsiColor::BrightBlack] = theme[Layer::Background].clone();
+        let issues = theme.lint();
+        assert!(matches!(
+            issues[..],
+            [
+                ThemeIssue::NotChromatic(AnsiColor::Blue),
+                ThemeIssue::LowContrast(AnsiColor::Blue, _),
+                ThemeIssue::NearDuplicate(AnsiColor::Blue, AnsiColor::BrightBlack, _),
+            ]
+        ));
+
+        let mut theme = Theme::from_seed(&Color::from_24bit(0x80, 0x40, 0xc0), None, true);
+        let red = theme[AnsiColor::Red].clone();
+        theme[AnsiColor::Red] = theme[AnsiColor::Green].clone();
+        theme[AnsiColor::Green] = red;
+        assert_eq!(theme.lint(), vec![ThemeIssue::HueOrder()]);
+
+        theme[AnsiColor::Magenta] = theme[AnsiColor::Magenta].darken(4.0);
+        let issues = theme.lint();
+        assert!(matches!(
+            issues[..],
+            [
+                ThemeIssue::LowContrast(AnsiColor::Magenta, _),
+                ThemeIssue::HueOrder()
+            ]
+        ));
+        assert_eq!(
+            format!("{}", ThemeIssue::HueOrder()),
+            "chromatic colors are out of hue order"
+        );
+    }
+}
@@ -0,0 +1,1290 @@
+//! Utility module implementing terminal color themes.
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use crate::error::OutOfBoundsError;
+use crate::style::Layer;
+use crate::termco::{AnsiColor, EmbeddedRgb, GrayGradient};
+use crate::{rgb, Color, ColorSpace, Float};
+
+#[cfg(feature = "pyffi")]
+use crate::error::ThemeFormatError;
+#[cfg(feature = "tty")]
+use prettytty::cmd::{DynRequestColor, DynResetColor, DynSetColor, RequestColor};
+#[cfg(feature = "tty")]
+use prettytty::{Command, Connection, Control, Query, Scan};
+#[cfg(feature = "tty")]
+use std::io::Write;
+
+#[cfg(feature = "serde")]
+mod alacritty;
+mod base16;
+mod estimate;
+mod generate;
+mod iterm;
+mod kitty;
+mod lint;
+mod query;
+#[cfg(feature = "serde")]
+mod serial;
+#[cfg(feature = "serde")]
+mod windows;
+
+pub use estimate::{ThemeEstimate, ThemeSource};
+pub use lint::ThemeIssue;
+pub use query::ThemeQuery;
+
+/// A color theme.
+///
+/// A color theme is a container with [`ThemeEntry::COUNT`] colors, one each for
+/// the 16 ANSI colors as well as the default foreground and background colors
+/// (in that order). It also has three optional extra colors for the cursor and
+/// the highlight (i.e., selection) foreground and background. Unless set
+/// explicitly, e.g., by [`Theme::query_extras`], the extra colors fall back on
+/// the default foreground, default background, and default foreground colors,
+/// respectively, which matches terminals rendering selections in reverse
+/// video. The public interface is a compromise between struct and
+/// array, a straurray if you will, to make the primary use case, processing the
+/// colors in a theme, safer than when using numeric indices. Hence, you index a
+/// color theme with semantic values, i.e., [`ThemeEntry`], [`Layer`], or
+/// [`AnsiColor`]. At the same time, you can still access the underlying array
+/// storage through [`AsRef<[Color]> for
+/// Theme`](struct.Theme.html#impl-AsRef%3C%5BColor%5D%3E-for-Theme), albeit
+/// Rust-only and read-only.
+#[cfg_attr(feature = "pyffi", pyclass(module = "prettypretty.color.theme"))]
+#[derive(Clone, PartialEq, Eq)]
+pub struct Theme {
+    inner: [Color; ThemeEntry::COUNT],
+    extras: [Option<Color>; 3],
+}
+
+impl Theme {
+    /// Create a new color theme with [`ThemeEntry::COUNT`] times the default color.
+    pub fn new() -> Self {
+        Self {
+            inner: <[Color; ThemeEntry::COUNT]>::default(),
+            extras: [None, None, None],
+        }
+    }
+
+    /// Create a new color theme with the given colors.
+    pub const fn with_array(colors: [Color; ThemeEntry::COUNT]) -> Self {
+        Self {
+            inner: colors,
+            extras: [None, None, None],
+        }
+    }
+
+    /// Create a new color theme with the given colors.
+    ///
+    /// The given slice must have length [`ThemeEntry::COUNT`]. Otherwise, this
+    /// method returns `None`.
+    pub fn with_slice(colors: &[Color]) -> Option<Self> {
+        if colors.len() != ThemeEntry::COUNT {
+            None
+        } else {
+            let mut inner = <[Color; ThemeEntry::COUNT]>::default();
+            inner.clone_from_slice(colors);
+            Some(Self {
+                inner,
+                extras: [None, None, None],
+            })
+        }
+    }
+
+    /// Determine whether the extra theme entry has its own color, i.e., does
+    /// not fall back on a default color.
+    ///
+    /// # Panics
+    ///
+    /// If the theme entry is not an extra entry.
+    pub fn has_extra(&self, entry: ThemeEntry) -> bool {
+        self.extras[entry.extra_slot().0].is_some()
+    }
+
+    /// Clear the extra theme entry's color, i.e., make it fall back on a
+    /// default color again.
+    ///
+    /// # Panics
+    ///
+    /// If the theme entry is not an extra entry.
+    pub fn clear_extra(&mut self, entry: ThemeEntry) {
+        self.extras[entry.extra_slot().0] = None;
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl Theme {
+    /// Determine whether this color theme is a dark theme.
+    ///
+    /// The Y component of a color in XYZ represents its luminance. This method
+    /// exploits that property of XYZ and checks whether the default foreground
+    /// color has a larger luminance than the default background color.
+    ///
+    /// ```
+    /// # use prettypretty::theme::{DRACULA, SOLARIZED_LIGHT};
+    /// assert!(DRACULA.is_dark());
+    /// assert!(!SOLARIZED_LIGHT.is_dark());
+    /// assert!(SOLARIZED_LIGHT.polarity_confidence() < DRACULA.polarity_confidence());
+    /// ```
+    pub fn is_dark(&self) -> bool {
+        let yf = self[Layer::Foreground].to(ColorSpace::Xyz)[1];
+        let yb = self[Layer::Background].to(ColorSpace::Xyz)[1];
+        yb < yf
+    }
+
+    /// Get the default background color's lightness in Oklrab.
+    ///
+    /// The lightness ranges from 0 for black to 1 for white. Unlike
+    /// [`Theme::is_dark`], it does not depend on the foreground color and
+    /// hence also helps with choosing colors that need to stand out against
+    /// the background.
+    pub fn background_lightness(&self) -> Float {
+        self[Layer::Background].to(ColorSpace::Oklrab)[0]
+    }
+
+    /// Get the confidence in this theme's polarity as determined by
+    /// [`Theme::is_dark`].
+    ///
+    /// The confidence is the magnitude of the perceptual contrast between
+    /// default foreground and background colors, capped at 1. It approaches 0
+    /// as the two colors become indistinguishable, in which case the polarity
+    /// is more noise than signal. Well-designed themes score above 0.3.
+    pub fn polarity_confidence(&self) -> Float {
+        self[Layer::Foreground]
+            .contrast_against(&self[Layer::Background])
+            .abs()
+            .min(1.0)
+    }
+
+    /// Check this color theme for colors that are hard to tell apart and for
+    /// violations of the hue-lightness algorithm's invariants.
+    ///
+    /// This method flags chromatic ANSI colors with low contrast against the
+    /// default background, pairs of ANSI colors that are almost
+    /// indistinguishable, gray ANSI colors that aren't gray, chromatic ANSI
+    /// colors that are, and chromatic ANSI colors whose hues are out of order.
+    /// Since [`Translator::to_ansi`](crate::Translator::to_ansi) silently falls
+    /// back on closest matches for themes with any of the latter three issues,
+    /// this method helps explain otherwise surprising translations. The
+    /// achromatic threshold is the default of
+    /// [`TranslatorOptions`](crate::trans::TranslatorOptions).
+    ///
+    /// ```
+    /// # use prettypretty::termco::AnsiColor;
+    /// # use prettypretty::theme::{ThemeIssue, DRACULA, VGA_COLORS};
+    /// assert_eq!(DRACULA.lint(), vec![ThemeIssue::NotGray(AnsiColor::BrightBlack)]);
+    /// assert!(matches!(
+    ///     VGA_COLORS.lint()[0],
+    ///     ThemeIssue::LowContrast(AnsiColor::BrightYellow, _)
+    /// ));
+    /// ```
+    pub fn lint(&self) -> Vec<ThemeIssue> {
+        lint::lint(self)
+    }
+
+    /// Create a new color theme with the given colors.
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub const fn py_with_array(inner: [Color; ThemeEntry::COUNT]) -> Self {
+        Self::with_array(inner)
+    }
+
+    /// Read a color theme from an iTerm2 `.itermcolors` file. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "from_itermcolors")]
+    #[staticmethod]
+    pub fn py_from_itermcolors(text: &str) -> Result<Self, ThemeFormatError> {
+        Self::from_itermcolors(text)
+    }
+
+    /// Read a color theme from a Kitty configuration file. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "from_kitty")]
+    #[staticmethod]
+    pub fn py_from_kitty(text: &str) -> Result<Self, ThemeFormatError> {
+        Self::from_kitty(text)
+    }
+
+    /// Read a color theme from a base16 or base24 scheme. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "from_base16")]
+    #[staticmethod]
+    pub fn py_from_base16(text: &str) -> Result<Self, ThemeFormatError> {
+        Self::from_base16(text)
+    }
+
+    /// Estimate the current color theme from the environment. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "estimate")]
+    #[staticmethod]
+    pub fn py_estimate() -> ThemeEstimate {
+        Self::estimate()
+    }
+
+    /// Generate a color theme from one or two seed colors. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "from_seed", signature = (primary, secondary = None, dark = true))]
+    #[staticmethod]
+    pub fn py_from_seed(primary: &Color, secondary: Option<Color>, dark: bool) -> Self {
+        Self::from_seed(primary, secondary.as_ref(), dark)
+    }
+
+    /// Get the color for the given theme entry.
+    #[cfg(feature = "pyffi")]
+    pub fn __getitem__(
+        &self,
+        #[pyo3(from_py_with = "into_theme_entry")] index: ThemeEntry,
+    ) -> Color {
+        self[index].clone()
+    }
+
+    /// Set the color for the given theme entry.
+    #[cfg(feature = "pyffi")]
+    pub fn __setitem__(
+        &mut self,
+        #[pyo3(from_py_with = "into_theme_entry")] index: ThemeEntry,
+        value: Color,
+    ) {
+        self[index] = value;
+    }
+
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    /// Get the coordinates of this theme's colors in the given color space. <i
+    /// class=python-only>Python only!</i>
+    ///
+    /// The table has one row per [`ThemeEntry`], in canonical order.
+    #[cfg(feature = "pyffi")]
+    pub fn coordinate_table(&self, space: ColorSpace) -> crate::array::CoordinateTable {
+        let data = self.inner.iter().map(|c| *c.to(space).as_ref()).collect();
+        crate::array::CoordinateTable::new(space, data)
+    }
+
+    /// Reduce this color theme for pickling. <i class=python-only>Python
+    /// only!</i>
+    ///
+    /// The constructor arguments are the regular theme colors, whereas the
+    /// state is the extra colors, which are restored by
+    /// [`Theme::__setstate__`].
+    #[cfg(feature = "pyffi")]
+    #[allow(clippy::type_complexity)]
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> (
+        Bound<'py, pyo3::types::PyType>,
+        ([Color; ThemeEntry::COUNT],),
+        [Option<Color>; 3],
+    ) {
+        let theme = slf.borrow();
+        (slf.get_type(), (theme.inner.clone(),), theme.extras.clone())
+    }
+
+    /// Restore the extra colors of an unpickled color theme. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __setstate__(&mut self, extras: [Option<Color>; 3]) {
+        self.extras = extras;
+    }
+
+    /// Copy this color theme. <i class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    /// Deeply copy this color theme. <i class=python-only>Python only!</i>
+    ///
+    /// Since colors are immutable, this method is the same as
+    /// [`Theme::__copy__`].
+    #[cfg(feature = "pyffi")]
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Theme {
+    /// Query the terminal for the current color theme with async I/O. <i
+    /// class=tokio-only>Tokio only!</i>
+    ///
+    /// This associated function runs a [`ThemeQuery`] with the given reader
+    /// and writer, which usually are the two halves of an async terminal
+    /// connection. It fails with a [`TimedOut`](std::io::ErrorKind::TimedOut)
+    /// error if the terminal does not respond within the timeout.
+    pub async fn query_async<R, W>(
+        reader: &mut R,
+        writer: &mut W,
+        timeout: std::time::Duration,
+    ) -> std::io::Result<Self>
+    where
+        R: tokio::io::AsyncBufRead + Unpin,
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        ThemeQuery::new().run_async(reader, writer, timeout).await
+    }
+}
+
+#[cfg(feature = "tty")]
+impl Theme {
+    /// Query the terminal for the current theme colors using one loop.
+    #[doc(hidden)]
+    pub fn query1(connection: &Connection) -> std::io::Result<Self> {
+        let (mut input, mut output) = connection.io();
+        let mut theme = Self::new();
+
+        for entry in ThemeEntry::all() {
+            output.exec(entry)?;
+            let payload = input.read_sequence(entry.control())?;
+            theme[entry] = <ThemeEntry as Query>::parse(&entry, payload)?;
+        }
+
+        Ok(theme)
+    }
+
+    /// Query the terminal for the current theme colors using two loops.
+    #[doc(hidden)]
+    pub fn query2(connection: &Connection) -> std::io::Result<Self> {
+        let (mut input, mut output) = connection.io();
+        let mut theme = Self::new();
+
+        for entry in ThemeEntry::all() {
+            write!(output, "{}", entry)?;
+        }
+        output.flush()?;
+
+        for entry in ThemeEntry::all() {
+            let payload = input.read_sequence(entry.control())?;
+            theme[entry] = <ThemeEntry as Query>::parse(&entry, payload)?;
+        }
+
+        Ok(theme)
+    }
+
+    /// Query the terminal for the current theme colors using three loops.
+    #[doc(hidden)]
+    pub fn query3(connection: &Connection) -> std::io::Result<Theme> {
+        let (mut input, mut output) = connection.io();
+        let mut theme = Self::new();
+
+        for entry in ThemeEntry::all() {
+            write!(output, "{}", entry)?;
+        }
+        output.flush()?;
+
+        let mut payloads = Vec::with_capacity(18);
+        for entry in ThemeEntry::all() {
+            let payload = input.read_sequence(entry.control())?;
+            payloads.push(payload.to_owned());
+        }
+
+        for (entry, payload) in ThemeEntry::all().zip(payloads) {
+            theme[entry] = <ThemeEntry as Query>::parse(&entry, &payload)?;
+        }
+
+        Ok(theme)
+    }
+
+    /// Query the terminal for the current color theme. <i class=tty-only>TTY
+    /// only!</i>
+    ///
+    /// This method runs a [`ThemeQuery`] with the connection's input and
+    /// output.
+    pub fn query(connection: &Connection) -> std::io::Result<Self> {
+        let (mut input, mut output) = connection.io();
+        ThemeQuery::new().run(&mut input, &mut output)
+    }
+
+    /// Query the terminal for the extra theme entries. <i class=tty-only>TTY
+    /// only!</i>
+    ///
+    /// This method queries the cursor color as well as the highlight
+    /// foreground and background colors and stores them in this theme. Since
+    /// some terminals do not respond to these queries, which results in a
+    /// timeout, [`Theme::query`] does not include them. When a query fails,
+    /// this method leaves the remaining extra entries unchanged and returns the
+    /// error.
+    pub fn query_extras(&mut self, connection: &Connection) -> std::io::Result<()> {
+        let (mut input, mut output) = connection.io();
+
+        for entry in ThemeEntry::extras() {
+            output.exec(entry)?;
+            let payload = input.read_sequence(entry.control())?;
+            self[entry] = <ThemeEntry as Query>::parse(&entry, payload)?;
+        }
+
+        Ok(())
+    }
+
+    /// Query the terminal for the 240 colors of the 256-color palette beyond
+    /// the ANSI colors. <i class=tty-only>TTY only!</i>
+    ///
+    /// Many terminals let users remap the 256-color palette. This method
+    /// determines the actual colors with one OSC 4 query per color. To avoid
+    /// overflowing the terminal's input buffer, it writes queries in batches
+    /// and reads each batch's responses before writing the next one.
+    pub fn query_palette(connection: &Connection) -> std::io::Result<Palette256> {
+        const BATCH_SIZE: usize = 24;
+
+        let (mut input, mut output) = connection.io();
+        let mut colors = Vec::with_capacity(Palette256::COUNT);
+
+        for batch in (16..=255).collect::<Vec<u8>>().chunks(BATCH_SIZE) {
+            for index in batch {
+                write!(output, "{}", DynRequestColor(*index))?;
+            }
+            output.flush()?;
+
+            for index in batch {
+                let query = DynRequestColor(*index);
+                let payload = input.read_sequence(query.control())?;
+                colors.push(to_color(query.parse(payload)?));
+            }
+        }
+
+        // Unwrap is safe b/c the loop pushes exactly 240 colors.
+        Ok(Palette256::with_slice(&colors).unwrap())
+    }
+}
+
+/// Convert the parsed response to a color query into a color.
+#[cfg(feature = "tty")]
+fn to_color([r, g, b]: [(u16, u16); 3]) -> Color {
+    fn as_float((numerator, denominator): (u16, u16)) -> Float {
+        // 1, 2, 3, 4 --> 4, 8, 12, 16 --> 0x10, 0x100, 0x1000, 0x10000
+        numerator as Float / ((1 << (denominator << 2)) - 1) as Float
+    }
+
+    Color::srgb(as_float(r), as_float(g), as_float(b))
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsRef<[Color]> for Theme {
+    fn as_ref(&self) -> &[Color] {
+        &self.inner
+    }
+}
+
+impl std::ops::Index<ThemeEntry> for Theme {
+    type Output = Color;
+
+    fn index(&self, index: ThemeEntry) -> &Self::Output {
+        match index {
+            ThemeEntry::Ansi(color) => &self.inner[color as usize],
+            ThemeEntry::DefaultForeground() => &self.inner[16],
+            ThemeEntry::DefaultBackground() => &self.inner[17],
+            _ => {
+                let (slot, fallback) = index.extra_slot();
+                self.extras[slot].as_ref().unwrap_or(&self.inner[fallback])
+            }
+        }
+    }
+}
+
+impl std::ops::IndexMut<ThemeEntry> for Theme {
+    /// Mutably access the theme entry's color.
+    ///
+    /// For an extra entry without color, this method first sets the color to
+    /// the fallback color.
+    fn index_mut(&mut self, index: ThemeEntry) -> &mut Self::Output {
+        match index {
+            ThemeEntry::Ansi(color) => &mut self.inner[color as usize],
+            ThemeEntry::DefaultForeground() => &mut self.inner[16],
+            ThemeEntry::DefaultBackground() => &mut self.inner[17],
+            _ => {
+                let (slot, fallback) = index.extra_slot();
+                self.extras[slot].get_or_insert_with(|| self.inner[fallback].clone())
+            }
+        }
+    }
+}
+
+impl std::ops::Index<AnsiColor> for Theme {
+    type Output = Color;
+
+    fn index(&self, index: AnsiColor) -> &Self::Output {
+        &self.inner[index as usize]
+    }
+}
+
+impl std::ops::IndexMut<AnsiColor> for Theme {
+    fn index_mut(&mut self, index: AnsiColor) -> &mut Self::Output {
+        &mut self.inner[index as usize]
+    }
+}
+
+impl std::ops::Index<Layer> for Theme {
+    type Output = Color;
+
+    fn index(&self, index: Layer) -> &Self::Output {
+        match index {
+            Layer::Foreground => &self.inner[16],
+            Layer::Background => &self.inner[17],
+        }
+    }
+}
+
+impl std::fmt::Debug for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debugger = f.debug_struct("Theme");
+        for entry in ThemeEntry::all() {
+            debugger.field(&entry.name().replace(" ", "_"), &self[entry]);
+        }
+        for entry in ThemeEntry::extras() {
+            if self.has_extra(entry) {
+                debugger.field(&entry.name().replace(" ", "_"), &self[entry]);
+            }
+        }
+        debugger.finish()
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// The colors of the 256-color palette beyond the 16 ANSI colors.
+///
+/// By default, the 240 colors with indices 16–255 are the 6x6x6 embedded RGB
+/// cube and the 24-step gray gradient, with the same colors as
+/// [`EmbeddedRgb`] and [`GrayGradient`]. However, many terminals let users
+/// remap them. [`Theme::query_palette`] determines the actual colors and
+/// [`Translator::with_palette`](crate::Translator::with_palette) uses them for
+/// translation. Since they are the same for all standard palettes, a palette
+/// does not include the ANSI colors, which are part of the [`Theme`].
+///
+/// ```
+/// # use prettypretty::Color;
+/// # use prettypretty::termco::{EmbeddedRgb, GrayGradient};
+/// # use prettypretty::theme::Palette256;
+/// let mut palette = Palette256::default();
+/// assert!(palette.is_standard());
+///
+/// let embedded = EmbeddedRgb::new(5, 0, 0).unwrap();
+/// assert_eq!(palette[embedded], Color::from(embedded));
+/// palette[embedded] = Color::from_24bit(200, 0, 0);
+/// assert!(!palette.is_standard());
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Palette256 {
+    inner: Vec<Color>,
+}
+
+impl Palette256 {
+    /// The number of colors in a palette.
+    pub const COUNT: usize = 240;
+
+    /// Create a new palette with the standard colors.
+    pub fn new() -> Self {
+        let inner = (16..=255)
+            .map(|index: u8| {
+                EmbeddedRgb::try_from(index)
+                    .map(Color::from)
+                    .or_else(|_| GrayGradient::try_from(index).map(Color::from))
+                    .expect("index should be 8-bit color but not ANSI color")
+            })
+            .collect();
+
+        Self { inner }
+    }
+
+    /// Create a new palette with the given colors.
+    ///
+    /// The given slice must have length [`Palette256::COUNT`], with the first
+    /// color having index 16 and the last color index 255. Otherwise, this
+    /// method returns `None`.
+    pub fn with_slice(colors: &[Color]) -> Option<Self> {
+        if colors.len() != Self::COUNT {
+            None
+        } else {
+            Some(Self {
+                inner: colors.to_vec(),
+            })
+        }
+    }
+
+    /// Get the color for the given 8-bit index.
+    ///
+    /// This method returns `None` for the indices of ANSI colors.
+    pub fn get(&self, index: u8) -> Option<&Color> {
+        (index as usize)
+            .checked_sub(16)
+            .and_then(|index| self.inner.get(index))
+    }
+
+    /// Determine whether this palette has the standard colors.
+    pub fn is_standard(&self) -> bool {
+        *self == Self::new()
+    }
+}
+
+impl Default for Palette256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsRef<[Color]> for Palette256 {
+    fn as_ref(&self) -> &[Color] {
+        &self.inner
+    }
+}
+
+impl std::ops::Index<EmbeddedRgb> for Palette256 {
+    type Output = Color;
+
+    fn index(&self, index: EmbeddedRgb) -> &Self::Output {
+        &self.inner[u8::from(index) as usize - 16]
+    }
+}
+
+impl std::ops::IndexMut<EmbeddedRgb> for Palette256 {
+    fn index_mut(&mut self, index: EmbeddedRgb) -> &mut Self::Output {
+        &mut self.inner[u8::from(index) as usize - 16]
+    }
+}
+
+impl std::ops::Index<GrayGradient> for Palette256 {
+    type Output = Color;
+
+    fn index(&self, index: GrayGradient) -> &Self::Output {
+        &self.inner[u8::from(index) as usize - 16]
+    }
+}
+
+impl std::ops::IndexMut<GrayGradient> for Palette256 {
+    fn index_mut(&mut self, index: GrayGradient) -> &mut Self::Output {
+        &mut self.inner[u8::from(index) as usize - 16]
+    }
+}
+
+impl std::fmt::Debug for Palette256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries((16_u8..).zip(self.inner.iter()))
+            .finish()
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// A color theme entry.
+///
+/// This enumeration combines a variant wrapping an [`AnsiColor`] with two more
+/// variants for the default foreground and background colors to identify the
+/// [`ThemeEntry::COUNT`] entries of a color theme. Three further variants
+/// identify a theme's extra entries for the cursor color as well as the
+/// highlight foreground and background colors used for selections. Displaying
+/// a theme entry produces the ANSI escape sequence used to query a terminal for
+/// the corresponding color.
+#[cfg_attr(
+    feature = "pyffi",
+    pyclass(eq, frozen, hash, ord, module = "prettypretty.color.theme")
+)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ThemeEntry {
+    Ansi(AnsiColor),
+    DefaultForeground(),
+    DefaultBackground(),
+    Cursor(),
+    HighlightForeground(),
+    HighlightBackground(),
+}
+
+impl ThemeEntry {
+    /// The total number of theme entries, not counting the extra entries.
+    pub const COUNT: usize = 18;
+
+    /// The number of extra theme entries.
+    pub const EXTRA_COUNT: usize = 3;
+
+    /// Create a new iterator over all theme entries in canonical order.
+    ///
+    /// The iterator does not include the extra entries.
+    pub fn all() -> ThemeEntryIterator {
+        ThemeEntryIterator::new()
+    }
+
+    /// Create a new iterator over the extra theme entries.
+    pub fn extras() -> impl Iterator<Item = ThemeEntry> {
+        [
+            ThemeEntry::Cursor(),
+            ThemeEntry::HighlightForeground(),
+            ThemeEntry::HighlightBackground(),
+        ]
+        .into_iter()
+    }
+
+    /// Determine whether this theme entry is one of the extra entries.
+    pub fn is_extra(&self) -> bool {
+        matches!(
+            self,
+            Self::Cursor() | Self::HighlightForeground() | Self::HighlightBackground()
+        )
+    }
+
+    /// Get the slot for this extra entry and the index of its fallback color.
+    fn extra_slot(&self) -> (usize, usize) {
+        match self {
+            Self::Cursor() => (0, 16),
+            Self::HighlightForeground() => (1, 17),
+            Self::HighlightBackground() => (2, 16),
+            _ => panic!("{} is not an extra theme entry", self.name()),
+        }
+    }
+
+    /// Get the OSC code for querying or setting this entry's color.
+    ///
+    /// This method returns `None` for ANSI colors, which share OSC 4.
+    fn osc_code(&self) -> Option<u8> {
+        match self {
+            Self::Ansi(_) => None,
+            Self::DefaultForeground() => Some(10),
+            Self::DefaultBackground() => Some(11),
+            Self::Cursor() => Some(12),
+            Self::HighlightForeground() => Some(19),
+            Self::HighlightBackground() => Some(17),
+        }
+    }
+
+    /// Get a command to change this theme entry's color in the terminal.
+    /// <i class=tty-only>TTY only!</i>
+    ///
+    /// The command's display is an OSC 4, 10, 11, 12, 17, or 19 sequence that sets the
+    /// entry's color to the given color, after conversion to 24-bit RGB.
+    /// Applications that temporarily change the terminal's theme should
+    /// restore the original colors with [`ThemeEntry::reset`] before exiting.
+    ///
+    /// ```
+    /// # use prettypretty::Color;
+    /// # use prettypretty::termco::AnsiColor;
+    /// # use prettypretty::theme::ThemeEntry;
+    /// let entry = ThemeEntry::Ansi(AnsiColor::Red);
+    /// let color = Color::from_24bit(255, 0, 8);
+    /// assert_eq!(entry.set(&color).to_string(), "\x1b]4;1;rgb:ff/00/08\x1b\\");
+    /// assert_eq!(entry.reset().to_string(), "\x1b]104;1\x1b\\");
+    /// ```
+    #[cfg(feature = "tty")]
+    pub fn set(&self, color: &Color) -> DynSetColor {
+        let [r, g, b] = color.to_24bit();
+        DynSetColor(self.request(), r, g, b)
+    }
+
+    /// Get a command to restore this theme entry's original color in the
+    /// terminal. <i class=tty-only>TTY only!</i>
+    ///
+    /// The command's display is an OSC 104, 110, 111, 112, 117, or 119
+    /// sequence.
+    #[cfg(feature = "tty")]
+    pub fn reset(&self) -> DynResetColor {
+        DynResetColor(self.request())
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl ThemeEntry {
+    /// Create a new iterator over all theme entries in canonical order.
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "all")]
+    #[staticmethod]
+    pub fn py_all() -> ThemeEntryIterator {
+        ThemeEntryIterator::new()
+    }
+
+    /// Try getting the theme entry for the given index.
+    #[cfg(feature = "pyffi")]
+    #[staticmethod]
+    pub fn try_from_index(value: usize) -> Result<ThemeEntry, OutOfBoundsError> {
+        ThemeEntry::try_from(value)
+    }
+
+    /// Get this theme entry's human-readable name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ansi(color) => color.name(),
+            Self::DefaultForeground() => "default foreground",
+            Self::DefaultBackground() => "default background",
+            Self::Cursor() => "cursor",
+            Self::HighlightForeground() => "highlight foreground",
+            Self::HighlightBackground() => "highlight background",
+        }
+    }
+
+    /// Get a command to change this theme entry's color in the terminal.
+    /// <i class=python-only>Python only!</i>
+    ///
+    /// This method is exposed as `set` in Python and returns the ANSI escape
+    /// sequence as a string.
+    #[cfg(all(feature = "pyffi", feature = "tty"))]
+    #[pyo3(name = "set")]
+    pub fn py_set(&self, color: &Color) -> String {
+        self.set(color).to_string()
+    }
+
+    /// Get a command to restore this theme entry's original color in the
+    /// terminal. <i class=python-only>Python only!</i>
+    ///
+    /// This method is exposed as `reset` in Python and returns the ANSI escape
+    /// sequence as a string.
+    #[cfg(all(feature = "pyffi", feature = "tty"))]
+    #[pyo3(name = "reset")]
+    pub fn py_reset(&self) -> String {
+        self.reset().to_string()
+    }
+
+    /// Get an abbreviation for this theme entry's name.
+    ///
+    /// This method returns a two-letter abbreviations for this theme entry. See
+    /// [`AnsiColor::abbr`] for a description of the abbreviations for ANSI
+    /// colors.
+    pub fn abbr(&self) -> &'static str {
+        match self {
+            Self::Ansi(color) => color.abbr(),
+            Self::DefaultForeground() => "fg",
+            Self::DefaultBackground() => "bg",
+            Self::Cursor() => "cu",
+            Self::HighlightForeground() => "hf",
+            Self::HighlightBackground() => "hb",
+        }
+    }
+
+    /// Render a debug representation for this theme entry. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    /// Render an ANSI escape sequence to query a terminal for this theme
+    /// entry's current color. <i class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __str__(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+#[cfg(feature = "tty")]
+impl ThemeEntry {
+    /// Convert the theme entry to a color request. <i class=tty-only>TTY
+    /// only!</i>
+    pub fn request(&self) -> RequestColor {
+        if let ThemeEntry::Ansi(color) = self {
+            match color {
+                AnsiColor::Black => RequestColor::Black,
+                AnsiColor::Red => RequestColor::Red,
+                AnsiColor::Green => RequestColor::Green,
+                AnsiColor::Yellow => RequestColor::Yellow,
+                AnsiColor::Blue => RequestColor::Blue,
+                AnsiColor::Magenta => RequestColor::Magenta,
+                AnsiColor::Cyan => RequestColor::Cyan,
+                AnsiColor::White => RequestColor::White,
+                AnsiColor::BrightBlack => RequestColor::BrightBlack,
+                AnsiColor::BrightRed => RequestColor::BrightRed,
+                AnsiColor::BrightGreen => RequestColor::BrightGreen,
+                AnsiColor::BrightYellow => RequestColor::BrightYellow,
+                AnsiColor::BrightBlue => RequestColor::BrightBlue,
+                AnsiColor::BrightMagenta => RequestColor::BrightMagenta,
+                AnsiColor::BrightCyan => RequestColor::BrightCyan,
+                AnsiColor::BrightWhite => RequestColor::BrightWhite,
+            }
+        } else {
+            match self {
+                ThemeEntry::DefaultForeground() => RequestColor::Foreground,
+                ThemeEntry::DefaultBackground() => RequestColor::Background,
+                ThemeEntry::Cursor() => RequestColor::Cursor,
+                ThemeEntry::HighlightForeground() => RequestColor::SelectionForeground,
+                ThemeEntry::HighlightBackground() => RequestColor::Selection,
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+impl From<AnsiColor> for ThemeEntry {
+    fn from(value: AnsiColor) -> Self {
+        ThemeEntry::Ansi(value)
+    }
+}
+
+impl TryFrom<usize> for ThemeEntry {
+    type Error = OutOfBoundsError;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        if value <= 15 {
+            Ok(ThemeEntry::Ansi(AnsiColor::try_from(value as u8)?))
+        } else if value == 16 {
+            Ok(ThemeEntry::DefaultForeground())
+        } else if value == 17 {
+            Ok(ThemeEntry::DefaultBackground())
+        } else if value == 18 {
+            Ok(ThemeEntry::Cursor())
+        } else if value == 19 {
+            Ok(ThemeEntry::HighlightForeground())
+        } else if value == 20 {
+            Ok(ThemeEntry::HighlightBackground())
+        } else {
+            Err(OutOfBoundsError::new(value, 0..=20))
+        }
+    }
+}
+
+/// Convert ANSI colors and layers into theme entries.
+#[cfg(feature = "pyffi")]
+pub(crate) fn into_theme_entry(obj: &Bound<'_, PyAny>) -> PyResult<ThemeEntry> {
+    obj.extract::<ThemeEntry>()
+        .or_else(|_| obj.extract::<AnsiColor>().map(ThemeEntry::Ansi))
+        .or_else(|_| {
+            obj.extract::<Layer>().map(|l| match l {
+                Layer::Foreground => ThemeEntry::DefaultForeground(),
+                Layer::Background => ThemeEntry::DefaultBackground(),
+            })
+        })
+}
+
+#[cfg(feature = "tty")]
+/// Theme entry as a command. <i class=tty-only>TTY only!</i>
+impl Command for ThemeEntry {}
+
+#[cfg(feature = "tty")]
+/// Theme entry as a query. <i class=tty-only>TTY only!</i>
+impl Query for ThemeEntry {
+    type Response = Color;
+
+    fn control(&self) -> prettytty::Control {
+        Control::OSC
+    }
+
+    fn parse(&self, payload: &[u8]) -> std::io::Result<Self::Response> {
+        Ok(to_color(self.request().parse(payload)?))
+    }
+}
+
+impl std::fmt::Display for ThemeEntry {
+    /// Get an ANSI escape sequence to query a terminal for this theme entry's
+    /// current color.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ansi(color) => f.write_fmt(format_args!("\x1b]4;{};?\x1b\\", *color as u8)),
+            _ => write!(f, "\x1b]{};?\x1b\\", self.osc_code().unwrap()),
+        }
+    }
+}
+
+/// An iterator over theme entries.
+///
+/// [`ThemeEntry::all`] returns this iterator, which produces all theme entries
+/// in the canonical order. It is fused, i.e., after returning `None` once, it
+/// will keep returning `None`. It also is exact, i.e., its `size_hint()`
+/// returns the exact number of remaining items.
+#[cfg_attr(feature = "pyffi", pyclass(module = "prettypretty.color.theme"))]
+#[derive(Debug)]
+pub struct ThemeEntryIterator {
+    index: usize,
+}
+
+impl ThemeEntryIterator {
+    fn new() -> Self {
+        Self { index: 0 }
+    }
+}
+
+impl Iterator for ThemeEntryIterator {
+    type Item = ThemeEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if ThemeEntry::COUNT <= self.index {
+            None
+        } else {
+            let item =
+                ThemeEntry::try_from(self.index).expect("index should be smaller than count");
+            self.index += 1;
+            Some(item)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = ThemeEntry::COUNT - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl std::iter::ExactSizeIterator for ThemeEntryIterator {
+    fn len(&self) -> usize {
+        ThemeEntry::COUNT - self.index
+    }
+}
+
+impl std::iter::FusedIterator for ThemeEntryIterator {}
+
+#[cfg(feature = "pyffi")]
+#[pymethods]
+impl ThemeEntryIterator {
+    /// Get the number of remaining theme entries. <i class=python-only>Python
+    /// only!</i>
+    pub fn __len__(&self) -> usize {
+        self.len()
+    }
+
+    /// Return this iterator. <i class=python-only>Python only!</i>
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Return the next theme entry. <i class=python-only>Python only!</i>
+    pub fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<ThemeEntry> {
+        slf.next()
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// The color theme with the 2+16 colors of [VGA text
+/// mode](https://en.wikipedia.org/wiki/ANSI_escape_code#3-bit_and_4-bit).
+pub const VGA_COLORS: Theme = Theme::with_array([
+    rgb!(0, 0, 0),       // Black
+    rgb!(170, 0, 0),     // Red
+    rgb!(0, 170, 0),     // Green
+    rgb!(170, 85, 0),    // Yellow(ish)
+    rgb!(0, 0, 170),     // Blue
+    rgb!(170, 0, 170),   // Magenta
+    rgb!(0, 170, 170),   // Cyan
+    rgb!(170, 170, 170), // White
+    rgb!(85, 85, 85),    // Bright Black
+    rgb!(255, 85, 85),   // Bright Red
+    rgb!(85, 255, 85),   // Bright Green
+    rgb!(255, 255, 85),  // Bright Yellow
+    rgb!(85, 85, 255),   // Bright Blue
+    rgb!(255, 85, 255),  // Bright Magenta
+    rgb!(85, 255, 255),  // Bright Cyan
+    rgb!(255, 255, 255), // Bright White
+    rgb!(0, 0, 0),       // Default Foreground
+    rgb!(255, 255, 255), // Default Background
+]);
+
+/// The dark variant of [Solarized](https://ethanschoonover.com/solarized/)
+/// by Ethan Schoonover.
+pub const SOLARIZED_DARK: Theme = Theme::with_array([
+    rgb!(0x07, 0x36, 0x42), // Black
+    rgb!(0xdc, 0x32, 0x2f), // Red
+    rgb!(0x85, 0x99, 0x00), // Green
+    rgb!(0xb5, 0x89, 0x00), // Yellow
+    rgb!(0x26, 0x8b, 0xd2), // Blue
+    rgb!(0xd3, 0x36, 0x82), // Magenta
+    rgb!(0x2a, 0xa1, 0x98), // Cyan
+    rgb!(0xee, 0xe8, 0xd5), // White
+    rgb!(0x00, 0x2b, 0x36), // Bright Black
+    rgb!(0xcb, 0x4b, 0x16), // Bright Red
+    rgb!(0x58, 0x6e, 0x75), // Bright Green
+    rgb!(0x65, 0x7b, 0x83), // Bright Yellow
+    rgb!(0x83, 0x94, 0x96), // Bright Blue
+    rgb!(0x6c, 0x71, 0xc4), // Bright Magenta
+    rgb!(0x93, 0xa1, 0xa1), // Bright Cyan
+    rgb!(0xfd, 0xf6, 0xe3), // Bright White
+    rgb!(0x83, 0x94, 0x96), // Default Foreground
+    rgb!(0x00, 0x2b, 0x36), // Default Background
+]);
+
+/// The light variant of [Solarized](https://ethanschoonover.com/solarized/)
+/// by Ethan Schoonover.
+pub const SOLARIZED_LIGHT: Theme = Theme::with_array([
+    rgb!(0x07, 0x36, 0x42), // Black
+    rgb!(0xdc, 0x32, 0x2f), // Red
+    rgb!(0x85, 0x99, 0x00), // Green
+    rgb!(0xb5, 0x89, 0x00), // Yellow
+    rgb!(0x26, 0x8b, 0xd2), // Blue
+    rgb!(0xd3, 0x36, 0x82), // Magenta
+    rgb!(0x2a, 0xa1, 0x98), // Cyan
+    rgb!(0xee, 0xe8, 0xd5), // White
+    rgb!(0x00, 0x2b, 0x36), // Bright Black
+    rgb!(0xcb, 0x4b, 0x16), // Bright Red
+    rgb!(0x58, 0x6e, 0x75), // Bright Green
+    rgb!(0x65, 0x7b, 0x83), // Bright Yellow
+    rgb!(0x83, 0x94, 0x96), // Bright Blue
+    rgb!(0x6c, 0x71, 0xc4), // Bright Magenta
+    rgb!(0x93, 0xa1, 0xa1), // Bright Cyan
+    rgb!(0xfd, 0xf6, 0xe3), // Bright White
+    rgb!(0x65, 0x7b, 0x83), // Default Foreground
+    rgb!(0xfd, 0xf6, 0xe3), // Default Background
+]);
+
+/// The [Dracula](https://draculatheme.com) color theme.
+pub const DRACULA: Theme = Theme::with_array([
+    rgb!(0x21, 0x22, 0x2c), // Black
+    rgb!(0xff, 0x55, 0x55), // Red
+    rgb!(0x50, 0xfa, 0x7b), // Green
+    rgb!(0xf1, 0xfa, 0x8c), // Yellow
+    rgb!(0xbd, 0x93, 0xf9), // Blue
+    rgb!(0xff, 0x79, 0xc6), // Magenta
+    rgb!(0x8b, 0xe9, 0xfd), // Cyan
+    rgb!(0xf8, 0xf8, 0xf2), // White
+    rgb!(0x62, 0x72, 0xa4), // Bright Black
+    rgb!(0xff, 0x6e, 0x6e), // Bright Red
+    rgb!(0x69, 0xff, 0x94), // Bright Green
+    rgb!(0xff, 0xff, 0xa5), // Bright Yellow
+    rgb!(0xd6, 0xac, 0xff), // Bright Blue
+    rgb!(0xff, 0x92, 0xdf), // Bright Magenta
+    rgb!(0xa4, 0xff, 0xff), // Bright Cyan
+    rgb!(0xff, 0xff, 0xff), // Bright White
+    rgb!(0xf8, 0xf8, 0xf2), // Default Foreground
+    rgb!(0x28, 0x2a, 0x36), // Default Background
+]);
+
+/// The dark variant of [Gruvbox](https://github.com/morhetz/gruvbox).
+pub const GRUVBOX_DARK: Theme = Theme::with_array([
+    rgb!(0x28, 0x28, 0x28), // Black
+    rgb!(0xcc, 0x24, 0x1d), // Red
+    rgb!(0x98, 0x97, 0x1a), // Green
+    rgb!(0xd7, 0x99, 0x21), // Yellow
+    rgb!(0x45, 0x85, 0x88), // Blue
+    rgb!(0xb1, 0x62, 0x86), // Magenta
+    rgb!(0x68, 0x9d, 0x6a), // Cyan
+    rgb!(0xa8, 0x99, 0x84), // White
+    rgb!(0x92, 0x83, 0x74), // Bright Black
+    rgb!(0xfb, 0x49, 0x34), // Bright Red
+    rgb!(0xb8, 0xbb, 0x26), // Bright Green
+    rgb!(0xfa, 0xbd, 0x2f), // Bright Yellow
+    rgb!(0x83, 0xa5, 0x98), // Bright Blue
+    rgb!(0xd3, 0x86, 0x9b), // Bright Magenta
+    rgb!(0x8e, 0xc0, 0x7c), // Bright Cyan
+    rgb!(0xeb, 0xdb, 0xb2), // Bright White
+    rgb!(0xeb, 0xdb, 0xb2), // Default Foreground
+    rgb!(0x28, 0x28, 0x28), // Default Background
+]);
+
+/// The [Nord](https://www.nordtheme.com) color theme.
+pub const NORD: Theme = Theme::with_array([
+    rgb!(0x3b, 0x42, 0x52), // Black
+    rgb!(0xbf, 0x61, 0x6a), // Red
+    rgb!(0xa3, 0xbe, 0x8c), // Green
+    rgb!(0xeb, 0xcb, 0x8b), // Yellow
+    rgb!(0x81, 0xa1, 0xc1), // Blue
+    rgb!(0xb4, 0x8e, 0xad), // Magenta
+    rgb!(0x88, 0xc0, 0xd0), // Cyan
+    rgb!(0xe5, 0xe9, 0xf0), // White
+    rgb!(0x4c, 0x56, 0x6a), // Bright Black
+    rgb!(0xbf, 0x61, 0x6a), // Bright Red
+    rgb!(0xa3, 0xbe, 0x8c), // Bright Green
+    rgb!(0xeb, 0xcb, 0x8b), // Bright Yellow
+    rgb!(0x81, 0xa1, 0xc1), // Bright Blue
+    rgb!(0xb4, 0x8e, 0xad), // Bright Magenta
+    rgb!(0x8f, 0xbc, 0xbb), // Bright Cyan
+    rgb!(0xec, 0xef, 0xf4), // Bright White
+    rgb!(0xd8, 0xde, 0xe9), // Default Foreground
+    rgb!(0x2e, 0x34, 0x40), // Default Background
+]);
+
+/// The color theme of macOS Terminal's default *Basic* profile.
+pub const MACOS_TERMINAL: Theme = Theme::with_array([
+    rgb!(0x00, 0x00, 0x00), // Black
+    rgb!(0x99, 0x00, 0x00), // Red
+    rgb!(0x00, 0xa6, 0x00), // Green
+    rgb!(0x99, 0x99, 0x00), // Yellow
+    rgb!(0x00, 0x00, 0xb2), // Blue
+    rgb!(0xb2, 0x00, 0xb2), // Magenta
+    rgb!(0x00, 0xa6, 0xb2), // Cyan
+    rgb!(0xbf, 0xbf, 0xbf), // White
+    rgb!(0x66, 0x66, 0x66), // Bright Black
+    rgb!(0xe5, 0x00, 0x00), // Bright Red
+    rgb!(0x00, 0xd9, 0x00), // Bright Green
+    rgb!(0xe5, 0xe5, 0x00), // Bright Yellow
+    rgb!(0x00, 0x00, 0xff), // Bright Blue
+    rgb!(0xe5, 0x00, 0xe5), // Bright Magenta
+    rgb!(0x00, 0xe5, 0xe5), // Bright Cyan
+    rgb!(0xe5, 0xe5, 0xe5), // Bright White
+    rgb!(0x00, 0x00, 0x00), // Default Foreground
+    rgb!(0xff, 0xff, 0xff), // Default Background
+]);
+
+/// The color theme of Windows Terminal's default *Campbell* scheme.
+pub const WINDOWS_TERMINAL: Theme = Theme::with_array([
+    rgb!(0x0c, 0x0c, 0x0c), // Black
+    rgb!(0xc5, 0x0f, 0x1f), // Red
+    rgb!(0x13, 0xa1, 0x0e), // Green
+    rgb!(0xc1, 0x9c, 0x00), // Yellow
+    rgb!(0x00, 0x37, 0xda), // Blue
+    rgb!(0x88, 0x17, 0x98), // Magenta
+    rgb!(0x3a, 0x96, 0xdd), // Cyan
+    rgb!(0xcc, 0xcc, 0xcc), // White
+    rgb!(0x76, 0x76, 0x76), // Bright Black
+    rgb!(0xe7, 0x48, 0x56), // Bright Red
+    rgb!(0x16, 0xc6, 0x0c), // Bright Green
+    rgb!(0xf9, 0xf1, 0xa5), // Bright Yellow
+    rgb!(0x3b, 0x78, 0xff), // Bright Blue
+    rgb!(0xb4, 0x00, 0x9e), // Bright Magenta
+    rgb!(0x61, 0xd6, 0xd6), // Bright Cyan
+    rgb!(0xf2, 0xf2, 0xf2), // Bright White
+    rgb!(0xcc, 0xcc, 0xcc), // Default Foreground
+    rgb!(0x0c, 0x0c, 0x0c), // Default Background
+]);
+
+#[cfg(test)]
+mod test {
+    use super::{
+        ThemeEntry, DRACULA, GRUVBOX_DARK, MACOS_TERMINAL, NORD, SOLARIZED_DARK, SOLARIZED_LIGHT,
+        VGA_COLORS, WINDOWS_TERMINAL,
+    };
+    use crate::style::Layer;
+    use crate::termco::AnsiColor;
+    use crate::Color;
+
+    #[test]
+    fn test_theme_entry() {
+        assert_eq!(
+            format!("{}", ThemeEntry::DefaultForeground()),
+            "\x1b]10;?\x1b\\"
+        );
+
+        assert_eq!(
+            ThemeEntry::Ansi(AnsiColor::BrightGreen).to_string(),
+            "\x1b]4;10;?\x1b\\".to_string()
+        );
+        assert_eq!(
+            ThemeEntry::HighlightForeground().to_string(),
+            "\x1b]19;?\x1b\\"
+        );
+        #[cfg(feature = "tty")]
+        assert_eq!(ThemeEntry::Cursor().reset().to_string(), "\x1b]112\x1b\\");
+
+        let mut theme = VGA_COLORS.clone();
+        assert!(!theme.has_extra(ThemeEntry::Cursor()));
+        assert_eq!(
+            theme[ThemeEntry::HighlightBackground()],
+            theme[Layer::Foreground]
+        );
+        theme[ThemeEntry::Cursor()] = Color::from_24bit(255, 0, 0);
+        assert!(theme.has_extra(ThemeEntry::Cursor()));
+        assert_eq!(theme[ThemeEntry::Cursor()], Color::from_24bit(255, 0, 0));
+    }
+
+    #[test]
+    fn test_builtin_themes() {
+        let themes = [
+            VGA_COLORS,
+            SOLARIZED_DARK,
+            SOLARIZED_LIGHT,
+            DRACULA,
+            GRUVBOX_DARK,
+            NORD,
+            MACOS_TERMINAL,
+            WINDOWS_TERMINAL,
+        ];
+
+        for (index, theme) in themes.iter().enumerate() {
+            // Default colors are readable and themes are distinct.
+            let contrast = theme[Layer::Foreground].contrast_against(&theme[Layer::Background]);
+            assert!(0.3 < contrast.abs(), "{}: {}", index, contrast);
+            assert!(themes[index + 1..].iter().all(|t| t != theme));
+        }
+    }
+}
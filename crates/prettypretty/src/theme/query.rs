@@ -0,0 +1,427 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use std::io::{BufRead, Error, ErrorKind, Write};
+use std::str::FromStr;
+
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use super::{Theme, ThemeEntry};
+use crate::termco::AnsiColor;
+use crate::Color;
+
+/// The maximum length of an OSC response's payload.
+const MAX_PAYLOAD: usize = 128;
+
+/// The state of the scanner for OSC responses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Ground,
+    Escape,
+    Payload,
+    PayloadEscape,
+}
+
+/// Parse the payload of an OSC response into a theme entry and color.
+///
+/// This function returns `None` if the payload does not report the color of a
+/// theme entry and an error if it does but the color is malformed.
+fn parse_payload(payload: &[u8]) -> Option<std::io::Result<(ThemeEntry, Color)>> {
+    let payload = std::str::from_utf8(payload).ok()?;
+    let (code, rest) = payload.split_once(';')?;
+    let (entry, color) = match code {
+        "4" => {
+            let (index, color) = rest.split_once(';')?;
+            let index = AnsiColor::try_from(index.parse::<u8>().ok()?).ok()?;
+            (ThemeEntry::Ansi(index), color)
+        }
+        "10" => (ThemeEntry::DefaultForeground(), rest),
+        "11" => (ThemeEntry::DefaultBackground(), rest),
+        "12" => (ThemeEntry::Cursor(), rest),
+        "17" => (ThemeEntry::HighlightBackground(), rest),
+        "19" => (ThemeEntry::HighlightForeground(), rest),
+        _ => return None,
+    };
+
+    Some(
+        Color::from_str(color)
+            .map(|color| (entry, color))
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e)),
+    )
+}
+
+/// A sans-I/O query for a terminal's color theme.
+///
+/// This struct implements the protocol for querying a terminal's color theme
+/// without performing any I/O itself. [`ThemeQuery::request`] returns the OSC
+/// sequences to write to the terminal and [`ThemeQuery::feed`] consumes the
+/// terminal's responses, in as many chunks as necessary. Since this struct
+/// only relies on byte slices, it works with blocking and async I/O alike and
+/// with terminals that are connected through other means than a TTY, e.g., an
+/// SSH multiplexer. [`ThemeQuery::run`] drives the query for blocking readers
+/// and writers, and [`Theme::query`] is a thin wrapper around that method.
+/// [`ThemeQuery::run_async`] and [`Theme::query_async`] are their async
+/// equivalents.
+///
+/// Responses may arrive in any order and the query ignores all bytes that are
+/// not part of OSC sequences, e.g., stray keyboard input.
+///
+/// ```
+/// # use prettypretty::Color;
+/// # use prettypretty::termco::AnsiColor;
+/// # use prettypretty::theme::{ThemeQuery, VGA_COLORS};
+/// let mut query = ThemeQuery::new();
+/// assert!(query.request().starts_with("\x1b]4;0;?\x1b\\"));
+///
+/// let mut responses = String::new();
+/// for (index, color) in VGA_COLORS.as_ref().iter().enumerate() {
+///     let [r, g, b] = color.to_24bit();
+///     let code = match index {
+///         16 => "10".to_string(),
+///         17 => "11".to_string(),
+///         _ => format!("4;{}", index),
+///     };
+///     responses.push_str(&format!("\x1b]{};rgb:{:02x}/{:02x}/{:02x}\x07", code, r, g, b));
+/// }
+///
+/// // Feed the responses in two chunks.
+/// let (first, second) = responses.as_bytes().split_at(100);
+/// assert_eq!(query.feed(first)?, 100);
+/// assert!(!query.is_complete());
+/// query.feed(second)?;
+/// assert!(query.is_complete());
+/// assert_eq!(query.theme(), Some(VGA_COLORS));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(feature = "pyffi", pyclass(module = "prettypretty.color.theme"))]
+#[derive(Clone, Debug)]
+pub struct ThemeQuery {
+    entries: Vec<ThemeEntry>,
+    answered: Vec<bool>,
+    remaining: usize,
+    theme: Theme,
+    state: State,
+    payload: Vec<u8>,
+}
+
+impl ThemeQuery {
+    /// Create a new query for the [`ThemeEntry::COUNT`] theme entries.
+    pub fn new() -> Self {
+        Self::with_entries(ThemeEntry::all().collect())
+    }
+
+    /// Create a new query for all theme entries including the extra entries.
+    pub fn with_extras() -> Self {
+        Self::with_entries(ThemeEntry::all().chain(ThemeEntry::extras()).collect())
+    }
+
+    fn with_entries(entries: Vec<ThemeEntry>) -> Self {
+        let count = entries.len();
+        Self {
+            entries,
+            answered: vec![false; count],
+            remaining: count,
+            theme: Theme::new(),
+            state: State::Ground,
+            payload: Vec::new(),
+        }
+    }
+
+    /// Process the payload of a complete OSC response.
+    fn process(&mut self) -> std::io::Result<()> {
+        let Some(result) = parse_payload(&self.payload) else {
+            return Ok(());
+        };
+
+        let (entry, color) = result?;
+        if let Some(index) = self.entries.iter().position(|e| *e == entry) {
+            if !self.answered[index] {
+                self.answered[index] = true;
+                self.remaining -= 1;
+            }
+            self.theme[entry] = color;
+        }
+        Ok(())
+    }
+
+    /// Write the request to the given writer and flush it.
+    pub fn write_request(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        for entry in self.entries.iter() {
+            write!(writer, "{}", entry)?;
+        }
+        writer.flush()
+    }
+
+    /// Run this query with the given reader and writer.
+    ///
+    /// This method writes the request, reads responses until the query is
+    /// complete, and returns the resulting color theme. It uses the reader's
+    /// buffer to leave any bytes following the last response unconsumed. If
+    /// the reader returns no more bytes, e.g., because a terminal does not
+    /// support the queries and the read times out, this method fails with
+    /// [`ErrorKind::TimedOut`].
+    pub fn run(
+        mut self,
+        reader: &mut impl BufRead,
+        writer: &mut impl Write,
+    ) -> std::io::Result<Theme> {
+        self.write_request(writer)?;
+
+        while !self.is_complete() {
+            let bytes = reader.fill_buf()?;
+            if bytes.is_empty() {
+                return Err(ErrorKind::TimedOut.into());
+            }
+            let count = self.feed(bytes)?;
+            reader.consume(count);
+        }
+
+        Ok(self.theme)
+    }
+
+    /// Run this query with the given async reader and writer. <i
+    /// class=tokio-only>Tokio only!</i>
+    ///
+    /// This method is the async equivalent of [`ThemeQuery::run`]. If the
+    /// terminal does not respond within the given timeout, it fails with
+    /// [`ErrorKind::TimedOut`]. Since terminals that do not support the queries
+    /// simply ignore them, the timeout is essential for not waiting forever.
+    #[cfg(feature = "tokio")]
+    pub async fn run_async<R, W>(
+        mut self,
+        reader: &mut R,
+        writer: &mut W,
+        timeout: std::time::Duration,
+    ) -> std::io::Result<Theme>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        writer.write_all(self.request().as_bytes()).await?;
+        writer.flush().await?;
+
+        let responses = async {
+            while !self.is_complete() {
+                let bytes = reader.fill_buf().await?;
+                if bytes.is_empty() {
+                    return Err(ErrorKind::TimedOut.into());
+                }
+                let count = self.feed(bytes)?;
+                reader.consume(count);
+            }
+            Ok::<(), Error>(())
+        };
+
+        tokio::time::timeout(timeout, responses)
+            .await
+            .map_err(|_| Error::from(ErrorKind::TimedOut))??;
+        Ok(self.theme)
+    }
+
+    /// Convert this query into the color theme, if it is complete.
+    pub fn into_theme(self) -> Option<Theme> {
+        if self.is_complete() {
+            Some(self.theme)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for ThemeQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl ThemeQuery {
+    /// Create a new query for the [`ThemeEntry::COUNT`] theme entries. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub fn py_new() -> Self {
+        Self::new()
+    }
+
+    /// Create a new query for all theme entries including the extra entries.
+    /// <i class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "with_extras")]
+    #[staticmethod]
+    pub fn py_with_extras() -> Self {
+        Self::with_extras()
+    }
+
+    /// Get the request to write to the terminal.
+    pub fn request(&self) -> String {
+        self.entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    /// Feed the terminal's responses to this query.
+    ///
+    /// This method consumes bytes until the query is complete and returns the
+    /// number of consumed bytes. Any remaining bytes are not part of the
+    /// responses and should be processed by the application. This method
+    /// fails with [`ErrorKind::InvalidData`] if a response has a malformed
+    /// color or is too long.
+    pub fn feed(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        for (index, byte) in bytes.iter().enumerate() {
+            if self.is_complete() {
+                return Ok(index);
+            }
+
+            self.state = match (self.state, *byte) {
+                (State::Ground, 0x1b) => State::Escape,
+                (State::Ground, _) => State::Ground,
+                (State::Escape, b']') => {
+                    self.payload.clear();
+                    State::Payload
+                }
+                (State::Escape, 0x1b) => State::Escape,
+                (State::Escape, _) => State::Ground,
+                (State::Payload, 0x07) => {
+                    self.process()?;
+                    State::Ground
+                }
+                (State::Payload, 0x1b) => State::PayloadEscape,
+                (State::Payload, byte) => {
+                    if MAX_PAYLOAD <= self.payload.len() {
+                        return Err(Error::new(ErrorKind::InvalidData, "OSC response too long"));
+                    }
+                    self.payload.push(byte);
+                    State::Payload
+                }
+                (State::PayloadEscape, b'\\') => {
+                    self.process()?;
+                    State::Ground
+                }
+                // Any other escape sequence aborts the OSC response.
+                (State::PayloadEscape, b']') => {
+                    self.payload.clear();
+                    State::Payload
+                }
+                (State::PayloadEscape, 0x1b) => State::Escape,
+                (State::PayloadEscape, _) => State::Ground,
+            };
+        }
+
+        Ok(bytes.len())
+    }
+
+    /// Determine whether the terminal has responded to all queries.
+    pub fn is_complete(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Get the color theme, if the query is complete.
+    pub fn theme(&self) -> Option<Theme> {
+        if self.is_complete() {
+            Some(self.theme.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Get this query's debug representation. <i class=python-only>Python
+    /// only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::ThemeQuery;
+    use crate::termco::AnsiColor;
+    use crate::theme::{Theme, ThemeEntry, VGA_COLORS};
+    use std::io::ErrorKind;
+
+    fn respond(theme: &Theme, entries: impl Iterator<Item = ThemeEntry>) -> String {
+        let mut responses = String::new();
+        for entry in entries {
+            let [r, g, b] = theme[entry].to_24bit();
+            let code = match entry {
+                ThemeEntry::Ansi(color) => format!("4;{}", color as u8),
+                ThemeEntry::DefaultForeground() => "10".to_string(),
+                ThemeEntry::DefaultBackground() => "11".to_string(),
+                ThemeEntry::Cursor() => "12".to_string(),
+                ThemeEntry::HighlightBackground() => "17".to_string(),
+                ThemeEntry::HighlightForeground() => "19".to_string(),
+            };
+            responses.push_str(&format!(
+                "\x1b]{};rgb:{:02x}{:02x}/{:02x}{:02x}/{:02x}{:02x}\x1b\\",
+                code, r, r, g, g, b, b
+            ));
+        }
+        responses
+    }
+
+    #[test]
+    fn test_query() -> std::io::Result<()> {
+        // Responses in reverse order, with noise, and followed by more input.
+        let mut responses = String::from("key\x1b[A\x1b]52;c;?\x07");
+        responses.push_str(&respond(
+            &VGA_COLORS,
+            ThemeEntry::all().collect::<Vec<_>>().into_iter().rev(),
+        ));
+        responses.push_str("more");
+
+        let mut request = Vec::new();
+        let mut reader = responses.as_bytes();
+        let theme = ThemeQuery::new().run(&mut reader, &mut request)?;
+        assert_eq!(theme, VGA_COLORS);
+        assert_eq!(reader, b"more");
+        assert_eq!(request, ThemeQuery::new().request().as_bytes());
+
+        // Extras
+        let mut query = ThemeQuery::with_extras();
+        let mut theme = VGA_COLORS.clone();
+        theme[ThemeEntry::Cursor()] = theme[AnsiColor::Red].clone();
+        let responses = respond(&theme, ThemeEntry::all().chain(ThemeEntry::extras()));
+        assert_eq!(query.feed(responses.as_bytes())?, responses.len());
+        let result = query.into_theme().unwrap();
+        assert_eq!(result[ThemeEntry::Cursor()], theme[AnsiColor::Red]);
+        assert!(result.has_extra(ThemeEntry::HighlightForeground()));
+
+        // Timeout and malformed colors
+        let mut responses = respond(&VGA_COLORS, ThemeEntry::all().take(17));
+        let error = ThemeQuery::new()
+            .run(&mut responses.as_bytes(), &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::TimedOut);
+
+        responses.push_str("\x1b]11;rgb:fff/ggg/fff\x07");
+        let error = ThemeQuery::new().feed(responses.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_query_async() -> std::io::Result<()> {
+        use std::time::Duration;
+
+        let responses = respond(&VGA_COLORS, ThemeEntry::all());
+        let mut reader = responses.as_bytes();
+        let mut request = Vec::new();
+        let theme =
+            Theme::query_async(&mut reader, &mut request, Duration::from_millis(100)).await?;
+        assert_eq!(theme, VGA_COLORS);
+        assert_eq!(request, ThemeQuery::new().request().as_bytes());
+
+        // The terminal never responds.
+        let (terminal, _other) = tokio::io::duplex(1024);
+        let (reader, mut writer) = tokio::io::split(terminal);
+        let mut reader = tokio::io::BufReader::new(reader);
+        let error = Theme::query_async(&mut reader, &mut writer, Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::TimedOut);
+        Ok(())
+    }
+}
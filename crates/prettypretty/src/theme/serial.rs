@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use serde::de::Error as _;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Theme, ThemeEntry};
+use crate::error::ThemeFormatError;
+use crate::Color;
+
+/// The keys for theme entries, in canonical order and including the extra
+/// entries.
+const KEYS: [&str; ThemeEntry::COUNT + ThemeEntry::EXTRA_COUNT] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright_black",
+    "bright_red",
+    "bright_green",
+    "bright_yellow",
+    "bright_blue",
+    "bright_magenta",
+    "bright_cyan",
+    "bright_white",
+    "foreground",
+    "background",
+    "cursor",
+    "highlight_foreground",
+    "highlight_background",
+];
+
+fn entries() -> impl Iterator<Item = (&'static str, ThemeEntry)> {
+    ThemeEntry::all()
+        .chain(ThemeEntry::extras())
+        .enumerate()
+        .map(|(index, entry)| (KEYS[index], entry))
+}
+
+impl Serialize for Theme {
+    /// Serialize this theme as a struct with one color per theme entry. <i
+    /// class=serde-only>Serde only!</i>
+    ///
+    /// Extra entries are only included if they have their own colors.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let count = ThemeEntry::COUNT + self.extras.iter().filter(|c| c.is_some()).count();
+        let mut state = serializer.serialize_struct("Theme", count)?;
+        for (key, entry) in entries() {
+            if entry.is_extra() && !self.has_extra(entry) {
+                state.skip_field(key)?;
+            } else {
+                state.serialize_field(key, &self[entry])?;
+            }
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Theme {
+    /// Deserialize a theme from a struct with one color per theme entry. <i
+    /// class=serde-only>Serde only!</i>
+    ///
+    /// All entries but the extra entries are required. Unknown keys are
+    /// rejected.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut colors = HashMap::<String, Color>::deserialize(deserializer)?;
+        let mut theme = Theme::new();
+
+        for (key, entry) in entries() {
+            match colors.remove(key) {
+                Some(color) => theme[entry] = color,
+                None if entry.is_extra() => (),
+                None => return Err(D::Error::missing_field(key)),
+            }
+        }
+
+        if let Some(key) = colors.keys().next() {
+            return Err(D::Error::unknown_field(key, &KEYS));
+        }
+        Ok(theme)
+    }
+}
+
+impl Theme {
+    /// Read a color theme from TOML. <i class=serde-only>Serde only!</i>
+    ///
+    /// The TOML document has one key per theme entry, i.e., `black`, `red`,
+    /// …, `bright_white`, `foreground`, and `background`, plus optional
+    /// `cursor`, `highlight_foreground`, and `highlight_background` keys.
+    /// Values are strings in any color notation supported by
+    /// [`Color::from_str`](crate::Color::from_str). That makes it possible to
+    /// cache a terminal's color theme on disk and reuse it for invocations that
+    /// cannot query the terminal.
+    pub fn from_toml(text: &str) -> Result<Self, ThemeFormatError> {
+        toml::from_str(text).map_err(|e| ThemeFormatError::new("TOML", e.message()))
+    }
+
+    /// Write this color theme as TOML. <i class=serde-only>Serde only!</i>
+    ///
+    /// ```
+    /// # use prettypretty::theme::{Theme, VGA_COLORS};
+    /// # use prettypretty::error::ThemeFormatError;
+    /// let toml = VGA_COLORS.to_toml();
+    /// assert!(toml.starts_with("black = \"#000000\"\nred = \"#aa0000\"\n"));
+    /// assert_eq!(Theme::from_toml(&toml)?, VGA_COLORS);
+    /// # Ok::<(), ThemeFormatError>(())
+    /// ```
+    pub fn to_toml(&self) -> String {
+        // Unwrap is safe b/c themes always serialize as a table of strings.
+        toml::to_string(self).unwrap()
+    }
+
+    /// Read a color theme from JSON. <i class=serde-only>Serde only!</i>
+    ///
+    /// The JSON document is an object with the same keys and values as for
+    /// [`Theme::from_toml`].
+    pub fn from_json(text: &str) -> Result<Self, ThemeFormatError> {
+        serde_json::from_str(text).map_err(|e| ThemeFormatError::new("JSON", e.to_string()))
+    }
+
+    /// Write this color theme as pretty-printed JSON. <i class=serde-only>Serde
+    /// only!</i>
+    pub fn to_json(&self) -> String {
+        // Unwrap is safe b/c themes always serialize as an object of strings.
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::error::ThemeFormatError;
+    use crate::theme::{Theme, ThemeEntry, VGA_COLORS};
+    use crate::{Color, ColorSpace};
+
+    #[test]
+    fn test_serial() -> Result<(), ThemeFormatError> {
+        let mut theme = VGA_COLORS.clone();
+        theme[ThemeEntry::Cursor()] = Color::oklch(0.7, 0.1, 40.123456789);
+        theme[ThemeEntry::DefaultBackground()] =
+            Color::from_24bit(3, 4, 5).to(ColorSpace::DisplayP3);
+
+        let toml = theme.to_toml();
+        assert!(toml.contains("cursor = \"oklch(0.7 0.1 40.123456789)\"\n"));
+        assert!(!toml.contains("highlight_foreground"));
+        assert_eq!(Theme::from_toml(&toml)?, theme);
+        assert_eq!(Theme::from_json(&theme.to_json())?, theme);
+
+        let error = Theme::from_toml("black = \"#000\"").unwrap_err();
+        assert_eq!(error.format(), "TOML");
+        assert!(error.message().contains("missing field `red`"));
+
+        let toml = VGA_COLORS.to_toml() + "purple = \"#808\"\n";
+        assert!(Theme::from_toml(&toml)
+            .unwrap_err()
+            .message()
+            .contains("unknown field `purple`"));
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use std::str::FromStr;
+
+use super::{Theme, ThemeEntry};
+use crate::error::ThemeFormatError;
+use crate::termco::AnsiColor;
+use crate::Color;
+
+fn error(message: impl Into<String>) -> ThemeFormatError {
+    ThemeFormatError::new("Windows Terminal", message)
+}
+
+/// Get the Windows Terminal key for the theme entry.
+fn key(entry: ThemeEntry) -> Option<&'static str> {
+    use AnsiColor::*;
+
+    let key = match entry {
+        ThemeEntry::Ansi(color) => match color {
+            Black => "black",
+            Red => "red",
+            Green => "green",
+            Yellow => "yellow",
+            Blue => "blue",
+            Magenta => "purple",
+            Cyan => "cyan",
+            White => "white",
+            BrightBlack => "brightBlack",
+            BrightRed => "brightRed",
+            BrightGreen => "brightGreen",
+            BrightYellow => "brightYellow",
+            BrightBlue => "brightBlue",
+            BrightMagenta => "brightPurple",
+            BrightCyan => "brightCyan",
+            BrightWhite => "brightWhite",
+        },
+        ThemeEntry::DefaultForeground() => "foreground",
+        ThemeEntry::DefaultBackground() => "background",
+        ThemeEntry::Cursor() => "cursorColor",
+        ThemeEntry::HighlightForeground() => return None,
+        ThemeEntry::HighlightBackground() => "selectionBackground",
+    };
+    Some(key)
+}
+
+impl Theme {
+    /// Read a color theme from a Windows Terminal color scheme. <i
+    /// class=serde-only>Serde only!</i>
+    ///
+    /// The color scheme is a JSON object as found in the `schemes` array of
+    /// Windows Terminal's `settings.json`. This method requires the 16 ANSI
+    /// color keys, i.e., `black`, `red`, …, `brightWhite` with `purple`
+    /// instead of magenta, as well as `foreground` and `background`. It also
+    /// reads the optional `cursorColor` and `selectionBackground` keys into the
+    /// extra theme entries and ignores all other keys, including `name`. Since
+    /// Windows Terminal has no selection foreground, that extra theme entry
+    /// always falls back on the default.
+    ///
+    /// ```
+    /// # use prettypretty::Color;
+    /// # use prettypretty::error::ThemeFormatError;
+    /// # use prettypretty::termco::AnsiColor;
+    /// # use prettypretty::theme::{Theme, ThemeEntry};
+    /// let scheme = r##"{
+    ///     "name": "Campbell",
+    ///     "black": "#0C0C0C", "red": "#C50F1F", "green": "#13A10E",
+    ///     "yellow": "#C19C00", "blue": "#0037DA", "purple": "#881798",
+    ///     "cyan": "#3A96DD", "white": "#CCCCCC",
+    ///     "brightBlack": "#767676", "brightRed": "#E74856",
+    ///     "brightGreen": "#16C60C", "brightYellow": "#F9F1A5",
+    ///     "brightBlue": "#3B78FF", "brightPurple": "#B4009E",
+    ///     "brightCyan": "#61D6D6", "brightWhite": "#F2F2F2",
+    ///     "foreground": "#CCCCCC", "background": "#0C0C0C",
+    ///     "cursorColor": "#FFFFFF", "selectionBackground": "#FFFFFF"
+    /// }"##;
+    ///
+    /// let theme = Theme::from_windows_terminal(scheme)?;
+    /// assert_eq!(theme[AnsiColor::Magenta], Color::from_24bit(0x88, 0x17, 0x98));
+    /// assert!(theme.has_extra(ThemeEntry::Cursor()));
+    /// assert!(!theme.has_extra(ThemeEntry::HighlightForeground()));
+    /// # Ok::<(), ThemeFormatError>(())
+    /// ```
+    pub fn from_windows_terminal(text: &str) -> Result<Self, ThemeFormatError> {
+        let scheme: serde_json::Value =
+            serde_json::from_str(text).map_err(|e| error(e.to_string()))?;
+        let scheme = scheme
+            .as_object()
+            .ok_or_else(|| error("color scheme is not an object"))?;
+
+        let mut theme = Theme::new();
+        for entry in ThemeEntry::all().chain(ThemeEntry::extras()) {
+            let Some(key) = key(entry) else {
+                continue;
+            };
+
+            match scheme.get(key) {
+                Some(value) => {
+                    let color = value
+                        .as_str()
+                        .and_then(|s| Color::from_str(s).ok())
+                        .ok_or_else(|| error(format!("{} is not a valid color", key)))?;
+                    theme[entry] = color;
+                }
+                None if entry.is_extra() => (),
+                None => return Err(error(format!("missing {}", key))),
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::error::ThemeFormatError;
+    use crate::termco::AnsiColor;
+    use crate::theme::{Theme, ThemeEntry, VGA_COLORS};
+
+    #[test]
+    fn test_windows_terminal() -> Result<(), ThemeFormatError> {
+        let keys = [
+            "black",
+            "red",
+            "green",
+            "yellow",
+            "blue",
+            "purple",
+            "cyan",
+            "white",
+            "brightBlack",
+            "brightRed",
+            "brightGreen",
+            "brightYellow",
+            "brightBlue",
+            "brightPurple",
+            "brightCyan",
+            "brightWhite",
+            "foreground",
+            "background",
+        ];
+
+        let mut scheme = String::from("{\"name\": \"VGA\"");
+        for (key, color) in keys.iter().zip(VGA_COLORS.as_ref()) {
+            scheme.push_str(&format!(", \"{}\": \"{}\"", key, color.to_hex_format()));
+        }
+
+        let theme = Theme::from_windows_terminal(&(scheme.clone() + "}"))?;
+        assert_eq!(theme, VGA_COLORS);
+        assert!(!theme.has_extra(ThemeEntry::Cursor()));
+
+        let theme = Theme::from_windows_terminal(
+            &(scheme.clone() + ", \"selectionBackground\": \"#123\"}"),
+        )?;
+        assert_eq!(
+            theme[ThemeEntry::HighlightBackground()].to_hex_format(),
+            "#112233"
+        );
+
+        let error =
+            Theme::from_windows_terminal(&scheme.replace("\"purple\"", "\"magenta\"")).unwrap_err();
+        assert_eq!(error.format(), "Windows Terminal");
+        assert!(error.message().contains("EOF"));
+
+        let error =
+            Theme::from_windows_terminal(&(scheme.replace("\"purple\"", "\"magenta\"") + "}"))
+                .unwrap_err();
+        assert_eq!(error.message(), "missing purple");
+
+        let error = Theme::from_windows_terminal(&(scheme + ", \"cursorColor\": 42}")).unwrap_err();
+        assert_eq!(error.message(), "cursorColor is not a valid color");
+        assert_eq!(theme[AnsiColor::Magenta], VGA_COLORS[AnsiColor::Magenta]);
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use crate::error::TableFormatError;
+use crate::{ColorSpace, Float};
+
+/// The color spaces in encoding order.
+const SPACES: [ColorSpace; 12] = [
+    ColorSpace::Srgb,
+    ColorSpace::LinearSrgb,
+    ColorSpace::DisplayP3,
+    ColorSpace::LinearDisplayP3,
+    ColorSpace::Rec2020,
+    ColorSpace::LinearRec2020,
+    ColorSpace::Oklab,
+    ColorSpace::Oklch,
+    ColorSpace::Oklrab,
+    ColorSpace::Oklrch,
+    ColorSpace::Xyz,
+    ColorSpace::XyzD50,
+];
+
+/// An encoder for the binary format of translator tables.
+#[derive(Debug, Default)]
+pub(crate) struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn byte(&mut self, byte: u8) {
+        self.bytes.push(byte);
+    }
+
+    pub fn space(&mut self, space: ColorSpace) {
+        // Unwrap is safe b/c SPACES includes all color spaces.
+        let index = SPACES.iter().position(|s| *s == space).unwrap();
+        self.byte(index as u8);
+    }
+
+    pub fn float(&mut self, value: Float) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    pub fn coordinates(&mut self, coordinates: &[Float; 3]) {
+        for value in coordinates {
+            self.float(*value);
+        }
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// A decoder for the binary format of translator tables.
+#[derive(Debug)]
+pub(crate) struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn bytes(&mut self, count: usize) -> Result<&'a [u8], TableFormatError> {
+        if self.bytes.len() < count {
+            return Err(TableFormatError::UnexpectedEnd);
+        }
+
+        let (head, tail) = self.bytes.split_at(count);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    pub fn byte(&mut self) -> Result<u8, TableFormatError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub fn space(&mut self) -> Result<ColorSpace, TableFormatError> {
+        SPACES
+            .get(self.byte()? as usize)
+            .copied()
+            .ok_or(TableFormatError::InvalidValue)
+    }
+
+    pub fn float(&mut self) -> Result<Float, TableFormatError> {
+        let bytes = self.bytes(std::mem::size_of::<Float>())?;
+        // Unwrap is safe b/c bytes has the right length.
+        let value = Float::from_le_bytes(bytes.try_into().unwrap());
+        if value.is_nan() {
+            Err(TableFormatError::InvalidValue)
+        } else {
+            Ok(value)
+        }
+    }
+
+    pub fn coordinates(&mut self) -> Result<[Float; 3], TableFormatError> {
+        Ok([self.float()?, self.float()?, self.float()?])
+    }
+
+    pub fn finish(self) -> Result<(), TableFormatError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(TableFormatError::TrailingBytes)
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{Decoder, Encoder};
+    use crate::error::TableFormatError;
+    use crate::ColorSpace;
+
+    #[test]
+    fn test_codec() -> Result<(), TableFormatError> {
+        let mut encoder = Encoder::default();
+        encoder.byte(42);
+        encoder.space(ColorSpace::XyzD50);
+        encoder.coordinates(&[0.25, -1.0, 3.5]);
+        let bytes = encoder.finish();
+
+        let mut decoder = Decoder::new(&bytes);
+        assert_eq!(decoder.byte()?, 42);
+        assert_eq!(decoder.space()?, ColorSpace::XyzD50);
+        assert_eq!(decoder.coordinates()?, [0.25, -1.0, 3.5]);
+        decoder.finish()?;
+
+        let mut decoder = Decoder::new(&[12, 0]);
+        assert_eq!(decoder.space(), Err(TableFormatError::InvalidValue));
+        assert_eq!(decoder.float(), Err(TableFormatError::UnexpectedEnd));
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::core::{delta_e_ok, find_closest};
+use crate::Float;
+
+/// The Floyd–Steinberg weights for distributing the quantization error to the
+/// right, bottom-left, bottom, and bottom-right neighbors.
+const WEIGHTS: [(isize, usize, Float); 4] = [
+    (1, 0, 7.0 / 16.0),
+    (-1, 1, 3.0 / 16.0),
+    (0, 1, 5.0 / 16.0),
+    (1, 1, 1.0 / 16.0),
+];
+
+/// Quantize the pixels to the palette with Floyd–Steinberg error diffusion.
+///
+/// The pixels are a row-major buffer of coordinates in the same Oklab variant
+/// as the palette coordinates, with the last row possibly being shorter than
+/// `width`. This function updates the pixels in place while processing them in
+/// order and returns the palette index for each pixel.
+pub(crate) fn diffuse(
+    pixels: &mut [[Float; 3]],
+    width: usize,
+    palette: &[[Float; 3]],
+) -> Vec<usize> {
+    let width = width.max(1);
+    let mut indices = Vec::with_capacity(pixels.len());
+
+    for index in 0..pixels.len() {
+        let pixel = pixels[index];
+        // Unwrap is safe b/c palettes are never empty.
+        let closest = find_closest(&pixel, palette, delta_e_ok).unwrap();
+        indices.push(closest);
+
+        let target = &palette[closest];
+        let error = [
+            pixel[0] - target[0],
+            pixel[1] - target[1],
+            pixel[2] - target[2],
+        ];
+
+        let (row, column) = (index / width, index % width);
+        for (dx, dy, weight) in WEIGHTS {
+            let Some(x) = column.checked_add_signed(dx) else {
+                continue;
+            };
+            if width <= x {
+                continue;
+            }
+
+            let neighbor = (row + dy) * width + x;
+            if let Some(coordinates) = pixels.get_mut(neighbor) {
+                for (c, e) in coordinates.iter_mut().zip(error) {
+                    *c += weight * e;
+                }
+            }
+        }
+    }
+
+    indices
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::diffuse;
+
+    #[test]
+    fn test_diffuse() {
+        let palette = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]];
+
+        // A uniform mid-gray alternates between the two palette colors.
+        let mut pixels = [[0.5, 0.0, 0.0]; 8];
+        let indices = diffuse(&mut pixels, 4, &palette);
+        let ones = indices.iter().filter(|i| **i == 1).count();
+        assert_eq!(ones, 4);
+
+        // Colors in the palette stay unmodified.
+        let mut pixels = [[1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [1.0, 0.0, 0.0]];
+        assert_eq!(diffuse(&mut pixels, 2, &palette), vec![1, 0, 1]);
+    }
+}
@@ -1,4 +1,6 @@
+use super::{Decoder, Encoder};
 use crate::core::is_achromatic_chroma_hue;
+use crate::error::TableFormatError;
 use crate::termco::AnsiColor;
 use crate::theme::Theme;
 use crate::{Bits, Color, ColorSpace, Float};
@@ -15,11 +17,9 @@ impl GrayEntry {
     ///
     /// This associated function returns `None` if the ANSI color or its color
     /// value is not gray.
-    fn new(spec: AnsiColor, value: &Color) -> Option<GrayEntry> {
+    fn new(spec: AnsiColor, value: &Color, threshold: Float) -> Option<GrayEntry> {
         let [lr, c, h] = *value.to(ColorSpace::Oklrch).as_ref();
-        if !spec.is_achromatic()
-            || !is_achromatic_chroma_hue(c, h, HueLightnessTable::ACHROMATIC_THRESHOLD)
-        {
+        if !spec.is_achromatic() || !is_achromatic_chroma_hue(c, h, threshold) {
             return None;
         }
 
@@ -46,11 +46,9 @@ impl ColorEntry {
     ///
     /// This associated function returns `None` if the ANSI color or its
     /// concrete color value is gray.
-    fn new(spec: AnsiColor, value: &Color) -> Option<Self> {
+    fn new(spec: AnsiColor, value: &Color, threshold: Float) -> Option<Self> {
         let [lr, c, mut h] = *value.to(ColorSpace::Oklrch).as_ref();
-        if spec.is_achromatic()
-            || is_achromatic_chroma_hue(c, h, HueLightnessTable::ACHROMATIC_THRESHOLD)
-        {
+        if spec.is_achromatic() || is_achromatic_chroma_hue(c, h, threshold) {
             return None;
         }
         h = h.rem_euclid(360.0); // Critical for correctness!
@@ -87,33 +85,33 @@ impl ColorEntry {
 /// values, which is automatically observed.
 #[derive(Debug)]
 pub(crate) struct HueLightnessTable {
+    threshold: Float,
     grays: Vec<GrayEntry>,
     colors: Vec<ColorEntry>,
 }
 
 impl HueLightnessTable {
-    const ACHROMATIC_THRESHOLD: Float = 0.05;
-
     /// Create a new hue lightness table.
     ///
+    /// The threshold is the chroma below which colors are treated as grays.
     /// This associated function returns `None` if the theme colors violate any
     /// of the invariants.
-    pub fn new(theme: &Theme) -> Option<HueLightnessTable> {
+    pub fn new(theme: &Theme, threshold: Float) -> Option<HueLightnessTable> {
         use AnsiColor::*;
 
         // Prep the grays
         let mut grays = Vec::with_capacity(4);
         for index in [Black, White, BrightBlack, BrightWhite] {
-            grays.push(GrayEntry::new(index, &theme[index])?);
+            grays.push(GrayEntry::new(index, &theme[index], threshold)?);
         }
         grays.sort_by_key(|entry| entry.key());
 
         // Prep the non-grays in hue order: red, yellow, green, cyan, blue, magenta.
         let mut colors = Vec::with_capacity(12);
         for index in [Red, Yellow, Green, Cyan, Blue, Magenta] {
-            let regular = ColorEntry::new(index, &theme[index])?;
+            let regular = ColorEntry::new(index, &theme[index], threshold)?;
             let index = index.to_bright();
-            let bright = ColorEntry::new(index, &theme[index])?;
+            let bright = ColorEntry::new(index, &theme[index], threshold)?;
 
             // Order each color pair by hue
             if regular.h <= bright.h {
@@ -150,7 +148,63 @@ impl HueLightnessTable {
             min_hue = entry.h;
         }
 
-        Some(HueLightnessTable { grays, colors })
+        Some(HueLightnessTable {
+            threshold,
+            grays,
+            colors,
+        })
+    }
+
+    /// Encode this table.
+    pub fn encode(&self, encoder: &mut Encoder) {
+        for entry in self.grays.iter() {
+            encoder.byte(entry.spec as u8);
+            encoder.float(entry.lr);
+        }
+        for entry in self.colors.iter() {
+            encoder.byte(entry.spec as u8);
+            encoder.float(entry.lr);
+            encoder.float(entry.h);
+        }
+    }
+
+    /// Decode a table.
+    ///
+    /// This associated function only checks that the entries have the right
+    /// number and kinds of ANSI colors. It trusts the encoded lightness and hue
+    /// values to observe the remaining invariants.
+    pub fn decode(decoder: &mut Decoder, threshold: Float) -> Result<Self, TableFormatError> {
+        fn spec(decoder: &mut Decoder, achromatic: bool) -> Result<AnsiColor, TableFormatError> {
+            AnsiColor::try_from(decoder.byte()?)
+                .ok()
+                .filter(|color| color.is_achromatic() == achromatic)
+                .ok_or(TableFormatError::InvalidValue)
+        }
+
+        let mut grays = Vec::with_capacity(4);
+        for _ in 0..4 {
+            let spec = spec(decoder, true)?;
+            grays.push(GrayEntry {
+                spec,
+                lr: decoder.float()?,
+            });
+        }
+
+        let mut colors = Vec::with_capacity(12);
+        for _ in 0..12 {
+            let spec = spec(decoder, false)?;
+            colors.push(ColorEntry {
+                spec,
+                lr: decoder.float()?,
+                h: decoder.float()?,
+            });
+        }
+
+        Ok(Self {
+            threshold,
+            grays,
+            colors,
+        })
     }
 
     /// Find matching color.
@@ -163,7 +217,7 @@ impl HueLightnessTable {
         let [lr, c, h] = *color.to(ColorSpace::Oklrch).as_ref();
 
         // Select gray index by lr only. Not that there is anything else to go by...
-        if is_achromatic_chroma_hue(c, h, Self::ACHROMATIC_THRESHOLD) {
+        if is_achromatic_chroma_hue(c, h, self.threshold) {
             for index in 0..(self.grays.len() - 1) {
                 let entry1 = &self.grays[index];
                 let entry2 = &self.grays[index + 1];
@@ -188,8 +242,8 @@ impl HueLightnessTable {
             }
 
             // index has type usize. Hence (index - 1) is unsafe,
-            // but (index - 1 + length) isn't. Go rem, go!
-            let previous_entry = &self.colors[(index - 1 + length).rem_euclid(length)];
+            // but (index + length - 1) isn't. Go rem, go!
+            let previous_entry = &self.colors[(index + length - 1).rem_euclid(length)];
             if previous_entry.base() == next_entry.base() {
                 // Hue is bracketed by versions of same color.
                 let result = self.pick_lightness(lr, previous_entry, next_entry);
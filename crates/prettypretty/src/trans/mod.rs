@@ -1,8 +1,16 @@
 //! State and algorithms for the translation between high- and low-resolution
 //! colors.
 
+mod codec;
+mod dither;
 mod hue_lightness;
+mod options;
+mod report;
 mod translator;
 
+pub(crate) use codec::{Decoder, Encoder};
+pub(crate) use dither::diffuse;
 pub(crate) use hue_lightness::HueLightnessTable;
+pub use options::{AnsiMatching, TranslatorOptionBuilder, TranslatorOptions};
+pub use report::TranslationReport;
 pub use translator::Translator;
@@ -0,0 +1,135 @@
+use crate::Float;
+
+/// The algorithm for converting high-resolution colors to ANSI colors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnsiMatching {
+    /// Match by hue and lightness if the theme supports it and fall back on
+    /// the closest color otherwise.
+    #[default]
+    HueLightness,
+    /// Always match with the closest color.
+    Closest,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct OptionData {
+    achromatic_threshold: Float,
+    eight_bit_with_ansi: bool,
+    ansi_matching: AnsiMatching,
+    approximate_gamma: bool,
+}
+
+impl OptionData {
+    pub const fn new() -> Self {
+        Self {
+            achromatic_threshold: 0.05,
+            eight_bit_with_ansi: false,
+            ansi_matching: AnsiMatching::HueLightness,
+            approximate_gamma: false,
+        }
+    }
+}
+
+/// A builder of translator options.
+#[derive(Debug)]
+pub struct TranslatorOptionBuilder(OptionData);
+
+impl TranslatorOptionBuilder {
+    /// Set the chroma threshold for treating colors as gray when matching by
+    /// hue and lightness.
+    ///
+    /// Negative thresholds and not-a-number are treated as zero.
+    pub fn achromatic_threshold(&mut self, threshold: Float) -> &mut Self {
+        self.0.achromatic_threshold = threshold.max(0.0);
+        self
+    }
+
+    /// Set whether conversion to 8-bit colors also considers ANSI colors.
+    pub fn eight_bit_with_ansi(&mut self, with_ansi: bool) -> &mut Self {
+        self.0.eight_bit_with_ansi = with_ansi;
+        self
+    }
+
+    /// Set the algorithm for converting to ANSI colors.
+    pub fn ansi_matching(&mut self, matching: AnsiMatching) -> &mut Self {
+        self.0.ansi_matching = matching;
+        self
+    }
+
+    /// Set whether matching to the closest color approximates sRGB's gamma
+    /// with lookup tables.
+    pub fn approximate_gamma(&mut self, approximate: bool) -> &mut Self {
+        self.0.approximate_gamma = approximate;
+        self
+    }
+
+    /// Instantiate the options.
+    pub fn build(&self) -> TranslatorOptions {
+        TranslatorOptions(self.0)
+    }
+}
+
+/// The options for a [`Translator`](crate::Translator).
+///
+/// By default, a translator uses a chroma threshold of 0.05 for gray detection
+/// when matching by hue and lightness, prefers hue and lightness over the
+/// closest color when converting to ANSI colors, and excludes ANSI colors when
+/// converting to 8-bit colors. Since the defaults do not fit every theme, this
+/// struct makes all three configurable.
+///
+/// Furthermore, bulk conversions, notably [`Translator::dither`], may trade
+/// exactness for speed. When matching to the closest color, a translator with
+/// approximate gamma decodes and encodes sRGB and Display P3 coordinates by
+/// interpolating precomputed lookup tables instead of exponentiation. The
+/// results differ from the exact computation by less than 2e-5.
+///
+/// [`Translator::dither`]: crate::Translator::dither
+///
+/// ```
+/// # use prettypretty::{AnsiMatching, TranslatorOptions};
+/// let options = TranslatorOptions::builder()
+///     .achromatic_threshold(0.02)
+///     .ansi_matching(AnsiMatching::Closest)
+///     .build();
+///
+/// assert_eq!(options.achromatic_threshold(), 0.02);
+/// assert_eq!(options.ansi_matching(), AnsiMatching::Closest);
+/// assert!(!options.eight_bit_with_ansi());
+/// assert!(!options.approximate_gamma());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TranslatorOptions(OptionData);
+
+impl Default for TranslatorOptions {
+    fn default() -> Self {
+        TranslatorOptions(OptionData::new())
+    }
+}
+
+impl TranslatorOptions {
+    /// Create a new builder with the default option values.
+    pub fn builder() -> TranslatorOptionBuilder {
+        TranslatorOptionBuilder(OptionData::new())
+    }
+
+    /// Get the chroma threshold for gray detection.
+    pub fn achromatic_threshold(&self) -> Float {
+        self.0.achromatic_threshold
+    }
+
+    /// Determine whether conversion to 8-bit colors considers ANSI colors.
+    pub fn eight_bit_with_ansi(&self) -> bool {
+        self.0.eight_bit_with_ansi
+    }
+
+    /// Get the algorithm for converting to ANSI colors.
+    pub fn ansi_matching(&self) -> AnsiMatching {
+        self.0.ansi_matching
+    }
+
+    /// Determine whether matching to the closest color approximates sRGB's
+    /// gamma.
+    pub fn approximate_gamma(&self) -> bool {
+        self.0.approximate_gamma
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use crate::termco::AnsiColor;
+use crate::{Color, Float};
+
+/// An explanation of how a translator converts a high-resolution color to an
+/// ANSI color.
+///
+/// [`Translator::explain_ansi`](crate::Translator::explain_ansi) produces this
+/// report, which includes the input color in Oklrch, whether the translator
+/// treated the color as gray, which algorithm ran, the distances to all ANSI
+/// colors, and the chosen ANSI color. Together, they help answer questions
+/// such as why an orange became gray.
+///
+/// ```
+/// # use prettypretty::{Color, OkVersion, Translator};
+/// # use prettypretty::termco::AnsiColor;
+/// # use prettypretty::theme::VGA_COLORS;
+/// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+/// let orange = Color::from_24bit(255, 140, 0);
+/// let report = translator.explain_ansi(&orange);
+///
+/// assert_eq!(report.target(), translator.to_ansi(&orange));
+/// assert!(report.used_hue_lightness());
+/// assert!(!report.is_achromatic());
+/// assert_eq!(report.distances().len(), 16);
+/// ```
+#[cfg_attr(feature = "pyffi", pyclass(frozen, module = "prettypretty.color"))]
+#[derive(Clone, Debug)]
+pub struct TranslationReport {
+    pub(crate) color: Color,
+    pub(crate) achromatic: bool,
+    pub(crate) threshold: Float,
+    pub(crate) supports_hue_lightness: bool,
+    pub(crate) used_hue_lightness: bool,
+    pub(crate) distances: Vec<(AnsiColor, Float)>,
+    pub(crate) target: AnsiColor,
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl TranslationReport {
+    /// Get the input color in Oklrch.
+    pub fn color(&self) -> Color {
+        self.color.clone()
+    }
+
+    /// Determine whether the input color's chroma is at or below the
+    /// translator's achromatic threshold, i.e., the color counts as gray.
+    pub fn is_achromatic(&self) -> bool {
+        self.achromatic
+    }
+
+    /// Get the translator's achromatic threshold.
+    pub fn threshold(&self) -> Float {
+        self.threshold
+    }
+
+    /// Determine whether the translator's theme supports matching by hue and
+    /// lightness.
+    pub fn supports_hue_lightness(&self) -> bool {
+        self.supports_hue_lightness
+    }
+
+    /// Determine whether the translator matched by hue and lightness as
+    /// opposed to closest color.
+    pub fn used_hue_lightness(&self) -> bool {
+        self.used_hue_lightness
+    }
+
+    /// Get the chosen ANSI color.
+    pub fn target(&self) -> AnsiColor {
+        self.target
+    }
+
+    /// Get the distances to all ANSI colors, from closest to farthest. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "distances")]
+    pub fn py_distances(&self) -> Vec<(AnsiColor, Float)> {
+        self.distances.clone()
+    }
+
+    /// Get this translation report's debug representation. <i
+    /// class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    /// Get a human-readable explanation. <i class=python-only>Python only!</i>
+    #[cfg(feature = "pyffi")]
+    pub fn __str__(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl TranslationReport {
+    /// Get the distances to all ANSI colors, from closest to farthest.
+    ///
+    /// The distances are Delta-E in the translator's version of Oklab.
+    pub fn distances(&self) -> &[(AnsiColor, Float)] {
+        &self.distances
+    }
+}
+
+impl std::fmt::Display for TranslationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "input: {}", self.color)?;
+        writeln!(
+            f,
+            "chroma {:.5} {} threshold {:.5} → {}",
+            self.color[1],
+            if self.achromatic { "≤" } else { ">" },
+            self.threshold,
+            if self.achromatic { "gray" } else { "color" }
+        )?;
+
+        let algorithm = if self.used_hue_lightness {
+            "hue-lightness"
+        } else if self.supports_hue_lightness {
+            "closest"
+        } else {
+            "closest (theme does not support hue-lightness)"
+        };
+        writeln!(f, "algorithm: {}", algorithm)?;
+        writeln!(f, "target: {}", self.target.name())?;
+
+        f.write_str("distances:")?;
+        for (color, distance) in self.distances.iter() {
+            write!(f, "\n  {:<14} {:.5}", color.name(), distance)?;
+        }
+        Ok(())
+    }
+}
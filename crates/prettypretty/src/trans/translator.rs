@@ -1,10 +1,14 @@
 #[cfg(feature = "pyffi")]
 use pyo3::prelude::*;
 
-use super::HueLightnessTable;
+use super::{
+    diffuse, AnsiMatching, Decoder, Encoder, HueLightnessTable, TranslationReport,
+    TranslatorOptions,
+};
+use crate::error::TableFormatError;
 use crate::style::{Fidelity, Layer};
 use crate::termco::{AnsiColor, Colorant, EightBitColor, EmbeddedRgb, GrayGradient};
-use crate::theme::Theme;
+use crate::theme::{Palette256, Theme, ThemeEntry};
 use crate::{Color, ColorSpace, Float, OkVersion};
 
 /// A color translator.
@@ -22,8 +26,12 @@ use crate::{Color, ColorSpace, Float, OkVersion};
 /// [`Style`](crate::style::Style) uses a translator instance to cap styles.
 #[cfg_attr(feature = "pyffi", pyclass(module = "prettypretty.color"))]
 pub struct Translator {
+    /// The options for matching colors.
+    options: TranslatorOptions,
     /// The theme colors. For converting *to* high-resolution colors.
     theme: Theme,
+    /// The remapped 256-color palette, if any.
+    palette: Option<Palette256>,
     /// The table for matching by hue and lightness.
     hue_lightness_table: Option<HueLightnessTable>,
     /// The color space for the ANSI and 8-bit color coordinates.
@@ -74,29 +82,14 @@ impl Translator {
     #[cfg(feature = "pyffi")]
     #[new]
     pub fn new(version: OkVersion, theme: Theme) -> Self {
-        let hue_lightness_table = HueLightnessTable::new(&theme);
-        let space = version.cartesian_space();
-        let ansi = ansi_coordinates(space, &theme);
-        let eight_bit = eight_bit_coordinates(space, &theme);
-
-        Self {
-            theme,
-            hue_lightness_table,
-            space,
-            ansi,
-            eight_bit,
-        }
+        Self::with_options(version, theme, TranslatorOptions::default())
     }
 
     /// Determine whether this translator's color theme is a dark theme.
     ///
-    /// The Y component of a color in XYZ represents it luminance. This method
-    /// exploits that property of XYZ and checks whether the default foreground
-    /// color has a larger luminance than the default background color.
+    /// This method delegates to [`Theme::is_dark`].
     pub fn is_dark_theme(&self) -> bool {
-        let yf = self.theme[Layer::Foreground].to(ColorSpace::Xyz)[1];
-        let yb = self.theme[Layer::Background].to(ColorSpace::Xyz)[1];
-        yb < yf
+        self.theme.is_dark()
     }
 
     /// Resolve a colorant other than the default to a high-resolution color. <i
@@ -144,10 +137,53 @@ impl Translator {
     /// this method forwards to [`Translator::to_ansi_hue_lightness`].
     /// Otherwise, it falls back on [`Translator::to_closest_ansi`]. Use
     /// [`Translator::supports_hue_lightness`] to test whether the current theme
-    /// supports hue-lightness search.
+    /// supports hue-lightness search. If the translator's options specify
+    /// [`AnsiMatching::Closest`](crate::AnsiMatching::Closest), this method
+    /// always forwards to [`Translator::to_closest_ansi`].
     pub fn to_ansi(&self, color: &Color) -> AnsiColor {
-        self.to_ansi_hue_lightness(color)
-            .unwrap_or_else(|| self.to_closest_ansi(color))
+        match self.options.ansi_matching() {
+            AnsiMatching::HueLightness => self
+                .to_ansi_hue_lightness(color)
+                .unwrap_or_else(|| self.to_closest_ansi(color)),
+            AnsiMatching::Closest => self.to_closest_ansi(color),
+        }
+    }
+
+    /// Explain how this translator converts the high-resolution color to an
+    /// ANSI color.
+    ///
+    /// The resulting report's target is the same ANSI color as returned by
+    /// [`Translator::to_ansi`]. See [`TranslationReport`] for details.
+    pub fn explain_ansi(&self, color: &Color) -> TranslationReport {
+        use crate::core::{delta_e_ok, is_achromatic_chroma_hue};
+
+        let oklrch = color.to(ColorSpace::Oklrch);
+        let threshold = self.options.achromatic_threshold();
+        let achromatic = is_achromatic_chroma_hue(oklrch[1], oklrch[2], threshold);
+
+        let coordinates = color.to(self.space);
+        let mut distances = AnsiColor::all()
+            .map(|ansi| {
+                (
+                    ansi,
+                    delta_e_ok(coordinates.as_ref(), &self.ansi[ansi as usize]),
+                )
+            })
+            .collect::<Vec<_>>();
+        distances.sort_by(|(_, d1), (_, d2)| d1.total_cmp(d2));
+
+        let used_hue_lightness = self.options.ansi_matching() == AnsiMatching::HueLightness
+            && self.supports_hue_lightness();
+
+        TranslationReport {
+            color: oklrch,
+            achromatic,
+            threshold,
+            supports_hue_lightness: self.supports_hue_lightness(),
+            used_hue_lightness,
+            distances,
+            target: self.to_ansi(color),
+        }
     }
 
     /// Determine whether this translator instance supports color translation
@@ -379,12 +415,61 @@ impl Translator {
     pub fn to_closest_ansi(&self, color: &Color) -> AnsiColor {
         use crate::core::{delta_e_ok, find_closest};
 
-        let color = color.to(self.space);
-        find_closest(color.as_ref(), &self.ansi, delta_e_ok)
+        let color = self.to_coordinates(color);
+        find_closest(&color, &self.ansi, delta_e_ok)
             .map(|idx| AnsiColor::try_from(idx as u8).unwrap())
             .unwrap()
     }
 
+    /// Convert the high-resolution color into an ANSI color with at least the
+    /// given contrast against the background.
+    ///
+    /// Downsampling a foreground color to ANSI may pick the theme color that
+    /// also serves as background, rendering text nearly invisible. Hence, this
+    /// method first checks whether the ANSI color picked by
+    /// [`Translator::to_ansi`] has an absolute perceptual contrast, as computed
+    /// by [`Color::contrast_against`], of at least `min_contrast` against the
+    /// background. If not, it picks the closest ANSI color that does. If no
+    /// ANSI color meets the threshold, it falls back on the result of
+    /// [`Translator::to_ansi`].
+    ///
+    /// ```
+    /// # use prettypretty::{Color, OkVersion, Translator};
+    /// # use prettypretty::termco::AnsiColor;
+    /// # use prettypretty::theme::VGA_COLORS;
+    /// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+    /// let navy = Color::from_24bit(0, 0, 100);
+    /// let black = Color::from_24bit(0, 0, 0);
+    /// assert_eq!(translator.to_ansi(&navy), AnsiColor::Blue);
+    /// assert_ne!(
+    ///     translator.to_ansi_with_contrast(&navy, &black, 0.6),
+    ///     AnsiColor::Blue
+    /// );
+    /// ```
+    pub fn to_ansi_with_contrast(
+        &self,
+        color: &Color,
+        background: &Color,
+        min_contrast: Float,
+    ) -> AnsiColor {
+        use crate::core::delta_e_ok;
+
+        let meets_threshold =
+            |ansi: AnsiColor| min_contrast <= self.theme[ansi].contrast_against(background).abs();
+
+        let candidate = self.to_ansi(color);
+        if meets_threshold(candidate) {
+            return candidate;
+        }
+
+        let origin = self.to_coordinates(color);
+        AnsiColor::all()
+            .filter(|ansi| meets_threshold(*ansi))
+            .map(|ansi| (ansi, delta_e_ok(&origin, &self.ansi[ansi as usize])))
+            .min_by(|(_, d1), (_, d2)| d1.total_cmp(d2))
+            .map_or(candidate, |(ansi, _)| ansi)
+    }
+
     /// Convert the high-resolution color to an ANSI color in RGB.
     ///
     /// This method performs a conversion from high-resolution color to ANSI
@@ -472,9 +557,9 @@ impl Translator {
     pub fn to_closest_8bit(&self, color: &Color) -> EightBitColor {
         use crate::core::{delta_e_ok, find_closest};
 
-        let color = color.to(self.space);
+        let color = self.to_coordinates(color);
         let index = find_closest(
-            color.as_ref(),
+            &color,
             self.eight_bit.last_chunk::<240>().unwrap(),
             delta_e_ok,
         )
@@ -507,26 +592,62 @@ impl Translator {
     pub fn to_closest_8bit_with_ansi(&self, color: &Color) -> EightBitColor {
         use crate::core::{delta_e_ok, find_closest};
 
-        let color = color.to(self.space);
-        let index = find_closest(color.as_ref(), &self.eight_bit, delta_e_ok).unwrap() as u8;
+        let color = self.to_coordinates(color);
+        let index = find_closest(&color, &self.eight_bit, delta_e_ok).unwrap() as u8;
 
         EightBitColor::from(index)
     }
 
+    /// Resolve the high-resolution color to the best colorant for the given
+    /// fidelity.
+    ///
+    /// This method is the one-stop shop for turning a color meant for display
+    /// into a colorant the terminal can render. It picks the conversion based
+    /// on the fidelity level:
+    ///
+    ///   * `Plain`, `NoColor`: `None`
+    ///   * `Ansi`: [`Translator::to_ansi`]
+    ///   * `EightBit`: [`Translator::to_closest_8bit`], or
+    ///     [`Translator::to_closest_8bit_with_ansi`] if so configured
+    ///   * `TwentyFourBit`: the gamut-mapped 24-bit color
+    ///   * `HiRes`: the color itself
+    ///
+    /// ```
+    /// # use prettypretty::{Color, OkVersion, Translator};
+    /// # use prettypretty::style::Fidelity;
+    /// # use prettypretty::termco::{AnsiColor, Colorant, Rgb};
+    /// # use prettypretty::theme::VGA_COLORS;
+    /// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+    /// let orange = Color::from_24bit(255, 140, 0);
+    /// assert_eq!(translator.resolve_for(&orange, Fidelity::NoColor), None);
+    /// assert_eq!(
+    ///     translator.resolve_for(&orange, Fidelity::Ansi),
+    ///     Some(Colorant::Ansi(AnsiColor::Yellow))
+    /// );
+    /// assert_eq!(
+    ///     translator.resolve_for(&orange, Fidelity::TwentyFourBit),
+    ///     Some(Colorant::Rgb(Rgb::new(255, 140, 0)))
+    /// );
+    /// ```
+    pub fn resolve_for(&self, display_color: &Color, fidelity: Fidelity) -> Option<Colorant> {
+        match fidelity {
+            Fidelity::Plain | Fidelity::NoColor => None,
+            Fidelity::Ansi => Some(Colorant::Ansi(self.to_ansi(display_color))),
+            Fidelity::EightBit => Some(self.to_8bit(display_color).into()),
+            Fidelity::TwentyFourBit => Some(Colorant::Rgb(display_color.into())),
+            Fidelity::HiRes => Some(Colorant::HiRes(display_color.clone())),
+        }
+    }
+
     /// Cap the high-resolution color by the given fidelity.
     ///
     /// This method borrows the high-resolution color and clones the color only
     /// in the uncommon case that the fidelity is high-resolution. For that
     /// reason, prefer this method over [`Translator::cap`] when capping known
-    /// high-resolution colors.
+    /// high-resolution colors. It is equivalent to
+    /// [`Translator::resolve_for`].
     pub fn cap_hires(&self, color: &Color, fidelity: Fidelity) -> Option<Colorant> {
-        match fidelity {
-            Fidelity::Plain | Fidelity::NoColor => None,
-            Fidelity::Ansi => Some(Colorant::Ansi(self.to_ansi(color))),
-            Fidelity::EightBit => Some(self.to_closest_8bit(color).into()),
-            Fidelity::TwentyFourBit => Some(Colorant::Rgb(color.into())),
-            Fidelity::HiRes => Some(Colorant::HiRes(color.clone())),
-        }
+        self.resolve_for(color, fidelity)
     }
 
     /// Cap the colorant by the given fidelity.
@@ -558,7 +679,7 @@ impl Translator {
                     _ => return Some(colorant.clone()),
                 };
 
-                Some(self.to_closest_8bit(hires_color).into())
+                Some(self.to_8bit(hires_color).into())
             }
             Fidelity::TwentyFourBit => {
                 if let Colorant::HiRes(ref hires_color) = colorant {
@@ -609,28 +730,170 @@ impl Translator {
     pub fn __repr__(&self) -> String {
         format!("{:?}", self)
     }
+
+    /// Serialize this translator's theme and precomputed tables. <i
+    /// class=python-only>Python only!</i>
+    ///
+    /// This method is exposed as `to_bytes` in Python and wraps
+    /// [`Translator::to_bytes`].
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "to_bytes")]
+    pub fn py_to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, pyo3::types::PyBytes> {
+        pyo3::types::PyBytes::new(py, &self.to_bytes())
+    }
+
+    /// Deserialize a translator's theme and precomputed tables. <i
+    /// class=python-only>Python only!</i>
+    ///
+    /// This method is exposed as `from_bytes` in Python and wraps
+    /// [`Translator::from_bytes`].
+    #[cfg(feature = "pyffi")]
+    #[pyo3(name = "from_bytes")]
+    #[staticmethod]
+    pub fn py_from_bytes(bytes: &[u8]) -> Result<Self, TableFormatError> {
+        Self::from_bytes(bytes)
+    }
+
+    /// Get the coordinates of the 256 8-bit colors as used for matching. <i
+    /// class=python-only>Python only!</i>
+    ///
+    /// The coordinates are in Oklab or Oklrab, depending on the version
+    /// passed to the constructor. The first 16 rows are the ANSI colors.
+    #[cfg(feature = "pyffi")]
+    pub fn coordinate_table(&self) -> crate::array::CoordinateTable {
+        crate::array::CoordinateTable::new(self.space, self.eight_bit.to_vec())
+    }
+
+    /// Reduce this translator to its serialized tables for pickling. <i
+    /// class=python-only>Python only!</i>
+    ///
+    /// Unpickling a translator hence does not repeat the conversions and table
+    /// construction performed by the constructor.
+    #[cfg(feature = "pyffi")]
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, (Bound<'py, pyo3::types::PyBytes>,))> {
+        let py = slf.py();
+        let restore = py.get_type::<Self>().getattr("from_bytes")?;
+        Ok((restore, (slf.borrow().py_to_bytes(py),)))
+    }
+
+    /// Copy this translator. <i class=python-only>Python only!</i>
+    ///
+    /// Since translators are immutable, this method returns the same object.
+    #[cfg(feature = "pyffi")]
+    pub fn __copy__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// Deeply copy this translator. <i class=python-only>Python only!</i>
+    ///
+    /// Since translators are immutable, this method returns the same object.
+    #[cfg(feature = "pyffi")]
+    pub fn __deepcopy__(slf: Py<Self>, _memo: &Bound<'_, PyAny>) -> Py<Self> {
+        slf
+    }
 }
 
 #[cfg(not(feature = "pyffi"))]
 impl Translator {
     /// Create a new translator for the given Oklab version and theme colors.
     pub fn new(version: OkVersion, theme: Theme) -> Self {
-        let hue_lightness_table = HueLightnessTable::new(&theme);
+        Self::with_options(version, theme, TranslatorOptions::default())
+    }
+}
+
+impl Translator {
+    /// Create a new translator for the given Oklab version, theme colors, and
+    /// options. <i class=rust-only>Rust only!</i>
+    ///
+    /// ```
+    /// # use prettypretty::{AnsiMatching, Color, OkVersion, Translator, TranslatorOptions};
+    /// # use prettypretty::termco::AnsiColor;
+    /// # use prettypretty::theme::VGA_COLORS;
+    /// let options = TranslatorOptions::builder()
+    ///     .ansi_matching(AnsiMatching::Closest)
+    ///     .build();
+    /// let translator = Translator::with_options(
+    ///     OkVersion::Revised, VGA_COLORS.clone(), options);
+    ///
+    /// let yellow = Color::srgb(1.0, 1.0, 0.0);
+    /// assert_eq!(translator.to_ansi(&yellow), translator.to_closest_ansi(&yellow));
+    /// ```
+    pub fn with_options(version: OkVersion, theme: Theme, options: TranslatorOptions) -> Self {
+        let hue_lightness_table = HueLightnessTable::new(&theme, options.achromatic_threshold());
         let space = version.cartesian_space();
         let ansi = ansi_coordinates(space, &theme);
         let eight_bit = eight_bit_coordinates(space, &theme);
 
         Self {
+            options,
             theme,
+            palette: None,
             hue_lightness_table,
             space,
             ansi,
             eight_bit,
         }
     }
-}
 
-impl Translator {
+    /// Use the given 256-color palette instead of the standard colors for
+    /// embedded RGB and gray gradient colors. <i class=rust-only>Rust only!</i>
+    ///
+    /// This method consumes the translator and updates both the precomputed
+    /// coordinates for matching 8-bit colors and the resolution of 8-bit colors
+    /// to high-resolution colors. Use [`Theme::query_palette`] to determine
+    /// the terminal's actual palette.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, OkVersion, Translator};
+    /// # use prettypretty::termco::{Colorant, EmbeddedRgb};
+    /// # use prettypretty::theme::{Palette256, VGA_COLORS};
+    /// let embedded = EmbeddedRgb::new(5, 0, 0).unwrap();
+    /// let mut palette = Palette256::default();
+    /// palette[embedded] = Color::from_24bit(200, 0, 0);
+    ///
+    /// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone())
+    ///     .with_palette(palette);
+    /// assert_eq!(translator.resolve(embedded), Color::from_24bit(200, 0, 0));
+    /// assert_eq!(
+    ///     translator.to_closest_8bit(&Color::from_24bit(200, 0, 0)),
+    ///     embedded.into()
+    /// );
+    /// ```
+    pub fn with_palette(mut self, palette: Palette256) -> Self {
+        for (coordinates, color) in self.eight_bit[16..].iter_mut().zip(palette.as_ref()) {
+            *coordinates = *color.to(self.space).as_ref();
+        }
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Get this translator's options. <i class=rust-only>Rust only!</i>
+    pub fn options(&self) -> &TranslatorOptions {
+        &self.options
+    }
+
+    /// Convert the high-resolution color to coordinates in this translator's
+    /// color space, approximating sRGB's gamma if so configured.
+    fn to_coordinates(&self, color: &Color) -> [Float; 3] {
+        if self.options.approximate_gamma() {
+            crate::core::convert_approximately(color.space(), self.space, color.as_ref())
+        } else {
+            *color.to(self.space).as_ref()
+        }
+    }
+
+    /// Convert the high-resolution color to an 8-bit color as configured by
+    /// the options.
+    pub(crate) fn to_8bit(&self, color: &Color) -> EightBitColor {
+        if self.options.eight_bit_with_ansi() {
+            self.to_closest_8bit_with_ansi(color)
+        } else {
+            self.to_closest_8bit(color)
+        }
+    }
+
     /// Resolve a colorant other than the default to a high-resolution color.
     ///
     ///
@@ -685,8 +948,14 @@ impl Translator {
         match color.into() {
             Colorant::Default() => self.theme[layer].clone(),
             Colorant::Ansi(c) => self.theme[c].clone(),
-            Colorant::Embedded(c) => c.into(),
-            Colorant::Gray(c) => c.into(),
+            Colorant::Embedded(c) => match &self.palette {
+                Some(palette) => palette[c].clone(),
+                None => c.into(),
+            },
+            Colorant::Gray(c) => match &self.palette {
+                Some(palette) => palette[c].clone(),
+                None => c.into(),
+            },
             Colorant::Rgb(c) => c.into(),
             Colorant::HiRes(c) => c,
         }
@@ -722,6 +991,241 @@ impl Translator {
     pub fn cap(&self, colorant: impl Into<Colorant>, fidelity: Fidelity) -> Option<Colorant> {
         self.cap_colorant(&colorant.into(), fidelity)
     }
+
+    /// Downsample a two-dimensional buffer of colors with error diffusion.
+    ///
+    /// Converting the pixels of an image or the steps of a gradient one color
+    /// at a time to ANSI or 8-bit colors results in visible banding. This
+    /// method instead uses Floyd–Steinberg dithering, which diffuses each
+    /// pixel's quantization error to its not yet processed neighbors, with the
+    /// nearby pixels averaging out to the original colors. It treats the
+    /// colors as a row-major buffer `width` pixels wide, with the last row
+    /// possibly being shorter, and returns one colorant per color as follows:
+    ///
+    ///   * `Plain`, `NoColor`: `None`
+    ///   * `Ansi`: ANSI colors, based on the theme colors
+    ///   * `EightBit`: embedded RGB and gray gradient colors, for the same
+    ///     reasons as [`Translator::to_closest_8bit`], plus ANSI colors if
+    ///     the translator's options say so
+    ///   * `TwentyFourBit`, `HiRes`: the same colors as
+    ///     [`Translator::cap_hires`], since dithering makes no difference
+    ///
+    /// Error diffusion and color matching both use this translator's version
+    /// of Oklab.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, OkVersion, Translator};
+    /// # use prettypretty::style::Fidelity;
+    /// # use prettypretty::termco::{AnsiColor, Colorant};
+    /// # use prettypretty::theme::VGA_COLORS;
+    /// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+    /// let gray = Color::from_24bit(120, 120, 120);
+    /// let pixels = translator
+    ///     .dither(&[gray.clone(), gray.clone(), gray.clone(), gray], 2, Fidelity::Ansi)
+    ///     .unwrap();
+    /// assert_eq!(pixels.len(), 4);
+    /// assert!(pixels.contains(&Colorant::Ansi(AnsiColor::BrightBlack)));
+    /// assert!(pixels.iter().any(|c| *c != Colorant::Ansi(AnsiColor::BrightBlack)));
+    /// ```
+    pub fn dither(
+        &self,
+        colors: &[Color],
+        width: usize,
+        fidelity: Fidelity,
+    ) -> Option<Vec<Colorant>> {
+        let (palette, offset) = match fidelity {
+            Fidelity::Plain | Fidelity::NoColor => return None,
+            Fidelity::Ansi => (&self.ansi[..], 0),
+            Fidelity::EightBit if self.options.eight_bit_with_ansi() => (&self.eight_bit[..], 0),
+            Fidelity::EightBit => (&self.eight_bit[16..], 16),
+            Fidelity::TwentyFourBit | Fidelity::HiRes => {
+                return colors
+                    .iter()
+                    .map(|color| self.cap_hires(color, fidelity))
+                    .collect()
+            }
+        };
+
+        let mut pixels = colors
+            .iter()
+            .map(|color| self.to_coordinates(color))
+            .collect::<Vec<_>>();
+
+        let colorants = diffuse(&mut pixels, width, palette)
+            .into_iter()
+            .map(|index| Colorant::from((index + offset) as u8))
+            .collect();
+        Some(colorants)
+    }
+}
+
+/// The signature of serialized translator tables.
+const TABLE_SIGNATURE: &[u8; 4] = b"PPTT";
+
+/// The version of the serialized translator tables' format.
+const TABLE_VERSION: u8 = 2;
+
+impl Translator {
+    /// Serialize this translator's theme and precomputed tables.
+    ///
+    /// Creating a translator converts all 256 8-bit colors into Oklab or Oklrab
+    /// and also builds a table for matching by hue and lightness. Short-lived
+    /// command line tools may instead save the result of this method, e.g., in
+    /// a cache directory, and recreate the translator with
+    /// [`Translator::from_bytes`] on subsequent runs.
+    ///
+    /// The compact binary format is specific to this crate and stores floating
+    /// point numbers as [`Float`] in little-endian byte order. It does not
+    /// include a checksum.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::default();
+        encoder.bytes(TABLE_SIGNATURE);
+        encoder.byte(TABLE_VERSION);
+        encoder.byte(std::mem::size_of::<Float>() as u8);
+        encoder.space(self.space);
+        encoder.float(self.options.achromatic_threshold());
+        encoder.byte(self.options.eight_bit_with_ansi() as u8);
+        encoder.byte(self.options.ansi_matching() as u8);
+        encoder.byte(self.options.approximate_gamma() as u8);
+
+        for color in self.theme.as_ref() {
+            encoder.space(color.space());
+            encoder.coordinates(color.as_ref());
+        }
+        for coordinates in self.eight_bit.iter() {
+            encoder.coordinates(coordinates);
+        }
+
+        match &self.palette {
+            None => encoder.byte(0),
+            Some(palette) => {
+                encoder.byte(1);
+                for color in palette.as_ref() {
+                    encoder.space(color.space());
+                    encoder.coordinates(color.as_ref());
+                }
+            }
+        }
+
+        match &self.hue_lightness_table {
+            None => encoder.byte(0),
+            Some(table) => {
+                encoder.byte(1);
+                table.encode(&mut encoder);
+            }
+        }
+
+        encoder.finish()
+    }
+
+    /// Deserialize a translator from the bytes produced by
+    /// [`Translator::to_bytes`].
+    ///
+    /// This method validates the format's structure but trusts the
+    /// precomputed coordinates. It fails if the bytes are malformed or were
+    /// produced by an incompatible version of this crate.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, OkVersion, Translator};
+    /// # use prettypretty::error::TableFormatError;
+    /// # use prettypretty::theme::VGA_COLORS;
+    /// let translator = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+    /// let bytes = translator.to_bytes();
+    /// let restored = Translator::from_bytes(&bytes)?;
+    ///
+    /// let orange = Color::from_24bit(255, 140, 0);
+    /// assert_eq!(restored.to_ansi(&orange), translator.to_ansi(&orange));
+    /// assert_eq!(
+    ///     Translator::from_bytes(&bytes[..100]).unwrap_err(),
+    ///     TableFormatError::UnexpectedEnd
+    /// );
+    /// # Ok::<(), TableFormatError>(())
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TableFormatError> {
+        let mut decoder = Decoder::new(bytes);
+        if decoder.bytes(TABLE_SIGNATURE.len())? != TABLE_SIGNATURE {
+            return Err(TableFormatError::BadSignature);
+        }
+        if decoder.byte()? != TABLE_VERSION
+            || decoder.byte()? as usize != std::mem::size_of::<Float>()
+        {
+            return Err(TableFormatError::Incompatible);
+        }
+        let space = decoder.space()?;
+        if !matches!(space, ColorSpace::Oklab | ColorSpace::Oklrab) {
+            return Err(TableFormatError::InvalidValue);
+        }
+
+        let threshold = decoder.float()?;
+        let with_ansi = match decoder.byte()? {
+            0 => false,
+            1 => true,
+            _ => return Err(TableFormatError::InvalidValue),
+        };
+        let matching = match decoder.byte()? {
+            0 => AnsiMatching::HueLightness,
+            1 => AnsiMatching::Closest,
+            _ => return Err(TableFormatError::InvalidValue),
+        };
+        let approximate = match decoder.byte()? {
+            0 => false,
+            1 => true,
+            _ => return Err(TableFormatError::InvalidValue),
+        };
+        let options = TranslatorOptions::builder()
+            .achromatic_threshold(threshold)
+            .eight_bit_with_ansi(with_ansi)
+            .ansi_matching(matching)
+            .approximate_gamma(approximate)
+            .build();
+
+        let mut theme = Theme::new();
+        for index in 0..ThemeEntry::COUNT {
+            let space = decoder.space()?;
+            // Unwrap is safe b/c index is in range.
+            theme[ThemeEntry::try_from(index).unwrap()] = Color::new(space, decoder.coordinates()?);
+        }
+
+        let mut eight_bit = [[0.0; 3]; 256];
+        for coordinates in eight_bit.iter_mut() {
+            *coordinates = decoder.coordinates()?;
+        }
+        let mut ansi = [[0.0; 3]; 16];
+        ansi.copy_from_slice(&eight_bit[..16]);
+
+        let palette = match decoder.byte()? {
+            0 => None,
+            1 => {
+                let mut colors = Vec::with_capacity(Palette256::COUNT);
+                for _ in 0..Palette256::COUNT {
+                    let space = decoder.space()?;
+                    colors.push(Color::new(space, decoder.coordinates()?));
+                }
+                Palette256::with_slice(&colors)
+            }
+            _ => return Err(TableFormatError::InvalidValue),
+        };
+
+        let hue_lightness_table = match decoder.byte()? {
+            0 => None,
+            1 => Some(HueLightnessTable::decode(
+                &mut decoder,
+                options.achromatic_threshold(),
+            )?),
+            _ => return Err(TableFormatError::InvalidValue),
+        };
+        decoder.finish()?;
+
+        Ok(Self {
+            options,
+            theme,
+            palette,
+            hue_lightness_table,
+            space,
+            ansi,
+            eight_bit,
+        })
+    }
 }
 
 impl std::fmt::Debug for Translator {
@@ -741,7 +1245,7 @@ impl std::fmt::Debug for Translator {
 
 #[cfg(test)]
 mod test {
-    use super::Translator;
+    use super::{Translator, TranslatorOptions};
     use crate::error::OutOfBoundsError;
     use crate::termco::AnsiColor;
     use crate::theme::VGA_COLORS;
@@ -756,4 +1260,26 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_approximate_gamma() {
+        let exact = Translator::new(OkVersion::Revised, VGA_COLORS.clone());
+        let options = TranslatorOptions::builder().approximate_gamma(true).build();
+        let approximate = Translator::with_options(OkVersion::Revised, VGA_COLORS.clone(), options);
+
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let color = Color::from_24bit(r, g, b);
+                    assert_eq!(
+                        approximate.to_closest_8bit(&color),
+                        exact.to_closest_8bit(&color)
+                    );
+                }
+            }
+        }
+
+        let restored = Translator::from_bytes(&approximate.to_bytes()).unwrap();
+        assert!(restored.options().approximate_gamma());
+    }
 }
@@ -48,6 +48,9 @@ impl Environment for Env {
     }
 }
 
+#[cfg(test)]
+pub(crate) use test::FakeEnv;
+
 #[cfg(test)]
 mod test {
     use super::Environment;
@@ -79,6 +82,3 @@ mod test {
         }
     }
 }
-
-#[cfg(test)]
-pub(crate) use test::FakeEnv;
@@ -0,0 +1,403 @@
+//! Optional module rendering chromaticity diagrams as SVG.
+//!
+//! This module brings the core of
+//! [plot.py](https://github.com/apparebit/prettypretty/blob/main/prettypretty/plot.py)'s
+//! chromaticity diagrams to Rust. A [`ChromaticityDiagram`] plots the spectral
+//! locus of the 1931 2º standard observer, the gamut triangles of RGB color
+//! spaces, MacAdam's ellipses, and individual colors in either the CIE 1931 xy
+//! or the CIE 1976 u'v' chromaticity diagram. It renders the result as a
+//! self-contained SVG document without further dependencies.
+//!
+//! ```
+//! # use prettypretty::{Color, ColorSpace};
+//! # use prettypretty::viz::{Chromaticity, ChromaticityDiagram};
+//! let svg = ChromaticityDiagram::new(Chromaticity::Xy)
+//!     .with_title("Primaries")
+//!     .with_gamut(ColorSpace::Srgb)
+//!     .with_gamut(ColorSpace::DisplayP3)
+//!     .with_color(Color::srgb(1.0, 0.5, 0.0), Some("orange"))
+//!     .to_svg();
+//!
+//! assert!(svg.starts_with("<svg"));
+//! assert!(svg.contains("Display P3"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::spectrum::{CIE_OBSERVER_2DEG_1931, MACADAM_ELLIPSES};
+use crate::{Color, ColorSpace, Float};
+
+/// A chromaticity diagram's coordinates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Chromaticity {
+    /// The CIE 1931 xy chromaticity diagram.
+    #[default]
+    Xy,
+    /// The CIE 1976 u'v' chromaticity diagram.
+    UvPrime,
+}
+
+impl Chromaticity {
+    /// Get the chromaticity coordinates for the color.
+    pub fn of(&self, color: &Color) -> (Float, Float) {
+        match self {
+            Self::Xy => color.xy_chromaticity(),
+            Self::UvPrime => color.uv_prime_chromaticity(),
+        }
+    }
+
+    /// Convert xy chromaticity coordinates to this diagram's coordinates.
+    pub fn from_xy(&self, (x, y): (Float, Float)) -> (Float, Float) {
+        match self {
+            Self::Xy => (x, y),
+            Self::UvPrime => {
+                let denominator = -2.0 * x + 12.0 * y + 3.0;
+                (4.0 * x / denominator, 9.0 * y / denominator)
+            }
+        }
+    }
+
+    /// Get the axis labels.
+    fn labels(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Xy => ("x", "y"),
+            Self::UvPrime => ("u′", "v′"),
+        }
+    }
+
+    /// Get the maximum coordinates shown in the diagram.
+    fn extent(&self) -> (Float, Float) {
+        match self {
+            Self::Xy => (0.8, 0.9),
+            Self::UvPrime => (0.7, 0.7),
+        }
+    }
+
+    /// Get the default title.
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Xy => "CIE 1931 xy Chromaticity",
+            Self::UvPrime => "CIE 1976 u′v′ Chromaticity",
+        }
+    }
+}
+
+/// The stroke colors for gamut triangles, used in order.
+const GAMUT_STROKES: [&str; 4] = ["#000", "#d00", "#07c", "#090"];
+
+/// The margin around the plot area in pixels.
+const MARGIN: Float = 50.0;
+
+/// A chromaticity diagram.
+///
+/// A chromaticity diagram is assembled with the `with_` methods and then
+/// rendered with [`ChromaticityDiagram::to_svg`] or
+/// [`ChromaticityDiagram::write_svg`].
+#[derive(Clone, Debug)]
+pub struct ChromaticityDiagram {
+    chromaticity: Chromaticity,
+    size: usize,
+    title: Option<String>,
+    locus: bool,
+    ellipses: Option<Float>,
+    gamuts: Vec<ColorSpace>,
+    colors: Vec<(Color, Option<String>)>,
+}
+
+impl ChromaticityDiagram {
+    /// Create a new chromaticity diagram.
+    ///
+    /// By default, the diagram is 600 pixels wide and includes the spectral
+    /// locus.
+    pub fn new(chromaticity: Chromaticity) -> Self {
+        Self {
+            chromaticity,
+            size: 600,
+            title: None,
+            locus: true,
+            ellipses: None,
+            gamuts: Vec::new(),
+            colors: Vec::new(),
+        }
+    }
+
+    /// Set the width of the plot area in pixels.
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Include or omit the spectral locus.
+    pub fn with_locus(mut self, locus: bool) -> Self {
+        self.locus = locus;
+        self
+    }
+
+    /// Include MacAdam's ellipses, scaled by the given number of steps.
+    ///
+    /// Since the ellipses are tiny, they usually are drawn ten times their
+    /// size.
+    pub fn with_macadam_ellipses(mut self, steps: Float) -> Self {
+        self.ellipses = Some(steps);
+        self
+    }
+
+    /// Add the triangle for the RGB color space's gamut.
+    ///
+    /// Color spaces that are not RGB are ignored.
+    pub fn with_gamut(mut self, space: ColorSpace) -> Self {
+        if space.is_rgb() && !self.gamuts.contains(&space) {
+            self.gamuts.push(space);
+        }
+        self
+    }
+
+    /// Add a color with an optional label.
+    pub fn with_color(mut self, color: Color, label: Option<&str>) -> Self {
+        self.colors.push((color, label.map(str::to_string)));
+        self
+    }
+
+    /// Render this chromaticity diagram as SVG.
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+        self.write_svg(&mut svg)
+            .expect("writing to string should not fail");
+        svg
+    }
+
+    /// Write this chromaticity diagram as SVG.
+    pub fn write_svg(&self, out: &mut impl Write) -> std::fmt::Result {
+        let (max_x, max_y) = self.chromaticity.extent();
+        let scale = self.size as Float / max_x;
+        let width = self.size as Float + 2.0 * MARGIN;
+        let height = max_y * scale + 2.0 * MARGIN;
+        let point = |(x, y): (Float, Float)| (MARGIN + x * scale, MARGIN + (max_y - y) * scale);
+
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.0}" height="{:.0}" viewBox="0 0 {:.0} {:.0}" font-family="sans-serif" font-size="12">"#,
+            width, height, width, height
+        )?;
+        writeln!(out, r##"<rect width="100%" height="100%" fill="#fff"/>"##)?;
+
+        // Title
+        let title = self
+            .title
+            .as_deref()
+            .unwrap_or_else(|| self.chromaticity.title());
+        writeln!(
+            out,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle" font-size="16" font-weight="bold">{}</text>"#,
+            width / 2.0,
+            MARGIN / 2.0,
+            escape(title)
+        )?;
+
+        // Grid and tick labels
+        let (x_label, y_label) = self.chromaticity.labels();
+        writeln!(out, r##"<g stroke="#ddd" stroke-width="1">"##)?;
+        for tick in 0..=((max_x * 10.0).round() as usize) {
+            let (x0, y0) = point((tick as Float / 10.0, 0.0));
+            let (_, y1) = point((0.0, max_y));
+            writeln!(
+                out,
+                r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}"/>"#,
+                x0, y0, x0, y1
+            )?;
+        }
+        for tick in 0..=((max_y * 10.0).round() as usize) {
+            let (x0, y0) = point((0.0, tick as Float / 10.0));
+            let (x1, _) = point((max_x, 0.0));
+            writeln!(
+                out,
+                r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}"/>"#,
+                x0, y0, x1, y0
+            )?;
+        }
+        writeln!(out, "</g>")?;
+
+        writeln!(out, r##"<g fill="#555">"##)?;
+        for tick in 0..=((max_x * 10.0).round() as usize) {
+            let (x, y) = point((tick as Float / 10.0, 0.0));
+            writeln!(
+                out,
+                r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{:.1}</text>"#,
+                x,
+                y + 16.0,
+                tick as Float / 10.0
+            )?;
+        }
+        for tick in 0..=((max_y * 10.0).round() as usize) {
+            let (x, y) = point((0.0, tick as Float / 10.0));
+            writeln!(
+                out,
+                r#"<text x="{:.1}" y="{:.1}" text-anchor="end">{:.1}</text>"#,
+                x - 6.0,
+                y + 4.0,
+                tick as Float / 10.0
+            )?;
+        }
+        let (x, y) = point((max_x / 2.0, 0.0));
+        writeln!(
+            out,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle" font-style="italic">{}</text>"#,
+            x,
+            y + 34.0,
+            x_label
+        )?;
+        let (x, y) = point((0.0, max_y / 2.0));
+        writeln!(
+            out,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle" font-style="italic">{}</text>"#,
+            x - 34.0,
+            y,
+            y_label
+        )?;
+        writeln!(out, "</g>")?;
+
+        // Spectral locus, closed by the line of purples
+        if self.locus {
+            write!(
+                out,
+                r##"<path fill="none" stroke="#000" stroke-width="1.5" d=""##
+            )?;
+            for (index, wavelength) in (380..=700).enumerate() {
+                let Some([x, y, z]) = CIE_OBSERVER_2DEG_1931.at(wavelength) else {
+                    continue;
+                };
+                let color = Color::new(ColorSpace::Xyz, [x, y, z]);
+                let (px, py) = point(self.chromaticity.of(&color));
+                let command = if index == 0 { 'M' } else { 'L' };
+                write!(out, "{}{:.2},{:.2} ", command, px, py)?;
+            }
+            writeln!(out, r#"Z"/>"#)?;
+        }
+
+        // MacAdam ellipses
+        if let Some(steps) = self.ellipses {
+            writeln!(out, r##"<g fill="none" stroke="#555" stroke-width="1">"##)?;
+            for ellipse in MACADAM_ELLIPSES.iter() {
+                write!(out, r#"<path d=""#)?;
+                for (index, xy) in ellipse.outline(steps, 36).into_iter().enumerate() {
+                    let (px, py) = point(self.chromaticity.from_xy(xy));
+                    let command = if index == 0 { 'M' } else { 'L' };
+                    write!(out, "{}{:.2},{:.2} ", command, px, py)?;
+                }
+                writeln!(out, r#"Z"/>"#)?;
+            }
+            writeln!(out, "</g>")?;
+        }
+
+        // Gamut triangles
+        for (index, space) in self.gamuts.iter().enumerate() {
+            let stroke = GAMUT_STROKES[index % GAMUT_STROKES.len()];
+            write!(
+                out,
+                r#"<path fill="none" stroke="{}" stroke-width="1.5" d=""#,
+                stroke
+            )?;
+            let mut label_at = (0.0, 0.0);
+            for (corner, primary) in [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+                .into_iter()
+                .enumerate()
+            {
+                let (px, py) = point(self.chromaticity.of(&Color::new(*space, primary)));
+                if corner == 1 {
+                    label_at = (px, py);
+                }
+                let command = if corner == 0 { 'M' } else { 'L' };
+                write!(out, "{}{:.2},{:.2} ", command, px, py)?;
+            }
+            writeln!(out, r#"Z"/>"#)?;
+            writeln!(
+                out,
+                r#"<text x="{:.1}" y="{:.1}" fill="{}">{}</text>"#,
+                label_at.0 + 6.0,
+                label_at.1 + 14.0 * (index as Float + 1.0),
+                stroke,
+                escape(&space.to_string())
+            )?;
+        }
+
+        // Colors, painted in their closest sRGB color
+        for (color, label) in self.colors.iter() {
+            let (px, py) = point(self.chromaticity.of(color));
+            let fill = color.to(ColorSpace::Srgb).to_gamut().to_hex_format();
+            writeln!(
+                out,
+                r##"<circle cx="{:.2}" cy="{:.2}" r="5" fill="{}" stroke="#000"/>"##,
+                px, py, fill
+            )?;
+            if let Some(label) = label {
+                writeln!(
+                    out,
+                    r#"<text x="{:.2}" y="{:.2}">{}</text>"#,
+                    px + 8.0,
+                    py - 8.0,
+                    escape(label)
+                )?;
+            }
+        }
+
+        writeln!(out, "</svg>")
+    }
+}
+
+/// Escape the text for inclusion in SVG.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{Chromaticity, ChromaticityDiagram};
+    use crate::{Color, ColorSpace};
+
+    #[test]
+    fn test_svg() {
+        let svg = ChromaticityDiagram::new(Chromaticity::UvPrime)
+            .with_size(350)
+            .with_gamut(ColorSpace::Srgb)
+            .with_gamut(ColorSpace::Oklab)
+            .with_gamut(ColorSpace::Srgb)
+            .with_color(Color::srgb(0.0, 0.0, 1.0), Some("<blue>"))
+            .to_svg();
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="450""#));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("CIE 1976 u′v′ Chromaticity"));
+        assert_eq!(svg.matches("<path").count(), 2);
+        assert!(svg.contains(r##"fill="#0000ff""##));
+        assert!(svg.contains("&lt;blue&gt;"));
+
+        let svg = ChromaticityDiagram::new(Chromaticity::Xy)
+            .with_locus(false)
+            .to_svg();
+        assert_eq!(svg.matches("<path").count(), 0);
+
+        let svg = ChromaticityDiagram::new(Chromaticity::Xy)
+            .with_locus(false)
+            .with_macadam_ellipses(10.0)
+            .to_svg();
+        assert_eq!(svg.matches("<path").count(), 25);
+    }
+}
@@ -0,0 +1,334 @@
+//! JavaScript bindings for WebAssembly.
+//!
+//! This module exports [`Color`], [`Translator`], and [`Style`] to JavaScript
+//! through [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/), so
+//! that web-based terminal emulators and documentation sites can reuse the
+//! exact same color math. Since wasm-bindgen cannot export types that are also
+//! exported to Python, the bindings wrap the Rust types. They retain the Rust
+//! names in JavaScript but use camel case for methods.
+//!
+//! Color spaces are identified by their CSS names, i.e., `srgb`,
+//! `linear-srgb`, `display-p3`, `rec2020`, `oklab`, `oklch`, `xyz`, `xyz-d65`,
+//! and `xyz-d50` as well as the non-standard `--linear-display-p3`,
+//! `--linear-rec2020`, `--oklrab`, and `--oklrch`. Fidelity levels are
+//! identified as `plain`, `no-color`, `ansi`, `8bit`, `24bit`, and `hires`.
+
+use std::str::FromStr;
+
+use wasm_bindgen::prelude::*;
+
+use crate::core::{parse_space, space_name};
+use crate::style::{Fidelity, Style};
+use crate::termco::Colorant;
+use crate::theme::{Theme, ThemeEntry, VGA_COLORS};
+use crate::{Color, ColorSpace, Float, OkVersion, Translator};
+
+/// Look up the color space by its CSS name.
+fn to_space(name: &str) -> Result<ColorSpace, JsError> {
+    parse_space(name).ok_or_else(|| JsError::new(&format!("unknown color space {}", name)))
+}
+
+/// Look up the fidelity level by name.
+fn to_fidelity(name: &str) -> Result<Fidelity, JsError> {
+    match name {
+        "plain" => Ok(Fidelity::Plain),
+        "no-color" => Ok(Fidelity::NoColor),
+        "ansi" => Ok(Fidelity::Ansi),
+        "8bit" => Ok(Fidelity::EightBit),
+        "24bit" => Ok(Fidelity::TwentyFourBit),
+        "hires" => Ok(Fidelity::HiRes),
+        _ => Err(JsError::new(&format!("unknown fidelity {}", name))),
+    }
+}
+
+/// A high-resolution color for JavaScript.
+#[wasm_bindgen(js_name = Color)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsColor(Color);
+
+#[wasm_bindgen(js_class = Color)]
+impl JsColor {
+    /// Create a new color with the given color space and coordinates.
+    #[wasm_bindgen(constructor)]
+    pub fn new(space: &str, c1: Float, c2: Float, c3: Float) -> Result<JsColor, JsError> {
+        Ok(Self(Color::new(to_space(space)?, [c1, c2, c3])))
+    }
+
+    /// Parse a color in `#`, `rgb:`, `color()`, `oklab()`, or `oklch()`
+    /// format.
+    pub fn parse(s: &str) -> Result<JsColor, JsError> {
+        Ok(Self(Color::from_str(s)?))
+    }
+
+    /// Create a new sRGB color from its 24-bit representation.
+    #[wasm_bindgen(js_name = from24bit)]
+    pub fn from_24bit(r: u8, g: u8, b: u8) -> JsColor {
+        Self(Color::from_24bit(r, g, b))
+    }
+
+    /// Get the CSS name of this color's color space.
+    #[wasm_bindgen(getter)]
+    pub fn space(&self) -> String {
+        space_name(self.0.space()).to_string()
+    }
+
+    /// Get this color's coordinates.
+    #[wasm_bindgen(getter)]
+    pub fn coordinates(&self) -> Vec<Float> {
+        self.0.as_ref().to_vec()
+    }
+
+    /// Convert this color to the color space with the given CSS name.
+    pub fn to(&self, space: &str) -> Result<JsColor, JsError> {
+        Ok(Self(self.0.to(to_space(space)?)))
+    }
+
+    /// Determine whether this color is in gamut for its color space.
+    #[wasm_bindgen(js_name = inGamut)]
+    pub fn in_gamut(&self) -> bool {
+        self.0.in_gamut()
+    }
+
+    /// Clip this color to its color space's gamut.
+    pub fn clip(&self) -> JsColor {
+        Self(self.0.clip())
+    }
+
+    /// Map this color into its color space's gamut with the CSS Color 4
+    /// algorithm.
+    #[wasm_bindgen(js_name = toGamut)]
+    pub fn to_gamut(&self) -> JsColor {
+        Self(self.0.to_gamut())
+    }
+
+    /// Lighten this color by the given factor in Oklrch.
+    pub fn lighten(&self, factor: Float) -> JsColor {
+        Self(self.0.lighten(factor))
+    }
+
+    /// Darken this color by the given factor in Oklrch.
+    pub fn darken(&self, factor: Float) -> JsColor {
+        Self(self.0.darken(factor))
+    }
+
+    /// Determine the perceptual contrast of text in this color against the
+    /// background color.
+    #[wasm_bindgen(js_name = contrastAgainst)]
+    pub fn contrast_against(&self, background: &JsColor) -> f64 {
+        self.0.contrast_against(&background.0)
+    }
+
+    /// Determine the distance between this and the other color in Oklrab.
+    pub fn distance(&self, other: &JsColor) -> f64 {
+        self.0.distance(&other.0, OkVersion::Revised)
+    }
+
+    /// Convert this color to 24-bit RGB.
+    #[wasm_bindgen(js_name = to24bit)]
+    pub fn to_24bit(&self) -> Vec<u8> {
+        self.0.to_24bit().to_vec()
+    }
+
+    /// Format this color in hashed hexadecimal notation.
+    #[wasm_bindgen(js_name = toHexFormat)]
+    pub fn to_hex_format(&self) -> String {
+        self.0.to_hex_format()
+    }
+
+    /// Format this color in CSS notation.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_css(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// A translator between color formats for JavaScript.
+#[wasm_bindgen(js_name = Translator)]
+#[derive(Debug)]
+pub struct JsTranslator(Translator);
+
+#[wasm_bindgen(js_class = Translator)]
+impl JsTranslator {
+    /// Create a new translator.
+    ///
+    /// The theme comprises the default foreground and background followed by
+    /// the 16 ANSI colors, in any format accepted by [`JsColor::parse`]. If
+    /// the theme is missing, this constructor uses the VGA colors.
+    #[wasm_bindgen(constructor)]
+    pub fn new(theme: Option<Vec<String>>) -> Result<JsTranslator, JsError> {
+        let theme = match theme {
+            None => VGA_COLORS,
+            Some(colors) => {
+                let colors = colors
+                    .iter()
+                    .map(|color| Color::from_str(color))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let colors = <[Color; ThemeEntry::COUNT]>::try_from(colors).map_err(|colors| {
+                    JsError::new(&format!(
+                        "theme should have {} colors but has {}",
+                        ThemeEntry::COUNT,
+                        colors.len()
+                    ))
+                })?;
+                Theme::with_array(colors)
+            }
+        };
+
+        Ok(Self(Translator::new(OkVersion::Revised, theme)))
+    }
+
+    /// Determine whether the theme is a dark theme.
+    #[wasm_bindgen(js_name = isDarkTheme)]
+    pub fn is_dark_theme(&self) -> bool {
+        self.0.is_dark_theme()
+    }
+
+    /// Resolve the 8-bit color to a high-resolution color.
+    pub fn resolve(&self, index: u8) -> JsColor {
+        JsColor(self.0.resolve(index))
+    }
+
+    /// Convert the color to an ANSI color.
+    #[wasm_bindgen(js_name = toAnsi)]
+    pub fn to_ansi(&self, color: &JsColor) -> u8 {
+        u8::from(self.0.to_ansi(&color.0))
+    }
+
+    /// Convert the color to the closest 8-bit color.
+    #[wasm_bindgen(js_name = toClosest8bit)]
+    pub fn to_closest_8bit(&self, color: &JsColor) -> u8 {
+        u8::from(self.0.to_closest_8bit(&color.0))
+    }
+}
+
+/// A terminal style for JavaScript.
+#[wasm_bindgen(js_name = Style)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsStyle(Style);
+
+#[wasm_bindgen(js_class = Style)]
+impl JsStyle {
+    /// Create a new, empty style.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> JsStyle {
+        Self::default()
+    }
+
+    /// Add bold formatting.
+    pub fn bold(&self) -> JsStyle {
+        Self(self.0.bold())
+    }
+
+    /// Add thin formatting.
+    pub fn thin(&self) -> JsStyle {
+        Self(self.0.thin())
+    }
+
+    /// Add italic formatting.
+    pub fn italic(&self) -> JsStyle {
+        Self(self.0.italic())
+    }
+
+    /// Add underlined formatting.
+    pub fn underlined(&self) -> JsStyle {
+        Self(self.0.underlined())
+    }
+
+    /// Add blinking formatting.
+    pub fn blinking(&self) -> JsStyle {
+        Self(self.0.blinking())
+    }
+
+    /// Add reversed formatting.
+    pub fn reversed(&self) -> JsStyle {
+        Self(self.0.reversed())
+    }
+
+    /// Add hidden formatting.
+    pub fn hidden(&self) -> JsStyle {
+        Self(self.0.hidden())
+    }
+
+    /// Add stricken formatting.
+    pub fn stricken(&self) -> JsStyle {
+        Self(self.0.stricken())
+    }
+
+    /// Set the high-resolution foreground color.
+    ///
+    /// Since terminals cannot display high-resolution colors, the style
+    /// should be capped before rendering it.
+    #[wasm_bindgen(js_name = withForeground)]
+    pub fn with_foreground(&self, color: &JsColor) -> JsStyle {
+        Self(self.0.with_foreground(color.0.clone()))
+    }
+
+    /// Set the high-resolution background color.
+    ///
+    /// Since terminals cannot display high-resolution colors, the style
+    /// should be capped before rendering it.
+    #[wasm_bindgen(js_name = withBackground)]
+    pub fn with_background(&self, color: &JsColor) -> JsStyle {
+        Self(self.0.with_background(color.0.clone()))
+    }
+
+    /// Set the 8-bit foreground color.
+    #[wasm_bindgen(js_name = withForeground8bit)]
+    pub fn with_foreground_8bit(&self, index: u8) -> JsStyle {
+        Self(self.0.with_foreground(Colorant::from(index)))
+    }
+
+    /// Set the 8-bit background color.
+    #[wasm_bindgen(js_name = withBackground8bit)]
+    pub fn with_background_8bit(&self, index: u8) -> JsStyle {
+        Self(self.0.with_background(Colorant::from(index)))
+    }
+
+    /// Cap this style to the fidelity level.
+    pub fn cap(&self, fidelity: &str, translator: &JsTranslator) -> Result<JsStyle, JsError> {
+        Ok(Self(self.0.cap(to_fidelity(fidelity)?, &translator.0)))
+    }
+
+    /// Render the text with this style, followed by the SGR sequence
+    /// restoring the default style.
+    pub fn paint(&self, text: &str) -> String {
+        self.0.paint(text).to_string()
+    }
+
+    /// Get the SGR sequence restoring the default style.
+    pub fn reset(&self) -> String {
+        (-&self.0).to_string()
+    }
+
+    /// Get the SGR sequence for this style.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_sgr(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{JsColor, JsStyle, JsTranslator};
+
+    #[test]
+    fn test_bindings() {
+        let red = JsColor::from_24bit(255, 0, 0);
+        assert_eq!(red.space(), "srgb");
+        assert_eq!(red.coordinates(), vec![1.0, 0.0, 0.0]);
+        assert_eq!(red.to_hex_format(), "#ff0000");
+
+        let translator = JsTranslator::new(None).unwrap();
+        assert_eq!(translator.to_ansi(&red), 9);
+        assert_eq!(translator.resolve(9).to_hex_format(), "#ff5555");
+
+        let style = JsStyle::new()
+            .bold()
+            .with_foreground(&red)
+            .cap("24bit", &translator)
+            .unwrap();
+        assert_eq!(style.paint("red"), "\x1b[1;38;2;255;0;0mred\x1b[22;39m");
+        assert_eq!(style.reset(), "\x1b[22;39m");
+        assert_eq!(style.with_foreground_8bit(9).to_sgr(), "\x1b[1;91m");
+    }
+}
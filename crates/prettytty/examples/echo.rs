@@ -73,20 +73,20 @@ fn run() -> std::io::Result<()> {
         let mut cursor = token_buffer.as_mut();
         let mut char_len = 4;
 
-        cursor.write("〈".as_bytes())?;
+        cursor.write_all("〈".as_bytes())?;
         for byte in buffer[..count].iter() {
             char_len += cursor.write_nicely(*byte)?;
         }
-        cursor.write("〉".as_bytes())?;
+        cursor.write_all("〉".as_bytes())?;
 
         let cursor_len = cursor.len();
         let token_len = token_buffer.len() - cursor_len;
 
         // Actually write out token.
         wrap!(char_len);
-        output.write(format!("{}", SetDefaultForeground).as_bytes())?;
-        output.write(&token_buffer[..token_len])?;
-        output.write(format!("{}", GRAY).as_bytes())?;
+        output.write_all(format!("{}", SetDefaultForeground).as_bytes())?;
+        output.write_all(&token_buffer[..token_len])?;
+        output.write_all(format!("{}", GRAY).as_bytes())?;
         output.flush()?;
 
         // Handle user input.
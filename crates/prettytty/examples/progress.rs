@@ -2,7 +2,6 @@ use std::io::{Result, Write};
 use std::thread;
 use std::time::Duration;
 
-use rand;
 use rand::rngs::ThreadRng;
 use rand_distr::{Distribution, Normal, Uniform};
 
@@ -40,6 +39,12 @@ impl ProgressReporter {
     }
 }
 
+impl Default for ProgressReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl std::iter::Iterator for ProgressReporter {
     type Item = Progress;
 
@@ -107,7 +112,7 @@ impl std::fmt::Display for Renderer {
 /// Animate a progress bar's progress from 0 to 100 percent.
 pub fn animate(tty: &Connection) -> Result<()> {
     // Nap time is between 1/60 and 1/10 seconds
-    let uniform = Uniform::new_inclusive(16, 100).map_err(|e| std::io::Error::other(e))?;
+    let uniform = Uniform::new_inclusive(16, 100).map_err(std::io::Error::other)?;
     let mut rng = rand::rng();
 
     let mut output = tty.output();
@@ -0,0 +1,278 @@
+use std::future::Future;
+use std::io::{BufRead, Error, Result, Write};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::task::{spawn_blocking, JoinError, JoinHandle};
+
+use crate::opt::Options;
+use crate::{Command, Connection, Query, Scan};
+
+fn join_error(error: JoinError) -> Error {
+    Error::other(error)
+}
+
+/// An async terminal connection providing [`AsyncInput`] and [`AsyncOutput`].
+///
+/// This object wraps a [`Connection`] for use with the
+/// [tokio](https://tokio.rs) runtime. It performs the blocking reads and
+/// writes on tokio's thread pool for blocking operations and hence never
+/// blocks an async worker thread. Since reads from the terminal time out, a
+/// pending read occupies a blocking thread for at most one timeout period at a
+/// time.
+///
+/// Cloning an async connection is cheap and shares the underlying connection.
+/// The terminal's configuration is restored when the last clone as well as all
+/// inputs and outputs have been dropped.
+///
+/// ```
+/// # use std::io::ErrorKind;
+/// # use prettytty::AsyncConnection;
+/// # use prettytty::cmd::{MoveToColumn, RequestCursorPosition};
+/// # async fn run() -> std::io::Result<()> {
+/// let tty = AsyncConnection::open().await?;
+/// tty.output().exec(MoveToColumn::<17>).await?;
+/// let position = tty.query(RequestCursorPosition).await?;
+/// assert_eq!(position.column, 17);
+/// # Ok(())
+/// # }
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+/// # match runtime.block_on(run()) {
+/// #     Ok(()) => (),
+/// #     Err(err) if err.kind() == ErrorKind::ConnectionRefused &&
+/// #         std::env::var_os("CI").is_some() => (),
+/// #     Err(err) => return Err(err),
+/// # };
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct AsyncConnection {
+    connection: Arc<Connection>,
+}
+
+impl AsyncConnection {
+    /// Open an async terminal connection with the default options.
+    pub async fn open() -> Result<Self> {
+        Self::with_options(Options::default()).await
+    }
+
+    /// Open an async terminal connection with the given options.
+    pub async fn with_options(options: Options) -> Result<Self> {
+        let connection = spawn_blocking(move || Connection::with_options(options))
+            .await
+            .map_err(join_error)??;
+        Ok(Self {
+            connection: Arc::new(connection),
+        })
+    }
+
+    /// Get the underlying, blocking connection.
+    #[inline]
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Get the options used when opening this connection.
+    #[inline]
+    pub fn options(&self) -> &Options {
+        self.connection.options()
+    }
+
+    /// Get both terminal input and output.
+    #[inline]
+    pub fn io(&self) -> (AsyncInput, AsyncOutput) {
+        (self.input(), self.output())
+    }
+
+    /// Get the terminal input.
+    ///
+    /// Unlike [`Connection::input`], the returned input object does not
+    /// ensure mutually exclusive access. Instead, each read acquires and
+    /// releases the terminal's input.
+    #[inline]
+    pub fn input(&self) -> AsyncInput {
+        AsyncInput {
+            connection: Arc::clone(&self.connection),
+            buffer: Vec::new(),
+            position: 0,
+            pending: None,
+        }
+    }
+
+    /// Get the terminal output.
+    ///
+    /// Unlike [`Connection::output`], the returned output object does not
+    /// ensure mutually exclusive access. Instead, each write acquires and
+    /// releases the terminal's output.
+    #[inline]
+    pub fn output(&self) -> AsyncOutput {
+        AsyncOutput {
+            connection: Arc::clone(&self.connection),
+            pending: None,
+        }
+    }
+
+    /// Write the query, read its response, and parse the payload.
+    ///
+    /// This method holds on to the terminal's input and output for the
+    /// duration of the query, which keeps the response from being consumed
+    /// by other readers.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            err,
+            fields(query = std::any::type_name::<Q>())
+        )
+    )]
+    pub async fn query<Q>(&self, query: Q) -> Result<Q::Response>
+    where
+        Q: Query + Send + 'static,
+        Q::Response: Send + 'static,
+    {
+        let connection = Arc::clone(&self.connection);
+        spawn_blocking(move || {
+            let (mut input, mut output) = connection.io();
+            output.exec(&query)?;
+            let payload = input.read_sequence(query.control())?;
+            query.parse(payload)
+        })
+        .await
+        .map_err(join_error)?
+    }
+}
+
+/// An async terminal connection's input.
+///
+/// This type implements [`AsyncRead`] and [`AsyncBufRead`]. Unlike
+/// [`Input`](crate::Input), a read never returns zero bytes because of a
+/// timeout. Instead, the read keeps waiting for input. Dropping this object
+/// while a read is pending may lose the input received by that read.
+pub struct AsyncInput {
+    connection: Arc<Connection>,
+    buffer: Vec<u8>,
+    position: usize,
+    pending: Option<JoinHandle<Result<Vec<u8>>>>,
+}
+
+impl AsyncInput {
+    /// Fill the buffer, if it is empty.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while self.position == self.buffer.len() {
+            let handle = self.pending.get_or_insert_with(|| {
+                let connection = Arc::clone(&self.connection);
+                spawn_blocking(move || {
+                    let mut input = connection.input();
+                    let data = input.fill_buf()?.to_vec();
+                    input.consume(data.len());
+                    Ok(data)
+                })
+            });
+
+            let result = ready!(Pin::new(handle).poll(cx));
+            self.pending = None;
+            self.buffer = result.map_err(join_error)??;
+            self.position = 0;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncRead for AsyncInput {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_fill(cx))?;
+
+        let count = buf.remaining().min(this.buffer.len() - this.position);
+        buf.put_slice(&this.buffer[this.position..this.position + count]);
+        this.position += count;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncBufRead for AsyncInput {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<&[u8]>> {
+        let this = self.get_mut();
+        ready!(this.poll_fill(cx))?;
+        Poll::Ready(Ok(&this.buffer[this.position..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.position = (this.position + amt).min(this.buffer.len());
+    }
+}
+
+/// An async terminal connection's output.
+///
+/// This type implements [`AsyncWrite`]. Each write is flushed to the terminal
+/// right away. As a convenience, [`AsyncOutput::print`],
+/// [`AsyncOutput::println`], and [`AsyncOutput::exec`] write strings and
+/// commands, respectively.
+pub struct AsyncOutput {
+    connection: Arc<Connection>,
+    pending: Option<JoinHandle<Result<()>>>,
+}
+
+impl AsyncOutput {
+    /// Wait for the pending write, if any.
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if let Some(handle) = self.pending.as_mut() {
+            let result = ready!(Pin::new(handle).poll(cx));
+            self.pending = None;
+            result.map_err(join_error)??;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Write and flush the text.
+    pub async fn print(&mut self, text: impl AsRef<str>) -> Result<()> {
+        self.write_all(text.as_ref().as_bytes()).await?;
+        self.flush().await
+    }
+
+    /// Write and flush the text followed by carriage return and line feed.
+    pub async fn println(&mut self, text: impl AsRef<str>) -> Result<()> {
+        let mut text = text.as_ref().to_string();
+        text.push_str("\r\n");
+        self.print(text).await
+    }
+
+    /// Write and flush the command.
+    pub async fn exec(&mut self, cmd: impl Command) -> Result<()> {
+        self.print(cmd.to_string()).await
+    }
+}
+
+impl AsyncWrite for AsyncOutput {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx))?;
+
+        let connection = Arc::clone(&this.connection);
+        let data = buf.to_vec();
+        this.pending = Some(spawn_blocking(move || {
+            let mut output = connection.output();
+            output.write_all(&data)?;
+            output.flush()
+        }));
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().poll_pending(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().poll_pending(cx)
+    }
+}
@@ -38,6 +38,121 @@ macro_rules! fuse {
     }}
 }
 
+/// A sequence of commands assembled at runtime.
+///
+/// Unlike [`fuse!`], which combines a statically known list of commands, this
+/// struct accumulates an arbitrary number of commands of arbitrary types, e.g.,
+/// as determined by application state. It renders each command as it is added.
+/// Upon display, it emits all commands, which makes it easy to write them with
+/// one write and one flush.
+///
+/// Command sequences can be built with [`CommandSeq::push`] and
+/// [`CommandSeq::with`], collected from an iterator over commands of the same
+/// type, including boxed command objects, or converted from tuples of up to
+/// eight commands.
+///
+/// ```
+/// # use prettytty::{Command, CommandSeq};
+/// # use prettytty::cmd::{EraseScreen, HideCursor, MoveTo, SetForeground8};
+/// let mut seq = CommandSeq::from((EraseScreen, MoveTo::<1, 1>));
+/// seq.push(HideCursor);
+///
+/// let dynamic: Vec<Box<dyn Command>> = vec![
+///     Box::new(SetForeground8::<1>),
+///     Box::new(MoveTo::<2, 3>),
+/// ];
+/// seq.extend(dynamic);
+///
+/// assert_eq!(
+///     format!("{}", seq),
+///     "\x1b[2J\x1b[1;1H\x1b[?25l\x1b[38;5;1m\x1b[2;3H"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandSeq(String);
+
+impl CommandSeq {
+    /// Create a new, empty command sequence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the command to this sequence.
+    pub fn push(&mut self, command: impl Command) -> &mut Self {
+        use std::fmt::Write;
+
+        // Writing to a string only fails if the command's Display does.
+        write!(self.0, "{}", command).expect("command displays without error");
+        self
+    }
+
+    /// Add the command to this sequence, consuming and returning the sequence.
+    pub fn with(mut self, command: impl Command) -> Self {
+        self.push(command);
+        self
+    }
+
+    /// Determine whether this sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove all commands from this sequence.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Get the rendered commands.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Command for CommandSeq {}
+
+impl std::fmt::Display for CommandSeq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<C: Command> Extend<C> for CommandSeq {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        for command in iter {
+            self.push(command);
+        }
+    }
+}
+
+impl<C: Command> FromIterator<C> for CommandSeq {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        let mut seq = Self::new();
+        seq.extend(iter);
+        seq
+    }
+}
+
+macro_rules! command_seq_from_tuple {
+    ($($name:ident: $index:tt),+) => {
+        impl<$($name: Command),+> From<($($name,)+)> for CommandSeq {
+            fn from(value: ($($name,)+)) -> Self {
+                let mut seq = Self::new();
+                $(seq.push(value.$index);)+
+                seq
+            }
+        }
+    };
+}
+
+command_seq_from_tuple!(A: 0);
+command_seq_from_tuple!(A: 0, B: 1);
+command_seq_from_tuple!(A: 0, B: 1, C: 2);
+command_seq_from_tuple!(A: 0, B: 1, C: 2, D: 3);
+command_seq_from_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+command_seq_from_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+command_seq_from_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+command_seq_from_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
 // ------------------------------------------------------------------------------------------------
 
 /// A command using select-graphic-rendition ANSI escape sequences.
@@ -200,7 +315,7 @@ impl std::fmt::Display for Control {
 /// }
 /// # };
 /// # drop(tty);
-/// # assert_eq!(pos.1, 17);
+/// # assert_eq!(pos.column, 17);
 /// # Ok::<(), std::io::Error>(())
 /// ```
 ///
@@ -255,9 +370,15 @@ pub enum Token<'t> {
     Control(&'t [u8]),
     /// A control sequence with its initial control and payload.
     Sequence(Control, &'t [u8]),
+    /// The content of a bracketed paste, without begin and end markers.
+    Paste(&'t [u8]),
+    /// The terminal gained focus.
+    FocusGained,
+    /// The terminal lost focus.
+    FocusLost,
 }
 
-impl Token<'_> {
+impl<'t> Token<'t> {
     /// Get this token's control.
     pub fn control(&self) -> Option<Control> {
         match self {
@@ -274,8 +395,17 @@ impl Token<'_> {
             Text(data) => data,
             Control(data) => data,
             Sequence(_, data) => data,
+            Paste(data) => data,
+            FocusGained | FocusLost => &[],
         }
     }
+
+    /// Decode the key events encoded by this token.
+    ///
+    /// See the [`key`](crate::key) module for details.
+    pub fn keys(&self) -> crate::key::Keys<'t> {
+        crate::key::Keys::new(self)
+    }
 }
 
 impl std::fmt::Debug for Token<'_> {
@@ -284,6 +414,9 @@ impl std::fmt::Debug for Token<'_> {
             Self::Text(_) => "Text",
             Self::Control(_) => "Control",
             Self::Sequence(_, _) => "Sequence",
+            Self::Paste(_) => "Paste",
+            Self::FocusGained => return f.write_str("FocusGained"),
+            Self::FocusLost => return f.write_str("FocusLost"),
         };
 
         let mut debug = f.debug_tuple(name);
@@ -322,7 +455,7 @@ pub trait Scan: std::io::BufRead {
     /// connection upon invocation. For text tokens, it performs no further
     /// reads. That is, a text token always ends with the currently buffered
     /// data.
-    fn read_token(&mut self) -> Result<Token>;
+    fn read_token(&mut self) -> Result<Token<'_>>;
 
     /// Read the next token as a control sequence.
     ///
@@ -350,7 +483,7 @@ impl<S: Scan + ?Sized> Scan for &mut S {
     }
 
     #[inline]
-    fn read_token(&mut self) -> Result<Token> {
+    fn read_token(&mut self) -> Result<Token<'_>> {
         (**self).read_token()
     }
 }
@@ -363,7 +496,7 @@ impl<S: Scan + ?Sized> Scan for Box<S> {
     }
 
     #[inline]
-    fn read_token(&mut self) -> Result<Token> {
+    fn read_token(&mut self) -> Result<Token<'_>> {
         (**self).read_token()
     }
 }
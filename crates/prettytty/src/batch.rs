@@ -0,0 +1,270 @@
+use std::io::{ErrorKind, Result, Write};
+
+use crate::cmd::RequestPrimaryDA;
+use crate::{Control, Query, Scan, Token};
+
+/// A batch of queries.
+///
+/// A batch writes all its queries with a single flush and then reads the
+/// responses in a single loop. That saves round trips and hence makes a
+/// noticeable difference over high-latency connections, e.g., when using SSH.
+/// Since terminals may not respond to all queries, a batch also writes
+/// [`RequestPrimaryDA`] as a final sentinel query, which all terminals answer.
+/// Once the response to the sentinel arrives, the batch is complete, even if
+/// some queries went unanswered. If the terminal does not respond to the
+/// sentinel, the batch is complete after the next read times out.
+///
+/// A batch demultiplexes responses by their controls and then by successfully
+/// parsing the payloads, assigning each response to the first unanswered query
+/// that accepts it. Hence, responses may arrive in any order. The batch
+/// ignores all other tokens. Its responses comprise one option per query,
+/// which is `None` if the terminal did not respond to the query.
+///
+/// This trait is implemented for slices, arrays, and vectors of queries with
+/// the same type as well as for tuples with up to eight queries of different
+/// types.
+///
+/// ```
+/// # use prettytty::{Batch, MockConnection};
+/// # use prettytty::cmd::{Position, RequestColor, RequestCursorPosition, RequestPrimaryDA};
+/// let tty = MockConnection::new();
+/// tty.respond(
+///     RequestPrimaryDA,
+///     b"\x1b]11;rgb:0/0/0\x1b\\\x1b[3;7R\x1b[?62;4c",
+/// );
+///
+/// let (mut input, mut output) = tty.io();
+/// let (position, foreground, background) = (
+///     RequestCursorPosition,
+///     RequestColor::Foreground,
+///     RequestColor::Background,
+/// )
+///     .run(&mut input, &mut output)?;
+///
+/// assert_eq!(position, Some(Position::new(3, 7)));
+/// assert_eq!(foreground, None);
+/// assert!(background.is_some());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub trait Batch {
+    /// The responses, with one option per query.
+    type Responses;
+
+    /// Write the queries without flushing.
+    fn write_queries(&self, output: &mut dyn Write) -> Result<()>;
+
+    /// Create the responses for a batch without answers.
+    fn no_responses(&self) -> Self::Responses;
+
+    /// Try to accept the response.
+    ///
+    /// If the payload with the given control is a response to a query that
+    /// does not have a response yet, this method updates the responses and
+    /// returns `true`.
+    fn accept(&self, responses: &mut Self::Responses, control: Control, payload: &[u8]) -> bool;
+
+    /// Run this batch of queries.
+    ///
+    /// This method writes the queries followed by the sentinel and flushes
+    /// the output. It then reads tokens until the sentinel's response arrives
+    /// or a read times out.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(batch = std::any::type_name::<Self>())
+        )
+    )]
+    fn run(&self, input: &mut impl Scan, output: &mut impl Write) -> Result<Self::Responses> {
+        self.write_queries(output)?;
+        write!(output, "{}", RequestPrimaryDA)?;
+        output.flush()?;
+
+        let mut responses = self.no_responses();
+        loop {
+            let token = match input.read_token() {
+                Ok(token) => token,
+                Err(error) if error.kind() == ErrorKind::TimedOut => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("batch timed out before sentinel response");
+                    break;
+                }
+                Err(error) => return Err(error),
+            };
+
+            if let Token::Sequence(control, payload) = token {
+                if !self.accept(&mut responses, control, payload)
+                    && control == RequestPrimaryDA.control()
+                    && RequestPrimaryDA.parse(payload).is_ok()
+                {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!("batch completed with sentinel response");
+                    break;
+                }
+            }
+        }
+
+        Ok(responses)
+    }
+}
+
+/// Accept the response for a single query, if it hasn't been answered yet.
+fn accept_one<Q: Query>(
+    query: &Q,
+    response: &mut Option<Q::Response>,
+    control: Control,
+    payload: &[u8],
+) -> bool {
+    if response.is_some() || query.control() != control {
+        return false;
+    }
+
+    match query.parse(payload) {
+        Ok(value) => {
+            *response = Some(value);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+impl<Q: Query> Batch for [Q] {
+    type Responses = Vec<Option<Q::Response>>;
+
+    fn write_queries(&self, output: &mut dyn Write) -> Result<()> {
+        for query in self {
+            write!(output, "{}", query)?;
+        }
+        Ok(())
+    }
+
+    fn no_responses(&self) -> Self::Responses {
+        self.iter().map(|_| None).collect()
+    }
+
+    fn accept(&self, responses: &mut Self::Responses, control: Control, payload: &[u8]) -> bool {
+        self.iter()
+            .zip(responses.iter_mut())
+            .any(|(query, response)| accept_one(query, response, control, payload))
+    }
+}
+
+impl<Q: Query, const N: usize> Batch for [Q; N] {
+    type Responses = Vec<Option<Q::Response>>;
+
+    fn write_queries(&self, output: &mut dyn Write) -> Result<()> {
+        self.as_slice().write_queries(output)
+    }
+
+    fn no_responses(&self) -> Self::Responses {
+        self.as_slice().no_responses()
+    }
+
+    fn accept(&self, responses: &mut Self::Responses, control: Control, payload: &[u8]) -> bool {
+        self.as_slice().accept(responses, control, payload)
+    }
+}
+
+impl<Q: Query> Batch for Vec<Q> {
+    type Responses = Vec<Option<Q::Response>>;
+
+    fn write_queries(&self, output: &mut dyn Write) -> Result<()> {
+        self.as_slice().write_queries(output)
+    }
+
+    fn no_responses(&self) -> Self::Responses {
+        self.as_slice().no_responses()
+    }
+
+    fn accept(&self, responses: &mut Self::Responses, control: Control, payload: &[u8]) -> bool {
+        self.as_slice().accept(responses, control, payload)
+    }
+}
+
+macro_rules! batch_tuple {
+    ($($name:ident: $index:tt),+) => {
+        impl<$($name: Query),+> Batch for ($($name,)+) {
+            type Responses = ($(Option<$name::Response>,)+);
+
+            fn write_queries(&self, output: &mut dyn Write) -> Result<()> {
+                $(write!(output, "{}", self.$index)?;)+
+                Ok(())
+            }
+
+            fn no_responses(&self) -> Self::Responses {
+                ($(None::<$name::Response>,)+)
+            }
+
+            fn accept(
+                &self,
+                responses: &mut Self::Responses,
+                control: Control,
+                payload: &[u8],
+            ) -> bool {
+                $(accept_one(&self.$index, &mut responses.$index, control, payload))||+
+            }
+        }
+    };
+}
+
+batch_tuple!(A: 0);
+batch_tuple!(A: 0, B: 1);
+batch_tuple!(A: 0, B: 1, C: 2);
+batch_tuple!(A: 0, B: 1, C: 2, D: 3);
+batch_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+batch_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+batch_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+batch_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::Batch;
+    use crate::cmd::{DynRequestColor, RequestPrimaryDA};
+    use crate::MockConnection;
+    use std::io::Result;
+
+    #[test]
+    fn test_batch() -> Result<()> {
+        // Responses out of order, with noise, and one unanswered query.
+        let tty = MockConnection::new();
+        tty.respond(
+            RequestPrimaryDA,
+            b"\x1b]4;2;rgb:2/2/2\x1b\\key\x1b[A\x1b]4;0;rgb:0/0/0\x1b\\\x1b[?64;4c",
+        );
+        tty.push_input(b"more");
+
+        let queries = [DynRequestColor(0), DynRequestColor(1), DynRequestColor(2)];
+        let (mut input, mut output) = tty.io();
+        let responses = queries.run(&mut input, &mut output)?;
+
+        assert_eq!(
+            responses,
+            vec![
+                Some([(0, 1), (0, 1), (0, 1)]),
+                None,
+                Some([(2, 1), (2, 1), (2, 1)])
+            ]
+        );
+        drop((input, output));
+        assert_eq!(
+            tty.written(),
+            b"\x1b]4;0;?\x1b\\\x1b]4;1;?\x1b\\\x1b]4;2;?\x1b\\\x1b[c"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_timeout() -> Result<()> {
+        let tty = MockConnection::new();
+        tty.push_input(b"\x1b]4;1;rgb:1/1/1\x1b\\");
+
+        let (mut input, mut output) = tty.io();
+        let responses = (DynRequestColor(1), RequestPrimaryDA).run(&mut input, &mut output)?;
+        assert_eq!(responses.0, Some([(1, 1), (1, 1), (1, 1)]));
+        assert!(responses.1.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+use std::sync::OnceLock;
+
+use crate::cmd::{
+    BatchMode, DynRequestCapability, PrimaryAttributes, RequestPrimaryDA, RequestSecondaryDA,
+    SecondaryAttributes,
+};
+#[cfg(feature = "terminfo")]
+use crate::terminfo::TermInfo;
+use crate::{Connection, Query, Scan};
+
+/// The cached results of probing a terminal's capabilities.
+#[derive(Debug, Default)]
+pub(crate) struct Cache {
+    pub(crate) batch: OnceLock<BatchMode>,
+    primary: OnceLock<Option<PrimaryAttributes>>,
+    secondary: OnceLock<Option<SecondaryAttributes>>,
+    #[cfg(feature = "terminfo")]
+    terminfo: OnceLock<Option<TermInfo>>,
+    rgb: OnceLock<Option<bool>>,
+    truecolor: OnceLock<bool>,
+    hyperlinks: OnceLock<bool>,
+}
+
+/// A terminal's capabilities.
+///
+/// [`Connection::caps`] returns this view onto the connection's capabilities.
+/// It combines several sources of information, namely environment variables,
+/// the terminal's responses to [`RequestPrimaryDA`], [`RequestSecondaryDA`],
+/// and [`DynRequestCapability`], as well as, with the `terminfo` feature, the
+/// terminfo entry for `$TERM`. Each source is probed only when first needed and the result is
+/// cached for the lifetime of the connection. Hence, querying a capability a
+/// second time never waits for the terminal.
+///
+/// When a terminal does not respond to a query, the query times out and the
+/// corresponding capability is treated as unsupported.
+pub struct Capabilities<'a> {
+    connection: &'a Connection,
+    cache: &'a Cache,
+}
+
+impl<'a> Capabilities<'a> {
+    pub(crate) fn new(connection: &'a Connection, cache: &'a Cache) -> Self {
+        Self { connection, cache }
+    }
+
+    /// Write the query and parse its response, returning `None` on failure.
+    fn probe<Q: Query>(&self, query: Q) -> Option<Q::Response> {
+        let (mut input, mut output) = self.connection.io();
+        output.exec(&query).ok()?;
+        let payload = input.read_sequence(query.control()).ok()?;
+        query.parse(payload).ok()
+    }
+
+    /// Get the terminal's primary device attributes.
+    pub fn primary_attributes(&self) -> Option<&'a PrimaryAttributes> {
+        self.cache
+            .primary
+            .get_or_init(|| self.probe(RequestPrimaryDA))
+            .as_ref()
+    }
+
+    /// Get the terminal's secondary device attributes.
+    pub fn secondary_attributes(&self) -> Option<&'a SecondaryAttributes> {
+        self.cache
+            .secondary
+            .get_or_init(|| self.probe(RequestSecondaryDA))
+            .as_ref()
+    }
+
+    /// Get the terminfo entry for `$TERM`.
+    #[cfg(feature = "terminfo")]
+    pub fn terminfo(&self) -> Option<&'a TermInfo> {
+        self.cache
+            .terminfo
+            .get_or_init(|| TermInfo::from_env().ok())
+            .as_ref()
+    }
+
+    /// Determine whether the terminal supports synchronized output.
+    ///
+    /// This method asks the terminal with
+    /// [`RequestBatchMode`](crate::cmd::RequestBatchMode). It shares its cached
+    /// result with [`Output::batch`](crate::Output::batch). If another thread
+    /// is holding the terminal's input or reading the response fails, this
+    /// method returns `false` without caching the result.
+    pub fn batch(&self) -> bool {
+        self.connection.output().supports_batch().unwrap_or(false)
+    }
+
+    /// Determine whether the terminal supports 24-bit colors.
+    ///
+    /// This method first checks `COLORTERM` and then, with the `terminfo`
+    /// feature, the terminfo entry's `RGB` and `Tc` capabilities. Failing
+    /// that, it asks a responsive terminal for its `RGB` capability.
+    pub fn truecolor(&self) -> bool {
+        *self.cache.truecolor.get_or_init(|| {
+            env_truecolor(env)
+                || self.terminfo_truecolor()
+                || (self.primary_attributes().is_some() && self.rgb() == Some(true))
+        })
+    }
+
+    /// Get the terminal's `RGB` capability.
+    ///
+    /// This method asks the terminal with [`DynRequestCapability`] only,
+    /// ignoring environment variables and the terminfo database. It returns
+    /// `None` if the terminal does not respond and otherwise whether the
+    /// terminal knows the capability.
+    pub fn rgb(&self) -> Option<bool> {
+        *self.cache.rgb.get_or_init(|| {
+            self.probe(DynRequestCapability::new("RGB"))
+                .map(|value| value.is_some())
+        })
+    }
+
+    /// Determine whether the terminal supports hyperlinks.
+    ///
+    /// There is no query for this capability. Hence, this method checks
+    /// environment variables identifying terminals with hyperlinks and falls
+    /// back on the secondary device attributes.
+    pub fn hyperlinks(&self) -> bool {
+        *self.cache.hyperlinks.get_or_init(|| {
+            env_hyperlinks(env)
+                || self
+                    .secondary_attributes()
+                    .is_some_and(|attributes| attributes.model_name() == Some("mintty"))
+        })
+    }
+
+    /// Determine whether the terminfo entry signals 24-bit colors.
+    #[cfg(feature = "terminfo")]
+    fn terminfo_truecolor(&self) -> bool {
+        self.terminfo().is_some_and(TermInfo::has_truecolor)
+    }
+
+    /// Determine whether the terminfo entry signals 24-bit colors.
+    #[cfg(not(feature = "terminfo"))]
+    fn terminfo_truecolor(&self) -> bool {
+        false
+    }
+
+    /// Determine whether the terminal supports sixel graphics.
+    pub fn sixel(&self) -> bool {
+        self.primary_attributes()
+            .is_some_and(PrimaryAttributes::has_sixel)
+    }
+}
+
+impl std::fmt::Debug for Capabilities<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Capabilities")
+            .field("batch", &self.cache.batch.get())
+            .field("primary", &self.cache.primary.get())
+            .field("secondary", &self.cache.secondary.get())
+            .field("rgb", &self.cache.rgb.get())
+            .field("truecolor", &self.cache.truecolor.get())
+            .field("hyperlinks", &self.cache.hyperlinks.get())
+            .finish_non_exhaustive()
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// Read the environment variable.
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Determine whether the environment signals 24-bit colors.
+fn env_truecolor(env: impl Fn(&str) -> Option<String>) -> bool {
+    env("COLORTERM").is_some_and(|value| value == "truecolor" || value == "24bit")
+}
+
+/// Determine whether the environment identifies a terminal with hyperlinks.
+fn env_hyperlinks(env: impl Fn(&str) -> Option<String>) -> bool {
+    if env("WT_SESSION").is_some() || env("KITTY_WINDOW_ID").is_some() {
+        return true;
+    }
+
+    if env("TERM_PROGRAM").is_some_and(|value| {
+        matches!(
+            value.as_str(),
+            "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper"
+        )
+    }) {
+        return true;
+    }
+
+    if env("VTE_VERSION")
+        .and_then(|value| value.parse::<u32>().ok())
+        .is_some_and(|version| 5000 <= version)
+    {
+        return true;
+    }
+
+    env("TERM").is_some_and(|value| {
+        ["kitty", "alacritty", "foot", "ghostty", "wezterm"]
+            .iter()
+            .any(|name| value.contains(name))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{env_hyperlinks, env_truecolor};
+
+    #[test]
+    fn test_env() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert!(!env_truecolor(env(&[])));
+        assert!(env_truecolor(env(&[("COLORTERM", "truecolor")])));
+        assert!(env_truecolor(env(&[("COLORTERM", "24bit")])));
+        assert!(!env_truecolor(env(&[("COLORTERM", "yes")])));
+
+        assert!(!env_hyperlinks(env(&[])));
+        assert!(!env_hyperlinks(env(&[("TERM", "xterm-256color")])));
+        assert!(env_hyperlinks(env(&[("TERM", "xterm-kitty")])));
+        assert!(env_hyperlinks(env(&[("TERM_PROGRAM", "iTerm.app")])));
+        assert!(!env_hyperlinks(env(&[("TERM_PROGRAM", "Apple_Terminal")])));
+        assert!(env_hyperlinks(env(&[("VTE_VERSION", "6003")])));
+        assert!(!env_hyperlinks(env(&[("VTE_VERSION", "4803")])));
+        assert!(env_hyperlinks(env(&[("WT_SESSION", "1")])));
+    }
+}
@@ -6,32 +6,55 @@
 //! are:
 //!
 //!   * Terminal management:
+//!       * [`SoftReset`] and [`FullReset`]
 //!       * [`RequestTerminalId`]
+//!       * [`RequestPrimaryDA`], [`RequestSecondaryDA`], and
+//!         [`RequestTertiaryDA`], which parse the device attributes into
+//!         [`PrimaryAttributes`], [`SecondaryAttributes`], and the unit ID
+//!       * [`DynRequestCapability`] for querying terminfo capabilities
+//!       * [`DynRequestMode`] for querying the [`ModeStatus`] of DEC private
+//!         modes
 //!   * Window title management:
-//!       * [`SaveWindowTitle`] and [`RestoreWindowTitle`]
+//!       * [`SaveWindowTitle`] and [`RestoreWindowTitle`], which push the
+//!         title onto and pop it off the terminal's title stack
 //!       * [`DynSetWindowTitle`]
+//!       * [`DynSetProgress`] with a [`ProgressState`]
 //!   * Screen management:
-//!       * [`RequestScreenSize`]
-//!       * [`EnterAlternateScreen`] and [`ExitAlternateScreen`]
-//!       * [`EraseScreen`] and [`EraseLine`]
+//!       * [`RequestScreenSize`] and [`RequestTextAreaSize`]
+//!       * [`RequestTextAreaPixels`] and [`RequestCellPixels`] for scaling
+//!         images to the terminal's cells
+//!       * [`EnterAlternateScreen`] and [`ExitAlternateScreen`], also
+//!         [`EnterPlainAlternateScreen`] and [`ExitPlainAlternateScreen`],
+//!         which neither save the cursor nor clear the alternate screen
+//!       * [`EraseScreen`], [`EraseToScreenEnd`], [`EraseToScreenStart`], and
+//!         [`EraseScrollback`]
+//!       * [`EraseLine`], [`EraseToLineEnd`], and [`EraseToLineStart`]
+//!       * [`EraseCharacters`] and [`DynEraseCharacters`]
+//!       * [`EnableFocusReports`] and [`DisableFocusReports`]
 //!   * Cursor management:
 //!       * [`HideCursor`] and [`ShowCursor`]
-//!       * [`RequestCursorPosition`]
+//!       * [`RequestCursorPosition`] with its [`Position`] response
 //!       * Relative [`MoveUp`], [`MoveDown`], [`MoveLeft`], [`MoveRight`],
 //!         [`DynMoveUp`], [`DynMoveDown`], [`DynMoveLeft`], and
 //!         [`DynMoveRight`]
 //!       * Absolute [`MoveToColumn`], [`MoveToRow`], [`MoveTo`],
 //!         [`DynMoveToColumn`], [`DynMoveToRow`], and [`DynMoveTo`]
+//!       * Relative to the line [`MoveToNextLine`], [`MoveToPreviousLine`],
+//!         [`DynMoveToNextLine`], and [`DynMoveToPreviousLine`]
 //!       * [`SaveCursorPosition`] and [`RestoreCursorPosition`]
+//!       * [`EnableCursorBlinking`] and [`DisableCursorBlinking`]
+//!       * [`SetCursorShape::Default`], [`SetCursorShape::BlinkingBlock`],
+//!         [`SetCursorShape::SteadyBar`], and so on
 //!   * Managing content:
 //!       * [`RequestBatchMode`]
 //!       * [`BeginBatch`] and [`EndBatch`]
-//!       * [`BeginPaste`] and [`EndPaste`] to perform a
-//!         [bracketed paste](https://cirw.in/blog/bracketed-paste) operation
-//!       * [`DynLink`]
+//!       * [`BeginPaste`] and [`EndPaste`] to enable and disable
+//!         [bracketed paste](https://cirw.in/blog/bracketed-paste) mode
+//!       * [`DynLink`] as well as [`DynBeginLink`] and [`EndLink`] for
+//!         hyperlinks
 //!   * Styling content:
 //!       * [`ResetStyle`]
-//!       * [`RequestActiveStyle`]
+//!       * [`RequestActiveStyle`] and [`DynRestoreStyle`]
 //!       * [`SetDefaultForeground`], [`SetForeground8`], [`SetForeground24`],
 //!         [`DynSetForeground8`], and [`DynSetForeground24`]
 //!       * [`SetDefaultBackground`], [`SetBackground8`], [`SetBackground24`],
@@ -45,8 +68,12 @@
 //!       * [`Format::NotStricken`] and [`Format::NotStricken`]
 //!       * [`RequestColor::Black`], [`RequestColor::Red`], and so on for all 16
 //!         ANSI colors, also [`RequestColor::Foreground`],
-//!         [`RequestColor::Background`], [`RequestColor::Cursor`], and
-//!         [`RequestColor::Selection`]
+//!         [`RequestColor::Background`], [`RequestColor::Cursor`],
+//!         [`RequestColor::Selection`], and
+//!         [`RequestColor::SelectionForeground`]
+//!       * [`DynRequestColor`] for all 256 colors
+//!       * [`DynSetColor`] and [`DynResetColor`] for changing the colors
+//!         covered by [`RequestColor`]
 //!
 //! Most commands are implemented by zero-sized unit structs and enum variants.
 //! Commands that require arguments may come in one or both of two flavors, a
@@ -78,6 +105,10 @@
 //! style="display: inline-block; vertical-align: text-top"
 //!      src="https://raw.githubusercontent.com/apparebit/prettypretty/main/docs/figures/wow.png"
 //!      alt="wow!" width="42">. Wow indeed 😜
+//!
+//! To combine commands determined at runtime, a
+//! [`CommandSeq`](crate::CommandSeq) accumulates commands of any type and
+//! writes them all at once.
 
 use crate::util::{is_semi_colon, Radix};
 use crate::{Command, Control, Query, Sgr};
@@ -236,6 +267,12 @@ macro_rules! define_cmd_1 {
 
 // -------------------------------- Terminal Management --------------------------------
 
+// DECSTR resets modes, text attributes, and the saved cursor but leaves the
+// screen contents alone. RIS resets the terminal to its initial state, which
+// also clears the screen and scrollback.
+define_unit_command!(SoftReset, "\x1b[!p");
+define_unit_command!(FullReset, "\x1bc");
+
 define_unit_command!(RequestTerminalId, "\x1b[>q");
 
 impl Query for RequestTerminalId {
@@ -257,9 +294,11 @@ impl Query for RequestTerminalId {
 
         let s = payload
             .strip_prefix(b">|")
-            .and_then(|s| {
-                s.strip_suffix(b"\0x7")
+            .map(|s| {
+                // The scanner strips the terminator, but be lenient.
+                s.strip_suffix(b"\x07")
                     .or_else(|| s.strip_suffix(b"\x1b\\"))
+                    .unwrap_or(s)
             })
             .ok_or_else(|| Error::from(ErrorKind::InvalidData))?
             .trim_ascii();
@@ -280,6 +319,326 @@ impl Query for RequestTerminalId {
     }
 }
 
+/// Parse semicolon-separated decimal parameters.
+fn parse_params(bytes: &[u8]) -> Result<Vec<u16>> {
+    bytes
+        .split(is_semi_colon)
+        .map(|param| {
+            if param.iter().all(u8::is_ascii_digit) {
+                Radix::Decimal.parse_u16(param)
+            } else {
+                None
+            }
+            .ok_or_else(|| Error::from(ErrorKind::InvalidData))
+        })
+        .collect()
+}
+
+/// A terminal's primary device attributes.
+///
+/// The [`RequestPrimaryDA`] query returns this struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrimaryAttributes {
+    level: u16,
+    attributes: Vec<u16>,
+}
+
+impl PrimaryAttributes {
+    /// Get the conformance level, e.g., 1 for a VT100 or 65 for a VT525.
+    pub fn level(&self) -> u16 {
+        self.level
+    }
+
+    /// Get the raw attributes.
+    pub fn attributes(&self) -> &[u16] {
+        &self.attributes
+    }
+
+    /// Determine whether the terminal reports the attribute.
+    pub fn has(&self, attribute: u16) -> bool {
+        self.attributes.contains(&attribute)
+    }
+
+    /// Determine whether the terminal supports sixel graphics.
+    pub fn has_sixel(&self) -> bool {
+        self.has(4)
+    }
+
+    /// Determine whether the terminal supports ANSI colors.
+    pub fn has_ansi_color(&self) -> bool {
+        self.has(22)
+    }
+
+    /// Determine whether the terminal supports rectangular editing.
+    pub fn has_rectangular_editing(&self) -> bool {
+        self.has(28)
+    }
+}
+
+define_unit_command!(RequestPrimaryDA, "\x1b[c");
+
+impl Query for RequestPrimaryDA {
+    type Response = PrimaryAttributes;
+
+    #[inline]
+    fn control(&self) -> Control {
+        Control::CSI
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
+        let params = payload
+            .strip_prefix(b"?")
+            .and_then(|s| s.strip_suffix(b"c"))
+            .ok_or_else(|| Error::from(ErrorKind::InvalidData))?;
+        let mut params = parse_params(params)?;
+        let level = params.remove(0);
+
+        Ok(PrimaryAttributes {
+            level,
+            attributes: params,
+        })
+    }
+}
+
+/// A terminal's secondary device attributes.
+///
+/// The [`RequestSecondaryDA`] query returns this struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SecondaryAttributes {
+    model: u16,
+    version: u16,
+}
+
+impl SecondaryAttributes {
+    /// Get the terminal model's numeric identifier.
+    pub fn model(&self) -> u16 {
+        self.model
+    }
+
+    /// Get the name for well-known terminal models.
+    ///
+    /// Many terminals report the identifier for a VT100, VT220, or similar
+    /// DEC terminal and hence do not identify themselves.
+    pub fn model_name(&self) -> Option<&'static str> {
+        Some(match self.model {
+            0 => "VT100",
+            1 => "VT220",
+            2 => "VT240",
+            18 => "VT330",
+            19 => "VT340",
+            24 => "VT320",
+            41 => "VT420",
+            61 => "VT510",
+            64 => "VT520",
+            65 => "VT525",
+            77 => "mintty",
+            83 => "screen",
+            84 => "tmux",
+            85 => "rxvt-unicode",
+            _ => return None,
+        })
+    }
+
+    /// Get the terminal's firmware or software version.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+}
+
+define_unit_command!(RequestSecondaryDA, "\x1b[>c");
+
+impl Query for RequestSecondaryDA {
+    type Response = SecondaryAttributes;
+
+    #[inline]
+    fn control(&self) -> Control {
+        Control::CSI
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
+        let params = payload
+            .strip_prefix(b">")
+            .and_then(|s| s.strip_suffix(b"c"))
+            .ok_or_else(|| Error::from(ErrorKind::InvalidData))?;
+        let params = parse_params(params)?;
+        if params.len() < 2 {
+            return Err(ErrorKind::InvalidData.into());
+        }
+
+        Ok(SecondaryAttributes {
+            model: params[0],
+            version: params[1],
+        })
+    }
+}
+
+define_unit_command!(RequestTertiaryDA, "\x1b[=c");
+
+impl Query for RequestTertiaryDA {
+    /// The unit ID, decoded from hexadecimal. For example, VTE-based terminals
+    /// respond with `~VTE`.
+    type Response = Vec<u8>;
+
+    #[inline]
+    fn control(&self) -> Control {
+        Control::DCS
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
+        payload
+            .strip_prefix(b"!|")
+            .ok_or_else(|| Error::from(ErrorKind::InvalidData))
+            .and_then(decode_hex)
+    }
+}
+
+/// Decode a string of hexadecimal digit pairs.
+fn decode_hex(hex: &[u8]) -> Result<Vec<u8>> {
+    let pairs = hex.chunks_exact(2);
+    if !pairs.remainder().is_empty() || !hex.iter().all(u8::is_ascii_hexdigit) {
+        return Err(ErrorKind::InvalidData.into());
+    }
+
+    // Unwrap is safe b/c all bytes are hexadecimal digits.
+    Ok(pairs
+        .map(|pair| Radix::Hexadecimal.parse(pair).unwrap() as u8)
+        .collect())
+}
+
+/// The dynamic `DynRequestCapability(NAME)` command.
+///
+/// This command uses xterm's XTGETTCAP to query the terminal for a terminfo
+/// capability, such as `RGB` or `Tc` for 24-bit color or `kcuu1` for the up
+/// arrow key. Besides xterm, kitty, WezTerm, foot, and Ghostty support this
+/// query. The response is `None` if the terminal does not know the capability,
+/// an empty value for a boolean capability, and the capability's value
+/// otherwise.
+///
+/// This command cannot be copied, only cloned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynRequestCapability(String);
+
+impl DynRequestCapability {
+    /// Create a new capability query.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self(name.into())
+    }
+}
+
+implement_command!(DynRequestCapability: self; f {
+    f.write_str("\x1bP+q")?;
+    for byte in self.0.bytes() {
+        write!(f, "{:02X}", byte)?;
+    }
+    f.write_str("\x1b\\")
+});
+
+impl Query for DynRequestCapability {
+    type Response = Option<Vec<u8>>;
+
+    #[inline]
+    fn control(&self) -> Control {
+        Control::DCS
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
+        if payload.starts_with(b"0+r") {
+            return Ok(None);
+        }
+
+        let s = payload
+            .strip_prefix(b"1+r")
+            .ok_or_else(|| Error::from(ErrorKind::InvalidData))?;
+        let (name, value) = match s.iter().position(|byte| *byte == b'=') {
+            Some(index) => (&s[..index], Some(&s[index + 1..])),
+            None => (s, None),
+        };
+
+        if decode_hex(name)? != self.0.as_bytes() {
+            return Err(ErrorKind::InvalidData.into());
+        }
+        value.map_or_else(|| Ok(Some(Vec::new())), |v| decode_hex(v).map(Some))
+    }
+}
+
+/// The status of a terminal mode.
+///
+/// [`DynRequestMode`] returns this enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModeStatus {
+    /// The terminal does not recognize the mode.
+    NotRecognized = 0,
+    /// The mode is set.
+    Set = 1,
+    /// The mode is reset.
+    Reset = 2,
+    /// The mode is permanently set.
+    PermanentlySet = 3,
+    /// The mode is permanently reset.
+    PermanentlyReset = 4,
+}
+
+impl ModeStatus {
+    /// Determine whether the terminal supports the mode, i.e., recognizes it
+    /// and allows for changing it.
+    pub fn is_supported(&self) -> bool {
+        matches!(self, Self::Set | Self::Reset)
+    }
+
+    /// Determine whether the mode is set, permanently or not.
+    pub fn is_set(&self) -> bool {
+        matches!(self, Self::Set | Self::PermanentlySet)
+    }
+}
+
+/// The dynamic `DynRequestMode(MODE)` command.
+///
+/// This command uses DECRQM to query the terminal for the status of a DEC
+/// private mode. That makes it possible to probe features before using them,
+/// e.g., synchronized output (2026), bracketed paste (2004), or the alternate
+/// screen (1049). The terminal responds with DECRPM, which this query parses
+/// into a [`ModeStatus`]. Terminals that do not support DECRQM do not respond
+/// at all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynRequestMode(pub u16);
+
+implement_command!(DynRequestMode: self; f {
+    f.write_str("\x1b[?")?;
+    <_ as ::std::fmt::Display>::fmt(&self.0, f)?;
+    f.write_str("$p")
+});
+
+impl Query for DynRequestMode {
+    type Response = ModeStatus;
+
+    #[inline]
+    fn control(&self) -> Control {
+        Control::CSI
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
+        let mode = self.0.to_string();
+        let bytes = payload
+            .strip_prefix(b"?")
+            .and_then(|s| s.strip_prefix(mode.as_bytes()))
+            .and_then(|s| s.strip_prefix(b";"))
+            .and_then(|s| s.strip_suffix(b"$y"))
+            .ok_or_else(|| Error::from(ErrorKind::InvalidData))?;
+        let status = Radix::Decimal
+            .parse_u32(bytes)
+            .ok_or_else(|| Error::from(ErrorKind::InvalidData))?;
+
+        Ok(match status {
+            0 => ModeStatus::NotRecognized,
+            1 => ModeStatus::Set,
+            2 => ModeStatus::Reset,
+            3 => ModeStatus::PermanentlySet,
+            4 => ModeStatus::PermanentlyReset,
+            _ => return Err(ErrorKind::InvalidData.into()),
+        })
+    }
+}
+
 // --------------------------------- Window Management ---------------------------------
 
 define_unit_command!(SaveWindowTitle, "\x1b[22;2t");
@@ -290,19 +649,80 @@ define_unit_command!(RestoreWindowTitle, "\x1b[23;2t");
 /// This command cannot be copied, only cloned.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct DynSetWindowTitle(String);
+
+impl DynSetWindowTitle {
+    /// Create a new command to set the window title.
+    pub fn new<T: Into<String>>(title: T) -> Self {
+        Self(title.into())
+    }
+}
+
 implement_command!(DynSetWindowTitle: self; f {
     f.write_str("\x1b]2;")?;
     f.write_str(self.0.as_str())?;
     f.write_str("\x1b\\")
 });
 
+/// The state of a task's progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ProgressState {
+    /// Remove the progress indicator.
+    Hidden = 0,
+    /// Show the progress.
+    Normal = 1,
+    /// Show the progress as failed.
+    Error = 2,
+    /// Show an indicator without progress.
+    Indeterminate = 3,
+    /// Show the progress as paused or with a warning.
+    Paused = 4,
+}
+
+/// The dynamic `DynSetProgress(STATE, PERCENT)` command.
+///
+/// This command uses the `OSC 9;4` sequence introduced by ConEmu and also
+/// supported by Windows Terminal, iTerm2 3.6 or later, and Ghostty, which show
+/// the progress in the tab or taskbar. Terminals that do not support the
+/// sequence should ignore it. Older versions of iTerm2 are an exception, since
+/// they show `OSC 9` sequences as notifications. This command clamps the
+/// percentage to 100.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynSetProgress(pub ProgressState, pub u8);
+
+implement_command!(DynSetProgress: self; f {
+    f.write_str("\x1b]9;4;")?;
+    <_ as ::std::fmt::Display>::fmt(&(self.0 as u8), f)?;
+    f.write_str(";")?;
+    <_ as ::std::fmt::Display>::fmt(&self.1.min(100), f)?;
+    f.write_str("\x1b\\")
+});
+
 // --------------------------------- Screen Management ---------------------------------
 
 define_unit_command!(EnterAlternateScreen, "\x1b[?1049h");
 define_unit_command!(ExitAlternateScreen, "\x1b[?1049l");
 
+// Unlike mode 1049, mode 47 neither saves and restores the cursor nor clears
+// the alternate screen.
+define_unit_command!(EnterPlainAlternateScreen, "\x1b[?47h");
+define_unit_command!(ExitPlainAlternateScreen, "\x1b[?47l");
+
+// While focus reports are enabled, the terminal sends `CSI I` and `CSI O`. The
+// scanner turns them into Token::FocusGained and Token::FocusLost.
+define_unit_command!(EnableFocusReports, "\x1b[?1004h");
+define_unit_command!(DisableFocusReports, "\x1b[?1004l");
+
 define_unit_command!(EraseScreen, "\x1b[2J");
+define_unit_command!(EraseToScreenEnd, "\x1b[0J");
+define_unit_command!(EraseToScreenStart, "\x1b[1J");
+define_unit_command!(EraseScrollback, "\x1b[3J");
+
 define_unit_command!(EraseLine, "\x1b[2K");
+define_unit_command!(EraseToLineEnd, "\x1b[0K");
+define_unit_command!(EraseToLineStart, "\x1b[1K");
+
+define_cmd_1!(EraseCharacters<COUNT: u16>, DynEraseCharacters, "\x1b[", "X");
 
 declare_unit_struct!(RequestScreenSize);
 impl Command for RequestScreenSize {}
@@ -315,7 +735,8 @@ impl std::fmt::Display for RequestScreenSize {
 }
 
 impl Query for RequestScreenSize {
-    type Response = <RequestCursorPosition as Query>::Response;
+    /// The number of rows and columns in that order.
+    type Response = (u16, u16);
 
     #[inline]
     fn control(&self) -> Control {
@@ -323,7 +744,75 @@ impl Query for RequestScreenSize {
     }
 
     fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
-        RequestCursorPosition.parse(payload)
+        RequestCursorPosition.parse(payload).map(Into::into)
+    }
+}
+
+/// Parse the payload of an xterm window report `CSI code ; n ; m t`.
+fn parse_window_report(code: &[u8], payload: &[u8]) -> Result<(u16, u16)> {
+    let s = payload
+        .strip_suffix(b"t")
+        .and_then(|s| s.strip_prefix(code))
+        .and_then(|s| s.strip_prefix(b";"))
+        .ok_or_else(|| Error::from(ErrorKind::InvalidData))?;
+
+    let mut params = s.split(is_semi_colon).map(|bytes| {
+        Radix::Decimal
+            .parse_u16(bytes)
+            .ok_or_else(|| Error::from(ErrorKind::InvalidData))
+    });
+
+    match (params.next(), params.next(), params.next()) {
+        (Some(first), Some(second), None) => Ok((first?, second?)),
+        _ => Err(ErrorKind::InvalidData.into()),
+    }
+}
+
+define_unit_command!(RequestTextAreaSize, "\x1b[18t");
+
+impl Query for RequestTextAreaSize {
+    /// The number of rows and columns in that order.
+    type Response = (u16, u16);
+
+    #[inline]
+    fn control(&self) -> Control {
+        Control::CSI
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
+        parse_window_report(b"8", payload)
+    }
+}
+
+define_unit_command!(RequestTextAreaPixels, "\x1b[14t");
+
+impl Query for RequestTextAreaPixels {
+    /// The height and width in pixels in that order.
+    type Response = (u16, u16);
+
+    #[inline]
+    fn control(&self) -> Control {
+        Control::CSI
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
+        parse_window_report(b"4", payload)
+    }
+}
+
+define_unit_command!(RequestCellPixels, "\x1b[16t");
+
+impl Query for RequestCellPixels {
+    /// The height and width in pixels in that order.
+    type Response = (u16, u16);
+
+    #[inline]
+    fn control(&self) -> Control {
+        Control::CSI
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
+        parse_window_report(b"6", payload)
     }
 }
 
@@ -366,11 +855,99 @@ define_cmd_1!(MoveToRow<ROW: u16>, DynMoveToRow, "\x1b[", "d");
 define_unit_command!(SaveCursorPosition, "\x1b7");
 define_unit_command!(RestoreCursorPosition, "\x1b8");
 
+define_cmd_1!(MoveToNextLine<ROWS: u16>, DynMoveToNextLine, "\x1b[", "E");
+define_cmd_1!(MoveToPreviousLine<ROWS: u16>, DynMoveToPreviousLine, "\x1b[", "F");
+
+define_unit_command!(EnableCursorBlinking, "\x1b[?12h");
+define_unit_command!(DisableCursorBlinking, "\x1b[?12l");
+
+/// The enumeration of unit `SetCursorShape` commands.
+///
+/// Blinking shapes override [`DisableCursorBlinking`] and steady shapes
+/// override [`EnableCursorBlinking`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SetCursorShape {
+    Default = 0,
+    BlinkingBlock = 1,
+    SteadyBlock = 2,
+    BlinkingUnderline = 3,
+    SteadyUnderline = 4,
+    BlinkingBar = 5,
+    SteadyBar = 6,
+}
+
+impl Command for SetCursorShape {}
+
+impl std::fmt::Display for SetCursorShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("\x1b[")?;
+        <_ as std::fmt::Display>::fmt(&(*self as u8), f)?;
+        f.write_str(" q")
+    }
+}
+
+/// A cursor position.
+///
+/// Terminals report cursor positions as 1-based row and column numbers, which
+/// is also how this struct stores them. Use [`Position::from_zero_based`] and
+/// [`Position::to_zero_based`] to convert from and to 0-based coordinates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// The 1-based row.
+    pub row: u16,
+    /// The 1-based column.
+    pub column: u16,
+}
+
+impl Position {
+    /// Create a new position from 1-based row and column.
+    #[inline]
+    pub const fn new(row: u16, column: u16) -> Self {
+        Self { row, column }
+    }
+
+    /// Create a new position from 0-based row and column.
+    #[inline]
+    pub const fn from_zero_based(row: u16, column: u16) -> Self {
+        Self {
+            row: row.saturating_add(1),
+            column: column.saturating_add(1),
+        }
+    }
+
+    /// Convert this position to 0-based row and column, in that order.
+    #[inline]
+    pub const fn to_zero_based(&self) -> (u16, u16) {
+        (self.row.saturating_sub(1), self.column.saturating_sub(1))
+    }
+}
+
+impl From<(u16, u16)> for Position {
+    #[inline]
+    fn from(value: (u16, u16)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl From<Position> for (u16, u16) {
+    #[inline]
+    fn from(value: Position) -> Self {
+        (value.row, value.column)
+    }
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{};{}", self.row, self.column)
+    }
+}
+
 define_unit_command!(RequestCursorPosition, "\x1b[6n");
 
 impl Query for RequestCursorPosition {
-    /// The row and column of the cursor in that order.
-    type Response = (u16, u16);
+    /// The 1-based position of the cursor.
+    type Response = Position;
 
     #[inline]
     fn control(&self) -> Control {
@@ -398,7 +975,7 @@ impl Query for RequestCursorPosition {
             return Err(ErrorKind::InvalidData.into());
         }
 
-        Ok((params[0], params[1]))
+        Ok(Position::new(params[0], params[1]))
     }
 }
 
@@ -446,9 +1023,57 @@ impl Query for RequestBatchMode {
 define_unit_command!(BeginBatch, "\x1b[?2026h");
 define_unit_command!(EndBatch, "\x1b[?2026l");
 
+// While bracketed paste mode is enabled, the terminal wraps pasted content in
+// `CSI 200~` and `CSI 201~`. The scanner turns that into a single Token::Paste.
 define_unit_command!(BeginPaste, "\x1b[?2004h");
 define_unit_command!(EndPaste, "\x1b[?2004l");
 
+/// The dynamic `DynBeginLink(ID, HREF)` command.
+///
+/// This command starts a hyperlink, which covers all text written until the
+/// next [`EndLink`]. Terminals that support hyperlinks use the optional ID to
+/// recognize text separated by other content as belonging to the same link.
+/// Unlike [`DynLink`], this command does not wrap text of its own and hence
+/// supports styled link text as well as links spanning several writes.
+///
+/// This command cannot be copied, only cloned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynBeginLink(Option<String>, String);
+
+impl DynBeginLink {
+    /// Create a new hyperlink start with the given URL.
+    pub fn new<H>(href: H) -> Self
+    where
+        H: Into<String>,
+    {
+        Self(None, href.into())
+    }
+
+    /// Create a new hyperlink start with the given ID and URL.
+    pub fn with_id<I, H>(id: Option<I>, href: H) -> Self
+    where
+        I: Into<String>,
+        H: Into<String>,
+    {
+        Self(id.map(|s| s.into()), href.into())
+    }
+}
+
+implement_command!(DynBeginLink: self; f {
+    if let Some(ref id) = self.0 {
+        f.write_str("\x1b]8;id=")?;
+        f.write_str(id)?;
+        f.write_str(";")?;
+    } else {
+        f.write_str("\x1b]8;;")?;
+    }
+
+    f.write_str(self.1.as_str())?;
+    f.write_str("\x1b\\")
+});
+
+define_unit_command!(EndLink, "\x1b]8;;\x1b\\");
+
 /// The dynamic `DynLink(ID, HREF, TEXT)` command.
 ///
 /// This command cannot be copied, only cloned.
@@ -578,11 +1203,44 @@ impl Query for RequestActiveStyle {
     }
 }
 
+/// The dynamic `DynRestoreStyle(PARAMETERS)` command.
+///
+/// This command resets all text attributes and then applies the SGR
+/// parameters, e.g., as reported by [`RequestActiveStyle`]. Since such
+/// parameters originate with the terminal, the constructor drops all bytes
+/// other than digits, colons, and semicolons.
+///
+/// This command cannot be copied, only cloned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynRestoreStyle(String);
+
+impl DynRestoreStyle {
+    /// Create a new command to restore the style with the SGR parameters.
+    pub fn new(parameters: &[u8]) -> Self {
+        Self(
+            parameters
+                .iter()
+                .filter(|byte| byte.is_ascii_digit() || **byte == b':' || **byte == b';')
+                .map(|byte| *byte as char)
+                .collect(),
+        )
+    }
+}
+
+implement_command!(DynRestoreStyle: self; f {
+    f.write_str("\x1b[0")?;
+    if !self.0.is_empty() {
+        f.write_str(";")?;
+        f.write_str(self.0.as_str())?;
+    }
+    f.write_str("m")
+});
+
 /// The enumeration of unit `RequestColor` commands.
 ///
 /// The discriminant ranges from 0 to 15 for the 16 ANSI colors. For the default
-/// foreground, default background, cursor, or selection colors, it is 100 plus
-/// the code used in the query. On Windows, this query is only supported by
+/// foreground, default background, cursor, selection (background), or selection
+/// foreground colors, it is 100 plus the code used in the query. On Windows, this query is only supported by
 /// Terminal 1.22 or later.
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[repr(u8)]
@@ -607,11 +1265,12 @@ pub enum RequestColor {
     Background = 111,
     Cursor = 112,
     Selection = 117,
+    SelectionForeground = 119,
 }
 
 impl RequestColor {
     /// The number of possible color requests.
-    pub const COUNT: usize = 20;
+    pub const COUNT: usize = 21;
 
     /// Get the successor.
     fn successor(&self) -> Option<RequestColor> {
@@ -637,7 +1296,8 @@ impl RequestColor {
             Foreground => Background,
             Background => Cursor,
             Cursor => Selection,
-            Selection => return None,
+            Selection => SelectionForeground,
+            SelectionForeground => return None,
         })
     }
 
@@ -698,35 +1358,122 @@ impl Query for RequestColor {
                 Self::Background => b"11",
                 Self::Cursor => b"12",
                 Self::Selection => b"17",
+                Self::SelectionForeground => b"19",
                 _ => panic!("unknown theme color"),
             })
         }
         .and_then(|bytes| bytes.strip_prefix(b";rgb:"))
         .ok_or(Error::from(ErrorKind::BadSequence))?;
 
-        fn parse(bytes: Option<&[u8]>) -> std::result::Result<(u16, u16), Error> {
-            let bytes = bytes.ok_or(Error::from(ErrorKind::TooFewCoordinates))?;
-            if bytes.is_empty() {
-                return Err(ErrorKind::EmptyCoordinate.into());
-            } else if 4 < bytes.len() {
-                return Err(ErrorKind::OversizedCoordinate.into());
-            }
+        parse_rgb(bytes)
+    }
+}
 
-            let n = Radix::Hexadecimal
-                .parse_u16(bytes)
-                .ok_or(Error::from(ErrorKind::MalformedCoordinate))?;
-            Ok((n, bytes.len() as u16))
-        }
+/// The dynamic `DynSetColor(COLOR, R, G, B)` command.
+///
+/// This command changes the terminal's color for one of the color requests to
+/// the given 24-bit color, emitting OSC 4 for ANSI colors and OSC 10, 11, 12,
+/// 17, or 19 for the default foreground, default background, cursor, selection,
+/// and selection foreground colors, respectively.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DynSetColor(pub RequestColor, pub u8, pub u8, pub u8);
+
+implement_command!(DynSetColor: self; f {
+    let code = self.0 as u32;
+    if code < 16 {
+        f.write_str("\x1b]4;")?;
+        <_ as ::std::fmt::Display>::fmt(&code, f)?;
+    } else {
+        f.write_str("\x1b]")?;
+        <_ as ::std::fmt::Display>::fmt(&(code - 100), f)?;
+    }
+    write!(f, ";rgb:{:02x}/{:02x}/{:02x}\x1b\\", self.1, self.2, self.3)
+});
+
+/// The dynamic `DynResetColor(COLOR)` command.
+///
+/// This command restores the terminal's original color for one of the color
+/// requests, emitting OSC 104 for ANSI colors and OSC 110, 111, 112, 117, or
+/// 119 for the default foreground, default background, cursor, selection, and
+/// selection foreground colors, respectively.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DynResetColor(pub RequestColor);
 
-        let mut iter = bytes.split(|b| *b == b'/');
-        let r = parse(iter.next())?;
-        let g = parse(iter.next())?;
-        let b = parse(iter.next())?;
-        if iter.next().is_some() {
-            return Err(ErrorKind::TooManyCoordinates.into());
+implement_command!(DynResetColor: self; f {
+    let code = self.0 as u32;
+    if code < 16 {
+        f.write_str("\x1b]104;")?;
+    } else {
+        f.write_str("\x1b]")?;
+    }
+    <_ as ::std::fmt::Display>::fmt(&code, f)?;
+    f.write_str("\x1b\\")
+});
+
+/// Parse the `rgb:` coordinates of a color query's response.
+fn parse_rgb(bytes: &[u8]) -> Result<[(u16, u16); 3]> {
+    use crate::err::ErrorKind;
+
+    fn parse(bytes: Option<&[u8]>) -> std::result::Result<(u16, u16), Error> {
+        let bytes = bytes.ok_or(Error::from(ErrorKind::TooFewCoordinates))?;
+        if bytes.is_empty() {
+            return Err(ErrorKind::EmptyCoordinate.into());
+        } else if 4 < bytes.len() {
+            return Err(ErrorKind::OversizedCoordinate.into());
         }
 
-        Ok([r, g, b])
+        let n = Radix::Hexadecimal
+            .parse_u16(bytes)
+            .ok_or(Error::from(ErrorKind::MalformedCoordinate))?;
+        Ok((n, bytes.len() as u16))
+    }
+
+    let mut iter = bytes.split(|b| *b == b'/');
+    let r = parse(iter.next())?;
+    let g = parse(iter.next())?;
+    let b = parse(iter.next())?;
+    if iter.next().is_some() {
+        return Err(ErrorKind::TooManyCoordinates.into());
+    }
+
+    Ok([r, g, b])
+}
+
+/// The dynamic `DynRequestColor(INDEX)` command.
+///
+/// This command queries the terminal for the color with the given index in the
+/// 256-color palette. Unlike [`RequestColor`], it also covers the embedded RGB
+/// cube and gray gradient with indices 16–255, which many terminals let users
+/// remap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynRequestColor(pub u8);
+
+implement_command!(DynRequestColor: self; f {
+    f.write_str("\x1b]4;")?;
+    <_ as ::std::fmt::Display>::fmt(&self.0, f)?;
+    f.write_str(";?\x1b\\")
+});
+
+impl Query for DynRequestColor {
+    /// An RGB color with the same representation as for [`RequestColor`].
+    type Response = [(u16, u16); 3];
+
+    #[inline]
+    fn control(&self) -> Control {
+        Control::OSC
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Response> {
+        use crate::err::ErrorKind;
+
+        let index = self.0.to_string();
+        let bytes = payload
+            .strip_prefix(b"4;")
+            .and_then(|bytes| bytes.strip_prefix(index.as_bytes()))
+            .and_then(|bytes| bytes.strip_prefix(b";rgb:"))
+            .ok_or(Error::from(ErrorKind::BadSequence))?;
+
+        parse_rgb(bytes)
     }
 }
 
@@ -735,8 +1482,14 @@ impl Query for RequestColor {
 #[cfg(test)]
 mod test {
     use super::{
-        BeginBatch, DynMoveLeft, DynMoveTo, MoveLeft, MoveTo, Query, RequestColor,
-        RequestCursorPosition, RequestTerminalId,
+        BeginBatch, BeginPaste, DynBeginLink, DynEraseCharacters, DynMoveLeft, DynMoveTo,
+        DynMoveToNextLine, DynRequestCapability, DynRequestColor, DynRequestMode, DynResetColor,
+        DynRestoreStyle, DynSetColor, DynSetProgress, DynSetWindowTitle, EnableFocusReports,
+        EndLink, EnterPlainAlternateScreen, EraseScrollback, FullReset, ModeStatus, MoveLeft,
+        MoveTo, Position, ProgressState, Query, RequestCellPixels, RequestColor,
+        RequestCursorPosition, RequestPrimaryDA, RequestScreenSize, RequestSecondaryDA,
+        RequestTerminalId, RequestTertiaryDA, RequestTextAreaPixels, RequestTextAreaSize,
+        SetCursorShape, SoftReset,
     };
 
     #[test]
@@ -748,10 +1501,61 @@ mod test {
         assert_eq!(std::mem::size_of::<DynMoveTo>(), 4);
 
         assert_eq!(format!("{}", BeginBatch), "\x1b[?2026h");
+        assert_eq!(format!("{}", SoftReset), "\x1b[!p");
+        assert_eq!(format!("{}", FullReset), "\x1bc");
+        assert_eq!(format!("{}", BeginPaste), "\x1b[?2004h");
+        assert_eq!(format!("{}", EnableFocusReports), "\x1b[?1004h");
+        assert_eq!(format!("{}", EnterPlainAlternateScreen), "\x1b[?47h");
         assert_eq!(format!("{}", MoveLeft::<2>), "\x1b[2C");
         assert_eq!(format!("{}", DynMoveLeft(2)), "\x1b[2C");
         assert_eq!(format!("{}", MoveTo::<5, 7>), "\x1b[5;7H");
         assert_eq!(format!("{}", DynMoveTo(5, 7)), "\x1b[5;7H");
+        assert_eq!(format!("{}", DynMoveToNextLine(3)), "\x1b[3E");
+        assert_eq!(format!("{}", EraseScrollback), "\x1b[3J");
+        assert_eq!(
+            format!("{}", DynSetWindowTitle::new("Pretty")),
+            "\x1b]2;Pretty\x1b\\"
+        );
+        assert_eq!(
+            format!("{}", DynSetProgress(ProgressState::Normal, 142)),
+            "\x1b]9;4;1;100\x1b\\"
+        );
+        assert_eq!(format!("{}", DynEraseCharacters(4)), "\x1b[4X");
+        assert_eq!(format!("{}", DynRestoreStyle::new(b"")), "\x1b[0m");
+        assert_eq!(
+            format!("{}", DynRestoreStyle::new(b"1;38:2::1:2:3\x1b]")),
+            "\x1b[0;1;38:2::1:2:3m"
+        );
+        assert_eq!(format!("{}", SetCursorShape::SteadyBar), "\x1b[6 q");
+        assert_eq!(
+            format!(
+                "{}docs{}",
+                DynBeginLink::with_id(Some("d"), "https://example.com"),
+                EndLink
+            ),
+            "\x1b]8;id=d;https://example.com\x1b\\docs\x1b]8;;\x1b\\"
+        );
+        assert_eq!(
+            format!("{}", DynBeginLink::new("https://example.com")),
+            "\x1b]8;;https://example.com\x1b\\"
+        );
+
+        assert_eq!(
+            DynSetColor(RequestColor::Cursor, 255, 8, 0).to_string(),
+            "\x1b]12;rgb:ff/08/00\x1b\\"
+        );
+        assert_eq!(
+            DynSetColor(RequestColor::BrightRed, 1, 2, 3).to_string(),
+            "\x1b]4;9;rgb:01/02/03\x1b\\"
+        );
+        assert_eq!(
+            DynResetColor(RequestColor::Background).to_string(),
+            "\x1b]111\x1b\\"
+        );
+        assert_eq!(
+            DynResetColor(RequestColor::Red).to_string(),
+            "\x1b]104;1\x1b\\"
+        );
     }
 
     #[test]
@@ -760,6 +1564,10 @@ mod test {
         assert_eq!(&term.unwrap(), b"Terminal".as_slice());
         assert!(version.is_none());
 
+        let (term, version) = RequestTerminalId.parse(b">|Terminal (6.65)")?;
+        assert_eq!(&term.unwrap(), b"Terminal".as_slice());
+        assert_eq!(&version.unwrap(), b"6.65".as_slice());
+
         let (term, version) = RequestTerminalId.parse(b">|Terminal (6.65)\x1b\\")?;
         assert_eq!(&term.unwrap(), b"Terminal".as_slice());
         assert_eq!(&version.unwrap(), b"6.65".as_slice());
@@ -782,10 +1590,68 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_device_attributes() -> std::io::Result<()> {
+        let attributes = RequestPrimaryDA.parse(b"?65;1;4;6;22c")?;
+        assert_eq!(attributes.level(), 65);
+        assert!(attributes.has_sixel());
+        assert!(attributes.has_ansi_color());
+        assert!(!attributes.has_rectangular_editing());
+
+        let attributes = RequestSecondaryDA.parse(b">41;397;0c")?;
+        assert_eq!(attributes.model_name(), Some("VT420"));
+        assert_eq!(attributes.version(), 397);
+        assert!(RequestSecondaryDA.parse(b">41c").is_err());
+
+        assert_eq!(RequestTertiaryDA.parse(b"!|7E565445")?, b"~VTE");
+        assert!(RequestTertiaryDA.parse(b"!|7E5").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_capability() -> std::io::Result<()> {
+        let query = DynRequestCapability::new("RGB");
+        assert_eq!(query.to_string(), "\x1bP+q524742\x1b\\");
+        assert_eq!(query.parse(b"1+r524742=38")?, Some(b"8".to_vec()));
+        assert_eq!(query.parse(b"1+r524742")?, Some(Vec::new()));
+        assert_eq!(query.parse(b"0+r524742")?, None);
+        assert!(query.parse(b"1+r5463").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_mode() -> std::io::Result<()> {
+        assert_eq!(format!("{}", DynRequestMode(2004)), "\x1b[?2004$p");
+        assert_eq!(DynRequestMode(2004).parse(b"?2004;2$y")?, ModeStatus::Reset);
+        assert_eq!(
+            DynRequestMode(1049).parse(b"?1049;0$y")?,
+            ModeStatus::NotRecognized
+        );
+        assert!(!DynRequestMode(2026).parse(b"?2026;4$y")?.is_set());
+        assert!(DynRequestMode(2026).parse(b"?2004;1$y").is_err());
+        assert!(DynRequestMode(2026).parse(b"?2026;7$y").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_parse_cursor_position() -> std::io::Result<()> {
         let position = RequestCursorPosition.parse(b"6;65R")?;
-        assert_eq!(position, (6, 65));
+        assert_eq!(position, Position::new(6, 65));
+        assert_eq!(position.to_zero_based(), (5, 64));
+        assert_eq!(Position::from_zero_based(5, 64), position);
+        assert_eq!(Position::new(0, 0).to_zero_based(), (0, 0));
+        assert_eq!(RequestScreenSize.parse(b"24;80R")?, (24, 80));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_window_report() -> std::io::Result<()> {
+        let size = RequestTextAreaSize.parse(b"8;24;80t")?;
+        assert_eq!(size, (24, 80));
+        assert!(RequestTextAreaSize.parse(b"4;24;80t").is_err());
+        assert!(RequestTextAreaSize.parse(b"8;24t").is_err());
+        assert_eq!(RequestTextAreaPixels.parse(b"4;768;1280t")?, (768, 1280));
+        assert_eq!(RequestCellPixels.parse(b"6;32;16t")?, (32, 16));
         Ok(())
     }
 
@@ -797,6 +1663,13 @@ mod test {
         assert_eq!(color, [(18, 2), (837, 3), (26_505, 4)]);
         let color = RequestColor::BrightMagenta.parse(b"4;13;rgb:ff/00/ff")?;
         assert_eq!(color, [(255, 2), (0, 2), (255, 2)]);
+
+        assert_eq!(DynRequestColor(196).to_string(), "\x1b]4;196;?\x1b\\");
+        let color = DynRequestColor(196).parse(b"4;196;rgb:ffff/0000/0000")?;
+        assert_eq!(color, [(65_535, 4), (0, 4), (0, 4)]);
+        assert!(DynRequestColor(19)
+            .parse(b"4;196;rgb:ffff/0000/0000")
+            .is_err());
         Ok(())
     }
 }
@@ -1,11 +1,18 @@
 use std::io::{BufRead, BufWriter, Error, ErrorKind, Read, Result, Write};
-use std::sync::{Mutex, MutexGuard};
+use std::sync::{Mutex, MutexGuard, TryLockError};
 
+use crate::caps::{Cache, Capabilities};
+use crate::cmd::{
+    BatchMode, BeginBatch, DisableFocusReports, DynSetWindowTitle, EndBatch, EndPaste,
+    EnterAlternateScreen, ExitAlternateScreen, Position, RequestBatchMode, RequestCursorPosition,
+    ResetStyle, RestoreWindowTitle, SaveWindowTitle, ShowCursor,
+};
+use crate::log::LogWriter;
 use crate::opt::{Options, Volume};
 use crate::read::{DoggedReader, VerboseReader};
 use crate::scan::Scanner;
-use crate::sys::{RawConfig, RawConnection, RawOutput};
-use crate::{Command, Scan};
+use crate::sys::{self, RawConfig, RawConnection, RawOutput, RawWaker};
+use crate::{Command, Control, Query, Scan, Token};
 
 /// A terminal connection providing [`Input`] and [`Output`].
 ///
@@ -22,7 +29,9 @@ pub struct Connection {
     stamp: u32,
     config: Option<RawConfig>,
     scanner: Mutex<Scanner<Box<dyn Read + Send>>>,
-    writer: Mutex<BufWriter<RawOutput>>,
+    writer: Mutex<BufWriter<LogWriter<RawOutput>>>,
+    modes: Mutex<Vec<(String, String)>>,
+    capabilities: Cache,
     connection: RawConnection,
 }
 
@@ -44,19 +53,66 @@ impl Connection {
     pub fn with_options(options: Options) -> Result<Self> {
         let connection = RawConnection::open(&options)
             .map_err(|e| Error::new(ErrorKind::ConnectionRefused, e))?;
+        Self::with_raw(connection, options)
+    }
 
-        let config = RawConfig::read(&connection)?;
+    /// Create a terminal connection with the given file descriptors.
+    /// <i class=unix-only>Unix only!</i>
+    ///
+    /// This method enables reuse of this crate's commands, queries, and
+    /// scanner for other devices than the controlling terminal, e.g., a
+    /// pseudo-terminal created with `openpty`. If the input is a terminal, the
+    /// connection configures it according to the options, just like
+    /// [`Connection::with_options`]. Otherwise, it leaves the input as is.
+    /// Since only terminals support read timeouts, a connection to another
+    /// kind of file descriptor should use
+    /// [`OptionBuilder::poll_timeout`](crate::opt::OptionBuilder::poll_timeout).
+    #[cfg(target_family = "unix")]
+    pub fn with_fds(
+        input: std::os::fd::OwnedFd,
+        output: std::os::fd::OwnedFd,
+        options: Options,
+    ) -> Result<Self> {
+        let connection = RawConnection::with_fds(input, output, &options)?;
+        Self::with_raw(connection, options)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "connect",
+            level = "debug",
+            skip_all,
+            fields(mode = ?options.mode(), timeout = options.timeout())
+        )
+    )]
+    fn with_raw(connection: RawConnection, options: Options) -> Result<Self> {
         let verbose = !matches!(options.volume(), Volume::Silent);
+        if !connection.is_terminal() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("input is not a terminal, leaving configuration as is");
+            return Self::with_config(connection, None, options);
+        }
+
+        let config = RawConfig::read(&connection)?;
         if verbose {
             println!("terminal::config {:?}", &config);
         }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(?config, "read terminal configuration");
         let config = config.apply(&options).map_or_else(
             || Ok::<Option<RawConfig>, Error>(None),
             |reconfig| {
                 if verbose {
                     println!("terminal::reconfig {:?}", &reconfig);
                 }
+                #[cfg(feature = "tracing")]
+                tracing::debug!(?reconfig, "applying terminal configuration");
                 reconfig.write(&connection)?;
+                if options.restore_hooks() {
+                    sys::install_hooks();
+                    sys::arm(&connection, &config, &reconfig);
+                }
                 if verbose {
                     // We need explicit carriage-return and line-feed characters
                     // because the reconfiguration just took effect.
@@ -66,15 +122,32 @@ impl Connection {
             },
         )?;
 
+        Self::with_config(connection, config, options)
+    }
+
+    fn with_config(
+        connection: RawConnection,
+        config: Option<RawConfig>,
+        options: Options,
+    ) -> Result<Self> {
+        let verbose = !matches!(options.volume(), Volume::Silent);
+
         let reader: Box<dyn Read + Send> = if matches!(options.volume(), Volume::Detailed) {
-            Box::new(VerboseReader::new(connection.input(), options.timeout()))
+            let timeout = options
+                .poll_timeout()
+                .map_or(options.timeout() as f32 * 0.1, |ms| ms as f32 * 0.001);
+            Box::new(VerboseReader::new(connection.input(), timeout))
         } else {
             Box::new(DoggedReader::new(connection.input()))
         };
+        let reader: Box<dyn Read + Send> = match options.logger() {
+            Some(logger) => Box::new(logger.reader(reader)),
+            None => reader,
+        };
         let scanner = Mutex::new(Scanner::with_options(&options, reader));
         let writer = Mutex::new(BufWriter::with_capacity(
             options.write_buffer_size(),
-            connection.output(),
+            LogWriter::with(connection.output(), options.logger().cloned()),
         ));
         let stamp = if verbose {
             // macOS duration has microsecond resolution only, so that's our
@@ -94,6 +167,8 @@ impl Connection {
             config,
             scanner,
             writer,
+            modes: Mutex::new(Vec::new()),
+            capabilities: Cache::default(),
             connection,
         };
 
@@ -107,13 +182,67 @@ impl Connection {
         &self.options
     }
 
+    /// Get the terminal's capabilities.
+    ///
+    /// The capabilities are probed lazily and cached for the lifetime of this
+    /// connection. Since probing may write queries and read their responses,
+    /// the calling thread must not hold on to this connection's [`Input`] or
+    /// [`Output`] while checking a capability for the first time.
+    #[inline]
+    pub fn caps(&self) -> Capabilities<'_> {
+        Capabilities::new(self, &self.capabilities)
+    }
+
+    /// Get a handle for interrupting reads from another thread.
+    ///
+    /// This method only returns a handle if the connection uses readiness
+    /// notifications, i.e., was opened with
+    /// [`OptionBuilder::poll_timeout`](crate::opt::OptionBuilder::poll_timeout).
+    pub fn wake_handle(&self) -> Option<WakeHandle> {
+        self.connection.waker().map(|raw| WakeHandle { raw })
+    }
+
+    /// Get the terminal's size in rows and columns.
+    ///
+    /// This method uses the `TIOCGWINSZ` ioctl on Unix and the console's
+    /// screen buffer info on Windows. Unlike the
+    /// [`RequestTextAreaSize`](crate::cmd::RequestTextAreaSize) query, it
+    /// does not require a response from the terminal and hence is fast.
+    #[inline]
+    pub fn window_size(&self) -> Result<(u16, u16)> {
+        self.connection.size()
+    }
+
+    /// Get the Windows console's current text attributes.
+    ///
+    /// The low nibble of the result is the foreground and the next nibble the
+    /// background color, with each nibble comprising intensity, red, green, and
+    /// blue bits, from most to least significant. On Unix, this method fails
+    /// with [`ErrorKind::Unsupported`].
+    #[inline]
+    pub fn console_attributes(&self) -> Result<u16> {
+        self.connection.attributes()
+    }
+
+    /// Get the Windows console's color table.
+    ///
+    /// The 16 colors are 24-bit RGB colors in the console's order, i.e., the
+    /// index of each color comprises intensity, red, green, and blue bits, from
+    /// most to least significant, just like the nibbles of
+    /// [`Connection::console_attributes`]. On Unix, this method fails with
+    /// [`ErrorKind::Unsupported`].
+    #[inline]
+    pub fn console_palette(&self) -> Result<[[u8; 3]; 16]> {
+        self.connection.palette()
+    }
+
     /// Get both terminal input and output.
     ///
     /// The returned input and output objects ensure mutually exclusive access
     /// to the terminal's input and output, respectively. Dropping them releases
     /// access again.
     #[inline]
-    pub fn io(&self) -> (Input, Output) {
+    pub fn io(&self) -> (Input<'_>, Output<'_>) {
         (self.input(), self.output())
     }
 
@@ -122,9 +251,10 @@ impl Connection {
     /// The returned input object ensures mutually exclusive access to the
     /// terminal's input. Dropping the input object releases access again.
     #[inline]
-    pub fn input(&self) -> Input {
+    pub fn input(&self) -> Input<'_> {
         Input {
             scanner: self.scanner.lock().expect("mutex is not poisoned"),
+            connection: &self.connection,
         }
     }
 
@@ -133,13 +263,196 @@ impl Connection {
     /// The returned output object ensures mutually exclusive access to the
     /// terminal's output. Dropping the output object releases access again.
     #[inline]
-    pub fn output(&self) -> Output {
+    pub fn output(&self) -> Output<'_> {
         Output {
             writer: self.writer.lock().expect("mutex is not poisoned"),
+            scanner: &self.scanner,
+            capabilities: &self.capabilities,
         }
     }
 
+    /// Switch to the alternate screen.
+    ///
+    /// This method returns a guard that switches back to the main screen and
+    /// shows the cursor again when dropped, including when unwinding from a
+    /// panic. That way, the user's scrollback survives even when the
+    /// application doesn't.
+    ///
+    /// ```
+    /// # use std::io::{ErrorKind, Result};
+    /// # use prettytty::Connection;
+    /// # use prettytty::cmd::HideCursor;
+    /// # fn run() -> Result<()> {
+    /// let tty = Connection::open()?;
+    /// {
+    ///     let _screen = tty.alternate_screen()?;
+    ///     tty.output().exec(HideCursor)?;
+    ///     // Draw on the alternate screen...
+    /// }
+    /// // Back on the main screen, with a visible cursor.
+    /// # Ok(())
+    /// # }
+    /// # match run() {
+    /// #     Ok(()) => (),
+    /// #     Err(err) if err.kind() == ErrorKind::ConnectionRefused &&
+    /// #         std::env::var_os("CI").is_some() => (),
+    /// #     Err(err) => return Err(err),
+    /// # };
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn alternate_screen(&self) -> Result<AlternateScreen<'_>> {
+        self.output().exec(EnterAlternateScreen)?;
+        sys::set_alternate_screen(true);
+        Ok(AlternateScreen { connection: self })
+    }
+
+    /// Enable a terminal mode and track it for [`Connection::resume`].
+    ///
+    /// This method writes the command enabling the mode, e.g.,
+    /// [`BeginPaste`](crate::cmd::BeginPaste), and remembers it together with
+    /// the command disabling the mode, e.g.,
+    /// [`EndPaste`](crate::cmd::EndPaste). [`Connection::suspend`] disables
+    /// all tracked modes before stopping the process and
+    /// [`Connection::resume`] enables them again.
+    pub fn enable_mode(&self, enable: impl Command, disable: impl Command) -> Result<()> {
+        self.output().exec(&enable)?;
+        let mut modes = self.modes.lock().expect("mutex is not poisoned");
+        modes.push((enable.to_string(), disable.to_string()));
+        if self.options.restore_hooks() {
+            sys::set_disabled_modes(&modes);
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(enable = ?modes.last().map(|m| &m.0), "enabled mode");
+        Ok(())
+    }
+
+    /// Disable a terminal mode and stop tracking it.
+    ///
+    /// This method writes the command disabling the mode and forgets about
+    /// the mode with the same disabling command.
+    pub fn disable_mode(&self, disable: impl Command) -> Result<()> {
+        self.output().exec(&disable)?;
+        let disable = disable.to_string();
+        let mut modes = self.modes.lock().expect("mutex is not poisoned");
+        modes.retain(|(_, command)| *command != disable);
+        if self.options.restore_hooks() {
+            sys::set_disabled_modes(&modes);
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(?disable, "disabled mode");
+        Ok(())
+    }
+
+    /// Suspend the process. <i class=unix-only>Unix only!</i>
+    ///
+    /// This method implements job control for applications that handle
+    /// control-z themselves, e.g., because they use raw mode. It disables all
+    /// modes tracked with [`Connection::enable_mode`], leaves the alternate
+    /// screen, restores the original terminal configuration, and then stops
+    /// the process with `SIGTSTP`. Once the process continues, it calls
+    /// [`Connection::resume`] before returning. On Windows, this method fails
+    /// with [`ErrorKind::Unsupported`].
+    ///
+    /// The calling thread must not hold on to this connection's [`Output`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn suspend(&self) -> Result<()> {
+        if cfg!(target_family = "windows") {
+            return Err(ErrorKind::Unsupported.into());
+        }
+
+        {
+            let mut output = self.output();
+            let modes = self.modes.lock().expect("mutex is not poisoned");
+            for (_, disable) in modes.iter().rev() {
+                output.write_all(disable.as_bytes())?;
+            }
+            if sys::is_alternate_screen() {
+                write!(output, "{}{}", ShowCursor, ExitAlternateScreen)?;
+            }
+            output.flush()?;
+        }
+
+        if self.options.restore_hooks() {
+            sys::disarm();
+        }
+        if let Some(config) = &self.config {
+            config.write(&self.connection)?;
+        }
+
+        sys::stop_process()?;
+        self.resume()
+    }
+
+    /// Resume after the process was stopped.
+    ///
+    /// This method reapplies the connection's terminal configuration,
+    /// re-enters the alternate screen if necessary, and enables all modes
+    /// tracked with [`Connection::enable_mode`]. [`Connection::suspend`]
+    /// invokes this method once the process continues. Applications only need
+    /// to invoke it directly if the process was stopped by other means.
+    /// Since the terminal does not preserve the alternate screen's contents,
+    /// applications should redraw the screen afterwards.
+    ///
+    /// The calling thread must not hold on to this connection's [`Output`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn resume(&self) -> Result<()> {
+        if let Some(config) = &self.config {
+            if let Some(reconfig) = config.apply(&self.options) {
+                reconfig.write(&self.connection)?;
+                if self.options.restore_hooks() {
+                    sys::arm(&self.connection, config, &reconfig);
+                }
+            }
+        }
+
+        let mut output = self.output();
+        if sys::is_alternate_screen() {
+            write!(output, "{}", EnterAlternateScreen)?;
+        }
+        let modes = self.modes.lock().expect("mutex is not poisoned");
+        for (enable, _) in modes.iter() {
+            output.write_all(enable.as_bytes())?;
+        }
+        output.flush()
+    }
+
+    /// Temporarily change the window title.
+    ///
+    /// This method pushes the current window title onto the terminal's title
+    /// stack and then sets the given title. It returns a guard that pops the
+    /// previous title off the stack again when dropped, including when
+    /// unwinding from a panic.
+    pub fn window_title(&self, title: impl Into<String>) -> Result<WindowTitle<'_>> {
+        let mut output = self.output();
+        write!(output, "{}", SaveWindowTitle)?;
+        output.exec(DynSetWindowTitle::new(title))?;
+        Ok(WindowTitle { connection: self })
+    }
+
+    /// Write the commands from a guard's drop handler.
+    ///
+    /// Since the drop handler may be running while unwinding from a panic,
+    /// this method neither blocks on the output, which may be held by the
+    /// same thread, nor panics on a poisoned mutex. If another thread is
+    /// writing to the terminal, it does not write the commands.
+    fn write_on_drop(&self, commands: &[&dyn Command]) {
+        let mut writer = match self.writer.try_lock() {
+            Ok(writer) => writer,
+            Err(TryLockError::Poisoned(error)) => error.into_inner(),
+            Err(TryLockError::WouldBlock) => return,
+        };
+
+        for cmd in commands {
+            let _ = write!(writer, "{}", cmd);
+        }
+        let _ = writer.flush();
+    }
+
     fn log(&self, message: impl AsRef<str>) -> Result<()> {
+        if let Some(logger) = self.options.logger() {
+            logger.message(message.as_ref());
+        }
+
         if !matches!(self.options.volume(), Volume::Silent) {
             // Don't wait for output.
             let mut writer = self
@@ -165,19 +478,79 @@ impl Connection {
 impl Drop for Connection {
     fn drop(&mut self) {
         let _ = self.log("terminal::disconnect");
+        #[cfg(feature = "tracing")]
+        tracing::debug!("disconnecting from terminal");
 
         // map() avoids panic for poisoned mutex
         let _ = self.writer.lock().map(|mut writer| {
             let _ = writer.flush();
         });
 
-        // Restore terminal configuration
+        // Restore terminal configuration. Only a connection with restore hooks
+        // armed them, so leave another connection's hooks alone.
+        if self.options.restore_hooks() {
+            sys::disarm();
+            sys::set_disabled_modes(&[]);
+        }
         if let Some(cfg) = &self.config {
             let _ = cfg.write(&self.connection);
         }
     }
 }
 
+/// A handle for interrupting reads from the terminal.
+///
+/// [`Connection::wake_handle`] returns this handle for connections that use
+/// readiness notifications. Invoking [`WakeHandle::wake`] makes a pending or
+/// the next read return without data, just as if it had timed out. That
+/// enables prompt cancellation of a thread blocked on terminal input. The
+/// handle can be cloned and sent to other threads. Once the connection has
+/// been dropped, waking has no effect.
+#[derive(Clone, Debug)]
+pub struct WakeHandle {
+    raw: RawWaker,
+}
+
+impl WakeHandle {
+    /// Wake up the thread reading from the terminal.
+    pub fn wake(&self) -> Result<()> {
+        self.raw.wake()
+    }
+}
+
+/// A guard for the alternate screen.
+///
+/// [`Connection::alternate_screen`] returns this guard. Dropping it switches
+/// back to the main screen and shows the cursor. If another thread is writing
+/// to the terminal at that time, the guard does not wait and leaves the screen
+/// as is.
+pub struct AlternateScreen<'a> {
+    connection: &'a Connection,
+}
+
+impl Drop for AlternateScreen<'_> {
+    fn drop(&mut self) {
+        self.connection
+            .write_on_drop(&[&ShowCursor, &ExitAlternateScreen]);
+        sys::set_alternate_screen(false);
+    }
+}
+
+/// A guard for a temporary window title.
+///
+/// [`Connection::window_title`] returns this guard. Dropping it restores the
+/// previous window title. Like [`AlternateScreen`], the guard does not wait if
+/// another thread is writing to the terminal.
+pub struct WindowTitle<'a> {
+    connection: &'a Connection,
+}
+
+impl Drop for WindowTitle<'_> {
+    fn drop(&mut self) {
+        self.connection.write_on_drop(&[&RestoreWindowTitle]);
+    }
+}
+
 /// A terminal [`Connection`]'s input.
 ///
 /// In addition to [`Read`] and [`BufRead`], terminal input also implements
@@ -194,6 +567,10 @@ impl Drop for Connection {
 /// parameters](https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/V1_chap11.html#tag_11_01_07_03)
 /// On Windows, the timeout is implemented with
 /// [`WaitForSingleObject`](https://learn.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-waitforsingleobject).
+/// Alternatively, with
+/// [`OptionBuilder::poll_timeout`](crate::opt::OptionBuilder::poll_timeout),
+/// reads wait for readiness notifications with millisecond-precision timeouts
+/// and can be interrupted with a [`WakeHandle`].
 ///
 ///
 /// # Scanning Tokens vs Reading Bytes
@@ -242,6 +619,23 @@ impl Drop for Connection {
 #[derive(Debug)]
 pub struct Input<'a> {
     scanner: MutexGuard<'a, Scanner<Box<dyn Read + Send>>>,
+    connection: &'a RawConnection,
+}
+
+impl Input<'_> {
+    /// Read the next token without waiting for the terminal.
+    ///
+    /// Unlike [`Scan::read_token`], this method does not block until the read
+    /// times out. Instead, it only reads from the terminal while the terminal
+    /// has data available. It returns `Ok(None)` if there is no complete token
+    /// buffered and no more data is pending. A partially received token
+    /// remains in-flight and a later read completes it.
+    pub fn try_read_token(&mut self) -> Result<Option<Token<'_>>> {
+        let connection = self.connection;
+        self.scanner
+            .try_read_token(|| connection.is_ready())
+            .map_err(|e| e.into())
+    }
 }
 
 impl Scan for Input<'_> {
@@ -251,7 +645,7 @@ impl Scan for Input<'_> {
     }
 
     #[inline]
-    fn read_token(&mut self) -> Result<crate::Token> {
+    fn read_token(&mut self) -> Result<crate::Token<'_>> {
         self.scanner.read_token().map_err(|e| e.into())
     }
 }
@@ -287,7 +681,9 @@ impl BufRead for Input<'_> {
 /// commands, while also flushing the output on every invocation.
 #[derive(Debug)]
 pub struct Output<'a> {
-    writer: MutexGuard<'a, BufWriter<RawOutput>>,
+    writer: MutexGuard<'a, BufWriter<LogWriter<RawOutput>>>,
+    scanner: &'a Mutex<Scanner<Box<dyn Read + Send>>>,
+    capabilities: &'a Cache,
 }
 
 impl Output<'_> {
@@ -312,6 +708,171 @@ impl Output<'_> {
         write!(self.writer, "{}", cmd)?;
         self.writer.flush()
     }
+
+    /// Write the closure's output as one batch.
+    ///
+    /// If the terminal supports synchronized output, this method wraps the
+    /// closure's output in [`BeginBatch`] and [`EndBatch`], so that the
+    /// terminal renders all of it at once. Either way, it flushes the output
+    /// only once, after the closure returns. For that to be effective, the
+    /// closure should use [`write!`] instead of the auto-flushing
+    /// [`Output::print`], [`Output::println`], and [`Output::exec`] methods.
+    ///
+    /// The first invocation determines whether the terminal supports
+    /// synchronized output by querying the terminal with [`RequestBatchMode`]
+    /// and caches the result with the connection's
+    /// [`Capabilities::batch`](crate::Capabilities::batch). While waiting for
+    /// the response, it discards unrelated input, such as key presses. If the
+    /// terminal does not respond, it blocks for one read timeout and treats
+    /// synchronized output as unsupported. If this thread or another thread
+    /// is holding the terminal's input or reading fails for other reasons, it
+    /// falls back on flushing only once without caching the result.
+    ///
+    /// ```
+    /// # use std::io::{ErrorKind, Result, Write};
+    /// # use prettytty::Connection;
+    /// # use prettytty::cmd::{EraseScreen, MoveTo};
+    /// # fn run() -> Result<()> {
+    /// let tty = Connection::open()?;
+    /// tty.output().batch(|out| {
+    ///     write!(out, "{}{}", EraseScreen, MoveTo::<1, 1>)?;
+    ///     write!(out, "Hello, world!")
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// # match run() {
+    /// #     Ok(()) => (),
+    /// #     Err(err) if err.kind() == ErrorKind::ConnectionRefused &&
+    /// #         std::env::var_os("CI").is_some() => (),
+    /// #     Err(err) => return Err(err),
+    /// # };
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn batch<R>(&mut self, f: impl FnOnce(&mut Self) -> Result<R>) -> Result<R> {
+        let is_batched = self.supports_batch()?;
+        if is_batched {
+            write!(self.writer, "{}", BeginBatch)?;
+        }
+
+        let result = f(self);
+        if is_batched {
+            write!(self.writer, "{}", EndBatch)?;
+        }
+        self.writer.flush()?;
+        result
+    }
+
+    /// Determine the cursor position.
+    ///
+    /// This method writes [`RequestCursorPosition`], reads the response, and
+    /// parses it in one go. Since doing so requires the terminal's input, this
+    /// method fails with [`ErrorKind::WouldBlock`], if this thread or another
+    /// thread is holding the input.
+    ///
+    /// ```
+    /// # use std::io::{ErrorKind, Result};
+    /// # use prettytty::Connection;
+    /// # use prettytty::cmd::MoveTo;
+    /// # fn run() -> Result<()> {
+    /// let tty = Connection::open()?;
+    /// let mut output = tty.output();
+    /// output.exec(MoveTo::<3, 17>)?;
+    /// let position = output.cursor_position()?;
+    /// assert_eq!(position.to_zero_based(), (2, 16));
+    /// # Ok(())
+    /// # }
+    /// # match run() {
+    /// #     Ok(()) => (),
+    /// #     Err(err) if err.kind() == ErrorKind::ConnectionRefused &&
+    /// #         std::env::var_os("CI").is_some() => (),
+    /// #     Err(err) => return Err(err),
+    /// # };
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, ret, err)
+    )]
+    pub fn cursor_position(&mut self) -> Result<Position> {
+        let mut scanner = match self.scanner.try_lock() {
+            Ok(scanner) => scanner,
+            Err(TryLockError::WouldBlock) => return Err(ErrorKind::WouldBlock.into()),
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+        };
+
+        write!(self.writer, "{}", RequestCursorPosition)?;
+        self.writer.flush()?;
+        match scanner.read_token()? {
+            Token::Sequence(control, payload) if control == RequestCursorPosition.control() => {
+                RequestCursorPosition.parse(payload)
+            }
+            Token::Sequence(_, _) => Err(crate::err::ErrorKind::BadControl.into()),
+            _ => Err(crate::err::ErrorKind::NotASequence.into()),
+        }
+    }
+
+    /// Determine whether the terminal supports synchronized output.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, ret)
+    )]
+    pub(crate) fn supports_batch(&mut self) -> Result<bool> {
+        let is_supported =
+            |mode: &BatchMode| matches!(mode, BatchMode::Enabled | BatchMode::Disabled);
+
+        if let Some(mode) = self.capabilities.batch.get() {
+            return Ok(is_supported(mode));
+        }
+
+        let Ok(mut scanner) = self.scanner.try_lock() else {
+            return Ok(false);
+        };
+
+        write!(self.writer, "{}", RequestBatchMode)?;
+        self.writer.flush()?;
+        let mode = loop {
+            match scanner.read_token() {
+                Ok(Token::Sequence(Control::CSI, payload)) => {
+                    if let Ok(mode) = RequestBatchMode.parse(payload) {
+                        break mode;
+                    }
+                }
+                Ok(_) => (),
+                Err(error) if error.kind() == crate::err::ErrorKind::NoData => {
+                    break BatchMode::NotSupported;
+                }
+                Err(_) => return Ok(false),
+            }
+        };
+
+        Ok(is_supported(self.capabilities.batch.get_or_init(|| mode)))
+    }
+
+    /// Reset the terminal's most common modes on a best-effort basis.
+    ///
+    /// This method is a last resort for cleaning up after, say, a crashed
+    /// child process that left the terminal in disarray. It resets text
+    /// attributes, disables mouse reports, bracketed paste, focus reports, and
+    /// synchronized output, shows the cursor, and leaves the alternate screen.
+    /// Unlike [`SoftReset`](crate::cmd::SoftReset) and
+    /// [`FullReset`](crate::cmd::FullReset), it does not touch other state,
+    /// such as the screen contents or the window title. It does not change the
+    /// terminal configuration either.
+    pub fn sanitize(&mut self) -> Result<()> {
+        write!(self.writer, "{}", ResetStyle)?;
+        // Disable X10, normal, button-event, and any-event mouse tracking as
+        // well as the UTF-8, SGR, and urxvt mouse encodings.
+        self.writer.write_all(
+            b"\x1b[?9l\x1b[?1000l\x1b[?1002l\x1b[?1003l\x1b[?1005l\x1b[?1006l\x1b[?1015l",
+        )?;
+        write!(
+            self.writer,
+            "{}{}{}{}{}",
+            EndPaste, DisableFocusReports, EndBatch, ShowCursor, ExitAlternateScreen
+        )?;
+        sys::set_alternate_screen(false);
+        self.writer.flush()
+    }
 }
 
 impl Write for Output<'_> {
@@ -325,3 +886,50 @@ impl Write for Output<'_> {
         self.writer.flush()
     }
 }
+
+#[cfg(all(test, target_family = "unix"))]
+mod test {
+    use std::fs::File;
+    use std::io::{Error, Result, Write};
+    use std::os::fd::{FromRawFd, OwnedFd};
+
+    use super::Connection;
+    use crate::cmd::BatchMode;
+    use crate::opt::{Options, Volume};
+
+    fn pipe() -> Result<(OwnedFd, OwnedFd)> {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+    }
+
+    /// Connect to pipes, with the input already holding the bytes. The
+    /// returned files keep the other ends of both pipes open.
+    fn connect(bytes: &[u8]) -> Result<(Connection, File, File)> {
+        let (input, writer) = pipe()?;
+        let mut writer = File::from(writer);
+        writer.write_all(bytes)?;
+        let (reader, output) = pipe()?;
+
+        let options = Options::builder()
+            .volume(Volume::Silent)
+            .poll_timeout(50)
+            .build();
+        let connection = Connection::with_fds(input, output, options)?;
+        Ok((connection, writer, File::from(reader)))
+    }
+
+    #[test]
+    fn test_batch() -> Result<()> {
+        let (tty, _writer, _reader) = connect(b"x\x1b[A\x1b[?2026;2$y")?;
+        assert!(tty.caps().batch());
+        assert_eq!(tty.capabilities.batch.get(), Some(&BatchMode::Disabled));
+
+        let (tty, _writer, _reader) = connect(b"x\x1b[A")?;
+        assert!(!tty.caps().batch());
+        assert_eq!(tty.capabilities.batch.get(), Some(&BatchMode::NotSupported));
+        Ok(())
+    }
+}
@@ -0,0 +1,386 @@
+//! Decoding of key presses.
+//!
+//! This module turns the [`Token`]s produced by a [`Scan`](crate::Scan)
+//! implementation into [`KeyEvent`]s. It supports the legacy encoding of keys
+//! used by xterm and most terminals compatible with it:
+//!
+//!   * Text tokens encode one key press per character.
+//!   * C0 controls encode the enter, tab, and backspace keys as well as
+//!     control key combinations.
+//!   * ESC sequences with a single character encode alt key combinations.
+//!   * SS3 sequences encode cursor keys as well as F1–F4 in application mode.
+//!   * CSI sequences encode cursor, editing, and function keys, optionally with
+//!     modifiers.
+//!
+//! The legacy encoding is ambiguous. Notably, a lone escape key press is
+//! indistinguishable from the start of an escape sequence until the next key
+//! press, alt-O and alt-\[ start SS3 and CSI sequences, respectively, and
+//! control-F3 is indistinguishable from the response to a cursor position
+//! query. This module makes no attempt at resolving these ambiguities.
+//!
+//! # Example
+//!
+//! ```
+//! # use prettytty::{Control, Token};
+//! # use prettytty::key::{KeyCode, KeyEvent, Modifiers};
+//! let token = Token::Sequence(Control::CSI, b"1;5A");
+//! let keys = token.keys().collect::<Vec<_>>();
+//! assert_eq!(keys, vec![KeyEvent::new(KeyCode::Up, Modifiers::CONTROL)]);
+//!
+//! let token = Token::Text(b"hi");
+//! assert_eq!(token.keys().count(), 2);
+//! ```
+
+use crate::util::{is_semi_colon, Radix};
+use crate::{Control, Token};
+
+/// A key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    /// A character key.
+    Char(char),
+    /// The enter key.
+    Enter,
+    /// The tab key.
+    Tab,
+    /// The backspace key.
+    Backspace,
+    /// The escape key.
+    Escape,
+    /// The up arrow key.
+    Up,
+    /// The down arrow key.
+    Down,
+    /// The right arrow key.
+    Right,
+    /// The left arrow key.
+    Left,
+    /// The home key.
+    Home,
+    /// The end key.
+    End,
+    /// The page up key.
+    PageUp,
+    /// The page down key.
+    PageDown,
+    /// The insert key.
+    Insert,
+    /// The delete key.
+    Delete,
+    /// A function key, numbered from 1.
+    F(u8),
+}
+
+/// The modifiers held down during a key press.
+///
+/// Modifiers are a set of flags, with [`Modifiers::NONE`] being the empty set
+/// and `|` computing the union.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    /// No modifiers.
+    pub const NONE: Self = Self(0);
+    /// The shift key.
+    pub const SHIFT: Self = Self(1);
+    /// The alt or option key.
+    pub const ALT: Self = Self(2);
+    /// The control key.
+    pub const CONTROL: Self = Self(4);
+    /// The meta or command key.
+    pub const META: Self = Self(8);
+
+    /// Convert xterm's modifier parameter into modifiers.
+    ///
+    /// The parameter is one plus the bitwise or of 1 for shift, 2 for alt, 4
+    /// for control, and 8 for meta.
+    fn from_param(param: u16) -> Option<Self> {
+        if (1..=16).contains(&param) {
+            Some(Self((param - 1) as u8))
+        } else {
+            None
+        }
+    }
+
+    /// Determine whether there are no modifiers.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Determine whether these modifiers include all of the other modifiers.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+    }
+}
+
+/// A key press.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+    /// The key.
+    pub code: KeyCode,
+    /// The modifiers.
+    pub modifiers: Modifiers,
+}
+
+impl KeyEvent {
+    /// Create a new key event.
+    pub const fn new(code: KeyCode, modifiers: Modifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Create a new key event without modifiers.
+    pub const fn plain(code: KeyCode) -> Self {
+        Self::new(code, Modifiers::NONE)
+    }
+
+    /// Decode a C0 control or DEL.
+    fn from_control(byte: u8) -> Option<Self> {
+        let (code, modifiers) = match byte {
+            0x00 => (KeyCode::Char(' '), Modifiers::CONTROL),
+            0x08 | 0x7f => (KeyCode::Backspace, Modifiers::NONE),
+            0x09 => (KeyCode::Tab, Modifiers::NONE),
+            0x0d => (KeyCode::Enter, Modifiers::NONE),
+            0x1b => (KeyCode::Escape, Modifiers::NONE),
+            0x01..=0x1a => (KeyCode::Char((b'a' + byte - 1) as char), Modifiers::CONTROL),
+            0x1c..=0x1f => (
+                KeyCode::Char((b'\\' + byte - 0x1c) as char),
+                Modifiers::CONTROL,
+            ),
+            _ => return None,
+        };
+
+        Some(Self::new(code, modifiers))
+    }
+
+    /// Decode a character.
+    fn from_char(c: char) -> Self {
+        match c {
+            '\x7f' => Self::plain(KeyCode::Backspace),
+            _ => Self::plain(KeyCode::Char(c)),
+        }
+    }
+
+    /// Decode an ESC sequence's payload, which is an alt key combination.
+    fn from_escape(payload: &[u8]) -> Option<Self> {
+        let [byte] = payload else {
+            return None;
+        };
+
+        let mut key = if byte.is_ascii_control() {
+            Self::from_control(*byte)?
+        } else {
+            Self::from_char(*byte as char)
+        };
+        key.modifiers |= Modifiers::ALT;
+        Some(key)
+    }
+
+    /// Decode an SS3 sequence's payload.
+    fn from_ss3(payload: &[u8]) -> Option<Self> {
+        let code = match payload {
+            b"A" => KeyCode::Up,
+            b"B" => KeyCode::Down,
+            b"C" => KeyCode::Right,
+            b"D" => KeyCode::Left,
+            b"H" => KeyCode::Home,
+            b"F" => KeyCode::End,
+            b"M" => KeyCode::Enter,
+            b"P" => KeyCode::F(1),
+            b"Q" => KeyCode::F(2),
+            b"R" => KeyCode::F(3),
+            b"S" => KeyCode::F(4),
+            _ => return None,
+        };
+
+        Some(Self::plain(code))
+    }
+
+    /// Decode a CSI sequence's payload.
+    fn from_csi(payload: &[u8]) -> Option<Self> {
+        let (&last, params) = payload.split_last()?;
+
+        if !params
+            .iter()
+            .all(|b| b.is_ascii_digit() || is_semi_colon(b))
+        {
+            return None;
+        }
+
+        let mut numbers = [1_u16; 2];
+        if !params.is_empty() {
+            for (index, param) in params.split(is_semi_colon).enumerate() {
+                if numbers.len() <= index {
+                    return None;
+                } else if !param.is_empty() {
+                    numbers[index] = Radix::Decimal.parse_u16(param)?;
+                }
+            }
+        }
+        let [number, modifiers] = numbers;
+        let modifiers = Modifiers::from_param(modifiers)?;
+
+        let code = match last {
+            b'A' => KeyCode::Up,
+            b'B' => KeyCode::Down,
+            b'C' => KeyCode::Right,
+            b'D' => KeyCode::Left,
+            b'H' => KeyCode::Home,
+            b'F' => KeyCode::End,
+            b'P' => KeyCode::F(1),
+            b'Q' => KeyCode::F(2),
+            b'R' => KeyCode::F(3),
+            b'S' => KeyCode::F(4),
+            b'Z' => return Some(Self::new(KeyCode::Tab, modifiers | Modifiers::SHIFT)),
+            b'~' => match number {
+                1 | 7 => KeyCode::Home,
+                2 => KeyCode::Insert,
+                3 => KeyCode::Delete,
+                4 | 8 => KeyCode::End,
+                5 => KeyCode::PageUp,
+                6 => KeyCode::PageDown,
+                11..=15 => KeyCode::F((number - 10) as u8),
+                17..=21 => KeyCode::F((number - 11) as u8),
+                23..=26 => KeyCode::F((number - 12) as u8),
+                28 | 29 => KeyCode::F((number - 13) as u8),
+                31..=34 => KeyCode::F((number - 14) as u8),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(Self::new(code, modifiers))
+    }
+}
+
+/// An iterator over the key events encoded by a token.
+///
+/// [`Token::keys`] creates this iterator. Text tokens may encode several key
+/// events and pastes as well as focus changes encode none, whereas all other
+/// tokens encode at most one.
+#[derive(Clone, Debug)]
+pub struct Keys<'t> {
+    text: std::str::Chars<'t>,
+    key: Option<KeyEvent>,
+}
+
+impl<'t> Keys<'t> {
+    pub(crate) fn new(token: &Token<'t>) -> Self {
+        let (text, key) = match *token {
+            // Scanners only produce text tokens with valid UTF-8.
+            Token::Text(data) => (std::str::from_utf8(data).unwrap_or(""), None),
+            Token::Control(data) => ("", data.first().and_then(|b| KeyEvent::from_control(*b))),
+            Token::Sequence(control, payload) => (
+                "",
+                match control {
+                    Control::ESC => KeyEvent::from_escape(payload),
+                    Control::SS3 => KeyEvent::from_ss3(payload),
+                    Control::CSI => KeyEvent::from_csi(payload),
+                    _ => None,
+                },
+            ),
+            Token::Paste(_) | Token::FocusGained | Token::FocusLost => ("", None),
+        };
+
+        Self {
+            text: text.chars(),
+            key,
+        }
+    }
+}
+
+impl Iterator for Keys<'_> {
+    type Item = KeyEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.key
+            .take()
+            .or_else(|| self.text.next().map(KeyEvent::from_char))
+    }
+}
+
+impl std::iter::FusedIterator for Keys<'_> {}
+
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{KeyCode, KeyEvent, Modifiers};
+    use crate::{Control, Token};
+
+    fn key(token: Token<'_>) -> Option<KeyEvent> {
+        let mut keys = token.keys();
+        let key = keys.next();
+        assert_eq!(keys.next(), None);
+        key
+    }
+
+    #[test]
+    fn test_keys() {
+        use self::KeyCode::*;
+
+        let plain = |code| Some(KeyEvent::plain(code));
+        let with = |code, modifiers| Some(KeyEvent::new(code, modifiers));
+
+        assert_eq!(
+            Token::Text("a✶\x7f".as_bytes()).keys().collect::<Vec<_>>(),
+            vec![
+                KeyEvent::plain(Char('a')),
+                KeyEvent::plain(Char('✶')),
+                KeyEvent::plain(Backspace),
+            ]
+        );
+
+        assert_eq!(key(Token::Control(b"\r")), plain(Enter));
+        assert_eq!(key(Token::Control(b"\t")), plain(Tab));
+        assert_eq!(
+            key(Token::Control(b"\x03")),
+            with(Char('c'), Modifiers::CONTROL)
+        );
+        assert_eq!(
+            key(Token::Control(b"\x1d")),
+            with(Char(']'), Modifiers::CONTROL)
+        );
+        assert_eq!(key(Token::Control(b"\x85")), None);
+
+        assert_eq!(
+            key(Token::Sequence(Control::ESC, b"x")),
+            with(Char('x'), Modifiers::ALT)
+        );
+        assert_eq!(key(Token::Sequence(Control::SS3, b"D")), plain(Left));
+        assert_eq!(key(Token::Sequence(Control::SS3, b"Q")), plain(F(2)));
+
+        assert_eq!(key(Token::Sequence(Control::CSI, b"A")), plain(Up));
+        assert_eq!(
+            key(Token::Sequence(Control::CSI, b"1;6C")),
+            with(Right, Modifiers::SHIFT | Modifiers::CONTROL)
+        );
+        assert_eq!(
+            key(Token::Sequence(Control::CSI, b"Z")),
+            with(Tab, Modifiers::SHIFT)
+        );
+        assert_eq!(key(Token::Sequence(Control::CSI, b"3~")), plain(Delete));
+        assert_eq!(
+            key(Token::Sequence(Control::CSI, b"6;3~")),
+            with(PageDown, Modifiers::ALT)
+        );
+        assert_eq!(key(Token::Sequence(Control::CSI, b"24~")), plain(F(12)));
+        assert_eq!(key(Token::Sequence(Control::CSI, b"31m")), None);
+        assert_eq!(key(Token::Sequence(Control::CSI, b"2 q")), None);
+        assert_eq!(key(Token::Sequence(Control::CSI, b"1;2;3A")), None);
+        assert_eq!(key(Token::Sequence(Control::OSC, b"A")), None);
+    }
+}
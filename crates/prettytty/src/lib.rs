@@ -11,7 +11,7 @@
 //! \]
 //!
 //! This crate provides **lightweight and cross-platform terminal access**. Its
-//! only dependency is the low-level crate enabling system calls, i.e.,
+//! only required dependency is the low-level crate enabling system calls, i.e.,
 //! [`libc`](https://crates.io/crates/libc) on Unix and
 //! [`windows-sys`](https://crates.io/crates/windows-sys) on Windows.
 //!
@@ -21,26 +21,68 @@
 //!   * Issue [`Command`]s by writing their [`Display`](std::fmt::Display) to
 //!     the connection's [`Output`].
 //!   * [`Scan`] and read [`Query`] responses from its [`Input`].
+//!   * Or, to save round trips, run a [`Batch`] of queries at once.
 //!
 //! More generally, [`Input`] implements [`Read`](std::io::Read),
 //! [`BufRead`](std::io::BufRead), and [`Scan`], with the latter turning bytes
 //! into UTF-8 text, control code, and control sequence tokens. Meanwhile,
 //! [`Output`] implements [`Write`](std::io::Write) as well as the auto-flushing
 //! [`print()`](Output::print), [`println()`](Output::println), and
-//! [`exec()`](Output::exec) methods.
+//! [`exec()`](Output::exec) methods. The [`Scanner`] underlying [`Input`]
+//! also scans any other reader, such as captured terminal output, with
+//! [`Scanner::tokens`].
 //!
 //! The [`cmd`] module provides a **library of common [`Command`] and [`Query`]
 //! implementations**. It includes, for example, commands to set the window
 //! title, erase (parts of) the screen, to move the cursor, and to style text.
+#![cfg_attr(
+    feature = "terminfo",
+    doc = "The optional [`terminfo`] module **reads the terminfo database**, which
+helps with determining a terminal's color support when environment variables
+don't."
+)]
+#![cfg_attr(
+    not(feature = "terminfo"),
+    doc = "The optional `terminfo` module **reads the terminfo database**, which
+helps with determining a terminal's color support when environment variables
+don't."
+)]
+//! The [`key`] module **decodes key presses** from [`Token`]s. Finally,
+//! [`Connection::caps`] **detects terminal [`Capabilities`]** by combining
+//! all of the above with environment variables.
 //!
 //! To facilitate orderly shutdown, **read operations time out** in configurable
 //! increments of 0.1s. That suffices for simple polling but is slow when there
-//! is no input. If you need faster timeouts or integration with I/O
-//! notifications, use a dedicated polling thread with either an
-//! [`std::sync::mpsc`] queue or Unix domain socket.
+//! is no input. If you need faster timeouts, configure the connection to wait
+//! for readiness notifications with millisecond-precision timeouts instead,
+//! which also enables interrupting reads with a [`WakeHandle`]. For integration
+//! with other event sources, a [`ReaderThread`] reads from the terminal in the
+//! background and forwards the bytes over an [`std::sync::mpsc`] channel.
+//! On Unix, [`Connection::with_fds`] connects to caller-supplied file
+//! descriptors, such as a pseudo-terminal, instead of the controlling terminal.
+//! For testing without any terminal, a [`MockConnection`] replays scripted
+//! responses to expected requests.
+//! The [`record`] module **records terminal sessions** and replays them
+//! through the scanner, e.g., for bug reports.
+//! The [`log`] module **logs terminal I/O** with timestamps, decoded escape
+//! sequences, and redacted clipboard contents.
 //!
 //! Since terminal connections reconfigure the terminal, an application should
-//! go out of its way to **always run [`Connection`]'s drop handler**.
+//! go out of its way to **always run [`Connection`]'s drop handler**. As a
+//! safety net, [`OptionBuilder::restore_hooks`](opt::OptionBuilder::restore_hooks)
+//! also restores the terminal upon panics and signals.
+//!
+//! The optional `async` feature adds an [`AsyncConnection`] with
+//! [`AsyncInput`] and [`AsyncOutput`] for use with the
+//! [tokio](https://tokio.rs) runtime. It is disabled by default. The optional
+//! `terminfo` feature adds the `terminfo` module and makes
+//! [`Capabilities`] consult the terminfo database. It, too, is disabled by
+//! default. Similarly, the optional `tracing` feature enables logging to a
+//! [tracing](https://docs.rs/tracing) subscriber. It also instruments
+//! connection setup, mode changes, suspend and resume, batched and async
+//! queries, read timeouts, and scanner errors with spans and events. They use
+//! the debug level, except for timeouts at the trace level and errors at the
+//! warn level.
 //!
 //!
 //! # Example
@@ -70,7 +112,7 @@
 //!     RequestCursorPosition.parse(response)?
 //! };
 //!
-//! assert_eq!(pos.1, 17);
+//! assert_eq!(pos.column, 17);
 //! # Ok(())
 //! # }
 //! # // Treat connection refused errors in CI as implying no TTY.
@@ -92,16 +134,40 @@
 //! supporting queries for the current color theme with Windows Terminal 1.22
 //! only. Hence, we strongly recommend using prettytty with Windows Terminal
 //! 1.22 or later.
+//!
+//! Prettytty reads console input as input records with `ReadConsoleInputW`.
+//! It combines UTF-16 surrogate pairs into UTF-8 and translates changes to the
+//! window size into xterm's in-band resize notification `CSI 48 ; rows ;
+//! columns ; 0 ; 0 t`. Other input records, such as focus events, are ignored.
 
+#[cfg(feature = "async")]
+mod aio;
 mod api;
+mod batch;
+mod caps;
 pub mod cmd;
 mod conn;
 pub mod err;
+pub mod key;
+pub mod log;
+mod mock;
 pub mod opt;
 mod read;
+pub mod record;
 mod scan;
 mod sys;
+#[cfg(feature = "terminfo")]
+pub mod terminfo;
+mod thread;
 pub mod util;
 
-pub use api::{Command, Control, Query, Scan, Sgr, Token};
-pub use conn::{Connection, Input, Output};
+pub use api::{Command, CommandSeq, Control, Query, Scan, Sgr, Token};
+pub use batch::Batch;
+pub use caps::Capabilities;
+pub use conn::{AlternateScreen, Connection, Input, Output, WakeHandle, WindowTitle};
+pub use mock::{MockConnection, MockInput, MockOutput};
+pub use scan::{Scanner, Tokens};
+pub use thread::ReaderThread;
+
+#[cfg(feature = "async")]
+pub use aio::{AsyncConnection, AsyncInput, AsyncOutput};
@@ -0,0 +1,620 @@
+//! Structured logging of terminal I/O.
+//!
+//! A [`Logger`] records every read from and write to the terminal with a
+//! timestamp. By default, it decodes escape sequences into human-readable
+//! names, e.g., `[CUP 5;7]` for moving the cursor, and redacts the payloads of
+//! OSC 52 clipboard sequences, which may contain passwords or other sensitive
+//! data. Log entries go to a file, any other writer, or, with the `tracing`
+//! feature enabled, to the current [tracing](https://docs.rs/tracing)
+//! subscriber.
+//!
+//! To log a connection's I/O, configure its options with
+//! [`OptionBuilder::logger`](crate::opt::OptionBuilder::logger). A logger can
+//! also wrap other readers and writers, just like a
+//! [`Recorder`](crate::record::Recorder).
+//!
+//! ```
+//! # use std::io::Write;
+//! # use prettytty::log::describe;
+//! assert_eq!(
+//!     describe(b"hi\x1b[5;7H\x1b]52;c;c2VjcmV0\x1b\\", true),
+//!     "\"hi\" [CUP 5;7] [OSC 52 clipboard c;\u{2039}redacted 8 bytes\u{203a}]"
+//! );
+//! ```
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Result, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::record::Direction;
+use crate::util::nicely_str;
+
+/// The destination of log entries.
+enum Sink {
+    Writer(Mutex<Box<dyn Write + Send>>),
+    #[cfg(feature = "tracing")]
+    Tracing,
+}
+
+struct LoggerData {
+    start: Instant,
+    decode: bool,
+    redact: bool,
+    sink: Sink,
+}
+
+/// A builder of loggers.
+///
+/// By default, loggers decode escape sequences and redact clipboard payloads.
+#[derive(Debug)]
+pub struct LoggerBuilder {
+    decode: bool,
+    redact: bool,
+}
+
+impl LoggerBuilder {
+    /// Decode escape sequences into human-readable names.
+    pub fn decode(&mut self, decode: bool) -> &mut Self {
+        self.decode = decode;
+        self
+    }
+
+    /// Redact the payloads of OSC 52 clipboard sequences.
+    pub fn redact(&mut self, redact: bool) -> &mut Self {
+        self.redact = redact;
+        self
+    }
+
+    fn build(&self, sink: Sink) -> Logger {
+        Logger(Arc::new(LoggerData {
+            start: Instant::now(),
+            decode: self.decode,
+            redact: self.redact,
+            sink,
+        }))
+    }
+
+    /// Create a logger that writes log entries, one per line, to the writer.
+    pub fn writer(&self, writer: impl Write + Send + 'static) -> Logger {
+        self.build(Sink::Writer(Mutex::new(Box::new(writer))))
+    }
+
+    /// Create a logger that writes log entries to the file, which is created
+    /// or truncated.
+    pub fn file(&self, path: impl AsRef<Path>) -> Result<Logger> {
+        let file = File::create(path)?;
+        Ok(self.writer(BufWriter::new(file)))
+    }
+
+    /// Create a logger that emits log entries as tracing events with target
+    /// `prettytty::log`.
+    #[cfg(feature = "tracing")]
+    pub fn tracing(&self) -> Logger {
+        self.build(Sink::Tracing)
+    }
+}
+
+/// A logger of terminal I/O.
+///
+/// A logger can be cloned cheaply, with all clones sharing the same
+/// destination. The writer destination is flushed after every entry, since
+/// logs are most useful when something went wrong.
+#[derive(Clone)]
+pub struct Logger(Arc<LoggerData>);
+
+impl Logger {
+    /// Create a new builder for loggers.
+    pub fn builder() -> LoggerBuilder {
+        LoggerBuilder {
+            decode: true,
+            redact: true,
+        }
+    }
+
+    /// Create a logger with default options that writes to the writer.
+    pub fn to_writer(writer: impl Write + Send + 'static) -> Self {
+        Self::builder().writer(writer)
+    }
+
+    /// Create a logger with default options that writes to the file.
+    pub fn to_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::builder().file(path)
+    }
+
+    /// Determine whether this logger decodes escape sequences.
+    pub fn decodes(&self) -> bool {
+        self.0.decode
+    }
+
+    /// Determine whether this logger redacts clipboard payloads.
+    pub fn redacts(&self) -> bool {
+        self.0.redact
+    }
+
+    /// Log the bytes read or written.
+    ///
+    /// Errors writing the log entry are ignored.
+    pub fn log(&self, direction: Direction, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+
+        let description = if self.0.decode {
+            describe(data, self.0.redact)
+        } else if self.0.redact {
+            format!("{}", nicely_str(&redact(data)))
+        } else {
+            format!("{}", nicely_str(data))
+        };
+        let label = match direction {
+            Direction::Input => "read",
+            Direction::Output => "write",
+        };
+
+        self.emit(label, data.len(), &description);
+    }
+
+    /// Log the message.
+    pub fn message(&self, message: impl AsRef<str>) {
+        self.emit("note", 0, message.as_ref());
+    }
+
+    fn emit(&self, label: &str, size: usize, description: &str) {
+        let elapsed = self.0.start.elapsed().as_secs_f64();
+        match self.0.sink {
+            Sink::Writer(ref writer) => {
+                let mut writer = match writer.lock() {
+                    Ok(writer) => writer,
+                    Err(err) => err.into_inner(),
+                };
+                let _ = writeln!(
+                    writer,
+                    "{:>11.6}s {:<5} {:>5}  {}",
+                    elapsed, label, size, description
+                );
+                let _ = writer.flush();
+            }
+            #[cfg(feature = "tracing")]
+            Sink::Tracing => {
+                tracing::debug!(
+                    target: "prettytty::log",
+                    elapsed,
+                    direction = label,
+                    size,
+                    "{}",
+                    description
+                );
+            }
+        }
+    }
+
+    /// Wrap the reader, logging all bytes read.
+    pub fn reader<R: Read>(&self, inner: R) -> LogReader<R> {
+        LogReader {
+            inner,
+            logger: self.clone(),
+        }
+    }
+
+    /// Wrap the writer, logging all bytes written.
+    pub fn writer<W: Write>(&self, inner: W) -> LogWriter<W> {
+        LogWriter {
+            inner,
+            logger: Some(self.clone()),
+        }
+    }
+}
+
+impl std::fmt::Debug for Logger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sink = match self.0.sink {
+            Sink::Writer(_) => "writer",
+            #[cfg(feature = "tracing")]
+            Sink::Tracing => "tracing",
+        };
+
+        f.debug_struct("Logger")
+            .field("decode", &self.0.decode)
+            .field("redact", &self.0.redact)
+            .field("sink", &sink)
+            .finish()
+    }
+}
+
+/// A reader that logs all bytes read.
+#[derive(Debug)]
+pub struct LogReader<R> {
+    inner: R,
+    logger: Logger,
+}
+
+impl<R> LogReader<R> {
+    /// Unwrap the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for LogReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.logger.log(Direction::Input, &buf[..count]);
+        Ok(count)
+    }
+}
+
+/// A writer that logs all bytes written.
+#[derive(Debug)]
+pub struct LogWriter<W> {
+    inner: W,
+    logger: Option<Logger>,
+}
+
+impl<W> LogWriter<W> {
+    /// Create a new writer that logs only if there is a logger.
+    pub(crate) fn with(inner: W, logger: Option<Logger>) -> Self {
+        Self { inner, logger }
+    }
+
+    /// Unwrap the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for LogWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let count = self.inner.write(buf)?;
+        if let Some(ref logger) = self.logger {
+            logger.log(Direction::Output, &buf[..count]);
+        }
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// Find the end of the OSC, DCS, or other control string starting at the
+/// index, returning the end of the payload and the end of the terminator.
+fn find_string_end(data: &[u8], start: usize) -> Option<(usize, usize)> {
+    let mut index = start;
+    while index < data.len() {
+        match data[index] {
+            0x07 | 0x9c => return Some((index, index + 1)),
+            0x1b if data.get(index + 1) == Some(&b'\\') => return Some((index, index + 2)),
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+/// Redact the payloads of clipboard sequences in the bytes.
+fn redact(data: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(data.len());
+    let mut index = 0;
+    while index < data.len() {
+        if data[index..].starts_with(b"\x1b]52;") {
+            if let Some((end, terminator)) = find_string_end(data, index + 2) {
+                let payload = &data[index + 2..end];
+                result.extend_from_slice(b"\x1b]");
+                result.extend_from_slice(redact_clipboard(payload).as_bytes());
+                result.extend_from_slice(&data[end..terminator]);
+                index = terminator;
+                continue;
+            }
+        }
+        result.push(data[index]);
+        index += 1;
+    }
+    result
+}
+
+/// Redact an OSC 52 payload, preserving the selection and queries.
+fn redact_clipboard(payload: &[u8]) -> String {
+    let mut parts = payload.splitn(3, |b| *b == b';');
+    let code = parts.next().unwrap_or(b"");
+    let selection = parts.next().unwrap_or(b"");
+    let content = parts.next().unwrap_or(b"");
+
+    if content.is_empty() || content == b"?" {
+        String::from_utf8_lossy(payload).into_owned()
+    } else {
+        format!(
+            "{};{};\u{2039}redacted {} bytes\u{203a}",
+            String::from_utf8_lossy(code),
+            String::from_utf8_lossy(selection),
+            content.len()
+        )
+    }
+}
+
+/// Determine the name of a CSI sequence.
+fn csi_name(params: &[u8], last: u8) -> &'static str {
+    let private = params.first() == Some(&b'?');
+    match last {
+        b'A' => "CUU",
+        b'B' => "CUD",
+        b'C' => "CUF",
+        b'D' => "CUB",
+        b'E' => "CNL",
+        b'F' => "CPL",
+        b'G' => "CHA",
+        b'H' | b'f' => "CUP",
+        b'J' => "ED",
+        b'K' => "EL",
+        b'X' => "ECH",
+        b'd' => "VPA",
+        b'm' => "SGR",
+        b'n' => "DSR",
+        b'R' => "CPR",
+        b'c' => match params.first() {
+            Some(b'>') => "DA2",
+            Some(b'=') => "DA3",
+            Some(b'?') => "DA1 response",
+            _ => "DA1",
+        },
+        b'h' if private => "DECSET",
+        b'l' if private => "DECRST",
+        b'h' => "SM",
+        b'l' => "RM",
+        b'p' if params.ends_with(b"$") => "DECRQM",
+        b'p' if params.ends_with(b"!") => "DECSTR",
+        b'y' if params.ends_with(b"$") => "DECRPM",
+        b'q' if params.ends_with(b" ") => "DECSCUSR",
+        b'q' if params.first() == Some(&b'>') => "XTVERSION",
+        b't' => "XTWINOPS",
+        b'u' => "key",
+        b'~' => match params {
+            b"200" => "paste start",
+            b"201" => "paste end",
+            _ => "key",
+        },
+        b'I' if params.is_empty() => "focus in",
+        b'O' if params.is_empty() => "focus out",
+        _ => "CSI",
+    }
+}
+
+/// Determine the name of an OSC code.
+fn osc_name(code: &[u8]) -> &'static str {
+    match code {
+        b"0" => "icon and title",
+        b"1" => "icon",
+        b"2" => "title",
+        b"4" => "palette color",
+        b"8" => "hyperlink",
+        b"9" => "notification",
+        b"10" => "foreground",
+        b"11" => "background",
+        b"12" => "cursor color",
+        b"17" => "selection",
+        b"19" => "selection foreground",
+        b"52" => "clipboard",
+        b"104" => "reset palette color",
+        b"110" | b"111" | b"112" => "reset color",
+        _ => "",
+    }
+}
+
+/// Describe the bytes read from or written to a terminal.
+///
+/// This function formats text as quoted strings and escape sequences as their
+/// standard mnemonics or other short names in square brackets, followed by
+/// their parameters. It formats control characters as well as incomplete and
+/// unrecognized escape sequences with [`nicely_str`]. If redaction is enabled,
+/// it replaces the payloads of OSC 52 clipboard sequences.
+pub fn describe(data: &[u8], redact: bool) -> String {
+    let mut parts = Vec::new();
+    let mut index = 0;
+
+    while index < data.len() {
+        let byte = data[index];
+        if byte != 0x1b && !byte.is_ascii_control() {
+            let start = index;
+            while index < data.len() && data[index] != 0x1b && !data[index].is_ascii_control() {
+                index += 1;
+            }
+            parts.push(format!("{}", nicely_str(&data[start..index])));
+            continue;
+        } else if byte != 0x1b {
+            let mut part = format!("{}", nicely_str(&data[index..index + 1]));
+            // Drop the quotes, since a control is not text.
+            part.pop();
+            part.remove(0);
+            parts.push(part);
+            index += 1;
+            continue;
+        }
+
+        let Some(&kind) = data.get(index + 1) else {
+            parts.push(format!("{}", nicely_str(&data[index..])));
+            break;
+        };
+
+        match kind {
+            b'[' => {
+                let start = index + 2;
+                let Some(offset) = data[start..].iter().position(|b| (0x40..=0x7e).contains(b))
+                else {
+                    parts.push(format!("{}", nicely_str(&data[index..])));
+                    break;
+                };
+                let last = data[start + offset];
+                let params = &data[start..start + offset];
+                let name = csi_name(params, last);
+                let shown = String::from_utf8_lossy(params);
+                let shown = match name {
+                    "DECSET" | "DECRST" => shown.trim_start_matches('?').to_string(),
+                    "DECRQM" | "DECRPM" => shown
+                        .trim_start_matches('?')
+                        .trim_end_matches('$')
+                        .to_string(),
+                    "CSI" => format!("{}{}", shown, last as char),
+                    _ => shown.into_owned(),
+                };
+
+                if shown.is_empty() {
+                    parts.push(format!("[{}]", name));
+                } else {
+                    parts.push(format!("[{} {}]", name, shown));
+                }
+                index = start + offset + 1;
+            }
+            b']' | b'P' | b'_' | b'^' | b'X' => {
+                let start = index + 2;
+                let Some((end, terminator)) = find_string_end(data, start) else {
+                    parts.push(format!("{}", nicely_str(&data[index..])));
+                    break;
+                };
+                let payload = &data[start..end];
+                if kind == b']' {
+                    let code_end = payload
+                        .iter()
+                        .position(|b| *b == b';')
+                        .unwrap_or(payload.len());
+                    let code = &payload[..code_end];
+                    let name = osc_name(code);
+                    let rest = if redact && code == b"52" {
+                        let redacted = redact_clipboard(payload);
+                        redacted
+                            .split_once(';')
+                            .map(|(_, rest)| rest.to_string())
+                            .unwrap_or_default()
+                    } else {
+                        String::from_utf8_lossy(payload.get(code_end + 1..).unwrap_or(b""))
+                            .into_owned()
+                    };
+
+                    let mut part = format!("[OSC {}", String::from_utf8_lossy(code));
+                    if !name.is_empty() {
+                        part.push(' ');
+                        part.push_str(name);
+                    }
+                    if !rest.is_empty() {
+                        part.push(' ');
+                        part.push_str(&rest);
+                    }
+                    part.push(']');
+                    parts.push(part);
+                } else {
+                    let name = match kind {
+                        b'P' => "DCS",
+                        b'_' => "APC",
+                        b'^' => "PM",
+                        _ => "SOS",
+                    };
+                    parts.push(format!("[{} {}]", name, String::from_utf8_lossy(payload)));
+                }
+                index = terminator;
+            }
+            b'O' => {
+                let Some(&last) = data.get(index + 2) else {
+                    parts.push(format!("{}", nicely_str(&data[index..])));
+                    break;
+                };
+                parts.push(format!("[SS3 {}]", last as char));
+                index += 3;
+            }
+            b'c' => {
+                parts.push("[RIS]".to_string());
+                index += 2;
+            }
+            b'7' => {
+                parts.push("[DECSC]".to_string());
+                index += 2;
+            }
+            b'8' => {
+                parts.push("[DECRC]".to_string());
+                index += 2;
+            }
+            _ if kind.is_ascii_graphic() => {
+                parts.push(format!("[ESC {}]", kind as char));
+                index += 2;
+            }
+            _ => {
+                parts.push(format!("{}", nicely_str(&data[index..index + 1])));
+                index += 1;
+            }
+        }
+    }
+
+    parts.join(" ")
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{describe, redact, Logger};
+    use crate::record::Direction;
+    use std::io::{Read, Result, Write};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(b"\x1b[?2026h\x1b[1;31mred\r\n\x1b[m\x1b[?1049l", true),
+            "[DECSET 2026] [SGR 1;31] \"red\" ‹𝖼𝗋› ‹𝗅𝖿› [SGR] [DECRST 1049]"
+        );
+        assert_eq!(
+            describe(b"\x1b[?2026$p\x1b[c\x1b]11;?\x1b\\\x1bOA\x1b[", true),
+            "[DECRQM 2026] [DA1] [OSC 11 background ?] [SS3 A] \"‹𝖾𝗌𝖼›[\""
+        );
+        assert_eq!(
+            describe(b"\x1b]52;c;c2VjcmV0\x07", false),
+            "[OSC 52 clipboard c;c2VjcmV0]"
+        );
+        assert_eq!(
+            describe(b"\x1b]52;c;?\x1b\\", true),
+            "[OSC 52 clipboard c;?]"
+        );
+    }
+
+    #[test]
+    fn test_logger() -> Result<()> {
+        assert_eq!(
+            redact(b"a\x1b]52;c;c2VjcmV0\x1b\\b"),
+            "a\x1b]52;c;\u{2039}redacted 8 bytes\u{203a}\x1b\\b".as_bytes()
+        );
+
+        let log = Shared::default();
+        let logger = Logger::to_writer(log.clone());
+        let mut input = logger.reader(b"\x1b[5;7R".as_slice());
+        let mut buffer = Vec::new();
+        input.read_to_end(&mut buffer)?;
+        write!(logger.writer(Vec::new()), "\x1b[6n")?;
+        logger.message("done");
+
+        let log = String::from_utf8(log.0.lock().unwrap().clone()).unwrap();
+        let lines = log.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("s read      6  [CPR 5;7]"));
+        assert!(lines[1].ends_with("s write     4  [DSR 6]"));
+        assert!(lines[2].ends_with("s note      0  done"));
+
+        let logger = Logger::builder().decode(false).writer(Shared::default());
+        logger.log(Direction::Output, b"");
+        assert!(!logger.decodes());
+        assert!(logger.redacts());
+        Ok(())
+    }
+}
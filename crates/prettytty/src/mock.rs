@@ -0,0 +1,398 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, Error, Read, Result, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::opt::Options;
+use crate::scan::Scanner;
+use crate::{Command, Scan, Token};
+
+/// A scripted expectation for the mock terminal's output.
+#[derive(Debug)]
+struct Expectation {
+    request: Vec<u8>,
+    response: Vec<u8>,
+}
+
+/// The state shared between a mock connection, its input, and its output.
+#[derive(Debug, Default)]
+struct MockState {
+    input: VecDeque<u8>,
+    written: Vec<u8>,
+    cursor: usize,
+    expectations: VecDeque<Expectation>,
+}
+
+impl MockState {
+    /// Match the written bytes against the expectations in order.
+    ///
+    /// For each matched expectation, this method queues the response as
+    /// input and advances the cursor past the request.
+    fn process(&mut self) {
+        while let Some(expectation) = self.expectations.front() {
+            let request = &expectation.request;
+            let unmatched = &self.written[self.cursor..];
+            let Some(index) = unmatched
+                .windows(request.len().max(1))
+                .position(|window| window == request.as_slice())
+            else {
+                return;
+            };
+
+            self.cursor += index + request.len();
+            let expectation = self.expectations.pop_front().unwrap();
+            self.input.extend(expectation.response);
+        }
+    }
+}
+
+/// A reader for the mock terminal's input.
+///
+/// Reading from an empty input returns no data, just like a terminal read that
+/// times out.
+struct MockReader(Arc<Mutex<MockState>>);
+
+impl Read for MockReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let mut state = self.0.lock().expect("mutex is not poisoned");
+        let count = buf.len().min(state.input.len());
+        for (target, source) in buf.iter_mut().zip(state.input.drain(..count)) {
+            *target = source;
+        }
+        Ok(count)
+    }
+}
+
+/// An in-memory mock terminal providing [`MockInput`] and [`MockOutput`].
+///
+/// This struct enables testing code that interacts with the terminal without
+/// an actual terminal, e.g., in continuous integration. It is scripted with
+/// canned input and expected requests with their responses:
+///
+///   * [`MockConnection::push_input`] makes bytes available for reading
+///     right away.
+///   * [`MockConnection::expect`] expects a command to be written.
+///   * [`MockConnection::respond`] expects a query to be written and, once it
+///     has been written, makes the response available for reading.
+///
+/// Expectations are matched in order against the written bytes, skipping any
+/// other output in between. Hence, a test only needs to script the requests
+/// it cares about. [`MockConnection::verify`] checks that all expectations
+/// have been met, while [`MockConnection::written`] returns all bytes written
+/// so far.
+///
+/// Like the input of an actual [`Connection`](crate::Connection), the mock
+/// input is a [`Scan`] as well as a [`BufRead`]. When it has no more data,
+/// reads time out immediately. Like the output of an actual connection, the
+/// mock output is a [`Write`] and has the same convenience methods for
+/// printing text and executing commands.
+///
+/// ```
+/// # use std::io::Result;
+/// # use prettytty::{Control, MockConnection, Query, Scan};
+/// # use prettytty::cmd::{Position, RequestCursorPosition};
+/// let tty = MockConnection::new();
+/// tty.respond(RequestCursorPosition, b"\x1b[5;10R");
+///
+/// let (mut input, mut output) = tty.io();
+/// output.exec(RequestCursorPosition)?;
+/// let payload = input.read_sequence(Control::CSI)?;
+/// assert_eq!(RequestCursorPosition.parse(payload)?, Position::new(5, 10));
+///
+/// drop((input, output));
+/// tty.verify()?;
+/// assert_eq!(tty.written(), b"\x1b[6n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct MockConnection {
+    options: Options,
+    state: Arc<Mutex<MockState>>,
+    scanner: Mutex<Scanner<MockReader>>,
+}
+
+fn _assert_mock_connection_is_sync_send() {
+    fn is_sync_send<T: Sync + Send>() {}
+    is_sync_send::<MockConnection>();
+}
+
+impl MockConnection {
+    /// Create a new mock terminal with default options.
+    pub fn new() -> Self {
+        Self::with_options(Options::default())
+    }
+
+    /// Create a new mock terminal with the given options.
+    ///
+    /// The mock terminal only uses the options affecting the scanner, i.e.,
+    /// the read buffer, pathological, and string sizes.
+    pub fn with_options(options: Options) -> Self {
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let scanner = Mutex::new(Scanner::with_options(
+            &options,
+            MockReader(Arc::clone(&state)),
+        ));
+
+        Self {
+            options,
+            state,
+            scanner,
+        }
+    }
+
+    /// Get the options used for creating this mock terminal.
+    #[inline]
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().expect("mutex is not poisoned")
+    }
+
+    /// Make the bytes available as input.
+    pub fn push_input(&self, bytes: impl AsRef<[u8]>) {
+        self.state().input.extend(bytes.as_ref());
+    }
+
+    /// Expect the request to be written.
+    pub fn expect(&self, request: impl std::fmt::Display) {
+        self.respond(request, b"");
+    }
+
+    /// Expect the request to be written and respond with the bytes.
+    ///
+    /// The response becomes available as input only after the request has
+    /// been written and all earlier expectations have been met.
+    pub fn respond(&self, request: impl std::fmt::Display, response: impl AsRef<[u8]>) {
+        let mut state = self.state();
+        state.expectations.push_back(Expectation {
+            request: request.to_string().into_bytes(),
+            response: response.as_ref().to_vec(),
+        });
+        state.process();
+    }
+
+    /// Verify that all expectations have been met.
+    ///
+    /// If any expectations remain, this method returns an error with
+    /// [`ErrorKind::Other`](std::io::ErrorKind::Other) describing the first such expectation.
+    pub fn verify(&self) -> Result<()> {
+        match self.state().expectations.front() {
+            None => Ok(()),
+            Some(expectation) => Err(Error::other(format!(
+                "expected request {:?} was not written",
+                String::from_utf8_lossy(&expectation.request)
+            ))),
+        }
+    }
+
+    /// Get all bytes written so far.
+    pub fn written(&self) -> Vec<u8> {
+        self.state().written.clone()
+    }
+
+    /// Take all bytes written so far.
+    ///
+    /// This method also clears the bytes, so that the next invocation only
+    /// returns newly written bytes.
+    pub fn take_written(&self) -> Vec<u8> {
+        let mut state = self.state();
+        state.cursor = 0;
+        std::mem::take(&mut state.written)
+    }
+
+    /// Get both mock input and output.
+    #[inline]
+    pub fn io(&self) -> (MockInput<'_>, MockOutput<'_>) {
+        (self.input(), self.output())
+    }
+
+    /// Get the mock input.
+    #[inline]
+    pub fn input(&self) -> MockInput<'_> {
+        MockInput {
+            scanner: self.scanner.lock().expect("mutex is not poisoned"),
+            state: &self.state,
+        }
+    }
+
+    /// Get the mock output.
+    #[inline]
+    pub fn output(&self) -> MockOutput<'_> {
+        MockOutput { state: &self.state }
+    }
+}
+
+impl Default for MockConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for MockConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state();
+        f.debug_struct("MockConnection")
+            .field("input", &state.input.len())
+            .field("written", &state.written.len())
+            .field("expectations", &state.expectations.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`MockConnection`]'s input.
+pub struct MockInput<'a> {
+    scanner: MutexGuard<'a, Scanner<MockReader>>,
+    state: &'a Mutex<MockState>,
+}
+
+impl MockInput<'_> {
+    /// Read the next token without waiting.
+    ///
+    /// This method returns `Ok(None)` if there is no complete token.
+    pub fn try_read_token(&mut self) -> Result<Option<Token<'_>>> {
+        let state = self.state;
+        self.scanner
+            .try_read_token(|| {
+                !state
+                    .lock()
+                    .expect("mutex is not poisoned")
+                    .input
+                    .is_empty()
+            })
+            .map_err(|e| e.into())
+    }
+}
+
+impl Scan for MockInput<'_> {
+    #[inline]
+    fn in_flight(&self) -> bool {
+        self.scanner.in_flight()
+    }
+
+    #[inline]
+    fn read_token(&mut self) -> Result<Token<'_>> {
+        self.scanner.read_token().map_err(|e| e.into())
+    }
+}
+
+impl Read for MockInput<'_> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let mut source = self.scanner.fill_buf()?;
+        let count = source.read(buf)?;
+        self.scanner.consume(count)?;
+        Ok(count)
+    }
+}
+
+impl BufRead for MockInput<'_> {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.scanner.fill_buf().map_err(|e| e.into())
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        // Don't panic...
+        let _ = self.scanner.consume(amt);
+    }
+}
+
+impl std::fmt::Debug for MockInput<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockInput")
+            .field("in_flight", &self.scanner.in_flight())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`MockConnection`]'s output.
+///
+/// Writes take effect immediately. In particular, writing a request that
+/// has a scripted response makes that response available as input right
+/// away.
+#[derive(Debug)]
+pub struct MockOutput<'a> {
+    state: &'a Mutex<MockState>,
+}
+
+impl MockOutput<'_> {
+    /// Write and flush the text.
+    #[inline]
+    pub fn print(&mut self, text: impl AsRef<str>) -> Result<()> {
+        self.write_all(text.as_ref().as_bytes())
+    }
+
+    /// Write and flush the text followed by carriage return and line feed.
+    #[inline]
+    pub fn println(&mut self, text: impl AsRef<str>) -> Result<()> {
+        self.write_all(text.as_ref().as_bytes())?;
+        self.write_all(b"\r\n")
+    }
+
+    /// Write and flush the command.
+    #[inline]
+    pub fn exec(&mut self, cmd: impl Command) -> Result<()> {
+        write!(self, "{}", cmd)
+    }
+}
+
+impl Write for MockOutput<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut state = self.state.lock().expect("mutex is not poisoned");
+        state.written.extend_from_slice(buf);
+        state.process();
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::MockConnection;
+    use crate::cmd::{RequestColor, SetForeground8};
+    use crate::{Control, Query, Scan, Token};
+    use std::io::{ErrorKind, Result, Write};
+
+    #[test]
+    fn test_mock() -> Result<()> {
+        let tty = MockConnection::new();
+        tty.push_input(b"q");
+        tty.expect(SetForeground8::<1>);
+        tty.respond(RequestColor::Background, b"\x1b]11;rgb:0/8080/ffff\x1b\\");
+
+        let (mut input, mut output) = tty.io();
+        assert_eq!(input.read_token()?, Token::Text(b"q"));
+        assert_eq!(input.try_read_token()?, None);
+        assert!(tty.verify().is_err());
+
+        // Unexpected output is skipped, expected output is matched in order.
+        write!(output, "hello{}", RequestColor::Background)?;
+        assert_eq!(input.try_read_token()?, None);
+        output.exec(SetForeground8::<1>)?;
+        assert_eq!(input.try_read_token()?, None);
+        output.exec(RequestColor::Background)?;
+
+        let payload = input.read_sequence(Control::OSC)?;
+        assert_eq!(
+            RequestColor::Background.parse(payload)?,
+            [(0, 1), (0x8080, 4), (0xffff, 4)]
+        );
+        assert_eq!(input.read_token().unwrap_err().kind(), ErrorKind::TimedOut);
+
+        drop((input, output));
+        tty.verify()?;
+        assert_eq!(
+            tty.take_written(),
+            b"hello\x1b]11;?\x1b\\\x1b[38;5;1m\x1b]11;?\x1b\\"
+        );
+        assert!(tty.written().is_empty());
+        Ok(())
+    }
+}
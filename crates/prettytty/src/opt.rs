@@ -15,6 +15,8 @@
 //! assert_eq!(options.timeout(), 50);
 //! ```
 
+use crate::log::Logger;
+
 /// The diagnostic logging volume.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Volume {
@@ -37,8 +39,8 @@ pub enum Volume {
 ///
 ///     On Windows, this mode optimizes for interoperability, enables the UTF-8
 ///     code page for input and output, while also activating
-///     `ENABLE_VIRTUAL_TERMINAL_INPUT`, `ENABLE_PROCESSED_OUTPUT`, and
-///     `ENABLE_VIRTUAL_TERMINAL_PROCESSING`.
+///     `ENABLE_VIRTUAL_TERMINAL_INPUT`, `ENABLE_WINDOW_INPUT`,
+///     `ENABLE_PROCESSED_OUTPUT`, and `ENABLE_VIRTUAL_TERMINAL_PROCESSING`.
 ///
 ///   * __Rare mode__, also called cbreak mode, disables the line editor but
 ///     leaves other terminal convenience features such as processing control-c
@@ -70,7 +72,11 @@ struct OptionData {
     volume: Volume,
     mode: Mode,
     timeout: u8,
+    poll_timeout: Option<u32>,
+    restore_hooks: bool,
+    logger: Option<Logger>,
     pathological_size: usize,
+    string_size: usize,
     read_buffer_size: usize,
     write_buffer_size: usize,
 }
@@ -81,7 +87,11 @@ impl OptionData {
             volume: Volume::Silent,
             mode: Mode::Rare,
             timeout: 50,
+            poll_timeout: None,
+            restore_hooks: false,
+            logger: None,
             pathological_size: 512,
+            string_size: 65_536,
             read_buffer_size: 256,
             write_buffer_size: 1_024,
         }
@@ -111,6 +121,54 @@ impl OptionBuilder {
         self
     }
 
+    /// Use readiness notifications with the timeout in milliseconds.
+    ///
+    /// By default, reads from the terminal time out in 0.1s increments, as
+    /// configured with [`OptionBuilder::timeout`]. With this option, the
+    /// terminal connection instead blocks on `poll` (or `select` on macOS) or
+    /// `WaitForMultipleObjects` on Windows. That not only enables
+    /// millisecond-precision timeouts but also a
+    /// [`WakeHandle`](crate::WakeHandle), which interrupts a pending read from
+    /// another thread.
+    pub fn poll_timeout(&mut self, milliseconds: u32) -> &mut Self {
+        self.0.poll_timeout = Some(milliseconds);
+        self
+    }
+
+    /// Restore the terminal upon panics and signals.
+    ///
+    /// By default, only the connection's drop handler restores the terminal
+    /// configuration. But a panic hook runs before unwinding and hence before
+    /// any drop handlers. It may thus print to a terminal in raw mode or on
+    /// the alternate screen. Worse, signals such as `SIGINT` or `SIGTERM`
+    /// terminate the process without running any drop handlers, leaving the
+    /// user's shell barely usable. With this option, the connection installs
+    /// a panic hook and handlers for `SIGINT`, `SIGTERM`, `SIGHUP`, `SIGQUIT`,
+    /// and `SIGTSTP` on Unix or a console control handler on Windows. They
+    /// restore the original terminal configuration, disable modes enabled with
+    /// [`Connection::enable_mode`](crate::Connection::enable_mode), leave the
+    /// alternate screen, and show the cursor before delegating to the previous
+    /// hook or handler. For `SIGTSTP`, a handler for `SIGCONT` reapplies the
+    /// connection's configuration when the process resumes.
+    ///
+    /// The hook and handlers are installed once per process and remain
+    /// installed. They only take action while a connection with this option
+    /// is open.
+    pub fn restore_hooks(&mut self, enabled: bool) -> &mut Self {
+        self.0.restore_hooks = enabled;
+        self
+    }
+
+    /// Log all reads from and writes to the terminal.
+    ///
+    /// Unlike the diagnostic output enabled by [`OptionBuilder::volume`],
+    /// which is written to the terminal itself, the logger writes structured
+    /// log entries to a file, another writer, or a tracing subscriber.
+    pub fn logger(&mut self, logger: Logger) -> &mut Self {
+        self.0.logger = Some(logger);
+        self
+    }
+
     /// Set the minimum length for pathological ANSI escape sequences.
     ///
     /// This method ensures that the given size is at least double the read
@@ -124,6 +182,19 @@ impl OptionBuilder {
         self
     }
 
+    /// Set the maximum size of control strings.
+    ///
+    /// The payloads of DCS, OSC, SOS, PM, and APC control strings, e.g., for
+    /// XTGETTCAP responses or sixel graphics, may be longer than the read
+    /// buffer. The scanner accumulates such payloads in a separate, growable
+    /// buffer, but only up to this size. The same limit applies to the
+    /// contents of bracketed pastes. Control strings are never treated as
+    /// pathological before reaching the pathological size.
+    pub fn string_size(&mut self, size: usize) -> &mut Self {
+        self.0.string_size = size;
+        self
+    }
+
     /// Set the read buffer size.
     ///
     /// This method also updates the pathological size to twice the given size.
@@ -184,6 +255,11 @@ impl Options {
         Self::builder().volume(Volume::Detailed).build()
     }
 
+    /// Instantiate the default options but with the given logger.
+    pub fn with_logger(logger: Logger) -> Options {
+        Self::builder().logger(logger).build()
+    }
+
     /// Get the volume.
     pub fn volume(&self) -> Volume {
         self.0.volume
@@ -199,11 +275,32 @@ impl Options {
         self.0.timeout
     }
 
+    /// Get the timeout in milliseconds for readiness notifications, if the
+    /// connection uses them.
+    pub fn poll_timeout(&self) -> Option<u32> {
+        self.0.poll_timeout
+    }
+
+    /// Determine whether to restore the terminal upon panics and signals.
+    pub fn restore_hooks(&self) -> bool {
+        self.0.restore_hooks
+    }
+
+    /// Get the logger, if any.
+    pub fn logger(&self) -> Option<&Logger> {
+        self.0.logger.as_ref()
+    }
+
     /// Get the pathological size.
     pub fn pathological_size(&self) -> usize {
         self.0.pathological_size
     }
 
+    /// Get the maximum size of control strings.
+    pub fn string_size(&self) -> usize {
+        self.0.string_size
+    }
+
     /// Get the size of the read buffer.
     pub fn read_buffer_size(&self) -> usize {
         self.0.read_buffer_size
@@ -35,12 +35,9 @@ pub(crate) struct VerboseReader<R> {
 }
 
 impl<R> VerboseReader<R> {
-    /// Create a new reader.
-    pub fn new(inner: R, timeout: u8) -> Self {
-        Self {
-            inner,
-            timeout: timeout as f32 * 0.1,
-        }
+    /// Create a new reader with the timeout in seconds.
+    pub fn new(inner: R, timeout: f32) -> Self {
+        Self { inner, timeout }
     }
 }
 
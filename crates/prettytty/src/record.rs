@@ -0,0 +1,465 @@
+//! Recording and replaying terminal sessions.
+//!
+//! A [`Recorder`] intercepts reads and writes by wrapping a reader or writer,
+//! typically a connection's [`Input`](crate::Input) and
+//! [`Output`](crate::Output), and records the bytes together with the time
+//! elapsed since recording started. The resulting [`Recording`] can be
+//! serialized in [asciinema's v2
+//! format](https://docs.asciinema.org/manual/asciicast/v2/) and then parsed
+//! again. Replaying a recording's input through a [`Scanner`](crate::Scanner)
+//! reproduces the same tokens, which makes recordings well-suited for bug
+//! reports about escape sequence handling.
+//!
+//! ```
+//! # use std::io::{Read, Result, Write};
+//! # use prettytty::{Scanner, Token};
+//! # use prettytty::record::{Direction, Recorder, Recording};
+//! let recorder = Recorder::new();
+//! let mut input = recorder.reader(b"\x1b[5;10R".as_slice());
+//! let mut output = recorder.writer(Vec::new());
+//! write!(output, "\x1b[6n")?;
+//! let mut buffer = Vec::new();
+//! input.read_to_end(&mut buffer)?;
+//!
+//! let mut cast = Vec::new();
+//! recorder.recording().write_cast(&mut cast, 80, 24)?;
+//! let recording = Recording::read_cast(cast.as_slice())?;
+//! assert_eq!(recording.events().len(), 2);
+//!
+//! let mut scanner = Scanner::new(recording.replay(Direction::Input));
+//! assert_eq!(scanner.read_token().unwrap(), Token::Sequence(
+//!     prettytty::Control::CSI, b"5;10R"));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Since asciinema's format uses JSON strings, it is restricted to UTF-8.
+//! Serializing a recording replaces malformed UTF-8 with the replacement
+//! character U+FFFD.
+
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Result, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The direction of recorded bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Bytes read from the terminal.
+    Input,
+    /// Bytes written to the terminal.
+    Output,
+}
+
+impl Direction {
+    /// Get asciinema's event code for this direction.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Input => "i",
+            Self::Output => "o",
+        }
+    }
+}
+
+/// A recorded event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    /// The time elapsed since recording started.
+    pub time: Duration,
+    /// The direction.
+    pub direction: Direction,
+    /// The bytes read or written.
+    pub data: Vec<u8>,
+}
+
+/// A recording of a terminal session.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Recording {
+    events: Vec<Event>,
+}
+
+impl Recording {
+    /// Create a new, empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the recorded events.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Add an event to this recording.
+    pub fn push(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Replay the bytes with the given direction.
+    ///
+    /// The returned reader produces the bytes of each matching event with one
+    /// read and then returns end-of-file. It ignores the timestamps.
+    pub fn replay(&self, direction: Direction) -> Replay<'_> {
+        Replay {
+            events: &self.events,
+            direction,
+            index: 0,
+            position: 0,
+        }
+    }
+
+    /// Write this recording in asciinema's v2 format.
+    pub fn write_cast(&self, mut writer: impl Write, width: u16, height: u16) -> Result<()> {
+        writeln!(
+            writer,
+            r#"{{"version": 2, "width": {}, "height": {}}}"#,
+            width, height
+        )?;
+
+        for event in &self.events {
+            write!(
+                writer,
+                r#"[{}.{:06}, "{}", ""#,
+                event.time.as_secs(),
+                event.time.subsec_micros(),
+                event.direction.code()
+            )?;
+            for c in String::from_utf8_lossy(&event.data).chars() {
+                match c {
+                    '"' => writer.write_all(b"\\\"")?,
+                    '\\' => writer.write_all(b"\\\\")?,
+                    '\n' => writer.write_all(b"\\n")?,
+                    '\r' => writer.write_all(b"\\r")?,
+                    '\t' => writer.write_all(b"\\t")?,
+                    c if c.is_control() => write!(writer, "\\u{:04x}", c as u32)?,
+                    c => write!(writer, "{}", c)?,
+                }
+            }
+            writer.write_all(b"\"]\n")?;
+        }
+
+        writer.flush()
+    }
+
+    /// Read a recording in asciinema's v2 format.
+    ///
+    /// This method ignores the header as well as events other than input and
+    /// output events. It fails with [`ErrorKind::InvalidData`] on malformed
+    /// lines.
+    pub fn read_cast(reader: impl Read) -> Result<Self> {
+        let mut recording = Self::new();
+        let mut lines = BufReader::new(reader).lines();
+
+        let header = lines.next().ok_or(ErrorKind::InvalidData)??;
+        if !header.trim_start().starts_with('{') {
+            return Err(ErrorKind::InvalidData.into());
+        }
+
+        for line in lines {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(event) = parse_event(line)? {
+                recording.push(event);
+            }
+        }
+
+        Ok(recording)
+    }
+}
+
+/// Parse one event line, returning `None` for unknown event codes.
+fn parse_event(line: &str) -> Result<Option<Event>> {
+    let invalid = || Error::from(ErrorKind::InvalidData);
+
+    let line = line
+        .strip_prefix('[')
+        .and_then(|l| l.strip_suffix(']'))
+        .ok_or_else(invalid)?;
+    let (time, rest) = line.split_once(',').ok_or_else(invalid)?;
+    let time = parse_time(time.trim()).ok_or_else(invalid)?;
+
+    let (code, rest) = parse_string(rest.trim_start()).ok_or_else(invalid)?;
+    let rest = rest.trim_start().strip_prefix(',').ok_or_else(invalid)?;
+    let (data, rest) = parse_string(rest.trim_start()).ok_or_else(invalid)?;
+    if !rest.trim().is_empty() {
+        return Err(invalid());
+    }
+
+    let direction = match code.as_str() {
+        "i" => Direction::Input,
+        "o" => Direction::Output,
+        _ => return Ok(None),
+    };
+
+    Ok(Some(Event {
+        time,
+        direction,
+        data: data.into_bytes(),
+    }))
+}
+
+/// Parse a non-negative decimal number of seconds.
+fn parse_time(s: &str) -> Option<Duration> {
+    let (secs, fraction) = s.split_once('.').unwrap_or((s, ""));
+    if secs.is_empty() || !secs.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if 9 < fraction.len() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let mut nanos = 0;
+    for index in 0..9 {
+        let digit = fraction.as_bytes().get(index).map_or(0, |b| b - b'0');
+        nanos = nanos * 10 + digit as u32;
+    }
+
+    Some(Duration::new(secs.parse().ok()?, nanos))
+}
+
+/// Parse four hexadecimal digits.
+fn parse_hex(chars: &mut std::str::CharIndices<'_>) -> Option<u32> {
+    let mut code = 0;
+    for _ in 0..4 {
+        code = code * 16 + chars.next()?.1.to_digit(16)?;
+    }
+    Some(code)
+}
+
+/// Parse a JSON string, returning the string and the remaining text.
+fn parse_string(s: &str) -> Option<(String, &str)> {
+    let mut chars = s.strip_prefix('"')?.char_indices();
+    let mut result = String::new();
+
+    loop {
+        let (index, c) = chars.next()?;
+        match c {
+            '"' => return Some((result, &s[index + 2..])),
+            '\\' => {
+                let (_, c) = chars.next()?;
+                result.push(match c {
+                    '"' | '\\' | '/' => c,
+                    'b' => '\x08',
+                    'f' => '\x0c',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'u' => {
+                        let high = parse_hex(&mut chars)?;
+                        if (0xd800..0xdc00).contains(&high) {
+                            if chars.next()?.1 != '\\' || chars.next()?.1 != 'u' {
+                                return None;
+                            }
+                            let low = parse_hex(&mut chars)?;
+                            if !(0xdc00..0xe000).contains(&low) {
+                                return None;
+                            }
+                            char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))?
+                        } else {
+                            char::from_u32(high)?
+                        }
+                    }
+                    _ => return None,
+                });
+            }
+            c => result.push(c),
+        }
+    }
+}
+
+/// A reader replaying a recording's bytes.
+#[derive(Debug)]
+pub struct Replay<'a> {
+    events: &'a [Event],
+    direction: Direction,
+    index: usize,
+    position: usize,
+}
+
+impl Read for Replay<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        while let Some(event) = self.events.get(self.index) {
+            if event.direction != self.direction || self.position == event.data.len() {
+                self.index += 1;
+                self.position = 0;
+                continue;
+            }
+
+            let count = buf.len().min(event.data.len() - self.position);
+            buf[..count].copy_from_slice(&event.data[self.position..self.position + count]);
+            self.position += count;
+            return Ok(count);
+        }
+
+        Ok(0)
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// A recorder of terminal sessions.
+///
+/// A recorder can be cloned cheaply, with all clones contributing to the same
+/// recording.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    start: Instant,
+    recording: Arc<Mutex<Recording>>,
+}
+
+impl Recorder {
+    /// Create a new recorder, which starts recording right away.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            recording: Arc::new(Mutex::new(Recording::new())),
+        }
+    }
+
+    /// Record the data.
+    pub fn record(&self, direction: Direction, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+
+        self.recording
+            .lock()
+            .expect("mutex is not poisoned")
+            .push(Event {
+                time: self.start.elapsed(),
+                direction,
+                data: data.to_vec(),
+            });
+    }
+
+    /// Get a copy of the recording so far.
+    pub fn recording(&self) -> Recording {
+        self.recording
+            .lock()
+            .expect("mutex is not poisoned")
+            .clone()
+    }
+
+    /// Wrap the reader, recording all bytes read as input.
+    pub fn reader<R: Read>(&self, inner: R) -> RecordingReader<R> {
+        RecordingReader {
+            inner,
+            recorder: self.clone(),
+        }
+    }
+
+    /// Wrap the writer, recording all bytes written as output.
+    pub fn writer<W: Write>(&self, inner: W) -> RecordingWriter<W> {
+        RecordingWriter {
+            inner,
+            recorder: self.clone(),
+        }
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A reader that records all bytes read.
+#[derive(Debug)]
+pub struct RecordingReader<R> {
+    inner: R,
+    recorder: Recorder,
+}
+
+impl<R> RecordingReader<R> {
+    /// Unwrap the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for RecordingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.recorder.record(Direction::Input, &buf[..count]);
+        Ok(count)
+    }
+}
+
+/// A writer that records all bytes written.
+#[derive(Debug)]
+pub struct RecordingWriter<W> {
+    inner: W,
+    recorder: Recorder,
+}
+
+impl<W> RecordingWriter<W> {
+    /// Unwrap the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for RecordingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let count = self.inner.write(buf)?;
+        self.recorder.record(Direction::Output, &buf[..count]);
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{parse_string, Direction, Event, Recording};
+    use std::io::{Read, Result};
+    use std::time::Duration;
+
+    #[test]
+    fn test_cast() -> Result<()> {
+        let mut recording = Recording::new();
+        recording.push(Event {
+            time: Duration::from_micros(1_500_000),
+            direction: Direction::Output,
+            data: b"\x1b]11;?\x1b\\\"hi\"\r\n".to_vec(),
+        });
+        recording.push(Event {
+            time: Duration::from_micros(2_250_001),
+            direction: Direction::Input,
+            data: "\x1b]11;rgb:0/0/0\x07ü🎉".as_bytes().to_vec(),
+        });
+
+        let mut cast = Vec::new();
+        recording.write_cast(&mut cast, 80, 24)?;
+        assert_eq!(
+            String::from_utf8(cast.clone()).unwrap(),
+            concat!(
+                "{\"version\": 2, \"width\": 80, \"height\": 24}\n",
+                "[1.500000, \"o\", \"\\u001b]11;?\\u001b\\\\\\\"hi\\\"\\r\\n\"]\n",
+                "[2.250001, \"i\", \"\\u001b]11;rgb:0/0/0\\u0007ü🎉\"]\n",
+            )
+        );
+
+        assert_eq!(Recording::read_cast(cast.as_slice())?, recording);
+
+        let mut bytes = Vec::new();
+        recording.replay(Direction::Input).read_to_end(&mut bytes)?;
+        assert_eq!(bytes, recording.events()[1].data);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_string() {
+        assert_eq!(
+            parse_string(r#""a\u00fc\ud83c\udf89\/" rest"#),
+            Some(("aü🎉/".to_string(), " rest"))
+        );
+        assert_eq!(parse_string(r#""unterminated"#), None);
+        assert_eq!(parse_string(r#""\ud83c""#), None);
+        assert_eq!(parse_string(r#""\x""#), None);
+    }
+}
@@ -12,6 +12,22 @@ use super::{Control, Token};
 
 // ================================================================================================
 
+/// The payload of the CSI sequence starting a bracketed paste.
+const PASTE_START: &[u8] = b"200~";
+
+/// The CSI sequence ending a bracketed paste.
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Emit a tracing event for the scanner error.
+#[cfg(feature = "tracing")]
+fn trace_error(error: &Error) {
+    if error.kind() == ErrorKind::NoData {
+        tracing::trace!(target: "prettytty::scan", "read timed out");
+    } else {
+        tracing::warn!(target: "prettytty::scan", %error, "failed to scan token");
+    }
+}
+
 /// A scanner for text and control tokens.
 ///
 /// This struct builds Paul Flo Williams' [parser for DEC's ANSI-compatible
@@ -47,9 +63,17 @@ pub struct Scanner<R> {
     /// The maximum length for any escape sequence, which must be at least as
     /// large as the buffer size.
     max_sequence_length: usize,
+    /// The maximum length for control strings.
+    max_string_length: usize,
     /// A single byte buffer for control characters in the middle of an escape
     /// sequence.
     extra: [u8; 1],
+    /// The flag for scanning a bracketed paste.
+    in_paste: bool,
+    /// The pasted bytes.
+    paste: Vec<u8>,
+    /// The control string bytes that did not fit into the buffer.
+    string: Vec<u8>,
 }
 
 impl<R: std::io::Read> Scanner<R> {
@@ -63,7 +87,11 @@ impl<R: std::io::Read> Scanner<R> {
             did_overflow: false,
             sequence_length: 0,
             max_sequence_length: options.pathological_size(),
+            max_string_length: options.string_size().max(options.pathological_size()),
             extra: [0; 1],
+            in_paste: false,
+            paste: Vec::new(),
+            string: Vec::new(),
         }
     }
 
@@ -78,10 +106,30 @@ impl<R: std::io::Read> Scanner<R> {
     /// the buffer and reads from the underlying input. If that read returns no
     /// bytes, this method returns `Some(0)`.
     fn ensure_readable(&mut self) -> Result<Option<usize>, Error> {
+        self.ensure_readable_if(&mut || true)
+    }
+
+    /// Ensure that the buffer has readable content, but only read from the
+    /// underlying input if it is ready.
+    ///
+    /// If the underlying input is not ready, this method returns `Some(0)`
+    /// without reading, just as if the read had timed out.
+    fn ensure_readable_if(
+        &mut self,
+        is_ready: &mut dyn FnMut() -> bool,
+    ) -> Result<Option<usize>, Error> {
         if !self.buffer.is_readable() {
+            if !is_ready() {
+                return Ok(Some(0));
+            }
+
             if matches!(self.state, State::Ground) {
                 // No readable data or token to preserve. Just reset buffer.
                 self.buffer.reset();
+            } else if self.buffer.is_exhausted() && self.is_string() {
+                // Move control string bytes into growable buffer.
+                self.string.extend_from_slice(self.buffer.token());
+                self.buffer.reset();
             } else if self.buffer.is_exhausted() {
                 // To make progress again, reset buffer but remember error.
                 self.buffer.reset();
@@ -99,12 +147,23 @@ impl<R: std::io::Read> Scanner<R> {
         Ok(None)
     }
 
+    /// Determine whether the current escape sequence is a control string.
+    fn is_string(&self) -> bool {
+        use self::Control::*;
+        matches!(self.control, Some(DCS | OSC | SOS | PM | APC))
+    }
+
     // ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~
     // Support for reading bytes
 
     /// Determine whether this scanner's state machine is in-flight.
     pub fn in_flight(&self) -> bool {
-        !matches!(self.state, State::Ground)
+        self.in_paste || !matches!(self.state, State::Ground)
+    }
+
+    /// Determine whether this scanner is in the middle of a bracketed paste.
+    pub fn in_paste(&self) -> bool {
+        self.in_paste
     }
 
     /// Get a buffer with unread bytes.
@@ -198,10 +257,16 @@ impl<R: std::io::Read> Scanner<R> {
             self.buffer.start_token();
             self.did_overflow = false;
             self.sequence_length = 1;
+            self.string.clear();
         } else if !matches!(self.state, State::Ground) {
             self.sequence_length += 1;
 
-            if self.max_sequence_length <= self.sequence_length {
+            let max_length = if self.is_string() {
+                self.max_string_length
+            } else {
+                self.max_sequence_length
+            };
+            if max_length <= self.sequence_length {
                 // Hard reset scanner upon pathological control sequence.
                 // That includes discarding buffered bytes.
                 self.state = State::Ground;
@@ -238,30 +303,145 @@ impl<R: std::io::Read> Scanner<R> {
     }
 
     /// Create a control token for the byte.
-    fn new_control_token(&mut self, byte: u8) -> Result<Token, Error> {
+    fn new_control_token(&mut self, byte: u8) -> Result<Token<'_>, Error> {
         self.extra[0] = byte;
         Ok(Token::Control(&self.extra))
     }
 
+    /// Determine whether the just dispatched sequence starts a bracketed paste.
+    fn is_paste_start(&self) -> bool {
+        !self.did_overflow
+            && matches!(self.control, Some(Control::CSI))
+            && self.buffer.token() == PASTE_START
+    }
+
+    /// Create a focus token for the just dispatched sequence, if it reports a
+    /// change in focus.
+    fn focus_token(&self) -> Option<Token<'static>> {
+        if self.did_overflow || !matches!(self.control, Some(Control::CSI)) {
+            return None;
+        }
+
+        match self.buffer.token() {
+            b"I" => Some(Token::FocusGained),
+            b"O" => Some(Token::FocusLost),
+            _ => None,
+        }
+    }
+
     /// Create a new sequence token.
-    fn new_sequence_token(&self) -> Result<Token, Error> {
+    ///
+    /// If the sequence is a control string that did not fit into the buffer,
+    /// the token's payload comes from the growable string buffer instead.
+    fn new_sequence_token(&mut self) -> Result<Token<'_>, Error> {
+        let control = self.control.expect("a control sequence has a control");
         if self.did_overflow {
             Err(ErrorKind::OutOfMemory.into())
+        } else if !self.string.is_empty() {
+            self.string.extend_from_slice(self.buffer.token());
+            Ok(Token::Sequence(control, &self.string))
         } else {
-            Ok(Token::Sequence(
-                self.control.expect("a control sequence has a control"),
-                self.buffer.token(),
-            ))
+            Ok(Token::Sequence(control, self.buffer.token()))
+        }
+    }
+
+    /// Scan the bytes of a bracketed paste.
+    ///
+    /// This method accumulates bytes until it encounters the paste-end marker.
+    /// Since pastes can be arbitrarily long, it does so in a separate, growable
+    /// buffer instead of the scanner's fixed-size buffer. Just like for control
+    /// strings, that buffer is limited to the maximum string size. Once the
+    /// paste exceeds that size, this method discards the pasted bytes, keeps
+    /// scanning for the paste-end marker, and then returns
+    /// [`ErrorKind::OutOfMemory`].
+    fn scan_paste(&mut self, is_ready: &mut dyn FnMut() -> bool) -> Result<Token<'_>, Error> {
+        loop {
+            if let Some(0) = self.ensure_readable_if(is_ready)? {
+                return Err(ErrorKind::NoData.into());
+            }
+
+            // The marker may straddle reads, so search from earlier bytes.
+            let previous = self.paste.len();
+            let start = previous.saturating_sub(PASTE_END.len() - 1);
+            let bytes = self.buffer.peek_many();
+            self.paste.extend_from_slice(bytes);
+            let count = bytes.len();
+
+            if let Some(index) = self.paste[start..]
+                .windows(PASTE_END.len())
+                .position(|window| window == PASTE_END)
+            {
+                let end = start + index;
+                self.buffer.consume_many(end + PASTE_END.len() - previous);
+                self.paste.truncate(end);
+                self.in_paste = false;
+
+                if self.did_overflow || self.max_string_length < end {
+                    self.did_overflow = false;
+                    self.paste.clear();
+                    return Err(ErrorKind::OutOfMemory.into());
+                }
+                return Ok(Token::Paste(&self.paste));
+            }
+
+            self.buffer.consume_many(count);
+            if self.max_string_length < self.paste.len() {
+                // Only retain enough bytes for recognizing the end marker.
+                let excess = self.paste.len() - (PASTE_END.len() - 1);
+                self.paste.drain(..excess);
+                self.did_overflow = true;
+            }
         }
     }
 
     // ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~ ~
 
     /// Read the next token.
-    pub fn read_token(&mut self) -> Result<Token, Error> {
+    ///
+    /// If the terminal is in bracketed paste mode, this method returns the
+    /// pasted content as a single [`Token::Paste`]. The content is the raw
+    /// bytes between the paste-begin and paste-end markers, which may include
+    /// control characters and escape sequences.
+    pub fn read_token(&mut self) -> Result<Token<'_>, Error> {
+        let result = self.read_token_if(&mut || true);
+        #[cfg(feature = "tracing")]
+        if let Err(ref error) = result {
+            trace_error(error);
+        }
+        result
+    }
+
+    /// Read the next token without waiting for input.
+    ///
+    /// This method only reads from the underlying input while the given
+    /// closure reports that the input is ready, i.e., a read returns data
+    /// without blocking. It returns `Ok(None)` if there is no complete token.
+    /// In that case, a partially scanned token remains in-flight and the next
+    /// read completes it.
+    pub fn try_read_token(
+        &mut self,
+        mut is_ready: impl FnMut() -> bool,
+    ) -> Result<Option<Token<'_>>, Error> {
+        match self.read_token_if(&mut is_ready) {
+            Ok(token) => Ok(Some(token)),
+            Err(error) if error.kind() == ErrorKind::NoData => Ok(None),
+            Err(error) => {
+                #[cfg(feature = "tracing")]
+                trace_error(&error);
+                Err(error)
+            }
+        }
+    }
+
+    /// Read the next token, reading from the underlying input only when ready.
+    fn read_token_if(&mut self, is_ready: &mut dyn FnMut() -> bool) -> Result<Token<'_>, Error> {
+        if self.in_paste {
+            return self.scan_paste(is_ready);
+        }
+
         loop {
             // Make sure that we have some bytes to process
-            if let Some(0) = self.ensure_readable()? {
+            if let Some(0) = self.ensure_readable_if(is_ready)? {
                 return Err(ErrorKind::NoData.into());
             }
 
@@ -276,7 +456,16 @@ impl<R: std::io::Read> Scanner<R> {
 
                 match self.step_sequence(byte)? {
                     HandleControl => return self.new_control_token(byte),
-                    Dispatch => return self.new_sequence_token(),
+                    Dispatch => {
+                        if self.is_paste_start() {
+                            self.in_paste = true;
+                            self.paste.clear();
+                            return self.scan_paste(is_ready);
+                        } else if let Some(token) = self.focus_token() {
+                            return Ok(token);
+                        }
+                        return self.new_sequence_token();
+                    }
                     _ => continue,
                 }
             }
@@ -284,6 +473,37 @@ impl<R: std::io::Read> Scanner<R> {
     }
 }
 
+impl<R: std::io::Read> Scanner<R> {
+    /// Create a new scanner with the default options.
+    pub fn new(reader: R) -> Self {
+        Self::with_options(&Options::default(), reader)
+    }
+
+    /// Iterate over the tokens.
+    ///
+    /// Since tokens borrow from this scanner's internal buffer, the returned
+    /// object is a lending iterator and does not implement [`Iterator`].
+    /// Instead, use `while let` to process one token at a time:
+    ///
+    /// ```
+    /// # use prettytty::{Control, Scanner, Token};
+    /// let input = b"a\x1b[1mb\x1b[m".as_slice();
+    /// let mut scanner = Scanner::new(input);
+    /// let mut tokens = scanner.tokens();
+    ///
+    /// let mut count = 0;
+    /// while let Some(token) = tokens.next() {
+    ///     if let Ok(Token::Sequence(Control::CSI, _)) = token {
+    ///         count += 1;
+    ///     }
+    /// }
+    /// assert_eq!(count, 2);
+    /// ```
+    pub fn tokens(&mut self) -> Tokens<'_, R> {
+        Tokens { scanner: self }
+    }
+}
+
 impl<R> std::fmt::Debug for Scanner<R> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Scanner")
@@ -293,12 +513,41 @@ impl<R> std::fmt::Debug for Scanner<R> {
             .field("did_overflow", &self.did_overflow)
             .field("sequence_length", &self.sequence_length)
             .field("max_sequence_length", &self.max_sequence_length)
+            .field("max_string_length", &self.max_string_length)
+            .field("in_paste", &self.in_paste)
             .finish_non_exhaustive()
     }
 }
 
 // ================================================================================================
 
+/// A lending iterator over a scanner's tokens.
+///
+/// [`Scanner::tokens`] returns this iterator. Each token borrows from the
+/// scanner's internal buffer and hence must be processed before requesting
+/// the next token. As a result, scanning does not allocate memory for tokens,
+/// with the exception of bracketed pastes and control strings exceeding the
+/// buffer. Tokens may span several reads from the underlying input. Malformed
+/// input results in errors, which do not end the iteration. However, reads
+/// that return no data do.
+#[derive(Debug)]
+pub struct Tokens<'s, R> {
+    scanner: &'s mut Scanner<R>,
+}
+
+impl<R: std::io::Read> Tokens<'_, R> {
+    /// Get the next token or `None` when the input has no more data.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Result<Token<'_>, Error>> {
+        match self.scanner.read_token() {
+            Err(error) if error.kind() == ErrorKind::NoData => None,
+            result => Some(result),
+        }
+    }
+}
+
+// ================================================================================================
+
 #[cfg(test)]
 mod test {
     use super::{transition, Action, Control, Error, ErrorKind, Scanner, State, Token};
@@ -329,6 +578,14 @@ mod test {
         assert!(matches!(step(input[2]), (RetainByte, None)));
         assert!(matches!(step(input[3]), (RetainByte, None)));
         assert!(matches!(step(input[4]), (Dispatch, None)));
+
+        let input = b"\x1b[38:5:1m";
+        assert!(matches!(step(input[0]), (StartSequence, Some(ESC))));
+        assert!(matches!(step(input[1]), (IgnoreByte, Some(CSI))));
+        for byte in &input[2..input.len() - 1] {
+            assert!(matches!(step(*byte), (RetainByte, None)));
+        }
+        assert!(matches!(step(input[input.len() - 1]), (Dispatch, None)));
     }
 
     #[test]
@@ -344,7 +601,7 @@ mod test {
             Ok(Token::Text(b"a \xe2\x9c\xb6 ".as_slice())),
             Err(ErrorKind::MalformedSequence.into()),
             Ok(Token::Text(b" ".as_slice())),
-            Ok(Token::Sequence(Control::SS3, &[b'R'])),
+            Ok(Token::Sequence(Control::SS3, b"R")),
             Ok(Token::Text(b" ".as_slice())),
             Err(ErrorKind::MalformedUtf8.into()),
             Ok(Token::Text(b"x ".as_slice())),
@@ -362,13 +619,9 @@ mod test {
             let result = scanner.read_token();
             println!("got {:?}, expected {:?}", result, expected);
             assert_eq!(result.is_ok(), expected.is_ok());
-            if result.is_ok() {
-                assert_eq!(&result.unwrap(), expected.as_ref().unwrap());
-            } else {
-                assert_eq!(
-                    result.unwrap_err().kind(),
-                    expected.as_ref().unwrap_err().kind()
-                );
+            match result {
+                Ok(token) => assert_eq!(&token, expected.as_ref().unwrap()),
+                Err(err) => assert_eq!(err.kind(), expected.as_ref().unwrap_err().kind()),
             }
         }
     }
@@ -384,4 +637,128 @@ mod test {
         assert!(t.is_ok());
         assert_eq!(t.unwrap(), Token::Sequence(Control::OSC, b"text"));
     }
+
+    #[test]
+    fn test_paste() {
+        let input = b"a\x1b[200~b\x1b[31mc\x1b[201~d\x1b[200~\x1b[201~".as_slice();
+        let mut scanner = Scanner::with_options(&Options::default(), input);
+        assert_eq!(scanner.read_token().unwrap(), Token::Text(b"a"));
+        assert!(!scanner.in_paste());
+        assert_eq!(scanner.read_token().unwrap(), Token::Paste(b"b\x1b[31mc"));
+        assert_eq!(scanner.read_token().unwrap(), Token::Text(b"d"));
+        assert_eq!(scanner.read_token().unwrap(), Token::Paste(b""));
+        assert!(!scanner.in_flight());
+
+        let input = b"\x1b[O\x1b[I".as_slice();
+        let mut scanner = Scanner::with_options(&Options::default(), input);
+        assert_eq!(scanner.read_token().unwrap(), Token::FocusLost);
+        assert_eq!(scanner.read_token().unwrap(), Token::FocusGained);
+
+        // A paste larger than the buffer with the end marker split across reads.
+        let options = Options::builder().read_buffer_size(8).build();
+        let input = b"\x1b[200~0123456789abcdef\x1b[201~".as_slice();
+        let mut scanner = Scanner::with_options(&options, input);
+        assert_eq!(
+            scanner.read_token().unwrap(),
+            Token::Paste(b"0123456789abcdef")
+        );
+
+        // A paste larger than the maximum string size.
+        let options = Options::builder().string_size(1_000).build();
+        let mut input = b"\x1b[200~".to_vec();
+        input.extend_from_slice(&[b'p'; 1_001]);
+        input.extend_from_slice(b"\x1b[201~x");
+        let mut scanner = Scanner::with_options(&options, input.as_slice());
+        assert_eq!(
+            scanner.read_token().unwrap_err().kind(),
+            ErrorKind::OutOfMemory
+        );
+        assert_eq!(scanner.read_token().unwrap(), Token::Text(b"x"));
+        assert!(!scanner.in_flight());
+
+        // A paste without end marker.
+        let input = b"\x1b[200~b".as_slice();
+        let mut scanner = Scanner::with_options(&Options::default(), input);
+        assert_eq!(scanner.read_token().unwrap_err().kind(), ErrorKind::NoData);
+        assert!(scanner.in_paste());
+    }
+
+    #[test]
+    fn test_try_read_token() {
+        let input = b"abcdef\x1b[1;31mb".as_slice();
+        let options = Options::builder().read_buffer_size(8).build();
+        let mut scanner = Scanner::with_options(&options, input);
+        let mut reads = 0;
+
+        // Only allow every other read.
+        assert_eq!(scanner.try_read_token(|| false).unwrap(), None);
+        let mut alternate = || {
+            reads += 1;
+            reads % 2 == 1
+        };
+        assert_eq!(
+            scanner.try_read_token(&mut alternate).unwrap(),
+            Some(Token::Text(b"abcdef"))
+        );
+        assert_eq!(scanner.try_read_token(&mut alternate).unwrap(), None);
+        assert!(scanner.in_flight());
+        assert_eq!(
+            scanner.try_read_token(&mut alternate).unwrap(),
+            Some(Token::Sequence(Control::CSI, b"1;31m"))
+        );
+        assert_eq!(
+            scanner.try_read_token(|| true).unwrap(),
+            Some(Token::Text(b"b"))
+        );
+        assert_eq!(scanner.try_read_token(|| true).unwrap(), None);
+    }
+
+    #[test]
+    fn test_tokens() {
+        let options = Options::builder().read_buffer_size(8).build();
+        let input = b"one\x1b[1;31mtwo\x1b]8;;http://x\x1b\\three\x1b[m".repeat(100);
+        let mut scanner = Scanner::with_options(&options, input.as_slice());
+        let mut tokens = scanner.tokens();
+
+        let mut text = Vec::new();
+        let mut sequences = 0;
+        while let Some(token) = tokens.next() {
+            match token.unwrap() {
+                Token::Text(bytes) => text.extend_from_slice(bytes),
+                Token::Sequence(_, _) => sequences += 1,
+                _ => panic!("unexpected token"),
+            }
+        }
+
+        assert_eq!(text, b"onetwothree".repeat(100));
+        assert_eq!(sequences, 300);
+        assert!(tokens.next().is_none());
+    }
+
+    #[test]
+    fn test_long_string() {
+        let options = Options::builder().read_buffer_size(8).build();
+        let input = b"\x1bP1+r524742\x1b\\\x1b_Gi=31;OK\x1b\\".as_slice();
+        let mut scanner = Scanner::with_options(&options, input);
+        assert_eq!(
+            scanner.read_token().unwrap(),
+            Token::Sequence(Control::DCS, b"1+r524742")
+        );
+        assert_eq!(
+            scanner.read_token().unwrap(),
+            Token::Sequence(Control::APC, b"Gi=31;OK")
+        );
+
+        let options = Options::builder()
+            .read_buffer_size(8)
+            .pathological_size(16)
+            .string_size(24)
+            .build();
+        let input = b"\x1bP0123456789abcdef0123456789\x1b\\".as_slice();
+        let mut scanner = Scanner::with_options(&options, input);
+        assert_eq!(
+            scanner.read_token().unwrap_err().kind(),
+            ErrorKind::PathologicalSequence
+        );
+    }
 }
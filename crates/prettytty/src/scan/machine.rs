@@ -174,6 +174,9 @@ const fn string_end(byte: u8) -> (State, Action, Option<Control>) {
 
 // ------------------------------------------------------------------------------------------------
 // CSI
+//
+// Unlike the original state machine, CSI parameters may include colons, since
+// SGR uses them to separate subparameters, e.g., for colors.
 
 const fn csi_entry(byte: u8) -> (State, Action, Option<Control>) {
     use self::Action::*;
@@ -181,8 +184,7 @@ const fn csi_entry(byte: u8) -> (State, Action, Option<Control>) {
 
     match byte {
         0x20..=0x2f => (CsiIntermediate, RetainByte, None),
-        0x30..=0x39 | 0x3b..=0x3f => (CsiParam, RetainByte, None),
-        0x3a => (CsiIgnore, IgnoreByte, None),
+        0x30..=0x3f => (CsiParam, RetainByte, None),
         0x40..=0x7e => (Ground, Dispatch, None),
         _ => otherwise(byte, CsiEntry),
     }
@@ -194,8 +196,8 @@ const fn csi_param(byte: u8) -> (State, Action, Option<Control>) {
 
     match byte {
         0x20..=0x2f => (CsiIntermediate, RetainByte, None),
-        0x30..=0x39 | 0x3b => (CsiParam, RetainByte, None),
-        0x3a | 0x3c..=0x3f => (CsiIgnore, IgnoreByte, None),
+        0x30..=0x3b => (CsiParam, RetainByte, None),
+        0x3c..=0x3f => (CsiIgnore, IgnoreByte, None),
         0x40..=0x7e => (Ground, Dispatch, None),
         _ => otherwise(byte, CsiParam),
     }
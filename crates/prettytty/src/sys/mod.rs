@@ -1,5 +1,9 @@
 // Platform-dependent types are prefixed with `Raw`.
 
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Once;
+
 #[cfg(target_family = "unix")]
 pub(crate) type RawHandle = std::os::fd::RawFd;
 #[cfg(target_family = "windows")]
@@ -13,6 +17,120 @@ mod unix;
 mod windows;
 
 #[cfg(target_family = "unix")]
-pub(crate) use self::unix::{RawConfig, RawConnection, RawOutput};
+pub(crate) use self::unix::{
+    arm, disarm, restore, stop_process, RawConfig, RawConnection, RawOutput, RawWaker,
+};
 #[cfg(target_family = "windows")]
-pub(crate) use windows::{RawConfig, RawConnection, RawOutput};
+pub(crate) use windows::{
+    arm, disarm, restore, stop_process, RawConfig, RawConnection, RawOutput, RawWaker,
+};
+
+#[cfg(target_family = "unix")]
+use self::unix::{install_handlers, set_armed};
+#[cfg(target_family = "windows")]
+use windows::{install_handlers, set_armed};
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// Flag for the terminal currently displaying the alternate screen.
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+
+/// Track whether the terminal displays the alternate screen.
+pub(crate) fn set_alternate_screen(enabled: bool) {
+    ALTERNATE_SCREEN.store(enabled, Ordering::SeqCst);
+}
+
+/// Determine whether the terminal displays the alternate screen.
+pub(crate) fn is_alternate_screen() -> bool {
+    ALTERNATE_SCREEN.load(Ordering::SeqCst)
+}
+
+/// The capacity of the buffer for disabling modes in an emergency.
+const MODE_CAPACITY: usize = 256;
+
+/// The escape sequences for disabling modes in an emergency.
+///
+/// Since signal handlers may only invoke async-signal-safe functions, this
+/// struct stores the escape sequences in a preallocated buffer. The platform's
+/// `is_armed` flag guards access: [`set_disabled_modes`] clears the flag
+/// before updating the buffer and restores it afterwards, whereas `restore`
+/// only reads the buffer after atomically clearing the flag.
+struct DisabledModes {
+    length: AtomicUsize,
+    bytes: UnsafeCell<[u8; MODE_CAPACITY]>,
+}
+
+// SAFETY: See the struct's documentation.
+unsafe impl Sync for DisabledModes {}
+
+static DISABLED_MODES: DisabledModes = DisabledModes {
+    length: AtomicUsize::new(0),
+    bytes: UnsafeCell::new([0; MODE_CAPACITY]),
+};
+
+/// Track the modes to disable in an emergency.
+///
+/// Given the pairs of commands for enabling and disabling modes, in the order
+/// they were enabled, this function stores the disabling commands in reverse
+/// order. If they exceed the buffer's capacity, it omits the earliest modes.
+pub(crate) fn set_disabled_modes(modes: &[(String, String)]) {
+    let is_armed = set_armed(false);
+
+    // SAFETY: The cleared flag keeps restore() from reading the buffer.
+    let bytes = unsafe { &mut *DISABLED_MODES.bytes.get() };
+    let mut length = 0;
+    for (_, disable) in modes.iter().rev() {
+        let disable = disable.as_bytes();
+        if MODE_CAPACITY < length + disable.len() {
+            break;
+        }
+        bytes[length..length + disable.len()].copy_from_slice(disable);
+        length += disable.len();
+    }
+    DISABLED_MODES.length.store(length, Ordering::SeqCst);
+
+    set_armed(is_armed);
+}
+
+/// Get the escape sequences for disabling modes in an emergency.
+///
+/// # Safety
+///
+/// The caller must have atomically cleared the platform's `is_armed` flag.
+unsafe fn disabled_modes() -> &'static [u8] {
+    let length = DISABLED_MODES.length.load(Ordering::SeqCst);
+    let bytes = &*DISABLED_MODES.bytes.get();
+    &bytes[..length]
+}
+
+/// Get the escape sequences for resetting the terminal in an emergency.
+///
+/// The sequences reset text attributes, show the cursor, and, if necessary,
+/// leave the alternate screen. Since this function may be invoked from a
+/// signal handler, it only loads an atomic flag.
+fn emergency_reset() -> &'static [u8] {
+    if ALTERNATE_SCREEN.load(Ordering::SeqCst) {
+        b"\x1b[0m\x1b[?25h\x1b[?1049l"
+    } else {
+        b"\x1b[0m\x1b[?25h"
+    }
+}
+
+/// Install the panic hook and signal handlers for restoring the terminal.
+///
+/// The panic hook restores the terminal before delegating to the previous
+/// hook, so that the panic message appears on the main screen in cooked mode.
+/// This function installs the hook and handlers only once per process.
+pub(crate) fn install_hooks() {
+    static INSTALLED: Once = Once::new();
+
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore();
+            previous(info);
+        }));
+
+        install_handlers();
+    });
+}
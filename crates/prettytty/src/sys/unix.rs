@@ -1,8 +1,12 @@
+use std::cell::UnsafeCell;
 use std::ffi::c_void;
 use std::fs::OpenOptions;
-use std::io::{stderr, stdin, stdout, IsTerminal, Read, Result, Write};
-use std::os::fd::{AsRawFd, OwnedFd};
+use std::io::{stderr, stdin, stdout, ErrorKind, IsTerminal, Read, Result, Write};
+use std::mem::MaybeUninit;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
 use std::ptr::{from_mut, from_ref};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::Arc;
 
 use super::util::{IdentList, IntoResult};
 use super::RawHandle;
@@ -13,6 +17,7 @@ use crate::opt::{Mode, Options};
 #[derive(Debug)]
 enum RawConnectionHandle {
     Owned(OwnedFd),
+    Pair(OwnedFd, OwnedFd),
     #[allow(dead_code)]
     StdIo(RawHandle, RawHandle),
 }
@@ -21,6 +26,7 @@ impl RawConnectionHandle {
     fn input(&self) -> RawHandle {
         match self {
             Self::Owned(handle) => handle.as_raw_fd(),
+            Self::Pair(handle, _) => handle.as_raw_fd(),
             Self::StdIo(handle, _) => *handle,
         }
     }
@@ -28,29 +34,70 @@ impl RawConnectionHandle {
     fn output(&self) -> RawHandle {
         match self {
             Self::Owned(handle) => handle.as_raw_fd(),
+            Self::Pair(_, handle) => handle.as_raw_fd(),
             Self::StdIo(_, handle) => *handle,
         }
     }
 }
 
+/// A self-pipe for interrupting reads that wait for readiness notifications.
+#[derive(Debug)]
+struct WakePipe {
+    input: OwnedFd,
+    output: Arc<OwnedFd>,
+}
+
+impl WakePipe {
+    /// Create a new non-blocking pipe.
+    fn new() -> Result<Self> {
+        let mut fds = [0; 2];
+        unsafe { libc::pipe(fds.as_mut_ptr()) }.into_result()?;
+        let (input, output) =
+            unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+
+        for fd in fds {
+            unsafe { libc::fcntl(fd, libc::F_SETFL, libc::O_NONBLOCK) }.into_result()?;
+            unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) }.into_result()?;
+        }
+
+        Ok(Self {
+            input,
+            output: Arc::new(output),
+        })
+    }
+}
+
 /// A connection to a terminal device.
 #[derive(Debug)]
 pub(crate) struct RawConnection {
     handle: RawConnectionHandle,
+    poll: Option<(WakePipe, i32)>,
 }
 
 impl RawConnection {
     /// Open a new terminal connection.
-    pub fn open(_: &Options) -> Result<Self> {
+    pub fn open(options: &Options) -> Result<Self> {
         let fd = OpenOptions::new()
             .read(true)
             .write(true)
             .open("/dev/tty")?
             .into();
 
-        Ok(Self {
-            handle: RawConnectionHandle::Owned(fd),
-        })
+        Self::with_handle(RawConnectionHandle::Owned(fd), options)
+    }
+
+    /// Create a new connection with the given file descriptors.
+    pub fn with_fds(input: OwnedFd, output: OwnedFd, options: &Options) -> Result<Self> {
+        Self::with_handle(RawConnectionHandle::Pair(input, output), options)
+    }
+
+    fn with_handle(handle: RawConnectionHandle, options: &Options) -> Result<Self> {
+        let poll = match options.poll_timeout() {
+            Some(timeout) => Some((WakePipe::new()?, timeout.min(i32::MAX as u32) as i32)),
+            None => None,
+        };
+
+        Ok(Self { handle, poll })
     }
 
     /// Simulate a terminal connection with standard I/O.
@@ -79,22 +126,66 @@ impl RawConnection {
 
             Some(Self {
                 handle: RawConnectionHandle::StdIo(stdin().as_raw_fd(), output),
+                poll: None,
             })
         } else {
             None
         }
     }
 
+    /// Determine whether the connection's input is a terminal.
+    pub fn is_terminal(&self) -> bool {
+        unsafe { libc::isatty(self.handle.input()) == 1 }
+    }
+
     /// Get process group ID.
     #[inline]
     pub fn group(&self) -> Result<u32> {
         unsafe { libc::tcgetsid(self.handle.input()) }.into_result()
     }
 
+    /// Get the console's text attributes, which are Windows-only.
+    #[inline]
+    pub fn attributes(&self) -> Result<u16> {
+        Err(ErrorKind::Unsupported.into())
+    }
+
+    /// Get the console's color table, which is Windows-only.
+    #[inline]
+    pub fn palette(&self) -> Result<[[u8; 3]; 16]> {
+        Err(ErrorKind::Unsupported.into())
+    }
+
+    /// Get the terminal's size in rows and columns.
+    pub fn size(&self) -> Result<(u16, u16)> {
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        unsafe { libc::ioctl(self.handle.output(), libc::TIOCGWINSZ, from_mut(&mut size)) }
+            .into_result()?;
+        Ok((size.ws_row, size.ws_col))
+    }
+
     /// Get a handle for reading from the connection.
     #[inline]
     pub fn input(&self) -> RawInput {
-        RawInput::new(self.handle.input())
+        RawInput::new(
+            self.handle.input(),
+            self.poll
+                .as_ref()
+                .map(|(pipe, timeout)| (pipe.input.as_raw_fd(), *timeout)),
+        )
+    }
+
+    /// Determine whether reading from the connection returns data right away.
+    pub fn is_ready(&self) -> bool {
+        wait([self.handle.input()], 0).is_ok_and(|[is_ready]| is_ready)
+    }
+
+    /// Get a handle for interrupting reads from the connection, if it uses
+    /// readiness notifications.
+    pub fn waker(&self) -> Option<RawWaker> {
+        self.poll.as_ref().map(|(pipe, _)| RawWaker {
+            handle: Arc::clone(&pipe.output),
+        })
     }
 
     /// Get a handle for writing to the connection.
@@ -169,8 +260,13 @@ impl RawConfig {
             }
         }
 
+        // With readiness notifications, reads must not wait at all.
         state.c_cc[libc::VMIN] = 0;
-        state.c_cc[libc::VTIME] = options.timeout();
+        state.c_cc[libc::VTIME] = if options.poll_timeout().is_some() {
+            0
+        } else {
+            options.timeout()
+        };
         Some(Self { state })
     }
 
@@ -287,16 +383,60 @@ impl std::fmt::Debug for RawConfig {
 
 // ----------------------------------------------------------------------------------------------------------
 
+/// Wait until one of the file descriptors becomes readable.
+#[cfg(not(target_os = "macos"))]
+fn wait<const N: usize>(handles: [RawHandle; N], timeout: i32) -> Result<[bool; N]> {
+    let mut fds = handles.map(|fd| libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    });
+
+    unsafe { libc::poll(fds.as_mut_ptr(), N as libc::nfds_t, timeout) }.into_result()?;
+    Ok(fds.map(|fd| fd.revents != 0))
+}
+
+/// Wait until one of the file descriptors becomes readable.
+///
+/// On macOS, `poll` does not support devices, so this function uses `select`.
+#[cfg(target_os = "macos")]
+fn wait<const N: usize>(handles: [RawHandle; N], timeout: i32) -> Result<[bool; N]> {
+    let mut set: libc::fd_set = unsafe { std::mem::zeroed() };
+    unsafe { libc::FD_ZERO(from_mut(&mut set)) };
+    for fd in handles {
+        unsafe { libc::FD_SET(fd, from_mut(&mut set)) };
+    }
+
+    let mut time = libc::timeval {
+        tv_sec: (timeout / 1_000) as libc::time_t,
+        tv_usec: ((timeout % 1_000) * 1_000) as libc::suseconds_t,
+    };
+
+    unsafe {
+        libc::select(
+            handles.iter().copied().max().unwrap_or(0) + 1,
+            from_mut(&mut set),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            from_mut(&mut time),
+        )
+    }
+    .into_result()?;
+
+    Ok(handles.map(|fd| unsafe { libc::FD_ISSET(fd, from_ref(&set)) }))
+}
+
 /// Raw unbuffered terminal input.
 #[derive(Debug)]
 pub(crate) struct RawInput {
     handle: RawHandle,
+    poll: Option<(RawHandle, i32)>,
 }
 
 impl RawInput {
     #[inline]
-    fn new(handle: RawHandle) -> Self {
-        Self { handle }
+    fn new(handle: RawHandle, poll: Option<(RawHandle, i32)>) -> Self {
+        Self { handle, poll }
     }
 
     #[inline]
@@ -307,6 +447,20 @@ impl RawInput {
 
 impl Read for RawInput {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if let Some((wake, timeout)) = self.poll {
+            let [is_readable, is_woken] = wait([self.handle, wake], timeout)?;
+            if is_woken {
+                // Drain the pipe, which is non-blocking.
+                let mut sink = [0_u8; 32];
+                while 0 < unsafe { libc::read(wake, sink.as_mut_ptr() as *mut c_void, sink.len()) }
+                {
+                }
+                return Ok(0);
+            } else if !is_readable {
+                return Ok(0);
+            }
+        }
+
         unsafe {
             libc::read(
                 self.handle,
@@ -355,3 +509,377 @@ impl Write for RawOutput {
         Ok(())
     }
 }
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// A handle for interrupting reads that wait for readiness notifications.
+#[derive(Clone, Debug)]
+pub(crate) struct RawWaker {
+    handle: Arc<OwnedFd>,
+}
+
+impl RawWaker {
+    /// Wake up the pending read, if any.
+    pub fn wake(&self) -> Result<()> {
+        let byte = 1_u8;
+        let result =
+            unsafe { libc::write(self.handle.as_raw_fd(), from_ref(&byte) as *const c_void, 1) }
+                .into_result();
+
+        match result {
+            // A full pipe already wakes up the reader.
+            Err(error) if error.kind() == ErrorKind::WouldBlock => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// The signals that terminate or stop the process by default.
+const SIGNALS: [libc::c_int; 5] = [
+    libc::SIGINT,
+    libc::SIGTERM,
+    libc::SIGHUP,
+    libc::SIGQUIT,
+    libc::SIGTSTP,
+];
+
+/// The state for restoring the terminal in an emergency.
+///
+/// Since signal handlers may only invoke async-signal-safe functions, this
+/// struct stores the terminal configurations in plain memory. The `is_armed`
+/// flag guards access: [`arm`] clears the flag before updating the
+/// configurations and sets it afterwards, whereas [`restore`] only reads the
+/// configurations after atomically clearing the flag.
+struct Emergency {
+    is_armed: AtomicBool,
+    is_suspended: AtomicBool,
+    input: AtomicI32,
+    output: AtomicI32,
+    original: UnsafeCell<MaybeUninit<libc::termios>>,
+    applied: UnsafeCell<MaybeUninit<libc::termios>>,
+}
+
+// SAFETY: See the struct's documentation.
+unsafe impl Sync for Emergency {}
+
+static EMERGENCY: Emergency = Emergency {
+    is_armed: AtomicBool::new(false),
+    is_suspended: AtomicBool::new(false),
+    input: AtomicI32::new(-1),
+    output: AtomicI32::new(-1),
+    original: UnsafeCell::new(MaybeUninit::uninit()),
+    applied: UnsafeCell::new(MaybeUninit::uninit()),
+};
+
+/// Prepare for restoring the original configuration in an emergency.
+pub(crate) fn arm(connection: &RawConnection, original: &RawConfig, applied: &RawConfig) {
+    EMERGENCY.is_armed.store(false, Ordering::SeqCst);
+    unsafe {
+        (*EMERGENCY.original.get()).write(original.state);
+        (*EMERGENCY.applied.get()).write(applied.state);
+    }
+    EMERGENCY
+        .input
+        .store(connection.handle.input(), Ordering::SeqCst);
+    EMERGENCY
+        .output
+        .store(connection.handle.output(), Ordering::SeqCst);
+    EMERGENCY.is_suspended.store(false, Ordering::SeqCst);
+    EMERGENCY.is_armed.store(true, Ordering::SeqCst);
+}
+
+/// Stop restoring the original configuration in an emergency.
+pub(crate) fn disarm() {
+    EMERGENCY.is_armed.store(false, Ordering::SeqCst);
+    EMERGENCY.is_suspended.store(false, Ordering::SeqCst);
+}
+
+/// Set whether to restore the original configuration in an emergency.
+///
+/// This function returns the previous value.
+pub(crate) fn set_armed(is_armed: bool) -> bool {
+    EMERGENCY.is_armed.swap(is_armed, Ordering::SeqCst)
+}
+
+/// Restore the original configuration in an emergency.
+///
+/// This function is async-signal-safe. It returns `true` if it restored the
+/// terminal, i.e., was armed.
+pub(crate) fn restore() -> bool {
+    if !EMERGENCY.is_armed.swap(false, Ordering::SeqCst) {
+        return false;
+    }
+
+    let modes = unsafe { super::disabled_modes() };
+    let reset = super::emergency_reset();
+    unsafe {
+        libc::write(
+            EMERGENCY.output.load(Ordering::SeqCst),
+            modes.as_ptr().cast(),
+            modes.len(),
+        );
+        libc::write(
+            EMERGENCY.output.load(Ordering::SeqCst),
+            reset.as_ptr().cast(),
+            reset.len(),
+        );
+        libc::tcsetattr(
+            EMERGENCY.input.load(Ordering::SeqCst),
+            libc::TCSAFLUSH,
+            (*EMERGENCY.original.get()).as_ptr(),
+        );
+    }
+    true
+}
+
+/// Reapply the connection's configuration after resuming the process.
+fn reapply() {
+    if !EMERGENCY.is_suspended.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    unsafe {
+        libc::tcsetattr(
+            EMERGENCY.input.load(Ordering::SeqCst),
+            libc::TCSAFLUSH,
+            (*EMERGENCY.applied.get()).as_ptr(),
+        );
+    }
+    if super::ALTERNATE_SCREEN.load(Ordering::SeqCst) {
+        let enter = b"\x1b[?1049h";
+        unsafe {
+            libc::write(
+                EMERGENCY.output.load(Ordering::SeqCst),
+                enter.as_ptr().cast(),
+                enter.len(),
+            );
+        }
+    }
+    EMERGENCY.is_armed.store(true, Ordering::SeqCst);
+}
+
+/// Determine whether the signal has its default disposition.
+fn is_default(signal: libc::c_int) -> bool {
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        libc::sigaction(signal, std::ptr::null(), from_mut(&mut action)) == 0
+            && action.sa_sigaction == libc::SIG_DFL
+    }
+}
+
+/// Set the signal's disposition.
+fn set_handler(signal: libc::c_int, handler: libc::sighandler_t) {
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handler;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(from_mut(&mut action.sa_mask));
+        libc::sigaction(signal, from_ref(&action), std::ptr::null_mut());
+    }
+}
+
+/// Get the signal handler as a disposition.
+fn signal_handler() -> libc::sighandler_t {
+    handle_signal as extern "C" fn(libc::c_int) as *const () as libc::sighandler_t
+}
+
+extern "C" fn handle_signal(signal: libc::c_int) {
+    if signal == libc::SIGCONT {
+        // This handler is only installed along with the handler for SIGTSTP,
+        // which reset its disposition before stopping the process.
+        set_handler(libc::SIGTSTP, signal_handler());
+        reapply();
+        return;
+    }
+
+    if restore() && signal == libc::SIGTSTP {
+        EMERGENCY.is_suspended.store(true, Ordering::SeqCst);
+    }
+
+    // Re-raise the signal with its default disposition. Since the signal is
+    // blocked while this handler runs, it is delivered once the handler
+    // returns.
+    set_handler(signal, libc::SIG_DFL);
+    unsafe { libc::raise(signal) };
+}
+
+/// Stop the process as if the user had typed control-z.
+pub(crate) fn stop_process() -> Result<()> {
+    unsafe { libc::raise(libc::SIGTSTP) }.into_result()?;
+    Ok(())
+}
+
+/// Install the signal handlers.
+///
+/// This function only installs handlers for signals with the default
+/// disposition. That way, it does not interfere with applications that
+/// handle or ignore the signals themselves.
+pub(crate) fn install_handlers() {
+    let handler = signal_handler();
+
+    for signal in SIGNALS {
+        if is_default(signal) {
+            set_handler(signal, handler);
+            if signal == libc::SIGTSTP && is_default(libc::SIGCONT) {
+                set_handler(libc::SIGCONT, handler);
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{restore, RawInput, RawWaker, WakePipe};
+    use crate::cmd::{BeginPaste, DisableFocusReports, EnableFocusReports, EndPaste};
+    use crate::opt::{Mode, Options, Volume};
+    use crate::{Connection, Control, Scan, Token};
+    use std::io::{Read, Result, Write};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_wake() -> Result<()> {
+        let tty = WakePipe::new()?;
+        let wake = WakePipe::new()?;
+        let mut input = RawInput::new(tty.input.as_raw_fd(), Some((wake.input.as_raw_fd(), 10)));
+        let waker = RawWaker {
+            handle: Arc::clone(&wake.output),
+        };
+        let mut buffer = [0; 8];
+
+        // Time out, wake up, and read data.
+        assert_eq!(input.read(&mut buffer)?, 0);
+        waker.wake()?;
+        waker.wake()?;
+        assert_eq!(input.read(&mut buffer)?, 0);
+        assert_eq!(input.read(&mut buffer)?, 0);
+
+        let tty_waker = RawWaker {
+            handle: Arc::clone(&tty.output),
+        };
+        tty_waker.wake()?;
+        assert_eq!(input.read(&mut buffer)?, 1);
+        assert_eq!(buffer[0], 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore() -> Result<()> {
+        let (mut master, mut slave) = (0, 0);
+        unsafe {
+            libc::openpty(
+                &mut master,
+                &mut slave,
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                std::ptr::null(),
+            )
+        };
+        assert!(0 < master && 0 < slave);
+        let (master, slave) =
+            unsafe { (OwnedFd::from_raw_fd(master), OwnedFd::from_raw_fd(slave)) };
+
+        let lflag = || {
+            let mut state: libc::termios = unsafe { std::mem::zeroed() };
+            unsafe { libc::tcgetattr(slave.as_raw_fd(), &mut state) };
+            state.c_lflag & libc::ICANON
+        };
+
+        assert_ne!(lflag(), 0);
+        let options = Options::builder()
+            .mode(Mode::Raw)
+            .restore_hooks(true)
+            .build();
+        let connection = Connection::with_fds(slave.try_clone()?, slave.try_clone()?, options)?;
+        assert_eq!(lflag(), 0);
+
+        // Disable enabled modes in reverse order.
+        connection.enable_mode(BeginPaste, EndPaste)?;
+        connection.enable_mode(EnableFocusReports, DisableFocusReports)?;
+
+        let mut buffer = [0; 64];
+        let count =
+            unsafe { libc::read(master.as_raw_fd(), buffer.as_mut_ptr().cast(), buffer.len()) };
+        assert!(0 < count);
+
+        // Restore once and only once.
+        assert!(restore());
+        assert_ne!(lflag(), 0);
+        assert!(!restore());
+
+        let count =
+            unsafe { libc::read(master.as_raw_fd(), buffer.as_mut_ptr().cast(), buffer.len()) };
+        assert_eq!(
+            &buffer[..count as usize],
+            b"\x1b[?1004l\x1b[?2004l\x1b[0m\x1b[?25h"
+        );
+
+        drop(connection);
+        assert_ne!(lflag(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_fds() -> Result<()> {
+        let input = WakePipe::new()?;
+        let output = WakePipe::new()?;
+        let options = Options::builder()
+            .volume(Volume::Silent)
+            .poll_timeout(10)
+            .build();
+        let connection = Connection::with_fds(
+            input.input.try_clone()?,
+            Arc::into_inner(output.output).unwrap(),
+            options,
+        )?;
+
+        let data = b"abc\x1b[1m";
+        let count =
+            unsafe { libc::write(input.output.as_raw_fd(), data.as_ptr().cast(), data.len()) };
+        assert_eq!(count as usize, data.len());
+        let mut tty_input = connection.input();
+        assert_eq!(tty_input.read_token()?, Token::Text(b"abc"));
+        assert_eq!(
+            tty_input.read_token()?,
+            Token::Sequence(Control::CSI, b"1m")
+        );
+        drop(tty_input);
+
+        let mut tty_output = connection.output();
+        tty_output.print("hello")?;
+        tty_output.flush()?;
+        drop(tty_output);
+        let mut buffer = [0; 8];
+        let count = unsafe {
+            libc::read(
+                output.input.as_raw_fd(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+            )
+        };
+        assert_eq!(&buffer[..count as usize], b"hello");
+
+        connection.enable_mode(BeginPaste, EndPaste)?;
+        connection.enable_mode(EnableFocusReports, DisableFocusReports)?;
+        connection.disable_mode(DisableFocusReports)?;
+        connection.resume()?;
+
+        let mut buffer = [0; 64];
+        let count = unsafe {
+            libc::read(
+                output.input.as_raw_fd(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+            )
+        };
+        assert_eq!(
+            &buffer[..count as usize],
+            b"\x1b[?2004h\x1b[?1004h\x1b[?1004l\x1b[?2004h"
+        );
+        Ok(())
+    }
+}
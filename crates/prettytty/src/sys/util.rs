@@ -96,3 +96,118 @@ impl std::fmt::Debug for IdentList<'_> {
         f.debug_list().entries(self.0.iter()).finish()
     }
 }
+
+// -------------------------------------------------------------------------------------
+
+/// A decoder from console input to UTF-8.
+///
+/// Windows reports console input as UTF-16 code units, one per key event.
+/// Hence, a character outside the basic multilingual plane arrives as two
+/// separate key events for the high and low surrogates, possibly split across
+/// reads. This decoder combines such surrogate pairs, replaces unpaired
+/// surrogates with U+FFFD, and buffers the resulting UTF-8 until it is read.
+/// It also translates changes to the window size into xterm's in-band resize
+/// notification `CSI 48 ; rows ; columns ; 0 ; 0 t`.
+#[cfg_attr(not(target_family = "windows"), allow(dead_code))]
+#[derive(Debug, Default)]
+pub(crate) struct ConsoleDecoder {
+    surrogate: Option<u16>,
+    pending: std::collections::VecDeque<u8>,
+}
+
+#[cfg_attr(not(target_family = "windows"), allow(dead_code))]
+impl ConsoleDecoder {
+    /// Determine whether the decoder has buffered bytes.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Append a character to the buffered bytes.
+    fn push_char(&mut self, c: char) {
+        let mut bytes = [0; 4];
+        self.pending.extend(c.encode_utf8(&mut bytes).as_bytes());
+    }
+
+    /// Decode the UTF-16 code unit.
+    pub fn push_unit(&mut self, unit: u16) {
+        match unit {
+            0xd800..=0xdbff => {
+                if self.surrogate.replace(unit).is_some() {
+                    self.push_char(char::REPLACEMENT_CHARACTER);
+                }
+            }
+            0xdc00..=0xdfff => {
+                let c = self
+                    .surrogate
+                    .take()
+                    .map_or(char::REPLACEMENT_CHARACTER, |high| {
+                        let code =
+                            0x10000 + (((high as u32) - 0xd800) << 10) + (unit as u32 - 0xdc00);
+                        char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                    });
+                self.push_char(c);
+            }
+            _ => {
+                if self.surrogate.take().is_some() {
+                    self.push_char(char::REPLACEMENT_CHARACTER);
+                }
+                // Any code unit outside the surrogate range is a valid char.
+                self.push_char(char::from_u32(unit as u32).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+        }
+    }
+
+    /// Translate a change to the window size.
+    pub fn push_resize(&mut self, rows: u16, columns: u16) {
+        let sequence = format!("\x1b[48;{};{};0;0t", rows, columns);
+        self.pending.extend(sequence.as_bytes());
+    }
+
+    /// Read buffered bytes into the buffer, returning the number of bytes.
+    pub fn read(&mut self, buf: &mut [u8]) -> usize {
+        let count = buf.len().min(self.pending.len());
+        for (target, source) in buf.iter_mut().zip(self.pending.drain(..count)) {
+            *target = source;
+        }
+        count
+    }
+}
+
+// -------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::ConsoleDecoder;
+
+    #[test]
+    fn test_console_decoder() {
+        let mut decoder = ConsoleDecoder::default();
+        let mut buffer = [0; 32];
+
+        // A surrogate pair split across reads.
+        decoder.push_unit('a' as u16);
+        decoder.push_unit(0xd83c);
+        assert_eq!(decoder.read(&mut buffer), 1);
+        assert_eq!(&buffer[..1], b"a");
+        assert!(!decoder.has_pending());
+
+        decoder.push_unit(0xdf89);
+        decoder.push_unit('ü' as u16);
+        let count = decoder.read(&mut buffer);
+        assert_eq!(&buffer[..count], "🎉ü".as_bytes());
+
+        // Unpaired surrogates.
+        decoder.push_unit(0xdf89);
+        decoder.push_unit(0xd83c);
+        decoder.push_unit(0x1b);
+        let count = decoder.read(&mut buffer);
+        assert_eq!(&buffer[..count], "\u{fffd}\u{fffd}\x1b".as_bytes());
+
+        // Resize events and partial reads.
+        decoder.push_resize(24, 80);
+        assert_eq!(decoder.read(&mut buffer[..4]), 4);
+        assert_eq!(&buffer[..4], b"\x1b[48");
+        let count = decoder.read(&mut buffer);
+        assert_eq!(&buffer[..count], b";24;80;0;0t");
+    }
+}
@@ -1,18 +1,23 @@
 use std::ffi::c_void;
 use std::fs::OpenOptions;
 use std::io::{Error, ErrorKind, Read, Result, Write};
-use std::os::windows::io::{AsRawHandle, OwnedHandle};
+use std::os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle};
 use std::ptr::{from_mut, null};
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, Ordering};
+use std::sync::Arc;
 
 use windows_sys::Win32::Foundation;
 use windows_sys::Win32::Globalization;
 use windows_sys::Win32::System::Console::{self, CONSOLE_MODE as ConsoleMode};
 use windows_sys::Win32::System::Threading;
 
-use super::util::{IdentList, IntoResult};
+use super::util::{ConsoleDecoder, IdentList, IntoResult};
 use super::RawHandle;
 use crate::opt::{Mode, Options};
 
+/// The virtual key code for the Alt key.
+const VK_MENU: u16 = 0x12;
+
 // ----------------------------------------------------------------------------------------------------------
 
 #[derive(Debug)]
@@ -20,12 +25,15 @@ pub(crate) struct RawConnection {
     timeout: u32,
     input: OwnedHandle,
     output: OwnedHandle,
+    wake: Option<Arc<OwnedHandle>>,
 }
 
 impl RawConnection {
     /// Open a new owned connection to the terminal device.
     pub fn open(options: &Options) -> Result<Self> {
-        let timeout = 100 * (options.timeout() as u32);
+        let timeout = options
+            .poll_timeout()
+            .unwrap_or(100 * (options.timeout() as u32));
         let input = OpenOptions::new()
             .read(true)
             .write(true)
@@ -37,23 +45,99 @@ impl RawConnection {
             .open("CONOUT$")?
             .into();
 
+        let wake = if options.poll_timeout().is_some() {
+            // An auto-reset event, which is initially not signalled.
+            let event = unsafe { Threading::CreateEventW(null(), 0, 0, null()) };
+            if event.is_null() {
+                return Err(Error::last_os_error());
+            }
+            Some(Arc::new(unsafe { OwnedHandle::from_raw_handle(event) }))
+        } else {
+            None
+        };
+
         Ok(Self {
             timeout,
             input,
             output,
+            wake,
         })
     }
 
+    /// Determine whether the connection's input is a terminal.
+    #[inline]
+    pub fn is_terminal(&self) -> bool {
+        true
+    }
+
     /// Get the process group ID.
     #[inline]
     pub fn group(&self) -> Result<u32> {
         Err(ErrorKind::Unsupported.into())
     }
 
+    /// Get the console's text attributes.
+    pub fn attributes(&self) -> Result<u16> {
+        let mut info: Console::CONSOLE_SCREEN_BUFFER_INFO = unsafe { std::mem::zeroed() };
+        unsafe {
+            Console::GetConsoleScreenBufferInfo(self.output.as_raw_handle(), from_mut(&mut info))
+        }
+        .into_result()?;
+        Ok(info.wAttributes)
+    }
+
+    /// Get the console window's size in rows and columns.
+    pub fn size(&self) -> Result<(u16, u16)> {
+        let mut info: Console::CONSOLE_SCREEN_BUFFER_INFO = unsafe { std::mem::zeroed() };
+        unsafe {
+            Console::GetConsoleScreenBufferInfo(self.output.as_raw_handle(), from_mut(&mut info))
+        }
+        .into_result()?;
+
+        let window = info.srWindow;
+        Ok((
+            (window.Bottom - window.Top + 1) as u16,
+            (window.Right - window.Left + 1) as u16,
+        ))
+    }
+
+    /// Get the console's color table.
+    pub fn palette(&self) -> Result<[[u8; 3]; 16]> {
+        let mut info: Console::CONSOLE_SCREEN_BUFFER_INFOEX = unsafe { std::mem::zeroed() };
+        info.cbSize = std::mem::size_of::<Console::CONSOLE_SCREEN_BUFFER_INFOEX>() as u32;
+        unsafe {
+            Console::GetConsoleScreenBufferInfoEx(self.output.as_raw_handle(), from_mut(&mut info))
+        }
+        .into_result()?;
+
+        // COLORREF is 0x00bbggrr.
+        Ok(info
+            .ColorTable
+            .map(|color| [color as u8, (color >> 8) as u8, (color >> 16) as u8]))
+    }
+
     /// Get a handle for the terminal's input.
     #[inline]
     pub fn input(&self) -> RawInput {
-        RawInput::new(self.input.as_raw_handle(), self.timeout)
+        RawInput::new(
+            self.input.as_raw_handle(),
+            self.timeout,
+            self.wake.as_ref().map(|event| event.as_raw_handle()),
+        )
+    }
+
+    /// Determine whether reading from the connection returns data right away.
+    pub fn is_ready(&self) -> bool {
+        let status = unsafe { Threading::WaitForSingleObject(self.input.as_raw_handle(), 0) };
+        status == Foundation::WAIT_OBJECT_0
+    }
+
+    /// Get a handle for interrupting reads from the connection, if it uses
+    /// readiness notifications.
+    pub fn waker(&self) -> Option<RawWaker> {
+        self.wake.as_ref().map(|event| RawWaker {
+            handle: Arc::clone(event),
+        })
     }
 
     /// Get a handle for the terminal's output.
@@ -132,7 +216,9 @@ impl RawConfig {
             return None;
         }
 
-        let mut input_modes = self.input_modes | Console::ENABLE_VIRTUAL_TERMINAL_INPUT;
+        let mut input_modes = self.input_modes
+            | Console::ENABLE_VIRTUAL_TERMINAL_INPUT
+            | Console::ENABLE_WINDOW_INPUT;
         if options.mode() != Mode::Cooked {
             input_modes &= !Console::ENABLE_ECHO_INPUT & !Console::ENABLE_LINE_INPUT;
         }
@@ -248,12 +334,64 @@ impl std::fmt::Debug for RawConfig {
 pub(crate) struct RawInput {
     timeout: u32,
     handle: RawHandle,
+    wake: Option<RawHandle>,
+    decoder: ConsoleDecoder,
 }
 
 impl RawInput {
     #[inline]
-    fn new(handle: RawHandle, timeout: u32) -> Self {
-        Self { handle, timeout }
+    fn new(handle: RawHandle, timeout: u32, wake: Option<RawHandle>) -> Self {
+        Self {
+            handle,
+            timeout,
+            wake,
+            decoder: ConsoleDecoder::default(),
+        }
+    }
+
+    /// Read the available input records and decode them.
+    ///
+    /// Unlike `ReadConsoleA` and `ReadConsoleW`, `ReadConsoleInputW` does not
+    /// block when the console only has records other than key presses, such
+    /// as window size changes. Those still signal the input handle and would
+    /// otherwise stall reads until the next key press.
+    fn read_records(&mut self) -> Result<()> {
+        let mut records: [Console::INPUT_RECORD; 32] = unsafe { std::mem::zeroed() };
+        let mut did_read: u32 = 0;
+        unsafe {
+            Console::ReadConsoleInputW(
+                self.handle,
+                records.as_mut_ptr(),
+                records.len() as u32,
+                from_mut(&mut did_read),
+            )
+        }
+        .into_result()?;
+
+        for record in &records[..did_read as usize] {
+            match record.EventType as u32 {
+                Console::KEY_EVENT => {
+                    let event = unsafe { record.Event.KeyEvent };
+                    let unit = unsafe { event.uChar.UnicodeChar };
+                    // Characters entered with Alt and the numeric keypad arrive
+                    // with the release of the Alt key.
+                    let is_input = event.bKeyDown != 0 || event.wVirtualKeyCode == VK_MENU;
+                    if is_input && unit != 0 {
+                        for _ in 0..event.wRepeatCount.max(1) {
+                            self.decoder.push_unit(unit);
+                        }
+                    }
+                }
+                Console::WINDOW_BUFFER_SIZE_EVENT => {
+                    let size = unsafe { record.Event.WindowBufferSizeEvent }.dwSize;
+                    self.decoder
+                        .push_resize(size.Y.max(0) as u16, size.X.max(0) as u16);
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
     }
 
     #[allow(dead_code)]
@@ -277,20 +415,30 @@ impl From<RawInput> for RawHandle {
 
 impl Read for RawInput {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        let status = unsafe { Threading::WaitForSingleObject(self.handle, self.timeout) };
-        if status == Foundation::WAIT_OBJECT_0 {
-            let mut did_read: u32 = 0;
+        if self.decoder.has_pending() {
+            return Ok(self.decoder.read(buf));
+        }
+
+        let status = if let Some(wake) = self.wake {
+            let handles = [self.handle, wake];
             unsafe {
-                Console::ReadConsoleA(
-                    self.handle,
-                    buf.as_mut_ptr() as *mut c_void,
-                    buf.len() as u32,
-                    from_mut(&mut did_read),
-                    null(),
+                Threading::WaitForMultipleObjects(
+                    handles.len() as u32,
+                    handles.as_ptr(),
+                    0,
+                    self.timeout,
                 )
             }
-            .into_result()?;
-            Ok(did_read as usize)
+        } else {
+            unsafe { Threading::WaitForSingleObject(self.handle, self.timeout) }
+        };
+
+        if status == Foundation::WAIT_OBJECT_0 + 1 {
+            // The wake event is auto-reset and hence not signalled anymore.
+            Ok(0)
+        } else if status == Foundation::WAIT_OBJECT_0 {
+            self.read_records()?;
+            Ok(self.decoder.read(buf))
         } else if status == Foundation::WAIT_TIMEOUT {
             Ok(0)
         } else if status == Foundation::WAIT_FAILED {
@@ -303,6 +451,22 @@ impl Read for RawInput {
 
 // ------------------------------------------------------------------------------------------------
 
+/// A handle for interrupting reads that wait for readiness notifications.
+#[derive(Clone, Debug)]
+pub(crate) struct RawWaker {
+    handle: Arc<OwnedHandle>,
+}
+
+impl RawWaker {
+    /// Wake up the pending read, if any.
+    pub fn wake(&self) -> Result<()> {
+        unsafe { Threading::SetEvent(self.handle.as_raw_handle()) }.into_result()?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
 #[derive(Debug)]
 pub(crate) struct RawOutput {
     //#[allow(dead_code)]
@@ -359,3 +523,109 @@ impl Write for RawOutput {
         Ok(())
     }
 }
+
+// ----------------------------------------------------------------------------------------------------------
+
+/// The state for restoring the console in an emergency.
+///
+/// Windows invokes console control handlers on a separate thread. Hence, the
+/// state only needs to be thread-safe. The `is_armed` flag guards access.
+struct Emergency {
+    is_armed: AtomicBool,
+    input: AtomicPtr<c_void>,
+    output: AtomicPtr<c_void>,
+    input_modes: AtomicU32,
+    input_encoding: AtomicU32,
+    output_modes: AtomicU32,
+    output_encoding: AtomicU32,
+}
+
+static EMERGENCY: Emergency = Emergency {
+    is_armed: AtomicBool::new(false),
+    input: AtomicPtr::new(std::ptr::null_mut()),
+    output: AtomicPtr::new(std::ptr::null_mut()),
+    input_modes: AtomicU32::new(0),
+    input_encoding: AtomicU32::new(0),
+    output_modes: AtomicU32::new(0),
+    output_encoding: AtomicU32::new(0),
+};
+
+/// Prepare for restoring the original configuration in an emergency.
+pub(crate) fn arm(connection: &RawConnection, original: &RawConfig, _applied: &RawConfig) {
+    EMERGENCY.is_armed.store(false, Ordering::SeqCst);
+    EMERGENCY
+        .input
+        .store(connection.input.as_raw_handle(), Ordering::SeqCst);
+    EMERGENCY
+        .output
+        .store(connection.output.as_raw_handle(), Ordering::SeqCst);
+    EMERGENCY
+        .input_modes
+        .store(original.input_modes, Ordering::SeqCst);
+    EMERGENCY
+        .input_encoding
+        .store(original.input_encoding, Ordering::SeqCst);
+    EMERGENCY
+        .output_modes
+        .store(original.output_modes, Ordering::SeqCst);
+    EMERGENCY
+        .output_encoding
+        .store(original.output_encoding, Ordering::SeqCst);
+    EMERGENCY.is_armed.store(true, Ordering::SeqCst);
+}
+
+/// Stop restoring the original configuration in an emergency.
+pub(crate) fn disarm() {
+    EMERGENCY.is_armed.store(false, Ordering::SeqCst);
+}
+
+/// Set whether to restore the original configuration in an emergency.
+///
+/// This function returns the previous value.
+pub(crate) fn set_armed(is_armed: bool) -> bool {
+    EMERGENCY.is_armed.swap(is_armed, Ordering::SeqCst)
+}
+
+/// Restore the original configuration in an emergency.
+///
+/// This function returns `true` if it restored the console, i.e., was armed.
+pub(crate) fn restore() -> bool {
+    if !EMERGENCY.is_armed.swap(false, Ordering::SeqCst) {
+        return false;
+    }
+
+    let modes = unsafe { super::disabled_modes() };
+    let reset = super::emergency_reset();
+    let mut output = RawOutput::new(EMERGENCY.output.load(Ordering::SeqCst));
+    let _ = output.write_all(modes);
+    let _ = output.write_all(reset);
+    unsafe {
+        Console::SetConsoleMode(
+            EMERGENCY.input.load(Ordering::SeqCst),
+            EMERGENCY.input_modes.load(Ordering::SeqCst),
+        );
+        Console::SetConsoleCP(EMERGENCY.input_encoding.load(Ordering::SeqCst));
+        Console::SetConsoleMode(
+            EMERGENCY.output.load(Ordering::SeqCst),
+            EMERGENCY.output_modes.load(Ordering::SeqCst),
+        );
+        Console::SetConsoleOutputCP(EMERGENCY.output_encoding.load(Ordering::SeqCst));
+    }
+    true
+}
+
+unsafe extern "system" fn handle_control(_control_type: u32) -> Foundation::BOOL {
+    restore();
+    // Let the next handler, by default ExitProcess, handle the event.
+    0
+}
+
+/// Stop the process, which Windows does not support.
+pub(crate) fn stop_process() -> Result<()> {
+    Err(ErrorKind::Unsupported.into())
+}
+
+/// Install the console control handler.
+pub(crate) fn install_handlers() {
+    unsafe { Console::SetConsoleCtrlHandler(Some(handle_control), 1) };
+}
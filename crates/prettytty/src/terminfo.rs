@@ -0,0 +1,433 @@
+//! Helper module for reading the terminfo database.
+//!
+//! The terminfo database describes terminal capabilities, one compiled entry
+//! per terminal name. While many of the described capabilities are outdated,
+//! the database remains a useful fallback source for a terminal's color
+//! support, notably for terminals that do not set `COLORTERM`. This module
+//! locates and parses compiled entries in both the legacy format with 16-bit
+//! numbers and the extended format with 32-bit numbers, including extended,
+//! user-defined capabilities such as `RGB` and `Tc`. Besides the color count,
+//! it exposes the `setaf` and `setab` strings for setting colors as well as
+//! boolean capabilities.
+//!
+//! ```
+//! # use prettytty::terminfo::TermInfo;
+//! // Not every system has a terminfo database...
+//! if let Ok(info) = TermInfo::load("xterm-256color") {
+//!     assert_eq!(info.colors(), Some(256));
+//! }
+//! ```
+
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+
+/// The magic number for the legacy format with 16-bit numbers.
+const LEGACY_MAGIC: u16 = 0o432;
+/// The magic number for the extended format with 32-bit numbers.
+const EXTENDED_MAGIC: u16 = 0o1036;
+/// The index of the `colors` number capability.
+const COLORS: usize = 13;
+/// The index of the `setaf` string capability.
+const SETAF: usize = 359;
+/// The index of the `setab` string capability.
+const SETAB: usize = 360;
+
+/// The names of the standard boolean capabilities, in order.
+const BOOLEANS: [&str; 37] = [
+    "bw", "am", "xsb", "xhp", "xenl", "eo", "gn", "hc", "km", "hs", "in", "da", "db", "mir",
+    "msgr", "os", "eslok", "xt", "hz", "ul", "xon", "nxon", "mc5i", "chts", "nrrmc", "npc",
+    "ndscr", "ccc", "bce", "hls", "xhpa", "crxm", "daisy", "xvpa", "sam", "cpix", "lpix",
+];
+
+fn invalid(message: &'static str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+/// A cursor over the bytes of a compiled terminfo entry.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(count)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| invalid("truncated terminfo entry"))?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn align(&mut self) {
+        if self.offset % 2 == 1 && self.offset < self.bytes.len() {
+            self.offset += 1;
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.len() <= self.offset
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        let bytes = self.take(2)?;
+        Ok(i16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn count(&mut self) -> Result<usize> {
+        let value = self.i16()?;
+        if value < 0 {
+            Err(invalid("negative count in terminfo header"))
+        } else {
+            Ok(value as usize)
+        }
+    }
+
+    fn number(&mut self, is_wide: bool) -> Result<i32> {
+        if is_wide {
+            let bytes = self.take(4)?;
+            Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        } else {
+            Ok(self.i16()? as i32)
+        }
+    }
+}
+
+/// Get the NUL-terminated string starting at the offset.
+fn string_at(table: &[u8], offset: i16) -> Option<&[u8]> {
+    if offset < 0 {
+        return None;
+    }
+    let bytes = table.get(offset as usize..)?;
+    let length = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    Some(&bytes[..length])
+}
+
+/// The value of an extended capability.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    /// A boolean capability, which is present.
+    Flag,
+    /// A numeric capability.
+    Number(i32),
+    /// A string capability.
+    String(Vec<u8>),
+}
+
+/// A parsed terminfo entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TermInfo {
+    names: Vec<String>,
+    booleans: Vec<bool>,
+    numbers: Vec<Option<i32>>,
+    strings: Vec<Option<Vec<u8>>>,
+    extended: Vec<(String, Value)>,
+}
+
+impl TermInfo {
+    /// Locate the compiled entry for the terminal name.
+    ///
+    /// This function searches the directory named by `TERMINFO`, then
+    /// `~/.terminfo`, then the directories named by `TERMINFO_DIRS`, and
+    /// finally the usual system directories. It supports both the
+    /// single-character and the hexadecimal subdirectory naming schemes.
+    pub fn locate(name: &str) -> Option<PathBuf> {
+        let first = name.chars().next()?;
+        if name.contains('/') || name.contains('\\') {
+            return None;
+        }
+
+        let mut directories = Vec::new();
+        if let Some(dir) = std::env::var_os("TERMINFO") {
+            directories.push(PathBuf::from(dir));
+        }
+        if let Some(home) = std::env::var_os("HOME") {
+            directories.push(Path::new(&home).join(".terminfo"));
+        }
+        if let Some(dirs) = std::env::var_os("TERMINFO_DIRS") {
+            for dir in std::env::split_paths(&dirs) {
+                if dir.as_os_str().is_empty() {
+                    directories.push(PathBuf::from("/usr/share/terminfo"));
+                } else {
+                    directories.push(dir);
+                }
+            }
+        }
+        for dir in [
+            "/etc/terminfo",
+            "/lib/terminfo",
+            "/usr/share/terminfo",
+            "/usr/lib/terminfo",
+        ] {
+            directories.push(PathBuf::from(dir));
+        }
+
+        let subdirs = [first.to_string(), format!("{:02x}", first as u32)];
+        for dir in directories {
+            for subdir in subdirs.iter() {
+                let path = dir.join(subdir).join(name);
+                if path.is_file() {
+                    return Some(path);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Load the compiled entry for the terminal name.
+    pub fn load(name: &str) -> Result<Self> {
+        let path = Self::locate(name).ok_or(ErrorKind::NotFound)?;
+        Self::parse(&std::fs::read(path)?)
+    }
+
+    /// Load the compiled entry for the terminal named by `TERM`.
+    pub fn from_env() -> Result<Self> {
+        let name = std::env::var("TERM").map_err(|_| ErrorKind::NotFound)?;
+        Self::load(&name)
+    }
+
+    /// Parse a compiled terminfo entry.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let mut cursor = Cursor { bytes, offset: 0 };
+
+        let is_wide = match cursor.i16()? as u16 {
+            LEGACY_MAGIC => false,
+            EXTENDED_MAGIC => true,
+            _ => return Err(invalid("not a compiled terminfo entry")),
+        };
+        let names_size = cursor.count()?;
+        let bool_count = cursor.count()?;
+        let num_count = cursor.count()?;
+        let str_count = cursor.count()?;
+        let table_size = cursor.count()?;
+
+        let names = cursor.take(names_size)?;
+        let names = string_at(names, 0).unwrap_or_default();
+        let names = String::from_utf8_lossy(names)
+            .split('|')
+            .map(str::to_string)
+            .collect();
+
+        let booleans = cursor.take(bool_count)?.iter().map(|b| *b == 1).collect();
+        cursor.align();
+
+        let mut numbers = Vec::with_capacity(num_count);
+        for _ in 0..num_count {
+            let n = cursor.number(is_wide)?;
+            numbers.push((0 <= n).then_some(n));
+        }
+
+        let mut offsets = Vec::with_capacity(str_count);
+        for _ in 0..str_count {
+            offsets.push(cursor.i16()?);
+        }
+        let table = cursor.take(table_size)?;
+        let strings = offsets
+            .into_iter()
+            .map(|offset| string_at(table, offset).map(<[u8]>::to_vec))
+            .collect();
+
+        let mut info = Self {
+            names,
+            booleans,
+            numbers,
+            strings,
+            extended: Vec::new(),
+        };
+
+        cursor.align();
+        if !cursor.is_empty() {
+            info.extended = Self::parse_extended(&mut cursor, is_wide)?;
+        }
+        Ok(info)
+    }
+
+    /// Parse the extended capabilities.
+    fn parse_extended(cursor: &mut Cursor<'_>, is_wide: bool) -> Result<Vec<(String, Value)>> {
+        let bool_count = cursor.count()?;
+        let num_count = cursor.count()?;
+        let str_count = cursor.count()?;
+        let _item_count = cursor.count()?;
+        let table_size = cursor.count()?;
+
+        let booleans = cursor.take(bool_count)?;
+        cursor.align();
+
+        let mut numbers = Vec::with_capacity(num_count);
+        for _ in 0..num_count {
+            numbers.push(cursor.number(is_wide)?);
+        }
+
+        let mut offsets = Vec::with_capacity(str_count);
+        for _ in 0..str_count {
+            offsets.push(cursor.i16()?);
+        }
+        let mut name_offsets = Vec::with_capacity(bool_count + num_count + str_count);
+        for _ in 0..(bool_count + num_count + str_count) {
+            name_offsets.push(cursor.i16()?);
+        }
+        let table = cursor.take(table_size)?;
+
+        // Names follow the string values in the table.
+        let names_start = offsets
+            .iter()
+            .filter_map(|offset| string_at(table, *offset).map(|s| *offset as usize + s.len() + 1))
+            .max()
+            .unwrap_or(0);
+        let names_table = table.get(names_start..).unwrap_or_default();
+
+        let values = booleans
+            .iter()
+            .map(|b| (*b == 1).then_some(Value::Flag))
+            .chain(
+                numbers
+                    .iter()
+                    .map(|n| (0 <= *n).then_some(Value::Number(*n))),
+            )
+            .chain(
+                offsets
+                    .iter()
+                    .map(|offset| string_at(table, *offset).map(|s| Value::String(s.to_vec()))),
+            );
+
+        let mut extended = Vec::new();
+        for (name_offset, value) in name_offsets.into_iter().zip(values) {
+            let name = string_at(names_table, name_offset)
+                .ok_or_else(|| invalid("missing name for extended terminfo capability"))?;
+            if let Some(value) = value {
+                extended.push((String::from_utf8_lossy(name).into_owned(), value));
+            }
+        }
+
+        Ok(extended)
+    }
+
+    /// Get the terminal's names.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Get the number of colors, i.e., the `colors` capability.
+    pub fn colors(&self) -> Option<u32> {
+        self.numbers
+            .get(COLORS)
+            .copied()
+            .flatten()
+            .map(|n| n as u32)
+    }
+
+    /// Get the parameterized string for setting the foreground color, i.e.,
+    /// the `setaf` capability.
+    pub fn setaf(&self) -> Option<&[u8]> {
+        self.strings.get(SETAF)?.as_deref()
+    }
+
+    /// Get the parameterized string for setting the background color, i.e.,
+    /// the `setab` capability.
+    pub fn setab(&self) -> Option<&[u8]> {
+        self.strings.get(SETAB)?.as_deref()
+    }
+
+    /// Determine whether the terminal has the boolean capability.
+    ///
+    /// This method checks the standard boolean capabilities, such as `am` for
+    /// automatic margins and `bce` for background color erase, as well as
+    /// extended capabilities, such as `Tc`.
+    pub fn flag(&self, name: &str) -> bool {
+        match BOOLEANS.iter().position(|n| *n == name) {
+            Some(index) => self.booleans.get(index).copied().unwrap_or(false),
+            None => matches!(self.extended(name), Some(Value::Flag)),
+        }
+    }
+
+    /// Get the extended capability with the given name.
+    pub fn extended(&self, name: &str) -> Option<&Value> {
+        self.extended
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Determine whether the terminal supports 24-bit colors.
+    ///
+    /// This method checks for the `RGB` and `Tc` extended capabilities as
+    /// well as a color count of at least 2<sup>24</sup>.
+    pub fn has_truecolor(&self) -> bool {
+        self.extended("RGB").is_some()
+            || self.extended("Tc").is_some()
+            || self.colors().is_some_and(|n| (1 << 24) <= n)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{TermInfo, Value};
+
+    /// Build a minimal compiled entry with colors and extended capabilities.
+    fn entry(is_wide: bool) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let push16 = |bytes: &mut Vec<u8>, n: i16| bytes.extend_from_slice(&n.to_le_bytes());
+
+        let names = b"test|Test Terminal\0";
+        push16(&mut bytes, if is_wide { 0o1036 } else { 0o432 });
+        push16(&mut bytes, names.len() as i16);
+        push16(&mut bytes, 1);
+        push16(&mut bytes, 14);
+        push16(&mut bytes, 1);
+        push16(&mut bytes, 4);
+        bytes.extend_from_slice(names);
+        bytes.push(1);
+        for index in 0..14 {
+            let n = if index == 13 { 256 } else { -1 };
+            if is_wide {
+                bytes.extend_from_slice(&(n as i32).to_le_bytes());
+            } else {
+                push16(&mut bytes, n);
+            }
+        }
+        push16(&mut bytes, 0);
+        bytes.extend_from_slice(b"\x1b[m\0");
+
+        // Extended capabilities: Tc flag and Ss string.
+        push16(&mut bytes, 1);
+        push16(&mut bytes, 0);
+        push16(&mut bytes, 1);
+        push16(&mut bytes, 3);
+        push16(&mut bytes, 10);
+        bytes.push(1);
+        bytes.push(0);
+        push16(&mut bytes, 0);
+        push16(&mut bytes, 0);
+        push16(&mut bytes, 3);
+        bytes.extend_from_slice(b"\x1b[q\0Tc\0Ss\0");
+        bytes
+    }
+
+    #[test]
+    fn test_parse() -> std::io::Result<()> {
+        for is_wide in [false, true] {
+            let info = TermInfo::parse(&entry(is_wide))?;
+            assert_eq!(info.names(), ["test", "Test Terminal"]);
+            assert_eq!(info.colors(), Some(256));
+            assert_eq!(info.extended("Tc"), Some(&Value::Flag));
+            assert_eq!(
+                info.extended("Ss"),
+                Some(&Value::String(b"\x1b[q".to_vec()))
+            );
+            assert!(info.has_truecolor());
+            assert!(info.flag("bw"));
+            assert!(!info.flag("am"));
+            assert!(info.flag("Tc"));
+            assert!(!info.flag("Ss"));
+            assert_eq!(info.setaf(), None);
+        }
+
+        assert!(TermInfo::parse(b"\x1a\x01").is_err());
+        Ok(())
+    }
+}
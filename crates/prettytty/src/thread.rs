@@ -0,0 +1,245 @@
+use std::io::{ErrorKind, Read, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::{Connection, WakeHandle};
+
+/// The maximum delay after reads that return no data without blocking.
+const MAX_BACKOFF: Duration = Duration::from_millis(50);
+
+/// A reader that acquires the connection's input for each read.
+struct ConnectionReader(Arc<Connection>);
+
+impl Read for ConnectionReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.0.input().read(buf)
+    }
+}
+
+/// A background thread reading from the terminal.
+///
+/// This struct spawns a thread that performs blocking reads and forwards the
+/// bytes over an [`std::sync::mpsc`] channel. It implements [`Read`] on top of
+/// that channel, so wrapping it in a [`Scanner`](crate::Scanner) yields
+/// tokens. Alternatively, [`ReaderThread::receiver`] exposes the channel for
+/// integration with other event sources.
+///
+/// Dropping this struct shuts down the thread. The thread notices the request
+/// after its current read returns. If the connection uses readiness
+/// notifications, i.e., was opened with
+/// [`OptionBuilder::poll_timeout`](crate::opt::OptionBuilder::poll_timeout),
+/// shutdown uses the connection's [`WakeHandle`] and hence is immediate.
+/// Otherwise, it may take up to one read timeout.
+///
+/// While the thread is running, it competes for the connection's input. Hence
+/// an application should not read from the connection directly at the same
+/// time.
+pub struct ReaderThread {
+    receiver: Receiver<Result<Vec<u8>>>,
+    stop: Arc<AtomicBool>,
+    waker: Option<WakeHandle>,
+    handle: Option<JoinHandle<()>>,
+    pending: Vec<u8>,
+    position: usize,
+}
+
+impl ReaderThread {
+    /// Spawn a thread reading from the terminal connection.
+    pub fn spawn(connection: Arc<Connection>) -> Result<Self> {
+        let waker = connection.wake_handle();
+        let size = connection.options().read_buffer_size();
+        Self::with_reader(ConnectionReader(connection), size, waker)
+    }
+
+    /// Spawn a thread reading from the given reader.
+    ///
+    /// The thread reads at most `size` bytes at a time. If the reader returns
+    /// no data, the thread checks for shutdown and then reads again. Hence,
+    /// the reader should block or time out when there is no data. If it
+    /// returns no data without blocking, e.g., because it reached the end of
+    /// its input, the thread sleeps before reading again, doubling the delay
+    /// up to 50ms. The waker, if any, should interrupt the reader's pending
+    /// read.
+    pub fn with_reader<R>(mut reader: R, size: usize, waker: Option<WakeHandle>) -> Result<Self>
+    where
+        R: Read + Send + 'static,
+    {
+        let (sender, receiver) = channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = Arc::clone(&stop);
+
+        let handle = std::thread::Builder::new()
+            .name("prettytty-reader".to_string())
+            .spawn(move || {
+                let mut buffer = vec![0; size.max(1)];
+                let mut backoff = Duration::ZERO;
+                while !stop_flag.load(Ordering::Acquire) {
+                    let start = Instant::now();
+                    match reader.read(&mut buffer) {
+                        Ok(0) => {
+                            if start.elapsed() < Duration::from_millis(1) {
+                                backoff =
+                                    (backoff * 2).clamp(Duration::from_millis(1), MAX_BACKOFF);
+                                std::thread::sleep(backoff);
+                            } else {
+                                backoff = Duration::ZERO;
+                            }
+                        }
+                        Ok(count) => {
+                            backoff = Duration::ZERO;
+                            if sender.send(Ok(buffer[..count].to_vec())).is_err() {
+                                break;
+                            }
+                        }
+                        Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                        Err(error) => {
+                            let _ = sender.send(Err(error));
+                            break;
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            receiver,
+            stop,
+            waker,
+            handle: Some(handle),
+            pending: Vec::new(),
+            position: 0,
+        })
+    }
+
+    /// Get the channel's receiving end.
+    ///
+    /// Bytes received directly from the channel bypass this struct's [`Read`]
+    /// implementation. Hence, an application should use either but not both.
+    pub fn receiver(&self) -> &Receiver<Result<Vec<u8>>> {
+        &self.receiver
+    }
+
+    /// Read bytes, waiting at most for the given duration.
+    ///
+    /// This method returns a count of zero if no data arrived in time or the
+    /// thread has stopped.
+    pub fn read_timeout(&mut self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        if self.position == self.pending.len() {
+            match self.receiver.recv_timeout(timeout) {
+                Ok(data) => {
+                    self.pending = data?;
+                    self.position = 0;
+                }
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return Ok(0),
+            }
+        }
+
+        let count = buf.len().min(self.pending.len() - self.position);
+        buf[..count].copy_from_slice(&self.pending[self.position..self.position + count]);
+        self.position += count;
+        Ok(count)
+    }
+
+    /// Stop the thread and wait for it to terminate.
+    fn stop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(waker) = &self.waker {
+            let _ = waker.wake();
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+
+    /// Shut down the thread.
+    ///
+    /// Dropping this struct has the same effect. This method merely makes the
+    /// shutdown explicit.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+}
+
+impl Read for ReaderThread {
+    /// Read bytes, blocking until data arrives or the thread stops.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.read_timeout(buf, Duration::MAX)
+    }
+}
+
+impl Drop for ReaderThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl std::fmt::Debug for ReaderThread {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReaderThread")
+            .field("is_stopped", &self.stop.load(Ordering::Relaxed))
+            .field("has_waker", &self.waker.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::ReaderThread;
+    use crate::{Control, Scanner, Token};
+    use std::io::{Read, Result};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// A reader that counts its reads.
+    struct CountingReader<R> {
+        reader: R,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl<R: Read> Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            self.reader.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_reader_thread() -> Result<()> {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let input = CountingReader {
+            reader: b"abc\x1b[1mdef".as_slice(),
+            count: Arc::clone(&reads),
+        };
+        let mut thread = ReaderThread::with_reader(input, 4, None)?;
+
+        let mut buffer = [0; 2];
+        assert_eq!(thread.read(&mut buffer)?, 2);
+        assert_eq!(&buffer, b"ab");
+
+        let mut scanner = Scanner::new(&mut thread);
+        assert_eq!(scanner.read_token().unwrap(), Token::Text(b"c"));
+        assert_eq!(
+            scanner.read_token().unwrap(),
+            Token::Sequence(Control::CSI, b"1m")
+        );
+        // The thread reads four bytes at a time.
+        assert_eq!(scanner.read_token().unwrap(), Token::Text(b"d"));
+        assert_eq!(scanner.read_token().unwrap(), Token::Text(b"ef"));
+
+        let count = thread.read_timeout(&mut buffer, Duration::from_millis(10))?;
+        assert_eq!(count, 0);
+
+        // At the end of input, the thread backs off instead of spinning.
+        let before = reads.load(Ordering::Relaxed);
+        std::thread::sleep(Duration::from_millis(100));
+        let after = reads.load(Ordering::Relaxed) - before;
+        assert!(after < 20, "{} reads after end of input", after);
+        thread.shutdown();
+        Ok(())
+    }
+}
@@ -168,7 +168,6 @@ pub fn nicely_str(bytes: &[u8]) -> impl std::fmt::Debug + std::fmt::Display + us
 #[non_exhaustive]
 pub(crate) enum Radix {
     Decimal = 10,
-    #[allow(dead_code)]
     Hexadecimal = 16,
 }
 
@@ -0,0 +1,262 @@
+//! Test the decoding of console input records through a pseudo console.
+//!
+//! The parent test creates a pseudo console, i.e., a ConPTY, and runs this
+//! test binary again as a child attached to that pseudo console. The child
+//! opens a terminal connection, which reads input records with
+//! `ReadConsoleInputW`, and reports the decoded bytes as a hexadecimal string.
+//! Meanwhile, the parent writes text including a non-BMP character to the
+//! pseudo console and resizes it. Both tests are ignored by default and run
+//! by CI's `conpty` job.
+#![cfg(target_family = "windows")]
+
+use std::ffi::{c_void, OsString};
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::io::FromRawHandle;
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use windows_sys::Win32::Foundation::{CloseHandle, HANDLE, S_OK};
+use windows_sys::Win32::System::Console::{
+    ClosePseudoConsole, CreatePseudoConsole, ResizePseudoConsole, COORD, HPCON,
+};
+use windows_sys::Win32::System::Pipes::CreatePipe;
+use windows_sys::Win32::System::Threading::{
+    CreateProcessW, DeleteProcThreadAttributeList, InitializeProcThreadAttributeList,
+    UpdateProcThreadAttribute, WaitForSingleObject, CREATE_UNICODE_ENVIRONMENT,
+    EXTENDED_STARTUPINFO_PRESENT, LPPROC_THREAD_ATTRIBUTE_LIST, PROCESS_INFORMATION,
+    PROC_THREAD_ATTRIBUTE_PSEUDOCONSOLE, STARTUPINFOEXW,
+};
+
+use prettytty::opt::{Mode, Options};
+use prettytty::Connection;
+
+/// The environment variable marking the child process.
+const CHILD: &str = "PRETTYTTY_CONPTY_CHILD";
+
+/// The prefix of the child's report.
+const MARKER: &str = "DECODED:";
+
+// ------------------------------------------------------------------------------------------------
+
+/// Convert a Windows boolean into a result.
+fn check(ok: i32) -> Result<()> {
+    if ok == 0 {
+        Err(Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Convert a Windows HRESULT into a result.
+fn check_hresult(result: i32) -> Result<()> {
+    if result == S_OK {
+        Ok(())
+    } else {
+        Err(Error::from_raw_os_error(result))
+    }
+}
+
+/// Create an anonymous pipe, returning the read and write handles.
+fn pipe() -> Result<(HANDLE, HANDLE)> {
+    let mut reader: HANDLE = std::ptr::null_mut();
+    let mut writer: HANDLE = std::ptr::null_mut();
+    check(unsafe { CreatePipe(&mut reader, &mut writer, std::ptr::null(), 0) })?;
+    Ok((reader, writer))
+}
+
+/// Run the child test attached to the pseudo console.
+fn spawn(console: HPCON) -> Result<PROCESS_INFORMATION> {
+    let mut size = 0;
+    // This call fails by design but determines the size of the attribute list.
+    unsafe { InitializeProcThreadAttributeList(std::ptr::null_mut(), 1, 0, &mut size) };
+    let mut attributes = vec![0_u8; size];
+    let list = attributes.as_mut_ptr() as LPPROC_THREAD_ATTRIBUTE_LIST;
+    check(unsafe { InitializeProcThreadAttributeList(list, 1, 0, &mut size) })?;
+
+    let result = (|| -> Result<PROCESS_INFORMATION> {
+        check(unsafe {
+            UpdateProcThreadAttribute(
+                list,
+                0,
+                PROC_THREAD_ATTRIBUTE_PSEUDOCONSOLE as usize,
+                console as *const c_void,
+                std::mem::size_of::<HPCON>(),
+                std::ptr::null_mut(),
+                std::ptr::null(),
+            )
+        })?;
+
+        let mut startup: STARTUPINFOEXW = unsafe { std::mem::zeroed() };
+        startup.StartupInfo.cb = std::mem::size_of::<STARTUPINFOEXW>() as u32;
+        startup.lpAttributeList = list;
+
+        let mut command: Vec<u16> = format!(
+            "\"{}\" conpty_child --exact --ignored --nocapture --test-threads=1",
+            std::env::current_exe()?.display()
+        )
+        .encode_utf16()
+        .chain([0])
+        .collect();
+
+        let mut environment = Vec::new();
+        for (key, value) in std::env::vars_os().chain([(CHILD.into(), "1".into())]) {
+            let mut entry: OsString = key;
+            entry.push("=");
+            entry.push(value);
+            environment.extend(entry.encode_wide());
+            environment.push(0);
+        }
+        environment.push(0);
+
+        let mut process: PROCESS_INFORMATION = unsafe { std::mem::zeroed() };
+        check(unsafe {
+            CreateProcessW(
+                std::ptr::null(),
+                command.as_mut_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                0,
+                EXTENDED_STARTUPINFO_PRESENT | CREATE_UNICODE_ENVIRONMENT,
+                environment.as_ptr() as *const c_void,
+                std::ptr::null(),
+                &startup.StartupInfo,
+                &mut process,
+            )
+        })?;
+        Ok(process)
+    })();
+
+    unsafe { DeleteProcThreadAttributeList(list) };
+    result
+}
+
+/// Accumulate the pseudo console's output until the predicate holds.
+fn wait_until(
+    receiver: &Receiver<Vec<u8>>,
+    transcript: &mut String,
+    predicate: impl Fn(&str) -> bool,
+) -> Result<()> {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !predicate(transcript) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(remaining) {
+            Ok(bytes) => transcript.push_str(&String::from_utf8_lossy(&bytes)),
+            Err(_) => {
+                return Err(Error::new(
+                    ErrorKind::TimedOut,
+                    format!("pseudo console output incomplete: {:?}", transcript),
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse the child's report from the transcript.
+fn parse_report(transcript: &str) -> Option<Vec<u8>> {
+    let start = transcript.find(MARKER)? + MARKER.len();
+    let hex = &transcript[start..];
+    let hex = &hex[..hex.find(|c: char| !c.is_ascii_hexdigit())?];
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+#[ignore = "requires a pseudo console; run by CI's conpty job"]
+fn conpty_decodes_input() -> Result<()> {
+    let (input_reader, input_writer) = pipe()?;
+    let (output_reader, output_writer) = pipe()?;
+
+    let mut console: HPCON = 0;
+    let size = COORD { X: 120, Y: 25 };
+    check_hresult(unsafe {
+        CreatePseudoConsole(size, input_reader, output_writer, 0, &mut console)
+    })?;
+
+    // The pseudo console holds on to its own copies of these handles.
+    unsafe {
+        CloseHandle(input_reader);
+        CloseHandle(output_writer);
+    }
+
+    let mut input = unsafe { File::from_raw_handle(input_writer) };
+    let mut output = unsafe { File::from_raw_handle(output_reader) };
+
+    // The pseudo console blocks when its output isn't drained.
+    let (sender, receiver) = channel();
+    thread::spawn(move || {
+        let mut buffer = [0; 1024];
+        loop {
+            match output.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(count) => {
+                    if sender.send(buffer[..count].to_vec()).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    let process = spawn(console)?;
+    let mut transcript = String::new();
+    let result = (|| -> Result<()> {
+        wait_until(&receiver, &mut transcript, |t| t.contains("READY"))?;
+
+        input.write_all("xé😀".as_bytes())?;
+        input.flush()?;
+        thread::sleep(Duration::from_millis(250));
+        check_hresult(unsafe { ResizePseudoConsole(console, COORD { X: 100, Y: 30 }) })?;
+        thread::sleep(Duration::from_millis(250));
+        input.write_all(b"q")?;
+        input.flush()?;
+
+        wait_until(&receiver, &mut transcript, |t| parse_report(t).is_some())
+    })();
+
+    unsafe {
+        WaitForSingleObject(process.hProcess, 5_000);
+        CloseHandle(process.hProcess);
+        CloseHandle(process.hThread);
+        ClosePseudoConsole(console);
+    }
+    result?;
+
+    let report = parse_report(&transcript).unwrap();
+    let decoded = String::from_utf8_lossy(&report);
+    assert!(decoded.contains("xé😀"), "{:?}", decoded);
+    assert!(decoded.contains("\x1b[48;30;100;0;0t"), "{:?}", decoded);
+    assert!(decoded.ends_with('q'), "{:?}", decoded);
+    Ok(())
+}
+
+#[test]
+#[ignore = "runs as child of conpty_decodes_input"]
+fn conpty_child() -> Result<()> {
+    if std::env::var_os(CHILD).is_none() {
+        return Ok(());
+    }
+
+    let options = Options::builder().mode(Mode::Raw).timeout(1).build();
+    let tty = Connection::with_options(options)?;
+    tty.output().print("READY\r\n")?;
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut bytes = Vec::new();
+    let mut buffer = [0; 64];
+    while !bytes.ends_with(b"q") && Instant::now() < deadline {
+        let count = tty.input().read(&mut buffer)?;
+        bytes.extend_from_slice(&buffer[..count]);
+    }
+
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    tty.output().print(format!("{}{}\r\n", MARKER, hex))?;
+    Ok(())
+}
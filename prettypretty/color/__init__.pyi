@@ -6,6 +6,7 @@ from . import gamut as gamut
 from . import spectrum as spectrum
 from . import style as style
 from . import termco as termco
+from . import terminal as terminal
 from . import theme as theme
 
 
@@ -30,10 +31,13 @@ class ColorSpace:
     def is_ok(self) -> bool: ...
     def is_bounded(self) -> bool: ...
     def gamut(self, segment_size: int) -> None | gamut.GamutTraversal: ...
+    def gamut_volume(self) -> None | float: ...
+    def coverage(self, other: ColorSpace) -> None | float: ...
     def __hash__(self) -> int: ...
     def __eq__(self, other: object) -> bool: ...
     def __ne__(self, other: object) -> bool: ...
     def __str__(self) -> str: ...
+    def __reduce__(self) -> tuple[object, ...]: ...
 
 
 class OkVersion:
@@ -108,10 +112,23 @@ class Color:
     def __getitem__(self, index: int) -> float: ...
     def __repr__(self) -> str: ...
     def __str__(self) -> str: ...
+    def __buffer__(self, flags: int) -> memoryview: ...
+    def __reduce__(self) -> tuple[object, ...]: ...
+    def __copy__(self) -> Self: ...
+    def __deepcopy__(self, memo: dict[int, object]) -> Self: ...
     def to_24bit(self) -> bytes: ...
     def to_hex_format(self) -> str: ...
 
 
+class CoordinateTable:
+    """A read-only table of color coordinates in the same color space."""
+    def space(self) -> ColorSpace: ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> Color: ...
+    def __buffer__(self, flags: int) -> memoryview: ...
+    def __repr__(self) -> str: ...
+
+
 class HueInterpolation:
     """The rule for deciding how to interpolate hues."""
     Shorter: ClassVar[HueInterpolation] = ...
@@ -139,11 +156,35 @@ class Interpolator:
 def close_enough(f1: float, f2: float) -> bool: ...
 
 
+class TranslationReport:
+    """An explanation of how a translator converts a color to ANSI."""
+    def color(self) -> Color: ...
+    def is_achromatic(self) -> bool: ...
+    def threshold(self) -> float: ...
+    def supports_hue_lightness(self) -> bool: ...
+    def used_hue_lightness(self) -> bool: ...
+    def target(self) -> termco.AnsiColor: ...
+    def distances(self) -> list[tuple[termco.AnsiColor, float]]: ...
+    def __repr__(self) -> str: ...
+    def __str__(self) -> str: ...
+
+
 class Translator:
     """A class for translating between terminal and high-resolution colors."""
     def __new__(cls, version: OkVersion, theme: theme.Theme) -> Self: ...
     def __repr__(self) -> str: ...
 
+    # Access the 8-bit color coordinates used for matching
+    def coordinate_table(self) -> CoordinateTable: ...
+
+    # Serialize and copy translators
+    def to_bytes(self) -> bytes: ...
+    @staticmethod
+    def from_bytes(data: bytes) -> Translator: ...
+    def __reduce__(self) -> tuple[object, ...]: ...
+    def __copy__(self) -> Self: ...
+    def __deepcopy__(self, memo: dict[int, object]) -> Self: ...
+
     # Interrogate the color theme
     def is_dark_theme(self) -> bool: ...
 
@@ -166,9 +207,13 @@ class Translator:
 
     # Translate high-resolution to ANSI colors
     def to_ansi(self, color: Color) -> Color: ...
+    def explain_ansi(self, color: Color) -> TranslationReport: ...
     def supports_hue_lightness(self) -> bool: ...
     def to_ansi_hue_lightness(self, color: Color) -> None | termco.AnsiColor: ...
     def to_closest_ansi(self, color: Color) -> termco.AnsiColor: ...
+    def to_ansi_with_contrast(
+        self, color: Color, background: Color, min_contrast: float
+    ) -> termco.AnsiColor: ...
     def to_ansi_rgb(self, color: Color) -> termco.AnsiColor: ...
 
     # Translate high-resolution to 8-bit colors
@@ -176,6 +221,9 @@ class Translator:
     def to_closest_8bit_with_ansi(self, color: Color) -> termco.EightBitColor: ...
 
     # Cap terminal colors
+    def resolve_for(
+        self, display_color: Color, fidelity: style.Fidelity
+    ) -> None | termco.Colorant: ...
     def cap_hires(self, color: Color, fidelity: style.Fidelity) -> None | termco.Colorant: ...
     def cap_colorant(self, color: termco.Colorant, fidelity: style.Fidelity) -> None | termco.Colorant: ...
     def cap(
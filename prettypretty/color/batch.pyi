@@ -0,0 +1,15 @@
+"""Batch conversion of NumPy arrays with color coordinates.
+
+All functions take C-contiguous ``float64`` arrays with shape ``(N, 3)`` and
+release the GIL while processing them.
+"""
+
+from typing import Any
+
+from . import ColorSpace, Translator
+
+
+def convert(array: Any, from_space: ColorSpace, to_space: ColorSpace) -> Any: ...
+def to_gamut(array: Any, space: ColorSpace) -> Any: ...
+def to_ansi(translator: Translator, array: Any, space: ColorSpace) -> Any: ...
+def to_8bit(translator: Translator, array: Any, space: ColorSpace) -> Any: ...
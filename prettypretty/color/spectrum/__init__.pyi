@@ -78,6 +78,7 @@ class SpectrumTraversal:
     def __repr__(self) -> str: ...
 
 
+CIE_ILLUMINANT_A: Illuminant = ...
 CIE_ILLUMINANT_D50: Illuminant = ...
 CIE_ILLUMINANT_D65: Illuminant = ...
 CIE_ILLUMINANT_E: Illuminant = ...
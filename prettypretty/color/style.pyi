@@ -2,6 +2,7 @@ from typing import ClassVar, Self
 
 from . import Color, Translator
 from .termco import AnsiColor, Colorant, EightBitColor, EmbeddedRgb, GrayGradient, Rgb
+from .theme import Theme
 
 
 class Attribute:
@@ -84,6 +85,8 @@ class Fidelity:
     ) -> Fidelity: ...
     @staticmethod
     def from_environment(has_tty: bool) -> Fidelity: ...
+    @staticmethod
+    def report_environment(has_tty: bool) -> FidelityReport: ...
 
     def __hash__(self) -> int: ...
     def __eq__(self, other: object) -> bool: ...
@@ -95,6 +98,18 @@ class Fidelity:
     def __repr__(self) -> str: ...
 
 
+class FidelityReport:
+    """The outcome of fidelity detection with its decision trail."""
+    def fidelity(self) -> Fidelity: ...
+    def variable(self) -> None | str: ...
+    def trail(self) -> list[str]: ...
+    def __hash__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def __str__(self) -> str: ...
+
+
 class Layer:
     """The render layer."""
     Foreground: ClassVar[Layer] = ...
@@ -135,6 +150,8 @@ class Style:
             | Color | Colorant
         ),
     ) -> Self: ...
+    def with_auto_foreground(self, background: Color) -> Self: ...
+    def with_theme_foreground(self, background: Color, theme: Theme) -> Self: ...
 
     # Method to inspect styles
     def is_default(self) -> bool: ...
@@ -143,9 +160,63 @@ class Style:
     def background(self) -> None | Colorant: ...
     def fidelity(self) -> Fidelity: ...
     def cap(self, fidelity: Fidelity, translator: Translator) -> Self: ...
+    def transition(self, next: Style) -> StyleDelta: ...
+    def merge(self, overlay: Style) -> Self: ...
     def __hash__(self) -> int: ...
     def __eq__(self, other: object) -> bool: ...
     def __ne__(self, other: object) -> bool: ...
+    def __add__(self, overlay: Style) -> Self: ...
     def __neg__(self) -> Self: ...
     def __repr__(self) -> str: ...
     def __str__(self) -> str: ...
+    def __reduce__(self) -> tuple[object, ...]: ...
+    def __copy__(self) -> Self: ...
+    def __deepcopy__(self, memo: dict[int, object]) -> Self: ...
+
+
+class StyleDelta:
+    """The minimal changes between two styles."""
+    def is_empty(self) -> bool: ...
+    def format(self) -> FormatUpdate: ...
+    def foreground(self) -> None | Colorant: ...
+    def background(self) -> None | Colorant: ...
+    def __hash__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def __str__(self) -> str: ...
+
+
+class Role:
+    """A semantic role for styled output."""
+    Error: ClassVar[Role] = ...
+    Warning: ClassVar[Role] = ...
+    Success: ClassVar[Role] = ...
+    Info: ClassVar[Role] = ...
+    Accent: ClassVar[Role] = ...
+
+    def name(self) -> str: ...
+    def ansi_color(self) -> AnsiColor: ...
+    def color(self, theme: Theme) -> Color: ...
+    def style(self, theme: Theme) -> Style: ...
+    def __hash__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+
+
+class StyleSheet:
+    """A style sheet mapping names to styles for light and dark themes."""
+    def __new__(cls) -> Self: ...
+    def insert(self, name: str, style: Style) -> None: ...
+    def insert_variants(self, name: str, light: Style, dark: Style) -> None: ...
+    def get(self, name: str, is_dark: bool) -> None | Style: ...
+    def names(self) -> list[str]: ...
+    def is_empty(self) -> bool: ...
+    def len(self) -> int: ...
+    def contains(self, name: str) -> bool: ...
+    def cap_all(self, fidelity: Fidelity, translator: Translator) -> Self: ...
+    def insert_roles(self, theme: Theme) -> None: ...
+    def __len__(self) -> int: ...
+    def __contains__(self, name: str) -> bool: ...
+    def __repr__(self) -> str: ...
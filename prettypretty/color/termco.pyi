@@ -140,6 +140,7 @@ class Rgb:
     def __ne__(self, other: object) -> bool: ...
     def __repr__(self) -> str: ...
     def __str__(self) -> str: ...
+    def __reduce__(self) -> tuple[object, ...]: ...
 
 
 class Colorant_Default(Colorant):
@@ -189,9 +190,9 @@ class Colorant:
     @staticmethod
     def of(
         colorant: (
-            int | AnsiColor | EmbeddedRgb | GrayGradient | EightBitColor | Rgb
-            | Color | Colorant
-        )
+            None | int | AnsiColor | EmbeddedRgb | GrayGradient | EightBitColor
+            | Rgb | Color | Colorant
+        ) = None
     ) -> Colorant: ...
 
     def try_to_8bit(self) -> int: ...
@@ -206,3 +207,6 @@ class Colorant:
     def __ne__(self, other: object) -> bool: ...
     def __repr__(self) -> str: ...
     def display(self, layer: Layer) -> str: ...
+    def __reduce__(self) -> tuple[object, ...]: ...
+    def __copy__(self) -> Self: ...
+    def __deepcopy__(self, memo: dict[int, object]) -> Self: ...
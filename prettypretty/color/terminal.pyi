@@ -0,0 +1,44 @@
+"""Terminal access through context managers.
+
+Entering a terminal context connects to the terminal and exiting it always
+restores the terminal's original configuration, even if the body of the
+``with`` statement raises an exception.
+"""
+
+from typing import Literal, Self
+
+from .theme import Theme
+
+
+class Terminal:
+    """Access to the terminal."""
+    @staticmethod
+    def access(
+        *,
+        mode: Literal["charred", "cooked", "rare", "cbreak", "raw"] = "rare",
+        timeout: int = 50,
+    ) -> Terminal: ...
+    def is_active(self) -> bool: ...
+    def __enter__(self) -> Self: ...
+    def __exit__(self, *args: object) -> bool: ...
+    def window_size(self) -> tuple[int, int]: ...
+    def print(self, text: str) -> None: ...
+    def println(self, text: str) -> None: ...
+    def write(self, data: bytes) -> None: ...
+    def read(self) -> bytes: ...
+    def query_theme(self) -> Theme: ...
+    def alternate_screen(self) -> AlternateScreen: ...
+    def window_title(self, title: str) -> WindowTitle: ...
+    def __repr__(self) -> str: ...
+
+
+class AlternateScreen:
+    """A context manager for the alternate screen."""
+    def __enter__(self) -> Self: ...
+    def __exit__(self, *args: object) -> bool: ...
+
+
+class WindowTitle:
+    """A context manager for a temporary window title."""
+    def __enter__(self) -> Self: ...
+    def __exit__(self, *args: object) -> bool: ...
@@ -1,6 +1,6 @@
-from typing import Self
+from typing import ClassVar, Self
 
-from . import Color
+from . import Color, ColorSpace, CoordinateTable
 from .style import AnsiColor, Layer
 
 
@@ -14,6 +14,21 @@ class ThemeEntry_DefaultBackground(ThemeEntry):
     def __new__(cls) -> Self: ...
 
 
+class ThemeEntry_Cursor(ThemeEntry):
+    """A theme entry for the cursor color."""
+    def __new__(cls) -> Self: ...
+
+
+class ThemeEntry_HighlightForeground(ThemeEntry):
+    """A theme entry for one of the highlight colors."""
+    def __new__(cls) -> Self: ...
+
+
+class ThemeEntry_HighlightBackground(ThemeEntry):
+    """A theme entry for one of the highlight colors."""
+    def __new__(cls) -> Self: ...
+
+
 class ThemeEntry_Ansi(ThemeEntry):
     """A theme entry for one of the ANSI colors."""
     def __new__(cls, color: AnsiColor) -> Self: ...
@@ -23,6 +38,9 @@ class ThemeEntry:
     """The 18 colors in a theme."""
     DefaultForeground = ThemeEntry_DefaultForeground
     DefaultBackground = ThemeEntry_DefaultBackground
+    Cursor = ThemeEntry_Cursor
+    HighlightForeground = ThemeEntry_HighlightForeground
+    HighlightBackground = ThemeEntry_HighlightBackground
     Ansi = ThemeEntry_Ansi
 
     @staticmethod
@@ -31,6 +49,8 @@ class ThemeEntry:
     def try_from_index(index: int) -> ThemeEntry: ...
     def name(self) -> str: ...
     def abbr(self) -> str: ...
+    def set(self, color: Color) -> str: ...
+    def reset(self) -> str: ...
     def parse(self, s: bytes) -> Color: ...
     def __hash__(self) -> int: ...
     def __eq__(self, other: object) -> bool: ...
@@ -50,15 +70,117 @@ class ThemeEntryIterator:
     def __next__(self) -> ThemeEntry: ...
 
 
+class ThemeIssue_LowContrast(ThemeIssue):
+    """A chromatic ANSI color with low contrast against the background."""
+    def __new__(cls, color: AnsiColor, contrast: float) -> Self: ...
+
+
+class ThemeIssue_NearDuplicate(ThemeIssue):
+    """Two almost indistinguishable ANSI colors."""
+    def __new__(cls, color1: AnsiColor, color2: AnsiColor, distance: float) -> Self: ...
+
+
+class ThemeIssue_NotGray(ThemeIssue):
+    """A gray ANSI color with a hue."""
+    def __new__(cls, color: AnsiColor) -> Self: ...
+
+
+class ThemeIssue_NotChromatic(ThemeIssue):
+    """A chromatic ANSI color without hue."""
+    def __new__(cls, color: AnsiColor) -> Self: ...
+
+
+class ThemeIssue_HueOrder(ThemeIssue):
+    """Chromatic ANSI colors out of hue order."""
+    def __new__(cls) -> Self: ...
+
+
+class ThemeIssue:
+    """A problem with a color theme."""
+    LowContrast = ThemeIssue_LowContrast
+    NearDuplicate = ThemeIssue_NearDuplicate
+    NotGray = ThemeIssue_NotGray
+    NotChromatic = ThemeIssue_NotChromatic
+    HueOrder = ThemeIssue_HueOrder
+
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __str__(self) -> str: ...
+
+
+class ThemeQuery:
+    """A sans-I/O query for a terminal's color theme."""
+    def __new__(cls) -> Self: ...
+    @staticmethod
+    def with_extras() -> ThemeQuery: ...
+    def request(self) -> str: ...
+    def feed(self, data: bytes) -> int: ...
+    def is_complete(self) -> bool: ...
+    def theme(self) -> None | Theme: ...
+    def __repr__(self) -> str: ...
+
+
+class ThemeSource:
+    """The source of a color theme."""
+    Query: ClassVar[ThemeSource] = ...
+    ConsolePalette: ClassVar[ThemeSource] = ...
+    ColorFgBg: ClassVar[ThemeSource] = ...
+    ConsoleAttributes: ClassVar[ThemeSource] = ...
+    TermProgram: ClassVar[ThemeSource] = ...
+    Default: ClassVar[ThemeSource] = ...
+
+    def __hash__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+
+
+class ThemeEstimate:
+    """A color theme with its source and confidence."""
+    def theme(self) -> Theme: ...
+    def source(self) -> ThemeSource: ...
+    def confidence(self) -> float: ...
+    def is_dark(self) -> bool: ...
+    def __repr__(self) -> str: ...
+
+
 class Theme:
     """A color theme."""
     @staticmethod
+    def estimate() -> ThemeEstimate: ...
+    @staticmethod
     def query_terminal() -> Theme: ...
+    @staticmethod
+    def from_itermcolors(text: str) -> Theme: ...
+    @staticmethod
+    def from_kitty(text: str) -> Theme: ...
+    @staticmethod
+    def from_base16(text: str) -> Theme: ...
+    @staticmethod
+    def from_seed(
+        primary: Color, secondary: None | Color = None, dark: bool = True
+    ) -> Theme: ...
 
     def __new__(cls, colors: list[Color]) -> Self: ...
+    def is_dark(self) -> bool: ...
+    def background_lightness(self) -> float: ...
+    def polarity_confidence(self) -> float: ...
+    def lint(self) -> list[ThemeIssue]: ...
     def __getitem__(self, index: ThemeEntry|AnsiColor|Layer) -> Color: ...
     def __setitem__(self, index: ThemeEntry|AnsiColor|Layer, color: Color) -> Color: ...
     def __repr__(self) -> str: ...
+    def coordinate_table(self, space: ColorSpace) -> CoordinateTable: ...
+    def __reduce__(self) -> tuple[object, ...]: ...
+    def __setstate__(self, extras: list[None | Color]) -> None: ...
+    def __copy__(self) -> Self: ...
+    def __deepcopy__(self, memo: dict[int, object]) -> Self: ...
 
 
 VGA_COLORS: Theme =  ...
+SOLARIZED_DARK: Theme = ...
+SOLARIZED_LIGHT: Theme = ...
+DRACULA: Theme = ...
+GRUVBOX_DARK: Theme = ...
+NORD: Theme = ...
+MACOS_TERMINAL: Theme = ...
+WINDOWS_TERMINAL: Theme = ...
@@ -377,12 +377,8 @@ if __name__ == '__main__':
     if options.truecolor is not None:
         fidelity = Fidelity.TwentyFourBit if options.truecolor else Fidelity.EightBit
 
-    with (
-        Terminal(fidelity=fidelity)
-        .terminal_theme(options.theme)
-        .scoped_style()
-        as term
-    ):
+    term = Terminal(fidelity=fidelity)
+    with term.terminal_theme(options.theme), term.scoped_style():
         term.writeln()
 
         write_color_cube(term, show_detail=options.detail)
@@ -6,6 +6,7 @@ import sys
 try:
     import matplotlib.pyplot as plt
     from matplotlib.ticker import FixedFormatter, FixedLocator, FuncFormatter
+    import numpy as np
 except ImportError:
     print("prettypretty.plot requires matplotlib. Please install the package,")
     print("e.g., by executing `pip install matplotlib`, and then")
@@ -308,7 +309,8 @@ class ColorPlotter:
             )
             self._white_point = self._illuminated_observer.white_point()
 
-        points: tuple[list[float], list[float], list[float], list[str]] = [], [], [], []
+        points: list[Color] = []
+        hex_colors: list[str] = []
         lines2d: list[list[tuple[float, float]]] = []
         all_colors: list[list[str]] = []
 
@@ -329,10 +331,8 @@ class ColorPlotter:
                 if locus_only and len(lines2d) > 1:
                     break
             c = step.color()
-            points[0].append(c[0])
-            points[1].append(c[1])
-            points[2].append(c[2])
-            points[3].append(c.to_hex_format())
+            points.append(c)
+            hex_colors.append(c.to_hex_format())
             lines2d[-1].append(self.to_2d(c))
             all_colors[-1].append(c.to_hex_format())
 
@@ -341,7 +341,9 @@ class ColorPlotter:
 
         if not locus_only:
             f, a = plt.subplots(subplot_kw={"projection": "3d"})  # type: ignore
-            a.scatter(points[0], points[1], points[2], c=points[3])  # type: ignore
+            # Colors implement the buffer protocol, so NumPy views their coordinates
+            xyz = np.array(points)
+            a.scatter(xyz[:, 0], xyz[:, 1], xyz[:, 2], c=hex_colors)  # type: ignore
             f.show()
             #f.savefig("spectrum-3d.svg") # type: ignore
             #plt.close(f) # type: ignore
@@ -778,11 +780,11 @@ def main(options: Any, term: Terminal) -> None:
 
 if __name__ == "__main__":
     options = add_fidelity(create_parser()).parse_args()
+    term = Terminal(options.fidelity)
     with (
-        Terminal(options.fidelity)
-        .cbreak_mode()
-        .terminal_theme(options.theme)
-        .hidden_cursor()
-        .scoped_style()
-    ) as term:
+        term.cbreak_mode(),
+        term.terminal_theme(options.theme),
+        term.hidden_cursor(),
+        term.scoped_style(),
+    ):
         main(options, term)
@@ -93,12 +93,8 @@ def main() -> None:
         argparse.ArgumentParser("simulate progress bar")
     ).parse_args()
 
-    with (
-        Terminal(fidelity=options.fidelity)
-        .terminal_theme()
-        .hidden_cursor()
-        .scoped_style()
-    ) as term:
+    term = Terminal(fidelity=options.fidelity)
+    with term.terminal_theme(), term.hidden_cursor(), term.scoped_style():
         progress = ProgressBar(term)
         term.writeln(f'Using {progress.color} as color!\n').flush()
 
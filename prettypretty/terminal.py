@@ -1,5 +1,5 @@
 from collections.abc import Iterator, Sequence
-from contextlib import AbstractContextManager, contextmanager, ExitStack
+from contextlib import contextmanager
 import enum
 import os
 import select
@@ -9,7 +9,6 @@ import textwrap
 import tty
 from typing import (
     Any,
-    Callable,
     cast,
     ClassVar,
     ContextManager,
@@ -27,6 +26,9 @@ from .color.style import (Fidelity, Layer, Style) # pyright: ignore [reportMissi
 from .color.termco import ( # pyright: ignore [reportMissingModuleSource]
     AnsiColor, Colorant, EmbeddedRgb, GrayGradient, Rgb
 )
+from .color.terminal import ( # pyright: ignore [reportMissingModuleSource]
+    Terminal as Connection
+)
 from .theme import new_theme, current_translator
 from .ident import identify_terminal, normalize_terminal_name
 
@@ -34,11 +36,6 @@ from .ident import identify_terminal, normalize_terminal_name
 TerminalMode: TypeAlias = list[Any]
 
 
-class TerminalModeComponent:
-    CC = 6
-    LFLAG = 3
-
-
 _REFLECTED_METHODS = {
     'write_control', 'at', 'column', 'down', 'left', 'link', 'right', 'up'
 }
@@ -84,202 +81,6 @@ class BatchMode(enum.Enum):
         return self is not BatchMode.ENABLED
 
 
-class TerminalContextManager(AbstractContextManager['Terminal']):
-    """
-    A context manager for terminal state.
-
-    This class manages operations that update and restore terminal state. It
-    ensures that all updates are applied on entry and restored on exit, that
-    updates are applied in registration order and restored in the opposite
-    order, and that terminal output is flushed after applying and also after
-    reverting all updates.
-
-    An update may be a pair of escape sequences or a function that instantiates
-    a context manager. In case of the former, the first sequence is written to
-    the output upon entry and the second sequence upon exit. In case of the
-    latter, the function and its result's ``__enter__()`` method are invoked on
-    entry and the result's ``__exit__()`` method is invoked on exit. Currently,
-    there is no public interface for registering the latter.
-
-    This class is reentrant and reusable, though an instance does nothing on
-    nested invocations.
-
-    :class:`Terminal` has several methods with the same names and signatures as
-    this class. They are the preferred way of creating terminal context manager
-    instances because they are far more convenient. For example, to get started
-    using prettypretty, you might write:
-
-    .. code-block:: python
-
-        terminal = Terminal()
-        with TerminalContextManager(terminal).terminal_theme().scoped_style():
-            ...
-
-    is equivalent to this far nicer alternative
-
-    .. code-block:: python
-
-        with Terminal().alternate_screen().hidden_cursor() as terminal:
-            ...
-
-    """
-    def __init__(self, terminal: 'Terminal') -> None:
-        self._terminal = terminal
-        self._updates: list[Callable[[], ContextManager[object]] | tuple[str, str]] = []
-        self._block_depth = 0
-        self._exit_stack: None | ExitStack = None
-
-    def _check_not_active(self) -> None:
-        if self._block_depth > 0:
-            raise ValueError(
-                'unable to update context manager after __enter__() has been called'
-            )
-
-    def register(self, do: str, undo: str) -> Self:
-        """
-        Register an update with this terminal context manager. Both ``do`` and
-        ``undo`` should be ANSI escape sequences, with ``undo`` restoring the
-        terminal to the state from before ``do``.
-        """
-        self._check_not_active()
-        self._updates.append((do, undo))
-        return self
-
-    @contextmanager
-    def _cbreak_mode(self) -> 'Iterator[Terminal]':
-        fileno = self._terminal._input_fileno  # type: ignore[reportPrivateUsage]
-        saved_mode = termios.tcgetattr(fileno)
-        change_mode = not self._terminal.is_cbreak_mode(saved_mode)
-        if change_mode:
-            tty.setcbreak(fileno)
-        try:
-            yield self._terminal
-        finally:
-            if change_mode:
-                termios.tcsetattr(fileno, termios.TCSAFLUSH, saved_mode)
-
-    def cbreak_mode(self) -> Self:
-        """
-        Put the terminal into cbreak mode.
-
-        If the terminal is not yet in cbreak mode, the context manager sets
-        cbreak mode upon entry and restores the previous mode upon exit. If the
-        terminal is in cbreak mode already, the context manager does not modify
-        the terminal mode. Mode changes only take effect after all queued output
-        has been written but queued input is discarded.
-        """
-        self._check_not_active()
-        self._updates.append(lambda: self._cbreak_mode())
-        return self
-
-    def _request_theme(self) -> theme.Theme:
-        with self._cbreak_mode():
-            return self._terminal.request_theme()
-
-    def terminal_theme(self, theme: None | theme.Theme = None) -> Self:
-        """
-        Use the terminal's color theme. Unless a theme is provided as argument,
-        the context manager puts the terminal temporarily into cbreak mode and
-        determines the current theme colors upon entry. It then makes that theme
-        the current theme until exit.
-        """
-        self._check_not_active()
-        if theme is None:
-            factory = lambda: new_theme(self._request_theme())
-        else:
-            factory = lambda: new_theme(theme)
-        self._updates.append(factory)
-        return self
-
-    def window_title(self, title: str) -> Self:
-        """Update the window title."""
-        # Save window title on stack, then update window title
-        return self.register(
-            Ansi.fuse(Ansi.CSI, "22;2t", Ansi.OSC, "0;", title, Ansi.ST),
-            Ansi.fuse(Ansi.CSI, "23;2t"),
-        )
-
-    def alternate_screen(self) -> Self:
-        """Switch to the terminal's alternate (unbuffered) screen."""
-        return self.register(
-            Ansi.fuse(Ansi.CSI, "?1049h"),
-            Ansi.fuse(Ansi.CSI, "?1049l"),
-        )
-
-    def hidden_cursor(self) -> Self:
-        """Make cursor invisible."""
-        return self.register(
-            Ansi.fuse(Ansi.CSI, "?25l"),
-            Ansi.fuse(Ansi.CSI, "?25h"),
-        )
-
-    def batched_output(self) -> Self:
-        """
-        Batch terminal output.
-
-        While batching, a terminal temporarily delays updating the screen by
-        buffering output. It avoids visual artifacts when rapidly updating the
-        screen.
-        """
-        return self.register(
-            Ansi.fuse(Ansi.CSI, "?2026h"),
-            Ansi.fuse(Ansi.CSI, "?2026l"),
-        )
-
-    def bracketed_paste(self) -> Self:
-        """
-        Enable `bracketed pasting
-        <https://gitlab.com/gnachman/iterm2/-/wikis/Paste-Bracketing>`_.
-        """
-        return self.register(
-            Ansi.fuse(Ansi.CSI, "?2004h"),
-            Ansi.fuse(Ansi.CSI, "?2004l"),
-        )
-
-    def scoped_style(self) -> Self:
-        """Scope style changes by resetting the style on exit."""
-        return self.register('', Ansi.fuse(Ansi.CSI, 'm'))
-
-    def _create_control_writer(self, control: str) -> Callable[[], None]:
-        def control_writer() -> None:
-            self._terminal.write_control(control)
-        return control_writer
-
-    def __enter__(self) -> 'Terminal':
-        if self._block_depth == 0:
-            with ExitStack() as stack:
-                # Protect against partial terminal updates with an exit stack.
-                for update in self._updates:
-                    if isinstance(update, tuple):
-                        self._terminal.write_control(update[0])
-                        stack.callback(self._create_control_writer(update[1]))
-                    else:
-                        stack.enter_context(update())
-
-                # All updates have been processed. Flush terminal output.
-                self._terminal.flush()
-
-                # All went well. Delay clean up until __exit__().
-                self._exit_stack = stack.pop_all()
-
-        self._block_depth += 1
-        return self._terminal
-
-    def __exit__(self, *args: Any) -> bool:
-        # Exception suppression isn't needed right now but easy enough to support
-        received_exception = args[0] is not None
-        suppressed_exception = False
-
-        if self._block_depth == 1:
-            assert self._exit_stack is not None
-            suppressed_exception = bool(self._exit_stack.__exit__(*args))
-            self._terminal.flush()
-            self._exit_stack = None
-
-        self._block_depth -= 1
-        return received_exception and suppressed_exception
-
-
 class Terminal:
     """
     Terminal input/output.
@@ -345,9 +146,10 @@ class Terminal:
 
     **Scoped changes of terminal state**
         To more easily update, restore, and flush terminal states, ``Terminal``
-        delegates to a terminal context manager. It makes it possible to
-        fluently queue up all restorable updates in a single ``with`` statement
-        without worrying about many of the implementation details.
+        has methods returning context managers. Several of them can be combined
+        in a single ``with`` statement. Cbreak mode and the theme query rely on
+        :class:`prettypretty.color.terminal.Terminal`, which restores the
+        terminal's mode on exit.
 
         See :meth:`cbreak_mode`, :meth:`terminal_theme`, :meth:`window_title`,
         :meth:`alternate_screen`, :meth:`hidden_cursor`, :meth:`batched_output`,
@@ -464,9 +266,9 @@ class Terminal:
         """"
         Determine whether *cbreak mode* is enabled. This method inspects the
         current terminal mode to see whether characters are not echoed (``ECHO``
-        is not set), line editing is disabled (``ICANON`` is not set), and reads
-        return upon the first available character (``VMIN`` is 1, ``VTIME`` is
-        0).
+        is not set) and line editing is disabled (``ICANON`` is not set). It
+        ignores ``VMIN`` and ``VTIME``, since :meth:`cbreak_mode` uses them for
+        read timeouts.
 
         Since raw mode makes the same changes and then some, this method detects
         raw mode as cbreak mode. That's just fine for its intended purpose,
@@ -476,15 +278,13 @@ class Terminal:
         if mode is None:
             mode = termios.tcgetattr(self._input_fileno)
 
-        # tty.setcbreak disables ECHO and ICANON, sets VMIN to 1, and VTIME to
-        # 0. tty.setraw does the same and then some. For our purposes, it
-        # suffices to check for cbreak's minimal settings, since we want the
+        # Rare mode disables ECHO and ICANON, sets VMIN to 0, and VTIME to the
+        # read timeout. Raw mode does the same and then some. For our purposes,
+        # it suffices to check for the minimal settings, since we want the
         # terminal to respond right away and not stuck in line-buffer mode.
         return (
-            not (mode[TerminalModeComponent.LFLAG] & termios.ECHO)
-            and not (mode[TerminalModeComponent.LFLAG] & termios.ICANON)
-            and mode[TerminalModeComponent.CC][termios.VMIN] == 1
-            and mode[TerminalModeComponent.CC][termios.VTIME] == 0
+            not (mode[tty.LFLAG] & termios.ECHO)
+            and not (mode[tty.LFLAG] & termios.ICANON)
         )
 
     def check_cbreak_mode(self) -> Self:
@@ -496,18 +296,20 @@ class Terminal:
             raise ValueError('terminal is expected to be in cbreak mode but is not')
         return self
 
-    def cbreak_mode(self) -> TerminalContextManager:
+    @contextmanager
+    def cbreak_mode(self) -> Iterator[Self]:
         """
         Put the terminal into cbreak mode.
 
-        If the terminal is not yet in cbreak mode, the context manager sets
-        cbreak mode upon entry and restores the previous mode upon exit. If the
-        terminal is in cbreak mode already, the context manager does not modify
-        the terminal mode, but it still restores the previous mode upon exit.
-        Mode changes only take effect after all queued output has been written
-        but queued input is discarded.
+        The context manager connects to the terminal with
+        :class:`prettypretty.color.terminal.Terminal` in ``rare`` mode, which
+        is cbreak mode, upon entry and disconnects again upon exit, which
+        restores the previous mode. Mode changes only take effect after all
+        queued output has been written but queued input is discarded.
         """
-        return TerminalContextManager(self).cbreak_mode()
+        self.flush()
+        with Connection.access(mode="rare"):
+            yield self
 
     # ----------------------------------------------------------------------------------
 
@@ -1076,44 +878,84 @@ class Terminal:
     # ----------------------------------------------------------------------------------
     # Terminal Context
 
-    def terminal_theme(self, theme: None | theme.Theme = None) -> TerminalContextManager:
+    @contextmanager
+    def terminal_theme(self, theme: None | theme.Theme = None) -> Iterator[Self]:
         """
         Use a different color theme. Unless a theme argument is provided, the
-        implementation queries the terminal for its current theme, while
-        temporarily putting the terminal in cbreak mode.
+        implementation queries the terminal for its current theme through a
+        temporary :class:`prettypretty.color.terminal.Terminal` connection.
         """
-        return TerminalContextManager(self).terminal_theme(theme)
+        if theme is None:
+            self.flush()
+            with Connection.access(mode="rare") as connection:
+                theme = connection.query_theme()
+        with new_theme(theme):
+            yield self
 
-    def window_title(self, title: str) -> TerminalContextManager:
+    @contextmanager
+    def _scoped(self, do: str, undo: str) -> Iterator[Self]:
+        """
+        Write the ``do`` escape sequence upon entry and the ``undo`` escape
+        sequence upon exit, flushing the output after either.
+        """
+        self.write_control(do).flush()
+        try:
+            yield self
+        finally:
+            self.write_control(undo).flush()
+
+    def window_title(self, title: str) -> ContextManager[Self]:
         """Use a different window title."""
-        return TerminalContextManager(self).window_title(title)
+        # Save window title on stack, then update window title
+        return self._scoped(
+            Ansi.fuse(Ansi.CSI, "22;2t", Ansi.OSC, "0;", title, Ansi.ST),
+            Ansi.fuse(Ansi.CSI, "23;2t"),
+        )
 
-    def alternate_screen(self) -> TerminalContextManager:
+    def alternate_screen(self) -> ContextManager[Self]:
         """Switch to the terminal's alternate (unbuffered) screen."""
-        return TerminalContextManager(self).alternate_screen()
+        return self._scoped(
+            Ansi.fuse(Ansi.CSI, "?1049h"),
+            Ansi.fuse(Ansi.CSI, "?1049l"),
+        )
 
-    def hidden_cursor(self) -> TerminalContextManager:
+    def hidden_cursor(self) -> ContextManager[Self]:
         """Make cursor invisible."""
-        return TerminalContextManager(self).hidden_cursor()
+        return self._scoped(
+            Ansi.fuse(Ansi.CSI, "?25l"),
+            Ansi.fuse(Ansi.CSI, "?25h"),
+        )
+
+    def batched_output(self) -> ContextManager[Self]:
+        """
+        Batch terminal output.
 
-    def batched_output(self) -> TerminalContextManager:
-        """Batch terminal output."""
-        return TerminalContextManager(self).batched_output()
+        While batching, a terminal temporarily delays updating the screen by
+        buffering output. It avoids visual artifacts when rapidly updating the
+        screen.
+        """
+        return self._scoped(
+            Ansi.fuse(Ansi.CSI, "?2026h"),
+            Ansi.fuse(Ansi.CSI, "?2026l"),
+        )
 
-    def bracketed_paste(self) -> TerminalContextManager:
+    def bracketed_paste(self) -> ContextManager[Self]:
         """
         Enable `bracketed pasting
         <https://gitlab.com/gnachman/iterm2/-/wikis/Paste-Bracketing>`_.
         """
-        return TerminalContextManager(self).bracketed_paste()
+        return self._scoped(
+            Ansi.fuse(Ansi.CSI, "?2004h"),
+            Ansi.fuse(Ansi.CSI, "?2004l"),
+        )
 
-    def scoped_style(self) -> TerminalContextManager:
+    def scoped_style(self) -> ContextManager[Self]:
         """
         Scope style changes by resetting the style on exit. The ``style()``
         context helps protect against unwanted style leakage upon unexpected
         exceptions or signals.
         """
-        return TerminalContextManager(self).scoped_style()
+        return self._scoped('', Ansi.fuse(Ansi.CSI, 'm'))
 
     # ----------------------------------------------------------------------------------
 
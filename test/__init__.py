@@ -1,2 +1,5 @@
+from .test_batch import TestBatch as TestBatch
 from .test_color import TestColor as TestColor
+from .test_pickle import TestPickle as TestPickle
 from .test_style import TestStyle as TestStyle
+from .test_terminal import TestTerminal as TestTerminal
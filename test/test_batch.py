@@ -0,0 +1,43 @@
+import unittest
+
+from prettypretty.color import Color, ColorSpace, OkVersion, Translator
+from prettypretty.color import batch # pyright: ignore [reportMissingModuleSource]
+from prettypretty.color.theme import VGA_COLORS # pyright: ignore [reportMissingModuleSource]
+
+try:
+    import numpy
+except ImportError:
+    numpy = None
+
+
+@unittest.skipIf(numpy is None, "requires NumPy")
+class TestBatch(unittest.TestCase):
+
+    def test_convert(self) -> None:
+        assert numpy is not None
+        srgb = numpy.array([[1.0, 0.0, 0.0], [0.5, 0.7, 0.2]])
+        oklab = batch.convert(srgb, ColorSpace.Srgb, ColorSpace.Oklab)
+        self.assertEqual(oklab.shape, (2, 3))
+        for row, expected in zip(oklab, srgb):
+            color = Color(ColorSpace.Srgb, expected.tolist()).to(ColorSpace.Oklab)
+            self.assertEqual(Color(ColorSpace.Oklab, row.tolist()), color)
+
+        with self.assertRaises(ValueError):
+            batch.convert(numpy.zeros((2, 2)), ColorSpace.Srgb, ColorSpace.Oklab)
+
+    def test_to_gamut(self) -> None:
+        assert numpy is not None
+        srgb = numpy.array([[1.2, -0.1, 0.5]])
+        mapped = batch.to_gamut(srgb, ColorSpace.Srgb)
+        expected = Color(ColorSpace.Srgb, [1.2, -0.1, 0.5]).to_gamut()
+        self.assertEqual(Color(ColorSpace.Srgb, mapped[0].tolist()), expected)
+
+    def test_to_ansi(self) -> None:
+        assert numpy is not None
+        translator = Translator(OkVersion.Revised, VGA_COLORS)
+        srgb = numpy.array([[1.0, 1.0, 0.0], [0.0, 0.0, 0.0]])
+        ansi = batch.to_ansi(translator, srgb, ColorSpace.Srgb)
+        self.assertEqual(ansi.dtype, numpy.uint8)
+        self.assertListEqual(ansi.tolist(), [11, 0])
+        eight_bit = batch.to_8bit(translator, srgb, ColorSpace.Srgb)
+        self.assertListEqual(eight_bit.tolist(), [226, 16])
@@ -6,6 +6,7 @@ from prettypretty.color import (
     ColorSpace,
 )
 from prettypretty.color import termco # pyright: ignore [reportMissingModuleSource]
+from prettypretty.color.theme import VGA_COLORS # pyright: ignore [reportMissingModuleSource]
 from prettypretty.theme import current_translator
 
 
@@ -240,6 +241,21 @@ class TestColor(unittest.TestCase):
         )
 
 
+    def test_buffer(self) -> None:
+        orange = Color.from_24bit(255, 140, 0)
+        view = memoryview(orange)
+        self.assertTrue(view.readonly)
+        self.assertEqual(view.shape, (3,))
+        self.assertListEqual(view.tolist(), [*orange])
+
+        table = VGA_COLORS.coordinate_table(ColorSpace.Oklab)
+        view = memoryview(table)
+        self.assertEqual(view.shape, (len(table), 3))
+        self.assertEqual(table.space(), ColorSpace.Oklab)
+        self.assertListEqual(view.tolist()[1], [*table[1]])
+        self.assertEqual(table[1], VGA_COLORS[termco.AnsiColor.Red].to(ColorSpace.Oklab))
+
+
     def test_x_parse_color(self) -> None:
         for text, expected in {
             'rgb:0/8/80': (0.0, 0.5333333333333333, 0.5019607843137255),
@@ -0,0 +1,75 @@
+import copy
+import pickle
+from typing import TypeVar
+import unittest
+
+from prettypretty.color import Color, ColorSpace, OkVersion, Translator
+from prettypretty.color.style import Style # pyright: ignore [reportMissingModuleSource]
+from prettypretty.color.termco import ( # pyright: ignore [reportMissingModuleSource]
+    AnsiColor, Colorant, Rgb
+)
+from prettypretty.color.theme import VGA_COLORS # pyright: ignore [reportMissingModuleSource]
+
+
+T = TypeVar("T")
+
+
+def roundtrip(value: T, protocol: int = pickle.HIGHEST_PROTOCOL) -> T:
+    return pickle.loads(pickle.dumps(value, protocol))
+
+
+class TestPickle(unittest.TestCase):
+
+    def test_color(self) -> None:
+        orange = Color.from_24bit(255, 140, 0).to(ColorSpace.Oklch)
+        for protocol in range(pickle.HIGHEST_PROTOCOL + 1):
+            self.assertIs(roundtrip(ColorSpace.Oklch, protocol), ColorSpace.Oklch)
+            self.assertEqual(roundtrip(orange, protocol), orange)
+
+        self.assertIs(copy.copy(orange), orange)
+        self.assertIs(copy.deepcopy(orange), orange)
+
+    def test_colorant(self) -> None:
+        for colorant in [
+            Colorant.of(),
+            Colorant.of(AnsiColor.BrightRed),
+            Colorant.of(100),
+            Colorant.of(240),
+            Colorant.of(Rgb(255, 140, 0)),
+            Colorant.of(Color.p3(0.0, 1.0, 0.0)),
+        ]:
+            self.assertEqual(roundtrip(colorant), colorant)
+
+    def test_style(self) -> None:
+        style = (
+            Style()
+            .bold()
+            .italic()
+            .with_foreground(Rgb(255, 140, 0))
+            .with_background(AnsiColor.Blue)
+        )
+        self.assertEqual(roundtrip(style), style)
+        self.assertEqual(roundtrip(-style), -style)
+        self.assertEqual(roundtrip(Style()), Style())
+        self.assertIs(copy.deepcopy(style), style)
+
+    def test_theme(self) -> None:
+        theme = roundtrip(VGA_COLORS)
+        self.assertEqual(repr(theme), repr(VGA_COLORS))
+
+        theme = copy.deepcopy(VGA_COLORS)
+        theme[AnsiColor.Red] = Color.from_24bit(255, 0, 0)
+        self.assertNotEqual(theme[AnsiColor.Red], VGA_COLORS[AnsiColor.Red])
+
+    def test_translator(self) -> None:
+        translator = Translator(OkVersion.Revised, VGA_COLORS)
+        restored = roundtrip(translator)
+        for color in [Color.from_24bit(255, 140, 0), Color.p3(0.2, 0.4, 0.9)]:
+            self.assertEqual(restored.to_ansi(color), translator.to_ansi(color))
+            self.assertEqual(
+                restored.to_closest_8bit(color), translator.to_closest_8bit(color)
+            )
+
+        self.assertIs(copy.copy(translator), translator)
+        with self.assertRaises(ValueError):
+            Translator.from_bytes(b"PPTT")
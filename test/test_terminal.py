@@ -0,0 +1,36 @@
+import unittest
+
+from prettypretty.color.terminal import Terminal # pyright: ignore [reportMissingModuleSource]
+
+
+class TestTerminal(unittest.TestCase):
+
+    def test_access(self) -> None:
+        terminal = Terminal.access(mode="raw")
+        self.assertFalse(terminal.is_active())
+        self.assertEqual(repr(terminal), "Terminal(mode=raw, active=false)")
+
+        with self.assertRaises(ValueError):
+            Terminal.access(mode="medium")
+
+    def test_inactive(self) -> None:
+        terminal = Terminal.access()
+        with self.assertRaises(RuntimeError):
+            terminal.print("Hello")
+        with self.assertRaises(RuntimeError):
+            with terminal.alternate_screen():
+                pass
+
+        # Exiting guards for an inactive terminal has no effect.
+        self.assertFalse(terminal.window_title("Pretty").__exit__(None, None, None))
+
+    def test_restore(self) -> None:
+        terminal = Terminal.access()
+        try:
+            with self.assertRaises(KeyError):
+                with terminal:
+                    self.assertTrue(terminal.is_active())
+                    raise KeyError("bail")
+        except OSError:
+            self.skipTest("requires terminal")
+        self.assertFalse(terminal.is_active())